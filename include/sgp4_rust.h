@@ -0,0 +1,208 @@
+/* Generated by cbindgen from src/ffi.rs; do not edit. */
+
+#ifndef SGP4_RUST_H
+#define SGP4_RUST_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Result codes of the C functions.
+ */
+typedef enum {
+  /**
+   * The call succeeded.
+   */
+  SGP4_STATUS_OK = 0,
+  /**
+   * A required pointer was null.
+   */
+  SGP4_STATUS_NULL_POINTER = 1,
+  /**
+   * The lines are not valid UTF-8 or do not form a TLE set.
+   */
+  SGP4_STATUS_INVALID_TLE = 2,
+  /**
+   * The mean eccentricity left the range [-0.001, 1).
+   */
+  SGP4_STATUS_MEAN_ECCENTRICITY = 3,
+  /**
+   * The mean motion became negative.
+   */
+  SGP4_STATUS_MEAN_MOTION = 4,
+  /**
+   * The eccentricity perturbed by the lunar-solar periodics left the range [0, 1].
+   */
+  SGP4_STATUS_PERTURBED_ECCENTRICITY = 5,
+  /**
+   * The semi-latus rectum became negative.
+   */
+  SGP4_STATUS_SEMI_LATUS_RECTUM = 6,
+  /**
+   * Kepler's equation could not be solved.
+   */
+  SGP4_STATUS_KEPLER = 7,
+  /**
+   * The satellite is below the Earth's surface.
+   */
+  SGP4_STATUS_DECAYED = 8,
+} Sgp4Status;
+
+/**
+ * Holds the SGP4 initialization of a set of orbital elements.
+ *
+ * `Sgp4::new` performs the time-independent computations once; `propagate` then only performs the
+ * work that depends on the time since epoch. Deep-space elements (period of 225 minutes or more)
+ * are propagated with the lunar-solar and resonance terms of SDP4.
+ */
+typedef struct Sgp4 Sgp4;
+
+/**
+ * Represents the orbital elements of a satellite.
+ *
+ * With the `serde` feature, angles are serialized in degrees and the mean motion in
+ * revolutions per day, under field names carrying the unit. The layout is the one of a C struct,
+ * so the elements can be passed through the `ffi` functions.
+ */
+typedef struct {
+  /**
+   * Inclination of the orbit in radians.
+   */
+  double inclination;
+  /**
+   * Right Ascension of the Ascending Node (RAAN) in radians.
+   */
+  double raan;
+  /**
+   * Eccentricity of the orbit, unitless.
+   */
+  double eccentricity;
+  /**
+   * Argument of perigee in radians.
+   */
+  double arg_perigee;
+  /**
+   * Mean anomaly in radians.
+   */
+  double mean_anomaly;
+  /**
+   * Mean motion in radians per minute.
+   */
+  double mean_motion;
+  /**
+   * Bstar drag term in 1/earth radii.
+   */
+  double bstar;
+  /**
+   * Flag indicating if the orbit is in deep space.
+   */
+  bool deep_space;
+  /**
+   * Four-digit year of the epoch.
+   */
+  int32_t epoch_year;
+  /**
+   * Day of the year of the epoch, including the fractional part of the day.
+   */
+  double epoch_day;
+} OrbitalElements;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Parses the two data lines of a TLE into orbital elements.
+ *
+ * # Arguments
+ * * `line1` - First data line, NUL-terminated.
+ * * `line2` - Second data line, NUL-terminated.
+ * * `elements` - Receives the orbital elements on success.
+ *
+ * # Returns
+ * * `Ok`, `NullPointer` or `InvalidTle`.
+ *
+ * # Safety
+ * The lines must be null or valid NUL-terminated strings, and `elements` null or valid for writes.
+ */
+Sgp4Status sgp4_parse_tle(const char *line1, const char *line2, OrbitalElements *elements);
+
+/**
+ * Performs the SGP4 initialization of a set of orbital elements.
+ *
+ * # Arguments
+ * * `elements` - Orbital elements, as filled by `sgp4_parse_tle`.
+ *
+ * # Returns
+ * * A propagator handle to release with `sgp4_free`, or null if `elements` is null.
+ *
+ * # Safety
+ * `elements` must be null or point to valid orbital elements.
+ */
+Sgp4 *sgp4_new(const OrbitalElements *elements);
+
+/**
+ * Releases a propagator handle. Null handles are ignored.
+ *
+ * # Safety
+ * `sgp4` must be null or a handle returned by `sgp4_new` that was not released yet.
+ */
+void sgp4_free(Sgp4 *sgp4);
+
+/**
+ * Returns the Julian date (UTC) of the epoch of a propagator, or NaN for a null handle.
+ *
+ * # Safety
+ * `sgp4` must be null or a live handle returned by `sgp4_new`.
+ */
+double sgp4_epoch_jd(const Sgp4 *sgp4);
+
+/**
+ * Computes the TEME state vector of the satellite at a time since epoch.
+ *
+ * # Arguments
+ * * `sgp4` - Propagator handle.
+ * * `tsince` - Time since epoch in minutes.
+ * * `position` - Receives the three position components in kilometers.
+ * * `velocity` - Receives the three velocity components in kilometers per second.
+ *
+ * # Returns
+ * * `Ok`, `NullPointer` or the propagation error; the arrays are left untouched on error.
+ *
+ * # Safety
+ * `sgp4` must be null or a live handle, and the arrays null or valid for three writes each.
+ */
+Sgp4Status sgp4_propagate(const Sgp4 *sgp4, double tsince, double *position, double *velocity);
+
+/**
+ * Computes the TEME state vectors of the satellite at several times since epoch.
+ *
+ * # Arguments
+ * * `sgp4` - Propagator handle.
+ * * `times` - Times since epoch in minutes.
+ * * `count` - Number of times.
+ * * `positions` - Receives `3 * count` position components in kilometers.
+ * * `velocities` - Receives `3 * count` velocity components in kilometers per second.
+ *
+ * # Returns
+ * * `Ok`, `NullPointer` or the error of the first state that could not be computed. Such states
+ *   are filled with NaN and the following times are still propagated.
+ *
+ * # Safety
+ * `sgp4` must be null or a live handle, `times` null or valid for `count` reads, and the output
+ * arrays null or valid for `3 * count` writes each.
+ */
+Sgp4Status sgp4_propagate_many(const Sgp4 *sgp4,
+                               const double *times,
+                               size_t count,
+                               double *positions,
+                               double *velocities);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* SGP4_RUST_H */
@@ -0,0 +1,172 @@
+//! Conversions between mean, eccentric and true anomalies.
+//! These functions cover elliptical orbits only; parabolic and hyperbolic eccentricities are rejected
+//! with an error rather than producing meaningless angles.
+
+use crate::kepler::KeplerError;
+use crate::solve_kepler;
+use core::fmt;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Tolerance used when solving Kepler's equation, in radians.
+const KEPLER_TOLERANCE: f64 = 1e-12;
+
+/// Errors that can occur while converting anomalies.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnomalyError {
+    /// The eccentricity is negative or not below 1 (parabolic or hyperbolic orbit).
+    Eccentricity(f64),
+    /// Kepler's equation could not be solved.
+    Kepler(KeplerError),
+}
+
+impl fmt::Display for AnomalyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnomalyError::Eccentricity(e) => write!(f, "eccentricity {} is not elliptical (0 <= e < 1)", e),
+            AnomalyError::Kepler(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl core::error::Error for AnomalyError {}
+
+/// Checks that an eccentricity describes an elliptical orbit.
+///
+/// # Arguments
+/// * `eccentricity` - Eccentricity of the orbit.
+///
+/// # Returns
+/// * `Ok(())`, or an error if the orbit is not elliptical.
+fn check_elliptical(eccentricity: f64) -> Result<(), AnomalyError> {
+    if (0.0..1.0).contains(&eccentricity) {
+        Ok(())
+    } else {
+        Err(AnomalyError::Eccentricity(eccentricity))
+    }
+}
+
+/// Converts a mean anomaly into an eccentric anomaly by solving Kepler's equation.
+///
+/// # Arguments
+/// * `mean_anomaly` - Mean anomaly in radians.
+/// * `eccentricity` - Eccentricity of the orbit.
+///
+/// # Returns
+/// * Eccentric anomaly in radians, or an error if the orbit is not elliptical.
+pub fn mean_to_eccentric(mean_anomaly: f64, eccentricity: f64) -> Result<f64, AnomalyError> {
+    check_elliptical(eccentricity)?;
+    solve_kepler(mean_anomaly, eccentricity, KEPLER_TOLERANCE).map_err(AnomalyError::Kepler)
+}
+
+/// Converts an eccentric anomaly into a mean anomaly (Kepler's equation).
+///
+/// # Arguments
+/// * `eccentric_anomaly` - Eccentric anomaly in radians.
+/// * `eccentricity` - Eccentricity of the orbit.
+///
+/// # Returns
+/// * Mean anomaly in radians, or an error if the orbit is not elliptical.
+pub fn eccentric_to_mean(eccentric_anomaly: f64, eccentricity: f64) -> Result<f64, AnomalyError> {
+    check_elliptical(eccentricity)?;
+    Ok(eccentric_anomaly - eccentricity * eccentric_anomaly.sin())
+}
+
+/// Converts an eccentric anomaly into a true anomaly.
+///
+/// # Arguments
+/// * `eccentric_anomaly` - Eccentric anomaly in radians.
+/// * `eccentricity` - Eccentricity of the orbit.
+///
+/// # Returns
+/// * True anomaly in radians, in the same revolution as the eccentric anomaly, or an error if the
+///   orbit is not elliptical.
+pub fn eccentric_to_true(eccentric_anomaly: f64, eccentricity: f64) -> Result<f64, AnomalyError> {
+    check_elliptical(eccentricity)?;
+    let half: f64 = 0.5 * eccentric_anomaly;
+    let true_anomaly: f64 = 2.0 * ((1.0 + eccentricity).sqrt() * half.sin()).atan2((1.0 - eccentricity).sqrt() * half.cos());
+    Ok(true_anomaly + same_revolution(eccentric_anomaly, true_anomaly))
+}
+
+/// Converts a true anomaly into an eccentric anomaly.
+///
+/// # Arguments
+/// * `true_anomaly` - True anomaly in radians.
+/// * `eccentricity` - Eccentricity of the orbit.
+///
+/// # Returns
+/// * Eccentric anomaly in radians, in the same revolution as the true anomaly, or an error if the
+///   orbit is not elliptical.
+pub fn true_to_eccentric(true_anomaly: f64, eccentricity: f64) -> Result<f64, AnomalyError> {
+    check_elliptical(eccentricity)?;
+    let half: f64 = 0.5 * true_anomaly;
+    let eccentric_anomaly: f64 = 2.0 * ((1.0 - eccentricity).sqrt() * half.sin()).atan2((1.0 + eccentricity).sqrt() * half.cos());
+    Ok(eccentric_anomaly + same_revolution(true_anomaly, eccentric_anomaly))
+}
+
+/// Converts a mean anomaly into a true anomaly.
+///
+/// # Arguments
+/// * `mean_anomaly` - Mean anomaly in radians.
+/// * `eccentricity` - Eccentricity of the orbit.
+///
+/// # Returns
+/// * True anomaly in radians, or an error if the orbit is not elliptical.
+pub fn mean_to_true(mean_anomaly: f64, eccentricity: f64) -> Result<f64, AnomalyError> {
+    eccentric_to_true(mean_to_eccentric(mean_anomaly, eccentricity)?, eccentricity)
+}
+
+/// Converts a true anomaly into a mean anomaly.
+///
+/// # Arguments
+/// * `true_anomaly` - True anomaly in radians.
+/// * `eccentricity` - Eccentricity of the orbit.
+///
+/// # Returns
+/// * Mean anomaly in radians, or an error if the orbit is not elliptical.
+pub fn true_to_mean(true_anomaly: f64, eccentricity: f64) -> Result<f64, AnomalyError> {
+    eccentric_to_mean(true_to_eccentric(true_anomaly, eccentricity)?, eccentricity)
+}
+
+/// Computes the multiple of 2π that brings an angle in (-π, π] into the revolution of a reference angle.
+///
+/// # Arguments
+/// * `reference` - The reference angle in radians.
+/// * `angle` - The angle in radians, in (-π, π].
+///
+/// # Returns
+/// * The offset to add to `angle`, in radians.
+fn same_revolution(reference: f64, angle: f64) -> f64 {
+    crate::TWOPI * ((reference - angle) / crate::TWOPI).round()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests the conversions against Vallado's example 2-1 and their round trips.
+    #[test]
+    fn test_anomaly_conversions() {
+        let eccentric: f64 = mean_to_eccentric(235.4_f64.to_radians(), 0.4).unwrap();
+        assert!((eccentric.to_degrees() - 220.512074767522).abs() < 1e-9);
+
+        let true_anomaly: f64 = mean_to_true(235.4_f64.to_radians(), 0.4).unwrap();
+        assert!((true_to_eccentric(true_anomaly, 0.4).unwrap() - eccentric).abs() < 1e-12);
+        assert!((true_to_mean(true_anomaly, 0.4).unwrap().to_degrees() - 235.4).abs() < 1e-9);
+
+        for k in -8..8 {
+            let mean: f64 = k as f64 * 1.1;
+            let back: f64 = true_to_mean(mean_to_true(mean, 0.2).unwrap(), 0.2).unwrap();
+            assert!((back - mean).abs() < 1e-9);
+        }
+    }
+
+    /// Tests that parabolic and hyperbolic eccentricities are rejected.
+    #[test]
+    fn test_anomaly_eccentricity() {
+        assert_eq!(mean_to_eccentric(1.0, 1.0), Err(AnomalyError::Eccentricity(1.0)));
+        assert_eq!(true_to_mean(1.0, 1.5), Err(AnomalyError::Eccentricity(1.5)));
+        assert_eq!(eccentric_to_true(1.0, -0.1), Err(AnomalyError::Eccentricity(-0.1)));
+        assert_eq!(eccentric_to_true(0.0, 0.0), Ok(0.0));
+    }
+}
@@ -0,0 +1,234 @@
+//! Positions of the Sun and the Moon.
+//! The low-precision analytical models of this module are accurate enough for illumination,
+//! visibility and third-body computations.
+
+use crate::time::{DAYS_PER_CENTURY, JD_J2000};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Astronomical unit in kilometers.
+pub const AU: f64 = 149597870.7;
+
+/// Largest periodic terms of the lunar longitude and distance (Meeus, table 47.A): multipliers of
+/// the fundamental arguments (D, M, M', F), then the longitude in 1e-6 degrees and the distance in meters.
+#[rustfmt::skip]
+const MOON_LONGITUDE_TERMS: [([f64; 4], f64, f64); 32] = [
+    ([ 0.0,  0.0,  1.0,  0.0], 6288774.0, -20905355.0),
+    ([ 2.0,  0.0, -1.0,  0.0], 1274027.0,  -3699111.0),
+    ([ 2.0,  0.0,  0.0,  0.0],  658314.0,  -2955968.0),
+    ([ 0.0,  0.0,  2.0,  0.0],  213618.0,   -569925.0),
+    ([ 0.0,  1.0,  0.0,  0.0], -185116.0,     48888.0),
+    ([ 0.0,  0.0,  0.0,  2.0], -114332.0,     -3149.0),
+    ([ 2.0,  0.0, -2.0,  0.0],   58793.0,    246158.0),
+    ([ 2.0, -1.0, -1.0,  0.0],   57066.0,   -152138.0),
+    ([ 2.0,  0.0,  1.0,  0.0],   53322.0,   -170733.0),
+    ([ 2.0, -1.0,  0.0,  0.0],   45758.0,   -204586.0),
+    ([ 0.0,  1.0, -1.0,  0.0],  -40923.0,   -129620.0),
+    ([ 1.0,  0.0,  0.0,  0.0],  -34720.0,    108743.0),
+    ([ 0.0,  1.0,  1.0,  0.0],  -30383.0,    104755.0),
+    ([ 2.0,  0.0,  0.0, -2.0],   15327.0,     10321.0),
+    ([ 0.0,  0.0,  1.0,  2.0],  -12528.0,         0.0),
+    ([ 0.0,  0.0,  1.0, -2.0],   10980.0,     79661.0),
+    ([ 4.0,  0.0, -1.0,  0.0],   10675.0,    -34782.0),
+    ([ 0.0,  0.0,  3.0,  0.0],   10034.0,    -23210.0),
+    ([ 4.0,  0.0, -2.0,  0.0],    8548.0,    -21636.0),
+    ([ 2.0,  1.0, -1.0,  0.0],   -7888.0,     24208.0),
+    ([ 2.0,  1.0,  0.0,  0.0],   -6766.0,     30824.0),
+    ([ 1.0,  0.0, -1.0,  0.0],   -5163.0,     -8379.0),
+    ([ 1.0,  1.0,  0.0,  0.0],    4987.0,    -16675.0),
+    ([ 2.0, -1.0,  1.0,  0.0],    4036.0,    -12831.0),
+    ([ 2.0,  0.0,  2.0,  0.0],    3994.0,    -10445.0),
+    ([ 4.0,  0.0,  0.0,  0.0],    3861.0,    -11650.0),
+    ([ 2.0,  0.0, -3.0,  0.0],    3665.0,     14403.0),
+    ([ 0.0,  1.0, -2.0,  0.0],   -2689.0,     -7003.0),
+    ([ 2.0,  0.0, -1.0,  2.0],   -2602.0,         0.0),
+    ([ 2.0, -1.0, -2.0,  0.0],    2390.0,     10056.0),
+    ([ 1.0,  0.0,  1.0,  0.0],   -2348.0,      6322.0),
+    ([ 2.0, -2.0,  0.0,  0.0],    2236.0,     -9884.0),
+];
+
+/// Largest periodic terms of the lunar latitude (Meeus, table 47.B): multipliers of the fundamental
+/// arguments (D, M, M', F), then the latitude in 1e-6 degrees.
+#[rustfmt::skip]
+const MOON_LATITUDE_TERMS: [([f64; 4], f64); 28] = [
+    ([ 0.0,  0.0,  0.0,  1.0], 5128122.0),
+    ([ 0.0,  0.0,  1.0,  1.0],  280602.0),
+    ([ 0.0,  0.0,  1.0, -1.0],  277693.0),
+    ([ 2.0,  0.0,  0.0, -1.0],  173237.0),
+    ([ 2.0,  0.0, -1.0,  1.0],   55413.0),
+    ([ 2.0,  0.0, -1.0, -1.0],   46271.0),
+    ([ 2.0,  0.0,  0.0,  1.0],   32573.0),
+    ([ 0.0,  0.0,  2.0,  1.0],   17198.0),
+    ([ 2.0,  0.0,  1.0, -1.0],    9266.0),
+    ([ 0.0,  0.0,  2.0, -1.0],    8822.0),
+    ([ 2.0, -1.0,  0.0, -1.0],    8216.0),
+    ([ 2.0,  0.0, -2.0, -1.0],    4324.0),
+    ([ 2.0,  0.0,  1.0,  1.0],    4200.0),
+    ([ 2.0,  1.0,  0.0, -1.0],   -3359.0),
+    ([ 2.0, -1.0, -1.0,  1.0],    2463.0),
+    ([ 2.0, -1.0,  0.0,  1.0],    2211.0),
+    ([ 2.0, -1.0, -1.0, -1.0],    2065.0),
+    ([ 0.0,  1.0, -1.0, -1.0],   -1870.0),
+    ([ 4.0,  0.0, -1.0, -1.0],    1828.0),
+    ([ 0.0,  1.0,  0.0,  1.0],   -1794.0),
+    ([ 0.0,  0.0,  0.0,  3.0],   -1749.0),
+    ([ 0.0,  1.0, -1.0,  1.0],   -1565.0),
+    ([ 1.0,  0.0,  0.0,  1.0],   -1491.0),
+    ([ 0.0,  1.0,  1.0,  1.0],   -1475.0),
+    ([ 0.0,  1.0,  1.0, -1.0],   -1410.0),
+    ([ 0.0,  1.0,  0.0, -1.0],   -1344.0),
+    ([ 1.0,  0.0,  0.0, -1.0],   -1335.0),
+    ([ 0.0,  0.0,  3.0,  1.0],    1107.0),
+];
+
+/// Computes the position of the Sun with the low-precision algorithm of the Astronomical Almanac,
+/// accurate to about 0.01° between 1950 and 2050.
+///
+/// The mean equator of date frame differs from TEME by less than an arcminute, so the result can be
+/// used directly with SGP4 state vectors.
+///
+/// # Arguments
+/// * `jd` - Julian date (UT1 or UTC).
+///
+/// # Returns
+/// * Geocentric position of the Sun in the mean equator of date frame, in kilometers.
+pub fn sun_position(jd: f64) -> [f64; 3] {
+    let t: f64 = (jd - JD_J2000) / DAYS_PER_CENTURY;
+    let mean_longitude: f64 = (280.460 + 36000.771 * t).to_radians();
+    let mean_anomaly: f64 = (357.5291092 + 35999.05034 * t).to_radians();
+    let longitude: f64 = mean_longitude
+        + (1.914666471 * mean_anomaly.sin() + 0.019994643 * (2.0 * mean_anomaly).sin()).to_radians();
+    let distance: f64 = 1.000140612 - 0.016708617 * mean_anomaly.cos() - 0.000139589 * (2.0 * mean_anomaly).cos();
+    let obliquity: f64 = (23.439291 - 0.0130042 * t).to_radians();
+
+    let (sin_lon, cos_lon) = longitude.sin_cos();
+    [
+        distance * AU * cos_lon,
+        distance * AU * obliquity.cos() * sin_lon,
+        distance * AU * obliquity.sin() * sin_lon,
+    ]
+}
+
+/// Computes the ecliptic coordinates of the Moon from the truncated ELP-2000/82 series of Meeus
+/// (chapter 47), accurate to about 0.01°.
+///
+/// # Arguments
+/// * `jd` - Julian date (TT).
+///
+/// # Returns
+/// * Ecliptic longitude and latitude of date in radians, and distance in kilometers.
+fn moon_ecliptic(jd: f64) -> (f64, f64, f64) {
+    let t: f64 = (jd - JD_J2000) / DAYS_PER_CENTURY;
+    let t2: f64 = t * t;
+    let t3: f64 = t2 * t;
+    let t4: f64 = t3 * t;
+
+    let mean_longitude: f64 = 218.3164477 + 481267.88123421 * t - 0.0015786 * t2 + t3 / 538841.0 - t4 / 65194000.0;
+    // Fundamental arguments (D, M, M', F) in degrees.
+    let arguments: [f64; 4] = [
+        297.8501921 + 445267.1114034 * t - 0.0018819 * t2 + t3 / 545868.0 - t4 / 113065000.0,
+        357.5291092 + 35999.0502909 * t - 0.0001536 * t2 + t3 / 24490000.0,
+        134.9633964 + 477198.8675055 * t + 0.0087414 * t2 + t3 / 69699.0 - t4 / 14712000.0,
+        93.2720950 + 483202.0175233 * t - 0.0036539 * t2 - t3 / 3526000.0 + t4 / 863310000.0,
+    ];
+    // Decrease of the eccentricity of the Earth's orbit, applied to the terms involving M.
+    let e: f64 = 1.0 - 0.002516 * t - 0.0000074 * t2;
+    let angle = |multipliers: [f64; 4]| (0..4).map(|k| multipliers[k] * arguments[k]).sum::<f64>().to_radians();
+    let factor = |multipliers: [f64; 4]| e.powi(multipliers[1].abs() as i32);
+
+    let mut longitude: f64 = 0.0;
+    let mut distance: f64 = 0.0;
+    for (multipliers, l, r) in MOON_LONGITUDE_TERMS {
+        let (sin, cos) = angle(multipliers).sin_cos();
+        longitude += factor(multipliers) * l * sin;
+        distance += factor(multipliers) * r * cos;
+    }
+    let mut latitude: f64 = 0.0;
+    for (multipliers, b) in MOON_LATITUDE_TERMS {
+        latitude += factor(multipliers) * b * angle(multipliers).sin();
+    }
+
+    // Additive terms for the action of Venus, Jupiter and the flattening of the Earth.
+    let a1: f64 = (119.75 + 131.849 * t).to_radians();
+    let a2: f64 = (53.09 + 479264.290 * t).to_radians();
+    let a3: f64 = (313.45 + 481266.484 * t).to_radians();
+    let l0: f64 = mean_longitude.to_radians();
+    let f: f64 = arguments[3].to_radians();
+    let m_moon: f64 = arguments[2].to_radians();
+    longitude += 3958.0 * a1.sin() + 1962.0 * (l0 - f).sin() + 318.0 * a2.sin();
+    latitude += -2235.0 * l0.sin() + 382.0 * a3.sin() + 175.0 * (a1 - f).sin() + 175.0 * (a1 + f).sin();
+    latitude += 127.0 * (l0 - m_moon).sin() - 115.0 * (l0 + m_moon).sin();
+
+    (
+        (mean_longitude + longitude * 1e-6).rem_euclid(360.0).to_radians(),
+        (latitude * 1e-6).to_radians(),
+        385000.56 + distance * 1e-3,
+    )
+}
+
+/// Computes the position of the Moon from a truncated ELP-2000/82 series (Meeus, chapter 47),
+/// accurate to about 0.01°.
+///
+/// # Arguments
+/// * `jd` - Julian date (TT; UTC is accurate enough for most uses).
+///
+/// # Returns
+/// * Geocentric position of the Moon in the mean equator of date frame, in kilometers.
+pub fn moon_position(jd: f64) -> [f64; 3] {
+    let (longitude, latitude, distance) = moon_ecliptic(jd);
+    let t: f64 = (jd - JD_J2000) / DAYS_PER_CENTURY;
+    let obliquity: f64 = (23.439291 - 0.0130042 * t).to_radians();
+
+    let (sin_lon, cos_lon) = longitude.sin_cos();
+    let (sin_lat, cos_lat) = latitude.sin_cos();
+    let (sin_eps, cos_eps) = obliquity.sin_cos();
+    let ecliptic: [f64; 3] = [cos_lat * cos_lon, cos_lat * sin_lon, sin_lat];
+    [
+        distance * ecliptic[0],
+        distance * (cos_eps * ecliptic[1] - sin_eps * ecliptic[2]),
+        distance * (sin_eps * ecliptic[1] + cos_eps * ecliptic[2]),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests the Sun position against Vallado's example 5-1 (2 April 2006, 00:00 UT).
+    #[test]
+    fn test_sun_position() {
+        let sun = sun_position(crate::time::julian_date(2006, 4, 2, 0, 0, 0.0));
+
+        assert!((sun[0] / AU - 0.9771945).abs() < 1e-4);
+        assert!((sun[1] / AU - 0.1924424).abs() < 1e-4);
+        assert!((sun[2] / AU - 0.0834308).abs() < 1e-4);
+    }
+
+    /// Tests the Sun distance near perihelion and aphelion.
+    #[test]
+    fn test_sun_distance() {
+        let distance = |jd: f64| {
+            let sun = sun_position(jd);
+            (sun[0] * sun[0] + sun[1] * sun[1] + sun[2] * sun[2]).sqrt() / AU
+        };
+
+        assert!((distance(crate::time::julian_date(2024, 1, 3, 0, 0, 0.0)) - 0.9833).abs() < 2e-4);
+        assert!((distance(crate::time::julian_date(2024, 7, 5, 0, 0, 0.0)) - 1.0167).abs() < 2e-4);
+    }
+
+    /// Tests the Moon position against Meeus' example 47.a (12 April 1992, 00:00 TT).
+    #[test]
+    fn test_moon_position() {
+        let (longitude, latitude, distance) = moon_ecliptic(2448724.5);
+        assert!((longitude.to_degrees() - 133.162655).abs() < 0.01);
+        assert!((latitude.to_degrees() + 3.229126).abs() < 0.01);
+        assert!((distance - 368409.7).abs() < 30.0);
+
+        let moon = moon_position(2448724.5);
+        let right_ascension: f64 = moon[1].atan2(moon[0]).to_degrees();
+        let declination: f64 = (moon[2] / distance).asin().to_degrees();
+        // Meeus gives apparent coordinates, which include about 0.004° of nutation.
+        assert!((right_ascension - 134.688470).abs() < 0.02);
+        assert!((declination - 13.768368).abs() < 0.02);
+    }
+}
@@ -0,0 +1,80 @@
+//! Exponential model of the atmospheric density.
+//! The density decays exponentially within altitude bands, with the reference densities and
+//! scale heights of Vallado's table 8-4 (CIRA-72 based), from the ground to 1000 km.
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Base altitude (km), density at the base altitude (kg/m³) and scale height (km) of each band.
+#[rustfmt::skip]
+const BANDS: [(f64, f64, f64); 28] = [
+    (0.0, 1.225, 7.249),
+    (25.0, 3.899e-2, 6.349),
+    (30.0, 1.774e-2, 6.682),
+    (40.0, 3.972e-3, 7.554),
+    (50.0, 1.057e-3, 8.382),
+    (60.0, 3.206e-4, 7.714),
+    (70.0, 8.770e-5, 6.549),
+    (80.0, 1.905e-5, 5.799),
+    (90.0, 3.396e-6, 5.382),
+    (100.0, 5.297e-7, 5.877),
+    (110.0, 9.661e-8, 7.263),
+    (120.0, 2.438e-8, 9.473),
+    (130.0, 8.484e-9, 12.636),
+    (140.0, 3.845e-9, 16.149),
+    (150.0, 2.070e-9, 22.523),
+    (180.0, 5.464e-10, 29.740),
+    (200.0, 2.789e-10, 37.105),
+    (250.0, 7.248e-11, 45.546),
+    (300.0, 2.418e-11, 53.628),
+    (350.0, 9.518e-12, 53.298),
+    (400.0, 3.725e-12, 58.515),
+    (450.0, 1.585e-12, 60.828),
+    (500.0, 6.967e-13, 63.822),
+    (600.0, 1.454e-13, 71.835),
+    (700.0, 3.614e-14, 88.667),
+    (800.0, 1.170e-14, 124.64),
+    (900.0, 5.245e-15, 181.05),
+    (1000.0, 3.019e-15, 268.00),
+];
+
+/// Computes the density of the atmosphere.
+///
+/// # Arguments
+/// * `altitude` - Altitude above the ellipsoid in kilometers. Negative altitudes use the sea-level
+///   density; altitudes above 1000 km extrapolate the last band.
+///
+/// # Returns
+/// * Density in kg/m³.
+pub fn density(altitude: f64) -> f64 {
+    let altitude: f64 = altitude.max(0.0);
+    let &(base, reference, scale_height) =
+        BANDS.iter().rev().find(|&&(base, _, _)| altitude >= base).unwrap_or(&BANDS[0]);
+    reference * (-(altitude - base) / scale_height).exp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests the density at the base of some bands and between them.
+    #[test]
+    fn test_density() {
+        assert_eq!(density(0.0), 1.225);
+        assert_eq!(density(-1.0), 1.225);
+        assert_eq!(density(400.0), 3.725e-12);
+        assert!((density(420.0) - 3.725e-12 * (-20.0_f64 / 58.515).exp()).abs() < 1e-20);
+        assert!(density(1500.0) < density(1000.0));
+    }
+
+    /// Tests that the density decreases with altitude and is nearly continuous between bands.
+    #[test]
+    fn test_density_continuity() {
+        for pair in BANDS.windows(2) {
+            let below: f64 = density(pair[1].0 - 1e-9);
+            let above: f64 = density(pair[1].0);
+            assert!(above < density(pair[0].0));
+            assert!((below - above).abs() / above < 0.1, "{} {} {}", pair[1].0, below, above);
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! Beta angle of an orbit.
+//! The beta angle is the angle between the orbital plane and the direction of the Sun. It drives
+//! the duration of eclipses and the thermal environment of a satellite.
+
+use crate::astro::sun_position;
+use crate::propagator::PropagationError;
+use crate::{OrbitalElements, Sgp4, StateVector};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Computes the beta angle of the orbit containing a state vector.
+///
+/// # Arguments
+/// * `state` - State vector of the satellite (km, km/s).
+/// * `sun` - Geocentric position of the Sun in kilometers, in the same frame.
+///
+/// # Returns
+/// * The beta angle in radians, positive when the Sun is on the side of the orbit normal.
+pub fn beta_angle(state: &StateVector, sun: [f64; 3]) -> f64 {
+    let [x, y, z] = state.position;
+    let [vx, vy, vz] = state.velocity;
+    let normal: [f64; 3] = [y * vz - z * vy, z * vx - x * vz, x * vy - y * vx];
+    let normal_norm: f64 = (normal[0] * normal[0] + normal[1] * normal[1] + normal[2] * normal[2]).sqrt();
+    let sun_norm: f64 = (sun[0] * sun[0] + sun[1] * sun[1] + sun[2] * sun[2]).sqrt();
+    ((normal[0] * sun[0] + normal[1] * sun[1] + normal[2] * sun[2]) / (normal_norm * sun_norm)).asin()
+}
+
+/// Computes the beta angle of an orbit over a time window.
+///
+/// # Arguments
+/// * `elements` - Orbital elements of the satellite.
+/// * `start` - Start of the window in minutes since epoch.
+/// * `end` - End of the window in minutes since epoch.
+/// * `step` - Time step in minutes.
+///
+/// # Returns
+/// * The time since epoch and beta angle in radians of each sample, or the first propagation error.
+pub fn beta_angle_history(elements: &OrbitalElements, start: f64, end: f64, step: f64) -> Result<Vec<(f64, f64)>, PropagationError> {
+    if step <= 0.0 || end < start {
+        return Ok(Vec::new());
+    }
+    let count: usize = ((end - start) / step).floor() as usize;
+    let propagator: Sgp4 = Sgp4::new(elements);
+    (0..=count)
+        .map(|i| {
+            let tsince: f64 = start + i as f64 * step;
+            Ok((tsince, beta_angle(&propagator.propagate(tsince)?, sun_position(elements.jd_at(tsince)))))
+        })
+        .collect()
+}
+
+impl OrbitalElements {
+    /// Computes the beta angle of the orbit at a time since epoch (see `beta_angle`).
+    ///
+    /// # Arguments
+    /// * `tsince` - Time since epoch in minutes.
+    ///
+    /// # Returns
+    /// * The beta angle in radians, or the propagation error.
+    pub fn beta_angle(&self, tsince: f64) -> Result<f64, PropagationError> {
+        Ok(beta_angle(&Sgp4::new(self).propagate(tsince)?, sun_position(self.jd_at(tsince))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::iss_elements;
+
+    /// Tests the beta angle for the Sun along and perpendicular to the orbit normal.
+    #[test]
+    fn test_beta_angle() {
+        let state = StateVector {
+            position: [7000.0, 0.0, 0.0],
+            velocity: [0.0, 7.5, 0.0],
+        };
+
+        assert!((beta_angle(&state, [0.0, 0.0, 1.0e8]).to_degrees() - 90.0).abs() < 1e-9);
+        assert!((beta_angle(&state, [-1.0e8, 1.0e8, 0.0])).abs() < 1e-12);
+        assert!((beta_angle(&state, [1.0e8, 0.0, -1.0e8]).to_degrees() + 45.0).abs() < 1e-9);
+    }
+
+    /// Tests that the beta angle of the ISS stays within the inclination plus the obliquity.
+    #[test]
+    fn test_beta_angle_history() {
+        let elements = iss_elements();
+
+        let history = beta_angle_history(&elements, 0.0, 30.0 * 1440.0, 1440.0).unwrap();
+
+        assert_eq!(history.len(), 31);
+        assert_eq!(history[30].0, 30.0 * 1440.0);
+        for &(_, beta) in &history {
+            assert!(beta.abs().to_degrees() < 51.7 + 23.5);
+        }
+        assert!((history[0].1 - history[30].1).abs() > 1e-3);
+
+        let mut decayed = elements;
+        decayed.eccentricity = 0.065;
+        assert!(matches!(beta_angle_history(&decayed, 0.0, 1440.0, 60.0), Err(PropagationError::Decayed { .. })));
+    }
+}
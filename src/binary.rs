@@ -0,0 +1,239 @@
+//! Compact binary storage of ephemerides and element sets.
+//! Files start with an 8-byte header (magic `ORBR`, format version, content kind) followed by
+//! fixed-size little-endian records, so they reload without parsing text or propagating again.
+
+use crate::{OrbitalElements, StateVector};
+use std::io::{self, ErrorKind, Read, Write};
+
+/// Magic bytes at the start of every file.
+const MAGIC: [u8; 4] = *b"ORBR";
+/// Version of the format written by this module.
+pub const VERSION: u16 = 1;
+/// Content kind of an ephemeris file.
+const KIND_EPHEMERIS: u8 = 1;
+/// Content kind of an element set file.
+const KIND_ELEMENTS: u8 = 2;
+/// Size of an ephemeris record: Julian date, position and velocity.
+const EPHEMERIS_RECORD: usize = 7 * 8;
+/// Size of an element set record: catalog number, 8 floats, epoch year and deep-space flag.
+const ELEMENTS_RECORD: usize = 4 + 8 * 8 + 4 + 1;
+
+/// Writes the file header.
+fn write_header<W: Write>(writer: &mut W, kind: u8) -> io::Result<()> {
+    writer.write_all(&MAGIC)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+    writer.write_all(&[kind, 0])
+}
+
+/// Reads and checks the file header.
+fn read_header<R: Read>(reader: &mut R, kind: u8) -> io::Result<()> {
+    let mut header: [u8; 8] = [0; 8];
+    reader.read_exact(&mut header)?;
+    if header[..4] != MAGIC {
+        return Err(io::Error::new(ErrorKind::InvalidData, "not an orbitalrs binary file"));
+    }
+    let version: u16 = u16::from_le_bytes([header[4], header[5]]);
+    if version != VERSION {
+        return Err(io::Error::new(ErrorKind::InvalidData, format!("unsupported binary format version {}", version)));
+    }
+    if header[6] != kind {
+        return Err(io::Error::new(ErrorKind::InvalidData, format!("unexpected content kind {}", header[6])));
+    }
+    Ok(())
+}
+
+/// Fills a record buffer, returning `false` at a clean end of file and an error on a truncated record.
+fn read_record<R: Read>(reader: &mut R, record: &mut [u8]) -> io::Result<bool> {
+    let mut filled: usize = 0;
+    while filled < record.len() {
+        match reader.read(&mut record[filled..]) {
+            Ok(0) if filled == 0 => return Ok(false),
+            Ok(0) => return Err(io::Error::new(ErrorKind::UnexpectedEof, "truncated record")),
+            Ok(count) => filled += count,
+            Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(true)
+}
+
+/// Decodes the little-endian float at an offset of a record.
+fn float(record: &[u8], offset: usize) -> f64 {
+    let mut bytes: [u8; 8] = [0; 8];
+    bytes.copy_from_slice(&record[offset..offset + 8]);
+    f64::from_le_bytes(bytes)
+}
+
+/// Writes the ephemeris of a satellite.
+///
+/// # Arguments
+/// * `writer` - Destination of the file.
+/// * `norad_id` - Catalog number of the satellite.
+/// * `states` - `(jd, state)` pairs, such as an `Ephemeris`.
+///
+/// # Returns
+/// * The number of states written, or an error if writing fails.
+pub fn write_ephemeris<W: Write>(
+    writer: &mut W,
+    norad_id: u32,
+    states: impl IntoIterator<Item = (f64, StateVector)>,
+) -> io::Result<usize> {
+    write_header(writer, KIND_EPHEMERIS)?;
+    writer.write_all(&norad_id.to_le_bytes())?;
+    let mut count: usize = 0;
+    for (jd, state) in states {
+        let mut record: Vec<u8> = Vec::with_capacity(EPHEMERIS_RECORD);
+        for value in [jd].iter().chain(&state.position).chain(&state.velocity) {
+            record.extend_from_slice(&value.to_le_bytes());
+        }
+        writer.write_all(&record)?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Reads an ephemeris written by `write_ephemeris`.
+///
+/// # Arguments
+/// * `reader` - Source of the file.
+///
+/// # Returns
+/// * The catalog number and the `(jd, state)` pairs, or an error if the file is not an ephemeris
+///   of this format version or is truncated.
+pub fn read_ephemeris<R: Read>(reader: &mut R) -> io::Result<(u32, Vec<(f64, StateVector)>)> {
+    read_header(reader, KIND_EPHEMERIS)?;
+    let mut id: [u8; 4] = [0; 4];
+    reader.read_exact(&mut id)?;
+
+    let mut states: Vec<(f64, StateVector)> = Vec::new();
+    let mut record: [u8; EPHEMERIS_RECORD] = [0; EPHEMERIS_RECORD];
+    while read_record(reader, &mut record)? {
+        states.push((
+            float(&record, 0),
+            StateVector {
+                position: [0, 1, 2].map(|k| float(&record, 8 + 8 * k)),
+                velocity: [0, 1, 2].map(|k| float(&record, 32 + 8 * k)),
+            },
+        ));
+    }
+    Ok((u32::from_le_bytes(id), states))
+}
+
+/// Writes a catalog of element sets.
+///
+/// # Arguments
+/// * `writer` - Destination of the file.
+/// * `catalog` - Catalog numbers and orbital elements of the satellites.
+///
+/// # Returns
+/// * An error if writing fails.
+pub fn write_elements<W: Write>(writer: &mut W, catalog: &[(u32, OrbitalElements)]) -> io::Result<()> {
+    write_header(writer, KIND_ELEMENTS)?;
+    for (norad_id, elements) in catalog {
+        let mut record: Vec<u8> = Vec::with_capacity(ELEMENTS_RECORD);
+        record.extend_from_slice(&norad_id.to_le_bytes());
+        for value in [
+            elements.inclination,
+            elements.raan,
+            elements.eccentricity,
+            elements.arg_perigee,
+            elements.mean_anomaly,
+            elements.mean_motion,
+            elements.bstar,
+            elements.epoch_day,
+        ] {
+            record.extend_from_slice(&value.to_le_bytes());
+        }
+        record.extend_from_slice(&elements.epoch_year.to_le_bytes());
+        record.push(elements.deep_space as u8);
+        writer.write_all(&record)?;
+    }
+    Ok(())
+}
+
+/// Reads a catalog of element sets written by `write_elements`.
+///
+/// # Arguments
+/// * `reader` - Source of the file.
+///
+/// # Returns
+/// * The catalog numbers and orbital elements, or an error if the file is not an element set file
+///   of this format version or is truncated.
+pub fn read_elements<R: Read>(reader: &mut R) -> io::Result<Vec<(u32, OrbitalElements)>> {
+    read_header(reader, KIND_ELEMENTS)?;
+    let mut catalog: Vec<(u32, OrbitalElements)> = Vec::new();
+    let mut record: [u8; ELEMENTS_RECORD] = [0; ELEMENTS_RECORD];
+    while read_record(reader, &mut record)? {
+        let norad_id: u32 = u32::from_le_bytes([record[0], record[1], record[2], record[3]]);
+        let year_offset: usize = 4 + 8 * 8;
+        let epoch_year: i32 = i32::from_le_bytes([
+            record[year_offset],
+            record[year_offset + 1],
+            record[year_offset + 2],
+            record[year_offset + 3],
+        ]);
+        catalog.push((
+            norad_id,
+            OrbitalElements {
+                inclination: float(&record, 4),
+                raan: float(&record, 12),
+                eccentricity: float(&record, 20),
+                arg_perigee: float(&record, 28),
+                mean_anomaly: float(&record, 36),
+                mean_motion: float(&record, 44),
+                bstar: float(&record, 52),
+                epoch_day: float(&record, 60),
+                epoch_year,
+                deep_space: record[ELEMENTS_RECORD - 1] != 0,
+            },
+        ));
+    }
+    Ok(catalog)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Ephemeris, Propagator, Sgp4, sgp4};
+    use crate::test_support::iss_elements;
+
+    /// Tests that an ephemeris reloads bit for bit, and that truncated or foreign files are rejected.
+    #[test]
+    fn test_ephemeris_round_trip() {
+        let propagator: Sgp4 = Sgp4::new(&iss_elements());
+        let start: f64 = propagator.epoch_jd();
+        let mut file: Vec<u8> = Vec::new();
+        let count: usize = write_ephemeris(&mut file, 25544, Ephemeris::new(&propagator, start, start + 0.1, 60.0)).unwrap();
+        assert_eq!(count, 145);
+        assert_eq!(file.len(), 8 + 4 + 145 * EPHEMERIS_RECORD);
+
+        let (norad_id, states) = read_ephemeris(&mut file.as_slice()).unwrap();
+        assert_eq!(norad_id, 25544);
+        assert_eq!(states.len(), 145);
+        let expected: Vec<(f64, StateVector)> = Ephemeris::new(&propagator, start, start + 0.1, 60.0).collect();
+        assert_eq!(states[100].0, expected[100].0);
+        assert_eq!(states[100].1.position, expected[100].1.position);
+        assert_eq!(states[100].1.velocity, expected[100].1.velocity);
+
+        let truncated: &[u8] = &file[..file.len() - 3];
+        assert_eq!(read_ephemeris(&mut &truncated[..]).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(read_elements(&mut file.as_slice()).err().map(|error| error.kind()), Some(ErrorKind::InvalidData));
+        file[4] = 2;
+        assert_eq!(read_ephemeris(&mut file.as_slice()).unwrap_err().kind(), ErrorKind::InvalidData);
+    }
+
+    /// Tests that reloaded element sets propagate exactly like the originals.
+    #[test]
+    fn test_elements_round_trip() {
+        let mut file: Vec<u8> = Vec::new();
+        write_elements(&mut file, &[(25544, iss_elements()), (25545, iss_elements())]).unwrap();
+        let catalog: Vec<(u32, OrbitalElements)> = read_elements(&mut file.as_slice()).unwrap();
+
+        assert_eq!(catalog.len(), 2);
+        assert_eq!(catalog[1].0, 25545);
+        let (_, elements) = &catalog[0];
+        assert_eq!(elements.epoch_year, 2008);
+        assert_eq!(elements.deep_space, iss_elements().deep_space);
+        assert_eq!(sgp4(720.0, elements).position, sgp4(720.0, &iss_elements()).position);
+    }
+}
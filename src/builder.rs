@@ -0,0 +1,238 @@
+//! Construction of orbital elements from Keplerian inputs.
+//! `OrbitalElements::builder()` takes the elements in the units of mission design (degrees,
+//! revolutions per day or kilometers) and validates them, so element sets of hypothetical
+//! missions can be built without writing TLE text.
+
+use crate::gravity::GravityConstants;
+use crate::{OrbitalElements, TWOPI, XKE, XKMPER, XMNPDA, brouwer_mean_motion, time};
+use core::fmt;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Represents the reasons why a builder cannot produce orbital elements.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ElementsError {
+    /// No epoch was given.
+    MissingEpoch,
+    /// Neither the mean motion nor the semi-major axis was given.
+    MissingSize,
+    /// Both the mean motion and the semi-major axis were given.
+    ConflictingSize,
+    /// The epoch day is outside the year.
+    EpochDay(f64),
+    /// The inclination in degrees is outside [0, 180].
+    Inclination(f64),
+    /// The eccentricity is outside [0, 1).
+    Eccentricity(f64),
+    /// The mean motion in revolutions per day is not positive.
+    MeanMotion(f64),
+    /// The perigee altitude in kilometers is below the surface of the Earth.
+    Perigee(f64),
+}
+
+impl fmt::Display for ElementsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ElementsError::MissingEpoch => write!(f, "Missing epoch"),
+            ElementsError::MissingSize => write!(f, "Missing mean motion or semi-major axis"),
+            ElementsError::ConflictingSize => write!(f, "Both the mean motion and the semi-major axis are given"),
+            ElementsError::EpochDay(day) => write!(f, "Epoch day {} outside the year", day),
+            ElementsError::Inclination(inclination) => write!(f, "Inclination {}° outside [0, 180]", inclination),
+            ElementsError::Eccentricity(eccentricity) => write!(f, "Eccentricity {} outside [0, 1)", eccentricity),
+            ElementsError::MeanMotion(mean_motion) => write!(f, "Mean motion {} rev/day is not positive", mean_motion),
+            ElementsError::Perigee(altitude) => write!(f, "Perigee altitude {} km below the surface", altitude),
+        }
+    }
+}
+
+impl core::error::Error for ElementsError {}
+
+/// Collects the elements of an orbit before validating them.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ElementsBuilder {
+    /// Four-digit year and day of the year of the epoch.
+    epoch: Option<(i32, f64)>,
+    /// Inclination in degrees.
+    inclination: f64,
+    /// Right ascension of the ascending node in degrees.
+    raan: f64,
+    /// Eccentricity.
+    eccentricity: f64,
+    /// Argument of perigee in degrees.
+    arg_perigee: f64,
+    /// Mean anomaly in degrees.
+    mean_anomaly: f64,
+    /// Kozai mean motion in revolutions per day, as in TLEs.
+    mean_motion: Option<f64>,
+    /// Mean semi-major axis in kilometers.
+    semi_major_axis: Option<f64>,
+    /// B* drag term in 1/earth radii.
+    bstar: f64,
+}
+
+impl OrbitalElements {
+    /// Starts building orbital elements from Keplerian inputs. The angles, the eccentricity and
+    /// B* default to zero; the epoch and the size of the orbit are required.
+    pub fn builder() -> ElementsBuilder {
+        ElementsBuilder::default()
+    }
+}
+
+impl ElementsBuilder {
+    /// Sets the epoch from a year and a day of the year.
+    ///
+    /// # Arguments
+    /// * `year` - Four-digit year.
+    /// * `day` - Day of the year, 1.0 at midnight on January 1st, with its fractional part.
+    pub fn epoch(self, year: i32, day: f64) -> ElementsBuilder {
+        ElementsBuilder { epoch: Some((year, day)), ..self }
+    }
+
+    /// Sets the epoch from a Julian date (UTC).
+    pub fn epoch_jd(self, jd: f64) -> ElementsBuilder {
+        ElementsBuilder { epoch: Some(time::day_of_year(jd)), ..self }
+    }
+
+    /// Sets the inclination in degrees.
+    pub fn inclination_deg(self, inclination: f64) -> ElementsBuilder {
+        ElementsBuilder { inclination, ..self }
+    }
+
+    /// Sets the right ascension of the ascending node in degrees.
+    pub fn raan_deg(self, raan: f64) -> ElementsBuilder {
+        ElementsBuilder { raan, ..self }
+    }
+
+    /// Sets the eccentricity.
+    pub fn eccentricity(self, eccentricity: f64) -> ElementsBuilder {
+        ElementsBuilder { eccentricity, ..self }
+    }
+
+    /// Sets the argument of perigee in degrees.
+    pub fn arg_perigee_deg(self, arg_perigee: f64) -> ElementsBuilder {
+        ElementsBuilder { arg_perigee, ..self }
+    }
+
+    /// Sets the mean anomaly in degrees.
+    pub fn mean_anomaly_deg(self, mean_anomaly: f64) -> ElementsBuilder {
+        ElementsBuilder { mean_anomaly, ..self }
+    }
+
+    /// Sets the size of the orbit from the mean motion in revolutions per day, with the Kozai
+    /// convention of TLEs.
+    pub fn mean_motion_rev_per_day(self, mean_motion: f64) -> ElementsBuilder {
+        ElementsBuilder {
+            mean_motion: Some(mean_motion),
+            ..self
+        }
+    }
+
+    /// Sets the size of the orbit from the mean semi-major axis in kilometers, as returned by
+    /// `OrbitalElements::semi_major_axis_km`.
+    pub fn semi_major_axis_km(self, semi_major_axis: f64) -> ElementsBuilder {
+        ElementsBuilder {
+            semi_major_axis: Some(semi_major_axis),
+            ..self
+        }
+    }
+
+    /// Sets the B* drag term in 1/earth radii.
+    pub fn bstar(self, bstar: f64) -> ElementsBuilder {
+        ElementsBuilder { bstar, ..self }
+    }
+
+    /// Validates the inputs and builds the elements.
+    ///
+    /// # Returns
+    /// * The orbital elements, or the first invalid input.
+    pub fn build(&self) -> Result<OrbitalElements, ElementsError> {
+        let (epoch_year, epoch_day) = self.epoch.ok_or(ElementsError::MissingEpoch)?;
+        let leap: bool = (epoch_year % 4 == 0 && epoch_year % 100 != 0) || epoch_year % 400 == 0;
+        if !(1.0..if leap { 367.0 } else { 366.0 }).contains(&epoch_day) {
+            return Err(ElementsError::EpochDay(epoch_day));
+        }
+        if !(0.0..=180.0).contains(&self.inclination) {
+            return Err(ElementsError::Inclination(self.inclination));
+        }
+        if !(0.0..1.0).contains(&self.eccentricity) {
+            return Err(ElementsError::Eccentricity(self.eccentricity));
+        }
+        let inclination: f64 = self.inclination.to_radians();
+        let mean_motion: f64 = match (self.mean_motion, self.semi_major_axis) {
+            (Some(_), Some(_)) => return Err(ElementsError::ConflictingSize),
+            (None, None) => return Err(ElementsError::MissingSize),
+            (Some(revolutions), None) if revolutions > 0.0 => brouwer_mean_motion(revolutions * TWOPI / XMNPDA, inclination, self.eccentricity, &GravityConstants::default()),
+            (Some(revolutions), None) => return Err(ElementsError::MeanMotion(revolutions)),
+            (None, Some(semi_major_axis)) => XKE * (XKMPER / semi_major_axis.max(0.0)).powf(1.5),
+        };
+        let elements = OrbitalElements {
+            inclination,
+            raan: self.raan.to_radians().rem_euclid(TWOPI),
+            eccentricity: self.eccentricity,
+            arg_perigee: self.arg_perigee.to_radians().rem_euclid(TWOPI),
+            mean_anomaly: self.mean_anomaly.to_radians().rem_euclid(TWOPI),
+            mean_motion,
+            bstar: self.bstar,
+            deep_space: TWOPI / mean_motion >= 225.0,
+            epoch_year,
+            epoch_day,
+        };
+        let perigee: f64 = elements.perigee_altitude_km();
+        if perigee < 0.0 || perigee.is_nan() {
+            return Err(ElementsError::Perigee(perigee));
+        }
+        Ok(elements)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sgp4;
+    use crate::test_support::iss_elements;
+
+    /// Tests that building from the values of a TLE reproduces its elements.
+    #[test]
+    fn test_builder() {
+        let expected: OrbitalElements = iss_elements();
+        let built: OrbitalElements = OrbitalElements::builder()
+            .epoch(2008, 264.51782528)
+            .inclination_deg(51.6416)
+            .raan_deg(247.4627)
+            .eccentricity(0.0006703)
+            .arg_perigee_deg(130.5360)
+            .mean_anomaly_deg(325.0288)
+            .mean_motion_rev_per_day(15.72125391)
+            .bstar(-0.11606e-4)
+            .build()
+            .unwrap();
+        assert!((built.mean_motion - expected.mean_motion).abs() < 1e-15);
+        assert_eq!(built.deep_space, expected.deep_space);
+        let (a, b) = (sgp4(100.0, &built), sgp4(100.0, &expected));
+        assert!((0..3).all(|k| (a.position[k] - b.position[k]).abs() < 1e-6));
+
+        let geo: OrbitalElements = OrbitalElements::builder().epoch_jd(expected.epoch_jd()).semi_major_axis_km(42164.0).raan_deg(-10.0).build().unwrap();
+        assert!((geo.semi_major_axis_km() - 42164.0).abs() < 1e-6);
+        assert!(geo.deep_space);
+        assert!((geo.raan.to_degrees() - 350.0).abs() < 1e-9);
+        assert_eq!(geo.epoch_year, 2008);
+        assert!((geo.epoch_day - 264.51782528).abs() < 1e-8);
+    }
+
+    /// Tests the validation of the inputs.
+    #[test]
+    fn test_builder_validation() {
+        let builder: ElementsBuilder = OrbitalElements::builder().epoch(2024, 1.5).semi_major_axis_km(7000.0);
+        assert!(builder.build().is_ok());
+        assert_eq!(OrbitalElements::builder().semi_major_axis_km(7000.0).build().err(), Some(ElementsError::MissingEpoch));
+        assert_eq!(builder.epoch(2023, 366.5).build().err(), Some(ElementsError::EpochDay(366.5)));
+        assert!(builder.epoch(2024, 366.5).build().is_ok());
+        assert_eq!(builder.inclination_deg(181.0).build().err(), Some(ElementsError::Inclination(181.0)));
+        assert_eq!(builder.eccentricity(1.0).build().err(), Some(ElementsError::Eccentricity(1.0)));
+        assert_eq!(builder.mean_motion_rev_per_day(15.0).build().err(), Some(ElementsError::ConflictingSize));
+        assert_eq!(OrbitalElements::builder().epoch(2024, 1.5).build().err(), Some(ElementsError::MissingSize));
+        assert_eq!(OrbitalElements::builder().epoch(2024, 1.5).mean_motion_rev_per_day(0.0).build().err(), Some(ElementsError::MeanMotion(0.0)));
+        assert!(matches!(builder.eccentricity(0.1).build(), Err(ElementsError::Perigee(altitude)) if altitude < 0.0));
+        assert_eq!(ElementsError::Eccentricity(1.0).to_string(), "Eccentricity 1 outside [0, 1)");
+    }
+}
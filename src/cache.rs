@@ -0,0 +1,151 @@
+//! Local cache of downloaded element sets.
+//! Each element set file is kept in a directory under a key naming its query, and its
+//! modification time records when it was fetched. A file younger than the maximum age is read back
+//! instead of downloading it again, which keeps batch jobs fast and spares the providers.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+/// Default maximum age of a cached file, matching the update rate of most element sets.
+pub const DEFAULT_MAX_AGE: Duration = Duration::from_secs(2 * 3600);
+
+/// Represents a directory of cached element set files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElementCache {
+    /// Directory holding the files, created on the first store.
+    pub directory: PathBuf,
+    /// Age beyond which a file is downloaded again.
+    pub max_age: Duration,
+}
+
+impl ElementCache {
+    /// Builds a cache in a directory.
+    ///
+    /// # Arguments
+    /// * `directory` - The directory holding the files.
+    /// * `max_age` - Age beyond which a file is downloaded again.
+    ///
+    /// # Returns
+    /// * The cache.
+    pub fn new(directory: impl Into<PathBuf>, max_age: Duration) -> ElementCache {
+        ElementCache {
+            directory: directory.into(),
+            max_age,
+        }
+    }
+
+    /// Returns the path of the file of a key, keeping only the characters safe in file names.
+    pub fn path(&self, key: &str) -> PathBuf {
+        let name: String = key.chars().map(|c| if c.is_ascii_alphanumeric() || "-_.".contains(c) { c } else { '_' }).collect();
+        // A leading dot would hide the file, or with `..` leave the directory.
+        let name: String = if name.is_empty() || name.starts_with('.') { format!("_{}", name) } else { name };
+        self.directory.join(name)
+    }
+
+    /// Returns the time elapsed since the file of a key was fetched.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the file.
+    ///
+    /// # Returns
+    /// * The age of the file, or `None` if it is not cached.
+    pub fn age(&self, key: &str) -> Option<Duration> {
+        let fetched: SystemTime = fs::metadata(self.path(key)).and_then(|metadata| metadata.modified()).ok()?;
+        // A fetch time in the future (clock change) counts as a fresh file.
+        Some(SystemTime::now().duration_since(fetched).unwrap_or_default())
+    }
+
+    /// Checks whether the file of a key is cached and younger than the maximum age.
+    pub fn is_fresh(&self, key: &str) -> bool {
+        self.age(key).is_some_and(|age| age <= self.max_age)
+    }
+
+    /// Reads the file of a key if it is fresh.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the file.
+    ///
+    /// # Returns
+    /// * The contents of the file, `None` if it is missing or too old, or the read error.
+    pub fn load(&self, key: &str) -> io::Result<Option<String>> {
+        if !self.is_fresh(key) {
+            return Ok(None);
+        }
+        fs::read_to_string(self.path(key)).map(Some)
+    }
+
+    /// Writes the file of a key, setting its fetch time to now.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the file.
+    /// * `text` - The downloaded element sets.
+    ///
+    /// # Returns
+    /// * Nothing, or the write error.
+    pub fn store(&self, key: &str, text: &str) -> io::Result<()> {
+        fs::create_dir_all(&self.directory)?;
+        // Write then rename, so that an interrupted job never leaves a truncated file.
+        let path: PathBuf = self.path(key);
+        let mut partial: std::ffi::OsString = path.clone().into_os_string();
+        partial.push(".partial");
+        fs::write(&partial, text)?;
+        fs::rename(&partial, &path)
+    }
+
+    /// Returns the fresh file of a key, or downloads and stores it.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the file.
+    /// * `fetch` - Downloads the element sets when the file is missing or too old.
+    ///
+    /// # Returns
+    /// * The element sets, or the error of the download or of the cache.
+    pub fn get_or_fetch<E: From<io::Error>>(&self, key: &str, fetch: impl FnOnce() -> Result<String, E>) -> Result<String, E> {
+        if let Some(text) = self.load(key)? {
+            return Ok(text);
+        }
+        let text: String = fetch()?;
+        self.store(key, &text)?;
+        Ok(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests that a fresh file is read back and a stale one downloaded again.
+    #[test]
+    fn test_get_or_fetch() {
+        let directory: PathBuf = std::env::temp_dir().join(format!("orbitalrs-cache-{}", std::process::id()));
+        let cache: ElementCache = ElementCache::new(&directory, Duration::from_secs(3600));
+        assert_eq!(cache.age("stations"), None);
+
+        let first: io::Result<String> = cache.get_or_fetch("stations", || Ok("ISS (ZARYA)".to_string()));
+        assert_eq!(first.unwrap(), "ISS (ZARYA)");
+        assert!(cache.is_fresh("stations"));
+        let second: io::Result<String> = cache.get_or_fetch("stations", || panic!("fresh file downloaded again"));
+        assert_eq!(second.unwrap(), "ISS (ZARYA)");
+
+        let stale = ElementCache { max_age: Duration::ZERO, ..cache.clone() };
+        std::thread::sleep(Duration::from_millis(10));
+        assert_eq!(stale.load("stations").unwrap(), None);
+        let third: io::Result<String> = stale.get_or_fetch("stations", || Ok("CSS (TIANHE)".to_string()));
+        assert_eq!(third.unwrap(), "CSS (TIANHE)");
+        assert_eq!(cache.load("stations").unwrap().as_deref(), Some("CSS (TIANHE)"));
+
+        let failed: io::Result<String> = stale.get_or_fetch("stations", || Err(io::Error::other("offline")));
+        assert!(failed.is_err());
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    /// Tests that keys are turned into safe file names.
+    #[test]
+    fn test_path() {
+        let cache: ElementCache = ElementCache::new("/tmp/tle", DEFAULT_MAX_AGE);
+        assert_eq!(cache.path("celestrak/GROUP=stations.tle"), PathBuf::from("/tmp/tle/celestrak_GROUP_stations.tle"));
+        assert_eq!(cache.path("../tle"), PathBuf::from("/tmp/tle/_.._tle"));
+    }
+}
@@ -0,0 +1,283 @@
+//! Client downloading element sets from Celestrak.
+//! Element sets are requested from the GP query interface by group, catalog number or
+//! international designator, as TLEs or GP JSON (OMM), and parsed into satellites. Requests are
+//! spaced by a minimum interval to stay within the usage policy of the service.
+
+use crate::cache::ElementCache;
+use crate::omm::{OmmError, parse_json};
+use crate::satellite::Satellite;
+use crate::tle::parse_tle_file;
+use std::fmt;
+use std::io;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Base URL of the Celestrak GP query interface.
+pub const CELESTRAK_URL: &str = "https://celestrak.org/NORAD/elements/gp.php";
+/// Default minimum interval between two requests.
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(1);
+/// Timeout of a request.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Selects the element sets of a query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CelestrakQuery {
+    /// A group of satellites, e.g. `stations` or `starlink`.
+    Group(String),
+    /// A NORAD catalog number.
+    CatalogNumber(u32),
+    /// An international designator, e.g. `1998-067` for all the pieces of a launch.
+    InternationalDesignator(String),
+}
+
+impl CelestrakQuery {
+    /// Returns the name and the value of the query parameter, keeping only the characters safe
+    /// in a URL.
+    fn parameter(&self) -> (&'static str, String) {
+        let (key, value): (&str, String) = match self {
+            CelestrakQuery::Group(group) => ("GROUP", group.clone()),
+            CelestrakQuery::CatalogNumber(number) => ("CATNR", number.to_string()),
+            CelestrakQuery::InternationalDesignator(designator) => ("INTDES", designator.clone()),
+        };
+        (key, value.chars().filter(|c| c.is_ascii_alphanumeric() || "-_.".contains(*c)).collect())
+    }
+}
+
+/// Format of the element sets requested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CelestrakFormat {
+    /// Three-line TLE sets.
+    #[default]
+    Tle,
+    /// GP JSON, in the keywords of the CCSDS OMM.
+    Json,
+}
+
+impl CelestrakFormat {
+    /// Returns the value of the format parameter.
+    fn parameter(&self) -> &'static str {
+        match self {
+            CelestrakFormat::Tle => "tle",
+            CelestrakFormat::Json => "json",
+        }
+    }
+}
+
+/// Errors that can occur while fetching element sets.
+#[derive(Debug)]
+pub enum FetchError {
+    /// The request failed before a response was received.
+    Http(String),
+    /// The server answered with an error status.
+    Status(u16),
+    /// The server answered with an error message.
+    Server(String),
+    /// The credentials were rejected.
+    Authentication,
+    /// The query matched no element set.
+    NotFound,
+    /// The GP JSON response cannot be parsed.
+    Omm(OmmError),
+    /// The element set cache cannot be read or written.
+    Io(io::Error),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Http(message) => write!(f, "HTTP request failed: {}", message),
+            FetchError::Status(status) => write!(f, "HTTP status {}", status),
+            FetchError::Server(message) => write!(f, "Server error: {}", message),
+            FetchError::Authentication => write!(f, "Authentication failed"),
+            FetchError::NotFound => write!(f, "No element set found"),
+            FetchError::Omm(error) => write!(f, "{}", error),
+            FetchError::Io(error) => write!(f, "Cache error: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+impl From<io::Error> for FetchError {
+    fn from(error: io::Error) -> FetchError {
+        FetchError::Io(error)
+    }
+}
+
+/// Waits until the minimum interval has elapsed since the last request, then records a new one.
+///
+/// # Arguments
+/// * `last_request` - Time of the last request, updated to now.
+/// * `min_interval` - Minimum interval between two requests.
+pub(crate) fn throttle(last_request: &mut Option<Instant>, min_interval: Duration) {
+    if let Some(last) = *last_request {
+        thread::sleep(min_interval.saturating_sub(last.elapsed()));
+    }
+    *last_request = Some(Instant::now());
+}
+
+/// Parses the body of a Celestrak response.
+///
+/// # Arguments
+/// * `text` - The body of the response.
+/// * `format` - The format requested.
+///
+/// # Returns
+/// * The satellites in the order of the response, or an error if the body holds none.
+pub fn parse_response(text: &str, format: CelestrakFormat) -> Result<Vec<Satellite>, FetchError> {
+    let satellites: Vec<Satellite> = match format {
+        // An unknown query is answered with a message such as "No GP data found".
+        CelestrakFormat::Tle => parse_tle_file(text.as_bytes()).unwrap_or_default().iter().map(Satellite::from_tle).collect(),
+        CelestrakFormat::Json if text.trim_start().starts_with('[') || text.trim_start().starts_with('{') => {
+            parse_json(text).map_err(FetchError::Omm)?.iter().map(Satellite::from_fields).collect()
+        }
+        CelestrakFormat::Json => Vec::new(),
+    };
+    if satellites.is_empty() {
+        return Err(FetchError::NotFound);
+    }
+    Ok(satellites)
+}
+
+/// Downloads element sets from Celestrak.
+pub struct CelestrakClient {
+    /// HTTP agent, reusing connections between requests.
+    agent: ureq::Agent,
+    /// Base URL of the GP query interface.
+    pub base_url: String,
+    /// Format of the element sets requested.
+    pub format: CelestrakFormat,
+    /// Minimum interval between two requests.
+    pub min_interval: Duration,
+    /// Time of the last request.
+    last_request: Option<Instant>,
+}
+
+impl CelestrakClient {
+    /// Builds a client identifying itself with a user agent.
+    ///
+    /// # Arguments
+    /// * `user_agent` - The user agent sent with the requests, ideally with a contact address.
+    ///
+    /// # Returns
+    /// * The client, requesting TLEs at most once per second.
+    pub fn new(user_agent: &str) -> CelestrakClient {
+        let agent: ureq::Agent = ureq::Agent::config_builder()
+            .user_agent(user_agent)
+            .timeout_global(Some(TIMEOUT))
+            .http_status_as_error(false)
+            .build()
+            .into();
+        CelestrakClient {
+            agent,
+            base_url: CELESTRAK_URL.to_string(),
+            format: CelestrakFormat::default(),
+            min_interval: DEFAULT_INTERVAL,
+            last_request: None,
+        }
+    }
+
+    /// Builds the URL of a query.
+    ///
+    /// # Arguments
+    /// * `query` - The element sets requested.
+    ///
+    /// # Returns
+    /// * The URL, with the query and the format as parameters.
+    pub fn url(&self, query: &CelestrakQuery) -> String {
+        let (key, value) = query.parameter();
+        format!("{}?{}={}&FORMAT={}", self.base_url, key, value, self.format.parameter())
+    }
+
+    /// Builds the key of a query in an element set cache.
+    ///
+    /// # Arguments
+    /// * `query` - The element sets requested.
+    ///
+    /// # Returns
+    /// * The key, naming the query and the format.
+    pub fn cache_key(&self, query: &CelestrakQuery) -> String {
+        let (key, value) = query.parameter();
+        format!("celestrak_{}_{}.{}", key, value, self.format.parameter())
+    }
+
+    /// Downloads and parses the element sets of a query, waiting for the minimum interval since
+    /// the previous request.
+    ///
+    /// # Arguments
+    /// * `query` - The element sets requested.
+    ///
+    /// # Returns
+    /// * The satellites, or the reason why none could be fetched.
+    pub fn fetch(&mut self, query: &CelestrakQuery) -> Result<Vec<Satellite>, FetchError> {
+        let text: String = self.fetch_text(query)?;
+        parse_response(&text, self.format)
+    }
+
+    /// Returns the element sets of a query from a cache, downloading them only when the cached
+    /// file is missing or older than the maximum age of the cache.
+    ///
+    /// # Arguments
+    /// * `query` - The element sets requested.
+    /// * `cache` - The cache of downloaded element sets.
+    ///
+    /// # Returns
+    /// * The satellites, or the reason why none could be fetched.
+    pub fn fetch_cached(&mut self, query: &CelestrakQuery, cache: &ElementCache) -> Result<Vec<Satellite>, FetchError> {
+        let text: String = cache.get_or_fetch(&self.cache_key(query), || self.fetch_text(query))?;
+        parse_response(&text, self.format)
+    }
+
+    /// Downloads the body of a query, waiting for the minimum interval since the previous request.
+    ///
+    /// # Arguments
+    /// * `query` - The element sets requested.
+    ///
+    /// # Returns
+    /// * The body of the response, or the reason why it could not be downloaded.
+    pub fn fetch_text(&mut self, query: &CelestrakQuery) -> Result<String, FetchError> {
+        throttle(&mut self.last_request, self.min_interval);
+
+        let mut response = self.agent.get(&self.url(query)).call().map_err(|e| FetchError::Http(e.to_string()))?;
+        let status: u16 = response.status().as_u16();
+        if status != 200 {
+            return Err(FetchError::Status(status));
+        }
+        response.body_mut().read_to_string().map_err(|e| FetchError::Http(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::iss_text;
+
+    /// Tests the URLs of the queries.
+    #[test]
+    fn test_url() {
+        let mut client: CelestrakClient = CelestrakClient::new("sgp4_rust tests");
+        assert_eq!(client.url(&CelestrakQuery::Group("stations".to_string())), "https://celestrak.org/NORAD/elements/gp.php?GROUP=stations&FORMAT=tle");
+        client.format = CelestrakFormat::Json;
+        assert_eq!(client.url(&CelestrakQuery::CatalogNumber(25544)), "https://celestrak.org/NORAD/elements/gp.php?CATNR=25544&FORMAT=json");
+        assert_eq!(client.url(&CelestrakQuery::InternationalDesignator("1998-067&x".to_string())), "https://celestrak.org/NORAD/elements/gp.php?INTDES=1998-067x&FORMAT=json");
+        assert_eq!(client.cache_key(&CelestrakQuery::CatalogNumber(25544)), "celestrak_CATNR_25544.json");
+    }
+
+    /// Tests the parsing of TLE and GP JSON responses.
+    #[test]
+    fn test_parse_response() {
+        let tle: &str = &iss_text();
+        let satellites: Vec<Satellite> = parse_response(tle, CelestrakFormat::Tle).unwrap();
+        assert_eq!(satellites.len(), 1);
+        assert_eq!(satellites[0].name.as_deref(), Some("ISS (ZARYA)"));
+
+        let json: &str = r#"[{"OBJECT_NAME":"ISS (ZARYA)","OBJECT_ID":"1998-067A","EPOCH":"2008-09-20T12:25:40.104192","MEAN_MOTION":15.72125391,"ECCENTRICITY":0.0006703,"INCLINATION":51.6416,"RA_OF_ASC_NODE":247.4627,"ARG_OF_PERICENTER":130.536,"MEAN_ANOMALY":325.0288,"EPHEMERIS_TYPE":0,"CLASSIFICATION_TYPE":"U","NORAD_CAT_ID":25544,"ELEMENT_SET_NO":292,"REV_AT_EPOCH":56353,"BSTAR":-1.1606e-5,"MEAN_MOTION_DOT":-2.182e-5,"MEAN_MOTION_DDOT":0}]"#;
+        let satellites: Vec<Satellite> = parse_response(json, CelestrakFormat::Json).unwrap();
+        assert_eq!(satellites[0].norad_id, 25544);
+        assert!((satellites[0].epoch_jd - parse_response(tle, CelestrakFormat::Tle).unwrap()[0].epoch_jd).abs() < 1e-8);
+
+        assert!(matches!(parse_response("No GP data found", CelestrakFormat::Tle), Err(FetchError::NotFound)));
+        assert!(matches!(parse_response("No GP data found", CelestrakFormat::Json), Err(FetchError::NotFound)));
+        assert!(matches!(parse_response("[{}]", CelestrakFormat::Json), Err(FetchError::Omm(_))));
+    }
+}
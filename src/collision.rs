@@ -0,0 +1,229 @@
+//! Probability of collision of a conjunction.
+//! The encounter is assumed short: the relative motion is a straight line and the position errors
+//! do not change during it, which reduces the problem to the integral of a 2-D Gaussian over the
+//! hard-body disk in the encounter plane, normal to the relative velocity (Foster's method), or to
+//! Chan's series for an equivalent isotropic covariance.
+
+use crate::conjunction::Conjunction;
+use crate::covariance::Covariance;
+use crate::vector::cross;
+use core::f64::consts::PI;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Number of intervals of the Simpson integration across the hard-body disk (even).
+const FOSTER_INTERVALS: usize = 200;
+/// Number of terms of Chan's series beyond the expected one.
+const CHAN_EXTRA_TERMS: usize = 50;
+
+/// Selects the computation of the probability of collision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PcMethod {
+    /// Numerical integration of the 2-D Gaussian over the hard-body disk.
+    #[default]
+    Foster,
+    /// Chan's analytical series, exact for isotropic covariances.
+    Chan,
+}
+
+/// Represents the probability of collision and the encounter-plane geometry it is based on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CollisionProbability {
+    /// Probability of collision.
+    pub probability: f64,
+    /// Miss distance in the encounter plane in kilometers.
+    pub miss_distance: f64,
+    /// Standard deviations of the combined position error along the principal axes of the
+    /// encounter plane, in kilometers, largest first.
+    pub sigma: [f64; 2],
+    /// Components of the miss vector along those principal axes in kilometers.
+    pub miss: [f64; 2],
+    /// Combined hard-body radius in kilometers.
+    pub hard_body_radius: f64,
+    /// Computation of the probability.
+    pub method: PcMethod,
+}
+
+/// Computes the complementary error function with a fractional error below 1.2e-7.
+///
+/// Chebyshev fit of Numerical Recipes, accurate in the tails where erf(x) is close to 1.
+fn erfc(x: f64) -> f64 {
+    let z: f64 = x.abs();
+    let t: f64 = 1.0 / (1.0 + 0.5 * z);
+    let polynomial: f64 = -z * z - 1.26551223
+        + t * (1.00002368
+            + t * (0.37409196 + t * (0.09678418 + t * (-0.18628806 + t * (0.27886807 + t * (-1.13520398 + t * (1.48851587 + t * (-0.82215223 + t * 0.17087277))))))));
+    let value: f64 = t * polynomial.exp();
+    if x >= 0.0 { value } else { 2.0 - value }
+}
+
+/// Computes erf(upper) - erf(lower) without cancellation in the tails.
+fn erf_difference(lower: f64, upper: f64) -> f64 {
+    if lower >= 0.0 {
+        erfc(lower) - erfc(upper)
+    } else if upper <= 0.0 {
+        erfc(-upper) - erfc(-lower)
+    } else {
+        2.0 - erfc(upper) - erfc(-lower)
+    }
+}
+
+/// Normalizes a vector.
+fn unit(a: [f64; 3]) -> [f64; 3] {
+    let norm: f64 = a.iter().map(|x| x * x).sum::<f64>().sqrt();
+    a.map(|x| x / norm)
+}
+
+/// Computes the probability of collision of a short encounter.
+///
+/// # Arguments
+/// * `miss` - Relative position at the time of closest approach in kilometers.
+/// * `relative_velocity` - Relative velocity at the time of closest approach in km/s.
+/// * `covariance` - Combined 3×3 covariance of the relative position in km².
+/// * `hard_body_radius` - Combined radius of the two objects in kilometers.
+/// * `method` - Computation of the probability.
+///
+/// # Returns
+/// * The probability of collision and the encounter-plane geometry.
+pub fn collision_probability(miss: [f64; 3], relative_velocity: [f64; 3], covariance: &[[f64; 3]; 3], hard_body_radius: f64, method: PcMethod) -> CollisionProbability {
+    // Encounter plane: normal to the relative velocity, the first axis along the miss vector.
+    let normal: [f64; 3] = unit(relative_velocity);
+    let along_normal: f64 = (0..3).map(|k| miss[k] * normal[k]).sum();
+    let in_plane: [f64; 3] = [0, 1, 2].map(|k| miss[k] - along_normal * normal[k]);
+    let first: [f64; 3] = if in_plane.iter().any(|x| *x != 0.0) {
+        unit(in_plane)
+    } else {
+        unit(cross(normal, if normal[0].abs() < 0.9 { [1.0, 0.0, 0.0] } else { [0.0, 1.0, 0.0] }))
+    };
+    let second: [f64; 3] = cross(normal, first);
+    let axes: [[f64; 3]; 2] = [first, second];
+
+    let project = |u: &[f64; 3], v: &[f64; 3]| -> f64 { (0..3).map(|i| (0..3).map(|j| u[i] * covariance[i][j] * v[j]).sum::<f64>()).sum() };
+    let (cxx, cxy, cyy) = (project(&axes[0], &axes[0]), project(&axes[0], &axes[1]), project(&axes[1], &axes[1]));
+    let miss_plane: [f64; 2] = [(0..3).map(|k| miss[k] * first[k]).sum(), (0..3).map(|k| miss[k] * second[k]).sum()];
+
+    // Principal axes of the projected covariance.
+    let angle: f64 = 0.5 * (2.0 * cxy).atan2(cxx - cyy);
+    let (sin, cos) = angle.sin_cos();
+    let variance_major: f64 = cxx * cos * cos + 2.0 * cxy * sin * cos + cyy * sin * sin;
+    let variance_minor: f64 = cxx * sin * sin - 2.0 * cxy * sin * cos + cyy * cos * cos;
+    let sigma: [f64; 2] = [variance_major.max(0.0).sqrt(), variance_minor.max(0.0).sqrt()];
+    let principal_miss: [f64; 2] = [cos * miss_plane[0] + sin * miss_plane[1], -sin * miss_plane[0] + cos * miss_plane[1]];
+
+    let probability: f64 = match method {
+        PcMethod::Foster => foster(principal_miss, sigma, hard_body_radius),
+        PcMethod::Chan => chan(principal_miss, sigma, hard_body_radius),
+    };
+    CollisionProbability {
+        probability,
+        miss_distance: (miss_plane[0] * miss_plane[0] + miss_plane[1] * miss_plane[1]).sqrt(),
+        sigma,
+        miss: principal_miss,
+        hard_body_radius,
+        method,
+    }
+}
+
+/// Integrates the Gaussian over the hard-body disk: analytically across each chord, and by
+/// Simpson's rule along the first axis, with x = R sin θ to remove the square-root behaviour at
+/// the edges of the disk.
+fn foster(miss: [f64; 2], sigma: [f64; 2], radius: f64) -> f64 {
+    let h: f64 = PI / FOSTER_INTERVALS as f64;
+    let chord = |theta: f64| -> f64 {
+        let (sin, cos) = theta.sin_cos();
+        let half: f64 = radius * cos;
+        let offset: f64 = radius * sin + miss[0];
+        let across: f64 = erf_difference((miss[1] - half) / (2.0_f64.sqrt() * sigma[1]), (miss[1] + half) / (2.0_f64.sqrt() * sigma[1]));
+        across * (-offset * offset / (2.0 * sigma[0] * sigma[0])).exp() * radius * cos
+    };
+    let sum: f64 = (0..=FOSTER_INTERVALS)
+        .map(|k| {
+            let weight: f64 = if k == 0 || k == FOSTER_INTERVALS { 1.0 } else if k % 2 == 1 { 4.0 } else { 2.0 };
+            weight * chord(-PI / 2.0 + k as f64 * h)
+        })
+        .sum();
+    sum * h / 3.0 / ((8.0 * PI).sqrt() * sigma[0])
+}
+
+/// Evaluates Chan's series with the isotropic covariance of the same area.
+fn chan(miss: [f64; 2], sigma: [f64; 2], radius: f64) -> f64 {
+    let u: f64 = radius * radius / (sigma[0] * sigma[1]);
+    let v: f64 = miss[0] * miss[0] / (sigma[0] * sigma[0]) + miss[1] * miss[1] / (sigma[1] * sigma[1]);
+    let terms: usize = (v / 2.0) as usize + CHAN_EXTRA_TERMS;
+
+    let mut outer: f64 = (-v / 2.0).exp();
+    let mut inner_term: f64 = (-u / 2.0).exp();
+    let mut inner_sum: f64 = inner_term;
+    let mut probability: f64 = 0.0;
+    for m in 0..terms {
+        probability += outer * (1.0 - inner_sum);
+        outer *= v / (2.0 * (m + 1) as f64);
+        inner_term *= u / (2.0 * (m + 1) as f64);
+        inner_sum += inner_term;
+    }
+    probability
+}
+
+impl Conjunction {
+    /// Computes the probability of collision of the conjunction.
+    ///
+    /// # Arguments
+    /// * `covariance_a` - Covariance of the state of the first object at the time of closest approach.
+    /// * `covariance_b` - Covariance of the state of the second object at the time of closest approach.
+    /// * `hard_body_radius` - Combined radius of the two objects in kilometers.
+    /// * `method` - Computation of the probability.
+    ///
+    /// # Returns
+    /// * The probability of collision, with the errors of the objects taken as uncorrelated.
+    pub fn collision_probability(&self, covariance_a: &Covariance, covariance_b: &Covariance, hard_body_radius: f64, method: PcMethod) -> CollisionProbability {
+        let combined: [[f64; 3]; 3] = core::array::from_fn(|i| core::array::from_fn(|j| covariance_a[i][j] + covariance_b[i][j]));
+        collision_probability(self.relative_position(), self.relative_velocity(), &combined, hard_body_radius, method)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests both methods against the closed form of a centered isotropic encounter, and against
+    /// each other for an offset one.
+    #[test]
+    fn test_isotropic_probability() {
+        let covariance: [[f64; 3]; 3] = [[0.04, 0.0, 0.0], [0.0, 0.04, 0.0], [0.0, 0.0, 0.04]];
+        let velocity: [f64; 3] = [0.0, 10.0, 0.0];
+        let radius: f64 = 0.02;
+        let expected: f64 = 1.0 - (-radius * radius / (2.0 * 0.04)).exp();
+        for method in [PcMethod::Foster, PcMethod::Chan] {
+            let centered = collision_probability([0.0; 3], velocity, &covariance, radius, method);
+            assert!((centered.probability / expected - 1.0).abs() < 1e-5, "{:?} {}", method, centered.probability);
+        }
+
+        let miss: [f64; 3] = [0.3, 5.0, 0.4];
+        let foster = collision_probability(miss, velocity, &covariance, radius, PcMethod::Foster);
+        let chan = collision_probability(miss, velocity, &covariance, radius, PcMethod::Chan);
+        assert!((foster.miss_distance - 0.5).abs() < 1e-12);
+        assert!((foster.probability / chan.probability - 1.0).abs() < 1e-4, "{} {}", foster.probability, chan.probability);
+        // Small disk: the centered value scaled by the density at 2.5 sigmas.
+        assert!((foster.probability / (expected * (-3.125_f64).exp()) - 1.0).abs() < 1e-2, "{}", foster.probability);
+    }
+
+    /// Tests an elongated covariance: the probability depends on the orientation of the miss.
+    #[test]
+    fn test_anisotropic_probability() {
+        // Position errors of 1 km along Y, 100 m across, seen along the X axis.
+        let covariance: [[f64; 3]; 3] = [[0.25, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 0.01]];
+        let velocity: [f64; 3] = [14.0, 0.0, 0.0];
+        let along = collision_probability([0.0, 0.5, 0.0], velocity, &covariance, 0.01, PcMethod::Foster);
+        let across = collision_probability([0.0, 0.0, 0.5], velocity, &covariance, 0.01, PcMethod::Foster);
+        assert!((along.sigma[0] - 1.0).abs() < 1e-9 && (along.sigma[1] - 0.1).abs() < 1e-9);
+        assert!(along.probability > 1e3 * across.probability, "{} {}", along.probability, across.probability);
+
+        // Small disk: the density at the miss point times the disk area.
+        let density: f64 = (-0.125_f64).exp() / (2.0 * PI * 1.0 * 0.1);
+        assert!((along.probability / (density * PI * 1e-4) - 1.0).abs() < 5e-3, "{}", along.probability);
+        let chan = collision_probability([0.0, 0.5, 0.0], velocity, &covariance, 0.01, PcMethod::Chan);
+        assert!(chan.probability > 0.0 && chan.probability < 1.0);
+    }
+}
@@ -0,0 +1,163 @@
+//! Close approaches between two satellites.
+//! The distance between the objects is sampled over the window to bracket its local minima, which
+//! are then refined by a golden-section search. Each object keeps its own propagator and epoch:
+//! times are Julian dates (UTC).
+
+use crate::passes::refine_maximum;
+use crate::propagator::{PropagationError, Propagator};
+use crate::StateVector;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Sampling step of the distance in minutes, small compared to the time between two approaches
+/// of objects in low Earth orbit.
+const SAMPLE_STEP: f64 = 1.0;
+
+/// Represents the closest approach of two satellites.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Conjunction {
+    /// Time of closest approach as a Julian date (UTC).
+    pub tca_jd: f64,
+    /// Distance between the objects at the time of closest approach in kilometers.
+    pub miss_distance: f64,
+    /// State of the first object in the TEME frame (km, km/s).
+    pub state_a: StateVector,
+    /// State of the second object in the TEME frame (km, km/s).
+    pub state_b: StateVector,
+}
+
+impl Conjunction {
+    /// Returns the position of the second object relative to the first one in kilometers.
+    pub fn relative_position(&self) -> [f64; 3] {
+        [0, 1, 2].map(|k| self.state_b.position[k] - self.state_a.position[k])
+    }
+
+    /// Returns the velocity of the second object relative to the first one in kilometers per second.
+    pub fn relative_velocity(&self) -> [f64; 3] {
+        [0, 1, 2].map(|k| self.state_b.velocity[k] - self.state_a.velocity[k])
+    }
+
+    /// Returns the relative speed of the objects in kilometers per second.
+    pub fn relative_speed(&self) -> f64 {
+        self.relative_velocity().iter().map(|v| v * v).sum::<f64>().sqrt()
+    }
+}
+
+/// Computes the states of two objects at a date.
+fn states<A: Propagator, B: Propagator>(a: &A, b: &B, jd: f64) -> Result<(StateVector, StateVector), PropagationError> {
+    Ok((a.propagate((jd - a.epoch_jd()) * 1440.0)?, b.propagate((jd - b.epoch_jd()) * 1440.0)?))
+}
+
+/// Computes the distance between two states in kilometers.
+fn distance(a: &StateVector, b: &StateVector) -> f64 {
+    (0..3).map(|k| (b.position[k] - a.position[k]).powi(2)).sum::<f64>().sqrt()
+}
+
+/// Finds the local minima of the distance between two objects.
+///
+/// # Arguments
+/// * `a` - Propagator of the first object.
+/// * `b` - Propagator of the second object.
+/// * `start_jd` - Start of the window as a Julian date (UTC).
+/// * `end_jd` - End of the window as a Julian date (UTC).
+/// * `threshold` - Largest miss distance reported, in kilometers.
+///
+/// # Returns
+/// * The approaches closer than the threshold in chronological order, including minima at the edges
+///   of the window, or the first propagation error.
+pub fn close_approaches<A: Propagator, B: Propagator>(a: &A, b: &B, start_jd: f64, end_jd: f64, threshold: f64) -> Result<Vec<Conjunction>, PropagationError> {
+    let count: usize = (((end_jd - start_jd) * 1440.0 / SAMPLE_STEP).ceil() as usize).max(1);
+    let times: Vec<f64> = (0..=count).map(|k| start_jd + (end_jd - start_jd) * k as f64 / count as f64).collect();
+    let distances: Vec<f64> = times
+        .iter()
+        .map(|&jd| states(a, b, jd).map(|(sa, sb)| distance(&sa, &sb)))
+        .collect::<Result<_, _>>()?;
+
+    let mut approaches: Vec<Conjunction> = Vec::new();
+    for k in 0..distances.len() {
+        let before: f64 = if k > 0 { distances[k - 1] } else { f64::INFINITY };
+        let after: f64 = distances.get(k + 1).copied().unwrap_or(f64::INFINITY);
+        if distances[k] > before || distances[k] >= after {
+            continue;
+        }
+        // The minimum lies between the neighbouring samples.
+        let low: f64 = times[k.saturating_sub(1)];
+        let high: f64 = times[(k + 1).min(times.len() - 1)];
+        let (tca_jd, _) = refine_maximum(|minutes: f64| states(a, b, low + minutes / 1440.0).map_or(f64::NEG_INFINITY, |(sa, sb)| -distance(&sa, &sb)), 0.0, (high - low) * 1440.0);
+        let tca_jd: f64 = low + tca_jd / 1440.0;
+        let (state_a, state_b) = states(a, b, tca_jd)?;
+        let miss_distance: f64 = distance(&state_a, &state_b);
+        if miss_distance <= threshold {
+            approaches.push(Conjunction {
+                tca_jd,
+                miss_distance,
+                state_a,
+                state_b,
+            });
+        }
+    }
+    Ok(approaches)
+}
+
+/// Finds the closest approach of two objects over a window.
+///
+/// # Arguments
+/// * `a` - Propagator of the first object.
+/// * `b` - Propagator of the second object.
+/// * `start_jd` - Start of the window as a Julian date (UTC).
+/// * `end_jd` - End of the window as a Julian date (UTC).
+///
+/// # Returns
+/// * The time and geometry of the smallest distance, or the first propagation error.
+pub fn closest_approach<A: Propagator, B: Propagator>(a: &A, b: &B, start_jd: f64, end_jd: f64) -> Result<Conjunction, PropagationError> {
+    let approaches: Vec<Conjunction> = close_approaches(a, b, start_jd, end_jd, f64::INFINITY)?;
+    // The sampled minima always include the smallest sample, so the list is not empty.
+    Ok(approaches.into_iter().min_by(|x, y| x.miss_distance.total_cmp(&y.miss_distance)).expect("no distance minimum"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{OrbitalElements, Sgp4};
+    use crate::test_support::iss_elements;
+
+    /// Tests the approach of two objects whose orbital planes differ by the inclination only: they
+    /// meet at the nodes twice per revolution.
+    #[test]
+    fn test_closest_approach() {
+        let elements: OrbitalElements = iss_elements();
+        let mut other: OrbitalElements = iss_elements();
+        other.inclination += 0.01;
+        let (a, b) = (Sgp4::new(&elements), Sgp4::new(&other));
+        let start_jd: f64 = elements.epoch_jd();
+        let conjunction = closest_approach(&a, &b, start_jd, start_jd + 0.5).unwrap();
+
+        // Both objects cross the common line of nodes at the same time.
+        assert!(conjunction.miss_distance < 5.0, "{}", conjunction.miss_distance);
+        let dot: f64 = (0..3).map(|k| conjunction.relative_position()[k] * conjunction.relative_velocity()[k]).sum();
+        assert!(dot.abs() < 1e-3 * conjunction.miss_distance * conjunction.relative_speed(), "{}", dot);
+        for minutes in [-0.5, 0.5] {
+            let (sa, sb) = states(&a, &b, conjunction.tca_jd + minutes / 1440.0).unwrap();
+            assert!(distance(&sa, &sb) > conjunction.miss_distance);
+        }
+    }
+
+    /// Tests that the approaches repeat twice per revolution and respect the threshold.
+    #[test]
+    fn test_close_approaches() {
+        let elements: OrbitalElements = iss_elements();
+        let mut other: OrbitalElements = iss_elements();
+        other.inclination += 0.01;
+        let (a, b) = (Sgp4::new(&elements), Sgp4::new(&other));
+        let start_jd: f64 = elements.epoch_jd();
+        let period: f64 = elements.period_minutes() / 1440.0;
+        let approaches: Vec<Conjunction> = close_approaches(&a, &b, start_jd, start_jd + 3.0 * period, 10.0).unwrap();
+        assert!((5..=7).contains(&approaches.len()), "{}", approaches.len());
+        for pair in approaches.windows(2) {
+            let spacing: f64 = (pair[1].tca_jd - pair[0].tca_jd) / period;
+            assert!((spacing - 0.5).abs() < 0.05, "{}", spacing);
+        }
+        assert!(close_approaches(&a, &b, start_jd, start_jd + 3.0 * period, 1e-3).unwrap().is_empty());
+    }
+}
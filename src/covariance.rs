@@ -0,0 +1,253 @@
+//! Propagation of the uncertainty of a state vector.
+//! The 6×6 covariance of the position (km) and velocity (km/s) at epoch is mapped to later times
+//! through the state transition matrix, computed by central differences of the propagator.
+
+use crate::anomaly::mean_to_true;
+use crate::keplerian::KeplerianElements;
+use crate::mean_elements::mean_from_keplerian;
+use crate::numerical::NumericalPropagator;
+use crate::propagator::{PropagationError, Propagator};
+use crate::{OrbitalElements, Sgp4, StateVector};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Covariance of a state vector: position (km) then velocity (km/s), in km² and km²/s².
+pub type Covariance = [[f64; 6]; 6];
+
+/// Finite difference steps of the position (km) and velocity (km/s) components.
+const STEPS: [f64; 6] = [1e-2, 1e-2, 1e-2, 1e-5, 1e-5, 1e-5];
+
+/// Represents a state vector together with its covariance.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StateWithCovariance {
+    /// State vector in the TEME frame (km, km/s).
+    pub state: StateVector,
+    /// Covariance of the state, in the same frame.
+    pub covariance: Covariance,
+}
+
+impl StateWithCovariance {
+    /// Returns the standard deviations of the position components in kilometers.
+    pub fn position_sigma(&self) -> [f64; 3] {
+        [0, 1, 2].map(|k| self.covariance[k][k].sqrt())
+    }
+
+    /// Returns the standard deviations of the velocity components in kilometers per second.
+    pub fn velocity_sigma(&self) -> [f64; 3] {
+        [3, 4, 5].map(|k| self.covariance[k][k].sqrt())
+    }
+}
+
+/// Packs a state vector into six components.
+fn to_array(state: &StateVector) -> [f64; 6] {
+    let [x, y, z] = state.position;
+    let [vx, vy, vz] = state.velocity;
+    [x, y, z, vx, vy, vz]
+}
+
+/// Computes the Jacobian of a function of a state vector by central differences.
+///
+/// # Arguments
+/// * `state` - The state at which the Jacobian is evaluated.
+/// * `function` - The function, typically a propagation from the state.
+///
+/// # Returns
+/// * The 6×6 Jacobian, or the error of the first failed evaluation.
+pub fn jacobian<F>(state: &StateVector, function: F) -> Result<[[f64; 6]; 6], PropagationError>
+where
+    F: Fn(&StateVector) -> Result<StateVector, PropagationError>,
+{
+    let mut jacobian: [[f64; 6]; 6] = [[0.0; 6]; 6];
+    for (column, step) in STEPS.iter().enumerate() {
+        let shifted = |sign: f64| -> Result<[f64; 6], PropagationError> {
+            let mut components: [f64; 6] = to_array(state);
+            components[column] += sign * step;
+            let shifted = StateVector {
+                position: [components[0], components[1], components[2]],
+                velocity: [components[3], components[4], components[5]],
+            };
+            function(&shifted).map(|result| to_array(&result))
+        };
+        let (plus, minus) = (shifted(1.0)?, shifted(-1.0)?);
+        for (row, (p, m)) in jacobian.iter_mut().zip(plus.iter().zip(&minus)) {
+            row[column] = (p - m) / (2.0 * step);
+        }
+    }
+    Ok(jacobian)
+}
+
+/// Maps a covariance through a linear transformation: Φ P Φᵀ.
+///
+/// # Arguments
+/// * `transition` - The state transition matrix Φ.
+/// * `covariance` - The covariance P.
+///
+/// # Returns
+/// * The transformed covariance.
+pub fn transform_covariance(transition: &[[f64; 6]; 6], covariance: &Covariance) -> Covariance {
+    let product: [[f64; 6]; 6] = core::array::from_fn(|i| core::array::from_fn(|j| (0..6).map(|k| transition[i][k] * covariance[k][j]).sum()));
+    core::array::from_fn(|i| core::array::from_fn(|j| (0..6).map(|k| product[i][k] * transition[j][k]).sum()))
+}
+
+/// Inverts a 6×6 matrix by Gauss-Jordan elimination with partial pivoting.
+///
+/// # Returns
+/// * The inverse, or `None` if the matrix is singular.
+fn invert(matrix: &[[f64; 6]; 6]) -> Option<[[f64; 6]; 6]> {
+    let mut a: [[f64; 6]; 6] = *matrix;
+    let mut inverse: [[f64; 6]; 6] = core::array::from_fn(|i| core::array::from_fn(|j| if i == j { 1.0 } else { 0.0 }));
+    for col in 0..6 {
+        let pivot: usize = (col..6).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < 1e-300 {
+            return None;
+        }
+        a.swap(col, pivot);
+        inverse.swap(col, pivot);
+        let scale: f64 = 1.0 / a[col][col];
+        a[col] = a[col].map(|x| x * scale);
+        inverse[col] = inverse[col].map(|x| x * scale);
+        for row in 0..6 {
+            if row != col {
+                let factor: f64 = a[row][col];
+                let (pivot_row, pivot_inverse) = (a[col], inverse[col]);
+                for k in 0..6 {
+                    a[row][k] -= factor * pivot_row[k];
+                    inverse[row][k] -= factor * pivot_inverse[k];
+                }
+            }
+        }
+    }
+    Some(inverse)
+}
+
+impl NumericalPropagator {
+    /// Propagates the state together with the covariance of the initial state.
+    ///
+    /// # Arguments
+    /// * `tsince` - Time since epoch in minutes.
+    /// * `covariance` - Covariance of the initial state.
+    ///
+    /// # Returns
+    /// * The state and its covariance at the requested time.
+    pub fn propagate_with_covariance(&self, tsince: f64, covariance: &Covariance) -> Result<StateWithCovariance, PropagationError> {
+        let transition = jacobian(&self.initial, |initial| {
+            let shifted = NumericalPropagator { initial: *initial, ..*self };
+            shifted.propagate(tsince)
+        })?;
+        Ok(StateWithCovariance {
+            state: self.propagate(tsince)?,
+            covariance: transform_covariance(&transition, covariance),
+        })
+    }
+}
+
+/// Computes the two-body state described by the mean elements, taken as Keplerian elements.
+fn mean_state(elements: &OrbitalElements) -> Result<StateVector, PropagationError> {
+    let true_anomaly: f64 = mean_to_true(elements.mean_anomaly, elements.eccentricity).map_err(|_| PropagationError::MeanEccentricity(elements.eccentricity))?;
+    let keplerian = KeplerianElements {
+        semi_major_axis: elements.semi_major_axis_km(),
+        eccentricity: elements.eccentricity,
+        inclination: elements.inclination,
+        raan: elements.raan,
+        arg_perigee: elements.arg_perigee,
+        true_anomaly,
+    };
+    Ok(keplerian.to_state())
+}
+
+/// Propagates a SGP4 state together with the covariance of the osculating state at epoch.
+///
+/// The mean elements are varied through their two-body state, as in the element fit; the
+/// covariance at epoch is mapped onto that mean state with the inverse of the epoch Jacobian.
+///
+/// # Arguments
+/// * `elements` - Mean orbital elements of the satellite.
+/// * `tsince` - Time since epoch in minutes.
+/// * `covariance` - Covariance of the state at epoch in the TEME frame.
+///
+/// # Returns
+/// * The state and its covariance at the requested time.
+pub fn sgp4_with_covariance(elements: &OrbitalElements, tsince: f64, covariance: &Covariance) -> Result<StateWithCovariance, PropagationError> {
+    let propagate = |time: f64| {
+        move |mean: &StateVector| -> Result<StateVector, PropagationError> {
+            let keplerian = KeplerianElements::from_state(mean);
+            if !(0.0..1.0).contains(&keplerian.eccentricity) {
+                return Err(PropagationError::MeanEccentricity(keplerian.eccentricity));
+            }
+            let shifted: OrbitalElements = mean_from_keplerian(&keplerian, elements.epoch_year, elements.epoch_day, elements.bstar);
+            Sgp4::new(&shifted).propagate(time)
+        }
+    };
+    let mean: StateVector = mean_state(elements)?;
+    let at_epoch = jacobian(&mean, propagate(0.0))?;
+    let at_time = jacobian(&mean, propagate(tsince))?;
+    let inverse = invert(&at_epoch).ok_or(PropagationError::MeanEccentricity(elements.eccentricity))?;
+    let transition: [[f64; 6]; 6] = core::array::from_fn(|i| core::array::from_fn(|j| (0..6).map(|k| at_time[i][k] * inverse[k][j]).sum()));
+    Ok(StateWithCovariance {
+        state: Sgp4::new(elements).propagate(tsince)?,
+        covariance: transform_covariance(&transition, covariance),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::iss_elements;
+
+    /// Returns a diagonal covariance of 100 m in position and 10 cm/s in velocity.
+    fn diagonal() -> Covariance {
+        core::array::from_fn(|i| core::array::from_fn(|j| if i != j { 0.0 } else if i < 3 { 0.01 } else { 1e-8 }))
+    }
+
+    /// Tests that the uncertainty grows along the track of a numerically propagated orbit.
+    #[test]
+    fn test_numerical_covariance() {
+        let elements = iss_elements();
+        let numerical = NumericalPropagator::new(crate::sgp4(0.0, &elements), elements.epoch_jd());
+
+        let start = numerical.propagate_with_covariance(0.0, &diagonal()).unwrap();
+        for (row, expected) in start.covariance.iter().zip(diagonal()) {
+            for (value, expected) in row.iter().zip(expected) {
+                assert!((value - expected).abs() < 1e-9);
+            }
+        }
+
+        // The velocity uncertainty of 10 cm/s moves the satellite by kilometers along the track.
+        let later = numerical.propagate_with_covariance(2.0 * elements.period_minutes(), &diagonal()).unwrap();
+        let r = later.state.position;
+        let v = later.state.velocity;
+        let speed: f64 = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+        let along: [f64; 3] = v.map(|x| x / speed);
+        let radius: f64 = r.iter().map(|x| x * x).sum::<f64>().sqrt();
+        let radial: [f64; 3] = r.map(|x| x / radius);
+        let variance = |u: [f64; 3]| -> f64 { (0..3).map(|i| (0..3).map(|j| u[i] * later.covariance[i][j] * u[j]).sum::<f64>()).sum() };
+        assert!(variance(along).sqrt() > 1.0, "{}", variance(along).sqrt());
+        assert!(variance(along) > 10.0 * variance(radial));
+        for i in 0..6 {
+            for j in 0..6 {
+                assert!((later.covariance[i][j] - later.covariance[j][i]).abs() < 1e-9 * later.covariance[i][i].max(later.covariance[j][j]));
+            }
+        }
+    }
+
+    /// Tests that the SGP4 covariance is the input at epoch and follows the numerical one.
+    #[test]
+    fn test_sgp4_covariance() {
+        let elements = iss_elements();
+        let start = sgp4_with_covariance(&elements, 0.0, &diagonal()).unwrap();
+        assert_eq!(start.state.position, crate::sgp4(0.0, &elements).position);
+        for (row, expected) in start.covariance.iter().zip(diagonal()) {
+            for (value, expected) in row.iter().zip(expected) {
+                assert!((value - expected).abs() < 1e-6 * expected.max(1e-8));
+            }
+        }
+
+        let tsince: f64 = elements.period_minutes();
+        let sgp4 = sgp4_with_covariance(&elements, tsince, &diagonal()).unwrap();
+        let numerical = NumericalPropagator::new(start.state, elements.epoch_jd()).propagate_with_covariance(tsince, &diagonal()).unwrap();
+        for (sigma, reference) in sgp4.position_sigma().iter().zip(numerical.position_sigma()) {
+            assert!((sigma / reference - 1.0).abs() < 0.05, "{} {}", sigma, reference);
+        }
+    }
+}
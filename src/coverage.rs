@@ -0,0 +1,119 @@
+//! Coverage footprint of a satellite.
+//! The footprint is the region of the Earth from which the satellite is seen above a minimum
+//! elevation angle. It is computed on a spherical Earth, which is accurate enough for coverage maps.
+
+use crate::OrbitalElements;
+use crate::geodetic::{Geodetic, WGS84_A};
+use crate::ground_track::subpoint;
+use crate::propagator::PropagationError;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Computes the Earth central angle between the sub-satellite point and the edge of the footprint.
+///
+/// # Arguments
+/// * `altitude` - Altitude of the satellite in kilometers.
+/// * `min_elevation` - Minimum elevation angle at the edge of the footprint in radians.
+///
+/// # Returns
+/// * The half-angle of the footprint seen from the Earth's center in radians.
+pub fn footprint_angle(altitude: f64, min_elevation: f64) -> f64 {
+    let ratio: f64 = WGS84_A / (WGS84_A + altitude);
+    (ratio * min_elevation.cos()).acos() - min_elevation
+}
+
+/// Computes the boundary of the coverage footprint around a sub-satellite point.
+///
+/// # Arguments
+/// * `subpoint` - Sub-satellite point, with the altitude of the satellite.
+/// * `min_elevation` - Minimum elevation angle at the edge of the footprint in radians.
+/// * `points` - Number of boundary points to generate.
+///
+/// # Returns
+/// * The boundary points, clockwise from north, on the surface of the Earth.
+pub fn footprint(subpoint: &Geodetic, min_elevation: f64, points: usize) -> Vec<Geodetic> {
+    let angle: f64 = footprint_angle(subpoint.altitude, min_elevation);
+    let (sin_angle, cos_angle) = angle.sin_cos();
+    let (sin_lat, cos_lat) = subpoint.latitude.sin_cos();
+
+    (0..points)
+        .map(|i| {
+            let bearing: f64 = crate::TWOPI * i as f64 / points as f64;
+            let (sin_bearing, cos_bearing) = bearing.sin_cos();
+            let latitude: f64 = (sin_lat * cos_angle + cos_lat * sin_angle * cos_bearing).asin();
+            let longitude: f64 = subpoint.longitude
+                + (sin_bearing * sin_angle * cos_lat).atan2(cos_angle - sin_lat * latitude.sin());
+            Geodetic {
+                latitude,
+                longitude: (longitude + core::f64::consts::PI).rem_euclid(crate::TWOPI) - core::f64::consts::PI,
+                altitude: 0.0,
+            }
+        })
+        .collect()
+}
+
+impl OrbitalElements {
+    /// Computes the coverage footprint of the satellite at a time since epoch (see `footprint`).
+    ///
+    /// # Arguments
+    /// * `tsince` - Time since epoch in minutes.
+    /// * `min_elevation` - Minimum elevation angle at the edge of the footprint in radians.
+    /// * `points` - Number of boundary points to generate.
+    ///
+    /// # Returns
+    /// * The boundary points, clockwise from north, on the surface of the Earth, or the propagation
+    ///   error.
+    pub fn footprint(&self, tsince: f64, min_elevation: f64, points: usize) -> Result<Vec<Geodetic>, PropagationError> {
+        Ok(footprint(&subpoint(self, tsince)?, min_elevation, points))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StateVector;
+    use crate::geodetic::geodetic_to_ecef;
+    use crate::topocentric::Observer;
+
+    /// Tests the horizon circle of a satellite above the equator.
+    #[test]
+    fn test_footprint_horizon() {
+        let center = Geodetic {
+            latitude: 0.0,
+            longitude: 0.0,
+            altitude: 1000.0,
+        };
+
+        let boundary = footprint(&center, 0.0, 4);
+
+        let expected: f64 = (WGS84_A / (WGS84_A + 1000.0)).acos();
+        assert_eq!(boundary.len(), 4);
+        assert!((boundary[0].latitude - expected).abs() < 1e-12);
+        assert!(boundary[0].longitude.abs() < 1e-12);
+        assert!((boundary[1].longitude - expected).abs() < 1e-12);
+        assert!((boundary[2].latitude + expected).abs() < 1e-12);
+        assert!((boundary[3].longitude + expected).abs() < 1e-12);
+    }
+
+    /// Tests that the satellite is seen at the minimum elevation from the edge of the footprint.
+    #[test]
+    fn test_footprint_elevation() {
+        let center = Geodetic {
+            latitude: 40.0_f64.to_radians(),
+            longitude: 179.0_f64.to_radians(),
+            altitude: 550.0,
+        };
+        let satellite = StateVector {
+            position: geodetic_to_ecef(&center),
+            velocity: [0.0; 3],
+        };
+
+        for point in footprint(&center, 10.0_f64.to_radians(), 16) {
+            assert!(point.longitude.abs() <= core::f64::consts::PI);
+            let observer = Observer { location: point };
+            let angles = observer.look_angles_ecef(&satellite);
+            // The ellipsoid departs from the spherical model by a fraction of a degree.
+            assert!((angles.elevation.to_degrees() - 10.0).abs() < 0.5);
+        }
+    }
+}
@@ -0,0 +1,626 @@
+//! Deep-space (SDP4) terms of the SGP4 propagator.
+//! Orbits with a period of 225 minutes or more receive lunar-solar secular and periodic
+//! perturbations, and resonance terms for 12-hour and geosynchronous orbits, following the
+//! `dscom`, `dsinit`, `dspace` and `dpper` routines of the reference implementation.
+
+use crate::{TWOPI, time};
+use core::f64::consts::PI;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Solar mean motion in radians per minute.
+const ZNS: f64 = 1.19459e-5;
+/// Solar eccentricity.
+const ZES: f64 = 0.01675;
+/// Lunar mean motion in radians per minute.
+const ZNL: f64 = 1.5835218e-4;
+/// Lunar eccentricity.
+const ZEL: f64 = 0.05490;
+/// Solar perturbation coefficient.
+const C1SS: f64 = 2.9864797e-6;
+/// Lunar perturbation coefficient.
+const C1L: f64 = 4.7968065e-7;
+/// Earth rotation rate in radians per minute.
+const RPTIM: f64 = 4.3752690880113e-3;
+/// Step of the resonance integrator in minutes.
+const STEP: f64 = 720.0;
+/// Inclination below which (or above π minus which) the nodal rates are not computed.
+const LOW_INCLINATION: f64 = 5.2359877e-2;
+
+/// Mean elements at a given time, updated by the deep-space terms.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Mean {
+    /// Eccentricity.
+    pub(crate) ecc: f64,
+    /// Inclination in radians.
+    pub(crate) incl: f64,
+    /// Right ascension of the ascending node in radians.
+    pub(crate) node: f64,
+    /// Argument of perigee in radians.
+    pub(crate) argp: f64,
+    /// Mean anomaly in radians.
+    pub(crate) m: f64,
+    /// Mean motion in radians per minute.
+    pub(crate) n: f64,
+}
+
+/// Orbit quantities shared by the lunar and solar coefficients.
+struct Orbit {
+    em: f64,
+    emsq: f64,
+    betasq: f64,
+    rtemsq: f64,
+    sinim: f64,
+    cosim: f64,
+    sinomm: f64,
+    cosomm: f64,
+    xnoi: f64,
+}
+
+/// Direction of a perturbing body (Sun or Moon) relative to the orbit.
+struct Body {
+    zcosg: f64,
+    zsing: f64,
+    zcosi: f64,
+    zsini: f64,
+    zcosh: f64,
+    zsinh: f64,
+    cc: f64,
+}
+
+/// Lunar or solar coefficients of the perturbations (`s` and `z` terms of `dscom`).
+struct Coefficients {
+    s1: f64,
+    s2: f64,
+    s3: f64,
+    s4: f64,
+    s5: f64,
+    s6: f64,
+    s7: f64,
+    z1: f64,
+    z2: f64,
+    z3: f64,
+    z11: f64,
+    z12: f64,
+    z13: f64,
+    z21: f64,
+    z22: f64,
+    z23: f64,
+    z31: f64,
+    z32: f64,
+    z33: f64,
+}
+
+impl Coefficients {
+    /// Computes the perturbation coefficients of a body.
+    fn new(orbit: &Orbit, body: &Body) -> Coefficients {
+        let a1: f64 = body.zcosg * body.zcosh + body.zsing * body.zcosi * body.zsinh;
+        let a3: f64 = -body.zsing * body.zcosh + body.zcosg * body.zcosi * body.zsinh;
+        let a7: f64 = -body.zcosg * body.zsinh + body.zsing * body.zcosi * body.zcosh;
+        let a8: f64 = body.zsing * body.zsini;
+        let a9: f64 = body.zsing * body.zsinh + body.zcosg * body.zcosi * body.zcosh;
+        let a10: f64 = body.zcosg * body.zsini;
+        let a2: f64 = orbit.cosim * a7 + orbit.sinim * a8;
+        let a4: f64 = orbit.cosim * a9 + orbit.sinim * a10;
+        let a5: f64 = -orbit.sinim * a7 + orbit.cosim * a8;
+        let a6: f64 = -orbit.sinim * a9 + orbit.cosim * a10;
+
+        let x1: f64 = a1 * orbit.cosomm + a2 * orbit.sinomm;
+        let x2: f64 = a3 * orbit.cosomm + a4 * orbit.sinomm;
+        let x3: f64 = -a1 * orbit.sinomm + a2 * orbit.cosomm;
+        let x4: f64 = -a3 * orbit.sinomm + a4 * orbit.cosomm;
+        let x5: f64 = a5 * orbit.sinomm;
+        let x6: f64 = a6 * orbit.sinomm;
+        let x7: f64 = a5 * orbit.cosomm;
+        let x8: f64 = a6 * orbit.cosomm;
+
+        let emsq: f64 = orbit.emsq;
+        let z31: f64 = 12.0 * x1 * x1 - 3.0 * x3 * x3;
+        let z32: f64 = 24.0 * x1 * x2 - 6.0 * x3 * x4;
+        let z33: f64 = 12.0 * x2 * x2 - 3.0 * x4 * x4;
+        let z1: f64 = 3.0 * (a1 * a1 + a2 * a2) + z31 * emsq;
+        let z2: f64 = 6.0 * (a1 * a3 + a2 * a4) + z32 * emsq;
+        let z3: f64 = 3.0 * (a3 * a3 + a4 * a4) + z33 * emsq;
+        let z11: f64 = -6.0 * a1 * a5 + emsq * (-24.0 * x1 * x7 - 6.0 * x3 * x5);
+        let z12: f64 = -6.0 * (a1 * a6 + a3 * a5) + emsq * (-24.0 * (x2 * x7 + x1 * x8) - 6.0 * (x3 * x6 + x4 * x5));
+        let z13: f64 = -6.0 * a3 * a6 + emsq * (-24.0 * x2 * x8 - 6.0 * x4 * x6);
+        let z21: f64 = 6.0 * a2 * a5 + emsq * (24.0 * x1 * x5 - 6.0 * x3 * x7);
+        let z22: f64 = 6.0 * (a4 * a5 + a2 * a6) + emsq * (24.0 * (x2 * x5 + x1 * x6) - 6.0 * (x4 * x7 + x3 * x8));
+        let z23: f64 = 6.0 * a4 * a6 + emsq * (24.0 * x2 * x6 - 6.0 * x4 * x8);
+        let s3: f64 = body.cc * orbit.xnoi;
+        let s4: f64 = s3 * orbit.rtemsq;
+
+        Coefficients {
+            s1: -15.0 * orbit.em * s4,
+            s2: -0.5 * s3 / orbit.rtemsq,
+            s3,
+            s4,
+            s5: x1 * x3 + x2 * x4,
+            s6: x2 * x3 + x1 * x4,
+            s7: x2 * x4 - x1 * x3,
+            z1: z1 + z1 + orbit.betasq * z31,
+            z2: z2 + z2 + orbit.betasq * z32,
+            z3: z3 + z3 + orbit.betasq * z33,
+            z11,
+            z12,
+            z13,
+            z21,
+            z22,
+            z23,
+            z31,
+            z32,
+            z33,
+        }
+    }
+
+    /// Computes the secular rates of the eccentricity, inclination, mean anomaly, argument of
+    /// perigee and node (before division by sin i) caused by the body.
+    fn secular_rates(&self, zn: f64, emsq: f64) -> (f64, f64, f64, f64, f64) {
+        (
+            self.s1 * zn * self.s5,
+            self.s2 * zn * (self.z11 + self.z13),
+            -zn * self.s3 * (self.z1 + self.z3 - 14.0 - 6.0 * emsq),
+            self.s4 * zn * (self.z31 + self.z33 - 6.0),
+            -zn * self.s2 * (self.z21 + self.z23),
+        )
+    }
+}
+
+/// Amplitudes of the long-period periodic perturbations of one body.
+#[derive(Debug, Clone)]
+struct Periodics {
+    e2: f64,
+    e3: f64,
+    i2: f64,
+    i3: f64,
+    l2: f64,
+    l3: f64,
+    l4: f64,
+    gh2: f64,
+    gh3: f64,
+    gh4: f64,
+    h2: f64,
+    h3: f64,
+    /// Mean anomaly of the body at epoch in radians.
+    zmo: f64,
+    /// Mean motion of the body in radians per minute.
+    zn: f64,
+    /// Eccentricity of the orbit of the body.
+    ze: f64,
+}
+
+impl Periodics {
+    /// Computes the periodic amplitudes of a body from its coefficients.
+    fn new(c: &Coefficients, emsq: f64, zmo: f64, zn: f64, ze: f64) -> Periodics {
+        Periodics {
+            e2: 2.0 * c.s1 * c.s6,
+            e3: 2.0 * c.s1 * c.s7,
+            i2: 2.0 * c.s2 * c.z12,
+            i3: 2.0 * c.s2 * (c.z13 - c.z11),
+            l2: -2.0 * c.s3 * c.z2,
+            l3: -2.0 * c.s3 * (c.z3 - c.z1),
+            l4: -2.0 * c.s3 * (-21.0 - 9.0 * emsq) * ze,
+            gh2: 2.0 * c.s4 * c.z32,
+            gh3: 2.0 * c.s4 * (c.z33 - c.z31),
+            gh4: -18.0 * c.s4 * ze,
+            h2: -2.0 * c.s2 * c.z22,
+            h3: -2.0 * c.s2 * (c.z23 - c.z21),
+            zmo,
+            zn,
+            ze,
+        }
+    }
+
+    /// Evaluates the perturbations of the eccentricity, inclination, mean longitude, argument of
+    /// perigee and node at a time since epoch.
+    fn evaluate(&self, t: f64) -> [f64; 5] {
+        let zm: f64 = self.zmo + self.zn * t;
+        let zf: f64 = zm + 2.0 * self.ze * zm.sin();
+        let (sinzf, coszf) = zf.sin_cos();
+        let f2: f64 = 0.5 * sinzf * sinzf - 0.25;
+        let f3: f64 = -0.5 * sinzf * coszf;
+        [
+            self.e2 * f2 + self.e3 * f3,
+            self.i2 * f2 + self.i3 * f3,
+            self.l2 * f2 + self.l3 * f3 + self.l4 * sinzf,
+            self.gh2 * f2 + self.gh3 * f3 + self.gh4 * sinzf,
+            self.h2 * f2 + self.h3 * f3,
+        ]
+    }
+}
+
+/// Geopotential resonance of the orbit.
+#[derive(Debug, Clone)]
+enum Resonance {
+    /// No resonance.
+    None,
+    /// One-day (geosynchronous) resonance.
+    Synchronous { del1: f64, del2: f64, del3: f64 },
+    /// Half-day (Molniya) resonance.
+    HalfDay { d: [f64; 10] },
+}
+
+/// Holds the deep-space initialization of a set of orbital elements.
+#[derive(Debug, Clone)]
+pub(crate) struct DeepSpace {
+    solar: Periodics,
+    lunar: Periodics,
+    // Secular rates of the elements caused by the Sun and the Moon.
+    dedt: f64,
+    didt: f64,
+    dmdt: f64,
+    domdt: f64,
+    dnodt: f64,
+    // Resonance terms.
+    resonance: Resonance,
+    gsto: f64,
+    xfact: f64,
+    xlamo: f64,
+    no: f64,
+    argpo: f64,
+    argpdot: f64,
+}
+
+impl DeepSpace {
+    /// Performs the deep-space initialization (`dscom` and `dsinit`).
+    ///
+    /// # Arguments
+    /// * `epoch_jd` - Julian date of the epoch.
+    /// * `mean` - Mean elements at epoch, with the Brouwer mean motion.
+    /// * `rates` - Secular rates of the mean anomaly, argument of perigee and node in radians per minute.
+    /// * `xke` - sqrt(μ) of the gravity model in earth radii^1.5 per minute.
+    ///
+    /// # Returns
+    /// * The deep-space terms.
+    pub(crate) fn new(epoch_jd: f64, mean: &Mean, rates: (f64, f64, f64), xke: f64) -> DeepSpace {
+        let (mdot, argpdot, nodedot) = rates;
+        let ecco: f64 = mean.ecc;
+        let no: f64 = mean.n;
+
+        // dscom: directions of the Sun and the Moon and their perturbation coefficients.
+        let (snodm, cnodm) = mean.node.sin_cos();
+        let (sinomm, cosomm) = mean.argp.sin_cos();
+        let (sinim, cosim) = mean.incl.sin_cos();
+        let emsq: f64 = ecco * ecco;
+        let betasq: f64 = 1.0 - emsq;
+        let orbit = Orbit {
+            em: ecco,
+            emsq,
+            betasq,
+            rtemsq: betasq.sqrt(),
+            sinim,
+            cosim,
+            sinomm,
+            cosomm,
+            xnoi: 1.0 / no,
+        };
+
+        let day: f64 = epoch_jd - 2433281.5 + 18261.5;
+        let xnodce: f64 = (4.5236020 - 9.2422029e-4 * day) % TWOPI;
+        let (stem, ctem) = xnodce.sin_cos();
+        let zcosil: f64 = 0.91375164 - 0.03568096 * ctem;
+        let zsinil: f64 = (1.0 - zcosil * zcosil).sqrt();
+        let zsinhl: f64 = 0.089683511 * stem / zsinil;
+        let zcoshl: f64 = (1.0 - zsinhl * zsinhl).sqrt();
+        let gam: f64 = 5.8351514 + 0.0019443680 * day;
+        let zx: f64 = (0.39785416 * stem / zsinil).atan2(zcoshl * ctem + 0.91744867 * zsinhl * stem);
+        let zx: f64 = gam + zx - xnodce;
+
+        let sun = Coefficients::new(
+            &orbit,
+            &Body {
+                zcosg: 0.1945905,
+                zsing: -0.98088458,
+                zcosi: 0.91744867,
+                zsini: 0.39785416,
+                zcosh: cnodm,
+                zsinh: snodm,
+                cc: C1SS,
+            },
+        );
+        let moon = Coefficients::new(
+            &orbit,
+            &Body {
+                zcosg: zx.cos(),
+                zsing: zx.sin(),
+                zcosi: zcosil,
+                zsini: zsinil,
+                zcosh: zcoshl * cnodm + zsinhl * snodm,
+                zsinh: snodm * zcoshl - cnodm * zsinhl,
+                cc: C1L,
+            },
+        );
+        let zmol: f64 = (4.7199672 + 0.22997150 * day - gam) % TWOPI;
+        let zmos: f64 = (6.2565837 + 0.017201977 * day) % TWOPI;
+
+        // dsinit: lunar-solar secular rates.
+        let low: bool = mean.incl < LOW_INCLINATION || mean.incl > PI - LOW_INCLINATION;
+        let (ses, sis, sls, sghs, shs) = sun.secular_rates(ZNS, emsq);
+        let (sel, sil, sll, sghl, shll) = moon.secular_rates(ZNL, emsq);
+        let shs: f64 = if low { 0.0 } else { shs };
+        let shll: f64 = if low { 0.0 } else { shll };
+        let shs: f64 = if sinim != 0.0 { shs / sinim } else { shs };
+        let mut domdt: f64 = sghs - cosim * shs + sghl;
+        let mut dnodt: f64 = shs;
+        if sinim != 0.0 {
+            domdt -= cosim / sinim * shll;
+            dnodt += shll / sinim;
+        }
+        let dmdt: f64 = sls + sll;
+
+        // dsinit: resonance terms.
+        let gsto: f64 = time::gmst(epoch_jd);
+        let aonv: f64 = (no / xke).powf(2.0 / 3.0);
+        let (resonance, xlamo, xfact) = if no < 0.0052359877 && no > 0.0034906585 {
+            let g200: f64 = 1.0 + emsq * (-2.5 + 0.8125 * emsq);
+            let g310: f64 = 1.0 + 2.0 * emsq;
+            let g300: f64 = 1.0 + emsq * (-6.0 + 6.60937 * emsq);
+            let f220: f64 = 0.75 * (1.0 + cosim) * (1.0 + cosim);
+            let f311: f64 = 0.9375 * sinim * sinim * (1.0 + 3.0 * cosim) - 0.75 * (1.0 + cosim);
+            let f330: f64 = 1.875 * (1.0 + cosim).powi(3);
+            let del1: f64 = 3.0 * no * no * aonv * aonv;
+            let resonance = Resonance::Synchronous {
+                del1: del1 * f311 * g310 * 2.1460748e-6 * aonv,
+                del2: 2.0 * del1 * f220 * g200 * 1.7891679e-6,
+                del3: 3.0 * del1 * f330 * g300 * 2.2123015e-7 * aonv,
+            };
+            let xlamo: f64 = (mean.m + mean.node + mean.argp - gsto) % TWOPI;
+            let xfact: f64 = mdot + argpdot + nodedot - RPTIM + dmdt + domdt + dnodt - no;
+            (resonance, xlamo, xfact)
+        } else if (8.26e-3..=9.24e-3).contains(&no) && ecco >= 0.5 {
+            let d: [f64; 10] = half_day_coefficients(ecco, sinim, cosim, no, aonv);
+            let xlamo: f64 = (mean.m + mean.node + mean.node - gsto - gsto) % TWOPI;
+            let xfact: f64 = mdot + dmdt + 2.0 * (nodedot + dnodt - RPTIM) - no;
+            (Resonance::HalfDay { d }, xlamo, xfact)
+        } else {
+            (Resonance::None, 0.0, 0.0)
+        };
+
+        DeepSpace {
+            solar: Periodics::new(&sun, emsq, zmos, ZNS, ZES),
+            lunar: Periodics::new(&moon, emsq, zmol, ZNL, ZEL),
+            dedt: ses + sel,
+            didt: sis + sil,
+            dmdt,
+            domdt,
+            dnodt,
+            resonance,
+            gsto,
+            xfact,
+            xlamo,
+            no,
+            argpo: mean.argp,
+            argpdot,
+        }
+    }
+
+    /// Applies the lunar-solar secular effects and the resonance terms (`dspace`).
+    ///
+    /// # Arguments
+    /// * `t` - Time since epoch in minutes.
+    /// * `mean` - Mean elements after the secular gravity and drag updates.
+    ///
+    /// # Returns
+    /// * The updated mean elements, with the mean motion including the resonance effects.
+    pub(crate) fn secular(&self, t: f64, mean: &Mean) -> Mean {
+        let mut result = Mean {
+            ecc: mean.ecc + self.dedt * t,
+            incl: mean.incl + self.didt * t,
+            node: mean.node + self.dnodt * t,
+            argp: mean.argp + self.domdt * t,
+            m: mean.m + self.dmdt * t,
+            n: mean.n,
+        };
+        if let Resonance::None = self.resonance {
+            return result;
+        }
+
+        // Integrate the resonance equations from epoch with fixed steps of half a day.
+        let theta: f64 = (self.gsto + t * RPTIM) % TWOPI;
+        let delt: f64 = if t > 0.0 { STEP } else { -STEP };
+        let mut atime: f64 = 0.0;
+        let mut xli: f64 = self.xlamo;
+        let mut xni: f64 = self.no;
+        let (xldot, xndt, xnddt) = loop {
+            let (xndt, xnddt) = self.resonance_rates(xli, atime);
+            let xldot: f64 = xni + self.xfact;
+            if (t - atime).abs() < STEP {
+                break (xldot, xndt, xnddt * xldot);
+            }
+            xli += xldot * delt + xndt * STEP * STEP / 2.0;
+            xni += xndt * delt + xnddt * xldot * STEP * STEP / 2.0;
+            atime += delt;
+        };
+
+        let ft: f64 = t - atime;
+        let nm: f64 = xni + xndt * ft + xnddt * ft * ft * 0.5;
+        let xl: f64 = xli + xldot * ft + xndt * ft * ft * 0.5;
+        result.m = match self.resonance {
+            Resonance::Synchronous { .. } => xl - result.node - result.argp + theta,
+            _ => xl - 2.0 * result.node + 2.0 * theta,
+        };
+        result.n = nm;
+        result
+    }
+
+    /// Computes the resonance derivatives of the mean motion.
+    ///
+    /// # Arguments
+    /// * `xli` - Resonance longitude in radians.
+    /// * `atime` - Time of the integration step in minutes.
+    ///
+    /// # Returns
+    /// * The first derivative of the mean motion and the factor of its second derivative.
+    fn resonance_rates(&self, xli: f64, atime: f64) -> (f64, f64) {
+        match &self.resonance {
+            Resonance::None => (0.0, 0.0),
+            Resonance::Synchronous { del1, del2, del3 } => {
+                const FASX2: f64 = 0.13130908;
+                const FASX4: f64 = 2.8843198;
+                const FASX6: f64 = 0.37448087;
+                (
+                    del1 * (xli - FASX2).sin() + del2 * (2.0 * (xli - FASX4)).sin() + del3 * (3.0 * (xli - FASX6)).sin(),
+                    del1 * (xli - FASX2).cos()
+                        + 2.0 * del2 * (2.0 * (xli - FASX4)).cos()
+                        + 3.0 * del3 * (3.0 * (xli - FASX6)).cos(),
+                )
+            }
+            Resonance::HalfDay { d } => {
+                const G22: f64 = 5.7686396;
+                const G32: f64 = 0.95240898;
+                const G44: f64 = 1.8014998;
+                const G52: f64 = 1.0508330;
+                const G54: f64 = 4.4108898;
+                let xomi: f64 = self.argpo + self.argpdot * atime;
+                let x2omi: f64 = xomi + xomi;
+                let x2li: f64 = xli + xli;
+                // Arguments of the ten terms, in the order of the coefficients.
+                let arguments: [f64; 10] = [
+                    x2omi + xli - G22,
+                    xli - G22,
+                    xomi + xli - G32,
+                    -xomi + xli - G32,
+                    x2omi + x2li - G44,
+                    x2li - G44,
+                    xomi + xli - G52,
+                    -xomi + xli - G52,
+                    xomi + x2li - G54,
+                    -xomi + x2li - G54,
+                ];
+                // Terms in 2λ have twice the derivative.
+                let factors: [f64; 10] = [1.0, 1.0, 1.0, 1.0, 2.0, 2.0, 1.0, 1.0, 2.0, 2.0];
+                let xndt: f64 = (0..10).map(|k| d[k] * arguments[k].sin()).sum();
+                let xnddt: f64 = (0..10).map(|k| factors[k] * d[k] * arguments[k].cos()).sum();
+                (xndt, xnddt)
+            }
+        }
+    }
+
+    /// Applies the lunar-solar long-period periodics (`dpper`).
+    ///
+    /// # Arguments
+    /// * `t` - Time since epoch in minutes.
+    /// * `mean` - Mean elements after the secular updates.
+    ///
+    /// # Returns
+    /// * The perturbed elements. The inclination may be negative, which the caller must fold back.
+    pub(crate) fn periodics(&self, t: f64, mean: &Mean) -> Mean {
+        let solar: [f64; 5] = self.solar.evaluate(t);
+        let lunar: [f64; 5] = self.lunar.evaluate(t);
+        let [pe, pinc, pl, pgh, ph] = [0, 1, 2, 3, 4].map(|k| solar[k] + lunar[k]);
+
+        let incl: f64 = mean.incl + pinc;
+        let ecc: f64 = mean.ecc + pe;
+        let (sinip, cosip) = incl.sin_cos();
+        let mut result = Mean { ecc, incl, ..*mean };
+
+        if incl >= 0.2 {
+            let ph: f64 = ph / sinip;
+            result.argp = mean.argp + pgh - cosip * ph;
+            result.node = mean.node + ph;
+            result.m = mean.m + pl;
+        } else {
+            // Lyddane modification for low inclinations.
+            let (sinop, cosop) = mean.node.sin_cos();
+            let alfdp: f64 = sinip * sinop + ph * cosop + pinc * cosip * sinop;
+            let betdp: f64 = sinip * cosop - ph * sinop + pinc * cosip * cosop;
+            let node: f64 = mean.node % TWOPI;
+            let xls: f64 = mean.m + mean.argp + cosip * node + pl + pgh - pinc * node * sinip;
+            let mut nodep: f64 = alfdp.atan2(betdp);
+            if (node - nodep).abs() > PI {
+                nodep += if nodep < node { TWOPI } else { -TWOPI };
+            }
+            result.node = nodep;
+            result.m = mean.m + pl;
+            result.argp = xls - result.m - cosip * nodep;
+        }
+        result
+    }
+}
+
+/// Computes the coefficients of the half-day resonance terms.
+///
+/// # Arguments
+/// * `ecco` - Eccentricity at epoch.
+/// * `sinim` - Sine of the inclination.
+/// * `cosim` - Cosine of the inclination.
+/// * `no` - Mean motion in radians per minute.
+/// * `aonv` - Inverse semi-major axis in earth radii.
+///
+/// # Returns
+/// * The coefficients d2201, d2211, d3210, d3222, d4410, d4422, d5220, d5232, d5421 and d5433.
+fn half_day_coefficients(ecco: f64, sinim: f64, cosim: f64, no: f64, aonv: f64) -> [f64; 10] {
+    let em: f64 = ecco;
+    let emsq: f64 = em * em;
+    let eoc: f64 = em * emsq;
+    let cosisq: f64 = cosim * cosim;
+
+    let g201: f64 = -0.306 - (em - 0.64) * 0.440;
+    let (g211, g310, g322, g410, g422, g520) = if em <= 0.65 {
+        (
+            3.616 - 13.2470 * em + 16.2900 * emsq,
+            -19.302 + 117.3900 * em - 228.4190 * emsq + 156.5910 * eoc,
+            -18.9068 + 109.7927 * em - 214.6334 * emsq + 146.5816 * eoc,
+            -41.122 + 242.6940 * em - 471.0940 * emsq + 313.9530 * eoc,
+            -146.407 + 841.8800 * em - 1629.014 * emsq + 1083.4350 * eoc,
+            -532.114 + 3017.977 * em - 5740.032 * emsq + 3708.2760 * eoc,
+        )
+    } else {
+        (
+            -72.099 + 331.819 * em - 508.738 * emsq + 266.724 * eoc,
+            -346.844 + 1582.851 * em - 2415.925 * emsq + 1246.113 * eoc,
+            -342.585 + 1554.908 * em - 2366.899 * emsq + 1215.972 * eoc,
+            -1052.797 + 4758.686 * em - 7193.992 * emsq + 3651.957 * eoc,
+            -3581.690 + 16178.110 * em - 24462.770 * emsq + 12422.520 * eoc,
+            if em > 0.715 {
+                -5149.66 + 29936.92 * em - 54087.36 * emsq + 31324.56 * eoc
+            } else {
+                1464.74 - 4664.75 * em + 3763.64 * emsq
+            },
+        )
+    };
+    let (g533, g521, g532) = if em < 0.7 {
+        (
+            -919.22770 + 4988.6100 * em - 9064.7700 * emsq + 5542.21 * eoc,
+            -822.71072 + 4568.6173 * em - 8491.4146 * emsq + 5337.524 * eoc,
+            -853.66600 + 4690.2500 * em - 8624.7700 * emsq + 5341.4 * eoc,
+        )
+    } else {
+        (
+            -37995.780 + 161616.52 * em - 229838.20 * emsq + 109377.94 * eoc,
+            -51752.104 + 218913.95 * em - 309468.16 * emsq + 146349.42 * eoc,
+            -40023.880 + 170470.89 * em - 242699.48 * emsq + 115605.82 * eoc,
+        )
+    };
+
+    let sini2: f64 = sinim * sinim;
+    let f220: f64 = 0.75 * (1.0 + 2.0 * cosim + cosisq);
+    let f221: f64 = 1.5 * sini2;
+    let f321: f64 = 1.875 * sinim * (1.0 - 2.0 * cosim - 3.0 * cosisq);
+    let f322: f64 = -1.875 * sinim * (1.0 + 2.0 * cosim - 3.0 * cosisq);
+    let f441: f64 = 35.0 * sini2 * f220;
+    let f442: f64 = 39.3750 * sini2 * sini2;
+    let f522: f64 =
+        9.84375 * sinim * (sini2 * (1.0 - 2.0 * cosim - 5.0 * cosisq) + 0.33333333 * (-2.0 + 4.0 * cosim + 6.0 * cosisq));
+    let f523: f64 =
+        sinim * (4.92187512 * sini2 * (-2.0 - 4.0 * cosim + 10.0 * cosisq) + 6.56250012 * (1.0 + 2.0 * cosim - 3.0 * cosisq));
+    let f542: f64 = 29.53125 * sinim * (2.0 - 8.0 * cosim + cosisq * (-12.0 + 8.0 * cosim + 10.0 * cosisq));
+    let f543: f64 = 29.53125 * sinim * (-2.0 - 8.0 * cosim + cosisq * (12.0 + 8.0 * cosim - 10.0 * cosisq));
+
+    let temp1: f64 = 3.0 * no * no * aonv * aonv;
+    let temp: f64 = temp1 * 1.7891679e-6;
+    let (d2201, d2211) = (temp * f220 * g201, temp * f221 * g211);
+    let temp1: f64 = temp1 * aonv;
+    let temp: f64 = temp1 * 3.7393792e-7;
+    let (d3210, d3222) = (temp * f321 * g310, temp * f322 * g322);
+    let temp1: f64 = temp1 * aonv;
+    let temp: f64 = 2.0 * temp1 * 7.3636953e-9;
+    let (d4410, d4422) = (temp * f441 * g410, temp * f442 * g422);
+    let temp1: f64 = temp1 * aonv;
+    let temp: f64 = temp1 * 1.1428639e-7;
+    let (d5220, d5232) = (temp * f522 * g520, temp * f523 * g532);
+    let temp: f64 = 2.0 * temp1 * 2.1765803e-9;
+    let (d5421, d5433) = (temp * f542 * g521, temp * f543 * g533);
+
+    [d2201, d2211, d3210, d3222, d4410, d4422, d5220, d5232, d5421, d5433]
+}
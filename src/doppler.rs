@@ -0,0 +1,102 @@
+//! Doppler shift of satellite signals received by a ground observer.
+//! The shift follows from the range-rate given by the topocentric look angles.
+
+use crate::propagator::PropagationError;
+use crate::topocentric::Observer;
+use crate::{OrbitalElements, Sgp4, StateVector, time};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Speed of light in kilometers per second.
+pub const SPEED_OF_LIGHT: f64 = 299792.458;
+
+/// Represents the Doppler state of a satellite signal at one instant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DopplerSample {
+    /// Time since epoch in minutes.
+    pub tsince: f64,
+    /// Range-rate in kilometers per second (positive when receding).
+    pub range_rate: f64,
+    /// Received frequency, in the unit of the transmitted frequency.
+    pub frequency: f64,
+    /// Received minus transmitted frequency.
+    pub shift: f64,
+}
+
+/// Computes the frequency received from a source moving along the line of sight.
+///
+/// # Arguments
+/// * `frequency` - Transmitted frequency (any unit).
+/// * `range_rate` - Range-rate in kilometers per second (positive when receding).
+///
+/// # Returns
+/// * The received frequency, in the unit of `frequency`.
+pub fn doppler_shift(frequency: f64, range_rate: f64) -> f64 {
+    frequency * (1.0 - range_rate / SPEED_OF_LIGHT)
+}
+
+/// Computes the range-rate and Doppler-shifted frequency of a satellite signal over time.
+///
+/// # Arguments
+/// * `observer` - The receiving ground observer.
+/// * `elements` - Orbital elements of the transmitting satellite.
+/// * `frequency` - Transmitted frequency (any unit).
+/// * `tsince_values` - Times since epoch in minutes.
+///
+/// # Returns
+/// * One Doppler sample per requested time, or the first propagation error.
+pub fn doppler_over_time(observer: &Observer, elements: &OrbitalElements, frequency: f64, tsince_values: &[f64]) -> Result<Vec<DopplerSample>, PropagationError> {
+    let propagator: Sgp4 = Sgp4::new(elements);
+    tsince_values
+        .iter()
+        .map(|&tsince| {
+            let state: StateVector = propagator.propagate(tsince)?;
+            let gmst: f64 = time::gmst(elements.jd_at(tsince));
+            let range_rate: f64 = observer.look_angles(&state, gmst).range_rate;
+            let received: f64 = doppler_shift(frequency, range_rate);
+
+            Ok(DopplerSample {
+                tsince,
+                range_rate,
+                frequency: received,
+                shift: received - frequency,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::iss_elements;
+
+    /// Tests the sign and magnitude of the Doppler shift.
+    #[test]
+    fn test_doppler_shift() {
+        let approaching: f64 = doppler_shift(437.0e6, -7.0);
+        assert!((approaching - 437.0e6 - 10204.0).abs() < 1.0);
+        assert_eq!(doppler_shift(437.0e6, 0.0), 437.0e6);
+        assert!(doppler_shift(437.0e6, 7.0) < 437.0e6);
+    }
+
+    /// Tests that Doppler samples follow the observer range-rate.
+    #[test]
+    fn test_doppler_over_time() {
+        let elements = iss_elements();
+        let observer = Observer::new(48.85, 2.35, 0.035);
+        let times: Vec<f64> = (0..100).map(|i| i as f64).collect();
+
+        let samples = doppler_over_time(&observer, &elements, 145.8e6, &times).unwrap();
+
+        assert_eq!(samples.len(), 100);
+        for sample in &samples {
+            assert!(sample.range_rate.abs() < 8.0);
+            assert!((sample.shift + 145.8e6 * sample.range_rate / SPEED_OF_LIGHT).abs() < 1e-6);
+        }
+
+        let mut decayed = elements;
+        decayed.eccentricity = 0.065;
+        assert!(matches!(doppler_over_time(&observer, &decayed, 145.8e6, &times), Err(PropagationError::Decayed { .. })));
+    }
+}
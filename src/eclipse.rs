@@ -0,0 +1,277 @@
+//! Eclipses of a satellite by the Earth.
+//! The shadow of the Earth is modelled as a cone (umbra) surrounded by a penumbra, by comparing the
+//! apparent radii of the Sun and the Earth with their angular separation as seen from the satellite.
+//! The eclipse intervals of any propagator are found with the event framework.
+
+use crate::astro::sun_position;
+use crate::events::{EventDetector, EventDirection, Interval, revolution_step};
+use crate::geodetic::WGS84_A;
+use crate::passes::positive_intervals;
+use crate::propagator::{PropagationError, Propagator};
+use crate::{OrbitalElements, Sgp4};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Radius of the Sun in kilometers.
+pub const SUN_RADIUS: f64 = 696000.0;
+
+/// Represents the illumination of a satellite by the Sun.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Illumination {
+    /// The whole solar disk is visible.
+    Sunlit,
+    /// The solar disk is partially hidden by the Earth.
+    Penumbra,
+    /// The solar disk is entirely hidden by the Earth.
+    Umbra,
+}
+
+/// Represents an eclipse of a satellite, from penumbra entry to penumbra exit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Eclipse {
+    /// Penumbra entry in minutes since epoch.
+    pub start: f64,
+    /// Penumbra exit in minutes since epoch.
+    pub end: f64,
+    /// Umbra entry and exit in minutes since epoch, if the satellite reaches the umbra.
+    pub umbra: Option<(f64, f64)>,
+}
+
+impl Eclipse {
+    /// Returns the duration of the eclipse in minutes.
+    pub fn duration(&self) -> f64 {
+        self.end - self.start
+    }
+}
+
+/// Computes the apparent geometry of the Sun and the Earth as seen from a satellite.
+///
+/// # Arguments
+/// * `position` - Geocentric position of the satellite in kilometers.
+/// * `sun` - Geocentric position of the Sun in kilometers, in the same frame.
+///
+/// # Returns
+/// * The apparent radii of the Sun and the Earth, and the separation of their centers, in radians.
+fn apparent_disks(position: [f64; 3], sun: [f64; 3]) -> (f64, f64, f64) {
+    let to_sun: [f64; 3] = [sun[0] - position[0], sun[1] - position[1], sun[2] - position[2]];
+    let sun_distance: f64 = (to_sun[0] * to_sun[0] + to_sun[1] * to_sun[1] + to_sun[2] * to_sun[2]).sqrt();
+    let earth_distance: f64 = (position[0] * position[0] + position[1] * position[1] + position[2] * position[2]).sqrt();
+
+    // Apparent radii of the Sun and the Earth, and separation of their centers.
+    let sun_radius: f64 = (SUN_RADIUS / sun_distance).asin();
+    let earth_radius: f64 = (WGS84_A / earth_distance).min(1.0).asin();
+    let cos_separation: f64 = -(position[0] * to_sun[0] + position[1] * to_sun[1] + position[2] * to_sun[2])
+        / (earth_distance * sun_distance);
+    let separation: f64 = cos_separation.clamp(-1.0, 1.0).acos();
+
+    (sun_radius, earth_radius, separation)
+}
+
+/// Computes the shadow margins of a satellite, positive when it is in the corresponding shadow.
+///
+/// # Arguments
+/// * `position` - Geocentric position of the satellite in kilometers.
+/// * `sun` - Geocentric position of the Sun in kilometers, in the same frame.
+///
+/// # Returns
+/// * The penumbra and umbra margins in radians.
+fn shadow_margins(position: [f64; 3], sun: [f64; 3]) -> (f64, f64) {
+    let (sun_radius, earth_radius, separation) = apparent_disks(position, sun);
+    (
+        sun_radius + earth_radius - separation,
+        earth_radius - sun_radius - separation,
+    )
+}
+
+/// Computes the fraction of the solar disk visible from a satellite (shadow function).
+///
+/// # Arguments
+/// * `position` - Geocentric position of the satellite in kilometers.
+/// * `sun` - Geocentric position of the Sun in kilometers, in the same frame.
+///
+/// # Returns
+/// * 1 when sunlit, 0 in the umbra, and the unobstructed fraction of the disk in between.
+pub fn sunlit_fraction(position: [f64; 3], sun: [f64; 3]) -> f64 {
+    let (a, b, c) = apparent_disks(position, sun);
+    if c >= a + b {
+        1.0
+    } else if c <= b - a {
+        0.0
+    } else if c <= a - b {
+        // Annular eclipse: the Earth is entirely in front of the solar disk.
+        1.0 - b * b / (a * a)
+    } else {
+        // Area of the intersection of the two disks.
+        let x: f64 = (c * c + a * a - b * b) / (2.0 * c);
+        let y: f64 = (a * a - x * x).max(0.0).sqrt();
+        let area: f64 = a * a * (x / a).clamp(-1.0, 1.0).acos() + b * b * ((c - x) / b).clamp(-1.0, 1.0).acos() - c * y;
+        1.0 - area / (core::f64::consts::PI * a * a)
+    }
+}
+
+/// Determines the illumination of a satellite with a conical shadow model.
+///
+/// # Arguments
+/// * `position` - Geocentric position of the satellite in kilometers.
+/// * `sun` - Geocentric position of the Sun in kilometers, in the same frame.
+///
+/// # Returns
+/// * Whether the satellite is sunlit, in the penumbra or in the umbra.
+pub fn illumination(position: [f64; 3], sun: [f64; 3]) -> Illumination {
+    let (penumbra, umbra) = shadow_margins(position, sun);
+    if umbra > 0.0 {
+        Illumination::Umbra
+    } else if penumbra > 0.0 {
+        Illumination::Penumbra
+    } else {
+        Illumination::Sunlit
+    }
+}
+
+/// Finds the eclipses of a satellite over a time window.
+///
+/// # Arguments
+/// * `elements` - Orbital elements of the satellite.
+/// * `start` - Start of the window in minutes since epoch.
+/// * `end` - End of the window in minutes since epoch.
+///
+/// # Returns
+/// * The eclipses in chronological order, or the first propagation error. Eclipses in progress at the
+///   edges of the window are clipped to it.
+pub fn eclipses(elements: &OrbitalElements, start: f64, end: f64) -> Result<Vec<Eclipse>, PropagationError> {
+    let propagator: Sgp4 = Sgp4::new(elements);
+    let margins = |tsince: f64| propagator.propagate(tsince).map(|state| shadow_margins(state.position, sun_position(elements.jd_at(tsince))));
+    let umbra: Vec<(f64, f64)> = positive_intervals(|tsince| margins(tsince).map(|(_, umbra)| umbra), elements, start, end)?;
+
+    Ok(positive_intervals(|tsince| margins(tsince).map(|(penumbra, _)| penumbra), elements, start, end)?
+        .into_iter()
+        .map(|(entry, exit)| Eclipse {
+            start: entry,
+            end: exit,
+            umbra: umbra.iter().copied().find(|&(a, b)| entry <= a && b <= exit),
+        })
+        .collect())
+}
+
+/// Represents the shadow intervals of an eclipse.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EclipseInterval {
+    /// From penumbra entry to penumbra exit, which includes the umbra.
+    pub penumbra: Interval,
+    /// From umbra entry to umbra exit, if the satellite reaches the umbra.
+    pub umbra: Option<Interval>,
+}
+
+/// Finds the eclipses of a satellite over a time window with any propagator.
+///
+/// # Arguments
+/// * `propagator` - Propagator of the satellite.
+/// * `start` - Start of the window in minutes since epoch.
+/// * `end` - End of the window in minutes since epoch.
+///
+/// # Returns
+/// * The eclipses in chronological order, clipped to the window, or the first propagation error.
+pub fn eclipse_intervals<P: Propagator>(propagator: &P, start: f64, end: f64) -> Result<Vec<EclipseInterval>, PropagationError> {
+    let epoch: f64 = propagator.epoch_jd();
+    let mut detector: EventDetector = EventDetector::new(revolution_step(propagator, start)?);
+    detector.register(|tsince, state| shadow_margins(state.position, sun_position(epoch + tsince / 1440.0)).0, EventDirection::Either);
+    detector.register(|tsince, state| shadow_margins(state.position, sun_position(epoch + tsince / 1440.0)).1, EventDirection::Either);
+    let intervals: Vec<Vec<Interval>> = detector.intervals(propagator, start, end)?;
+    Ok(intervals[0]
+        .iter()
+        .map(|&penumbra| EclipseInterval {
+            penumbra,
+            umbra: intervals[1].iter().copied().find(|umbra| penumbra.contains(umbra.start) && penumbra.contains(umbra.end)),
+        })
+        .collect())
+}
+
+impl OrbitalElements {
+    /// Determines the illumination of the satellite at a time since epoch (see `illumination`).
+    ///
+    /// # Arguments
+    /// * `tsince` - Time since epoch in minutes.
+    ///
+    /// # Returns
+    /// * Whether the satellite is sunlit, in the penumbra or in the umbra, or the propagation error.
+    pub fn illumination(&self, tsince: f64) -> Result<Illumination, PropagationError> {
+        Ok(illumination(Sgp4::new(self).propagate(tsince)?.position, sun_position(self.jd_at(tsince))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astro::AU;
+    use crate::Sgp4;
+    use crate::test_support::iss_elements;
+
+    /// Tests the illumination on both sides of the Earth and at the edge of the shadow.
+    #[test]
+    fn test_illumination() {
+        let sun: [f64; 3] = [AU, 0.0, 0.0];
+
+        assert_eq!(illumination([7000.0, 0.0, 0.0], sun), Illumination::Sunlit);
+        assert_eq!(illumination([-7000.0, 0.0, 0.0], sun), Illumination::Umbra);
+        assert_eq!(illumination([-7000.0, 0.0, 6500.0], sun), Illumination::Sunlit);
+        // The penumbra is a few tens of kilometers wide at low altitude.
+        assert_eq!(illumination([-7000.0, 0.0, WGS84_A], sun), Illumination::Penumbra);
+
+        assert_eq!(sunlit_fraction([7000.0, 0.0, 0.0], sun), 1.0);
+        assert_eq!(sunlit_fraction([-7000.0, 0.0, 0.0], sun), 0.0);
+        let fraction: f64 = sunlit_fraction([-7000.0, 0.0, WGS84_A], sun);
+        assert!(fraction > 0.0 && fraction < 1.0);
+        // Half of the disk is hidden when the center of the Sun is on the limb of the Earth.
+        let limb: f64 = (WGS84_A / 7000.0).asin();
+        let edge: f64 = sunlit_fraction([-7000.0 * limb.cos(), 0.0, 7000.0 * limb.sin()], sun);
+        assert!((edge - 0.5).abs() < 0.01, "{}", edge);
+    }
+
+    /// Tests that a low Earth orbit has one eclipse per revolution, with a short penumbra.
+    #[test]
+    fn test_eclipses() {
+        let elements = iss_elements();
+
+        let found = eclipses(&elements, 0.0, 1440.0).unwrap();
+
+        assert!((15..=17).contains(&found.len()));
+        for eclipse in &found[1..found.len() - 1] {
+            let (entry, exit) = eclipse.umbra.unwrap();
+            assert!(eclipse.duration() > 20.0 && eclipse.duration() < 40.0);
+            assert!(entry - eclipse.start > 0.0 && entry - eclipse.start < 0.5);
+            assert!(eclipse.end - exit > 0.0 && eclipse.end - exit < 0.5);
+            assert_eq!(elements.illumination(0.5 * (entry + exit)), Ok(Illumination::Umbra));
+        }
+    }
+
+    /// Tests the eclipse intervals found with the event framework against the eclipses.
+    #[test]
+    fn test_eclipse_intervals() {
+        let elements = iss_elements();
+        let intervals: Vec<EclipseInterval> = eclipse_intervals(&Sgp4::new(&elements), 0.0, 1440.0).unwrap();
+        let found: Vec<Eclipse> = eclipses(&elements, 0.0, 1440.0).unwrap();
+        assert_eq!(intervals.len(), found.len());
+        for (interval, eclipse) in intervals.iter().zip(&found) {
+            assert!((interval.penumbra.start - eclipse.start).abs() < 1e-4);
+            assert!((interval.penumbra.duration() - eclipse.duration()).abs() < 1e-4);
+            match (interval.umbra, eclipse.umbra) {
+                (Some(umbra), Some((entry, exit))) => assert!((umbra.start - entry).abs() < 1e-4 && (umbra.end - exit).abs() < 1e-4),
+                (umbra, expected) => assert_eq!(umbra.is_none(), expected.is_none()),
+            }
+        }
+        assert!(intervals[1].penumbra.contains(intervals[1].umbra.unwrap().start));
+        assert!(eclipse_intervals(&Sgp4::new(&elements), 10.0, 0.0).unwrap().is_empty());
+    }
+
+    /// Tests that the eclipses of a satellite that decays during the search report the propagation error.
+    #[test]
+    fn test_eclipses_decayed() {
+        let mut elements = iss_elements();
+        elements.eccentricity = 0.065;
+
+        assert!(matches!(eclipses(&elements, 0.0, 1440.0), Err(PropagationError::Decayed { .. })));
+    }
+}
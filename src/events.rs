@@ -0,0 +1,509 @@
+//! Detection of events along a propagated trajectory.
+//! An event is a sign change of a scalar function g(t, state), such as the Z coordinate for node
+//! crossings or the elevation for station visibility. The functions are sampled on a regular grid
+//! to bracket their sign changes, which are then refined with Brent's method. Ready-made detectors
+//! cover the node crossings and the apsis passages, and `Interval` values hold the spans during
+//! which a function is positive.
+
+use crate::keplerian::{EARTH_MU, KeplerianElements};
+use crate::propagator::{PropagationError, Propagator};
+use crate::{StateVector, TWOPI, time};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
+/// Default time tolerance of the refinement in minutes.
+const TIME_TOLERANCE: f64 = 1e-6;
+/// Maximum number of iterations of Brent's method.
+const MAX_ITERATIONS: usize = 100;
+/// Number of samples per revolution of the ready-made detectors.
+const SAMPLES_PER_REVOLUTION: f64 = 36.0;
+
+/// Selects the sign changes reported for an event function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EventDirection {
+    /// From negative to positive.
+    Rising,
+    /// From positive to negative.
+    Falling,
+    /// Both directions.
+    #[default]
+    Either,
+}
+
+/// Represents a sign change of an event function.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Event {
+    /// Index of the event function, in the order of registration.
+    pub index: usize,
+    /// Time of the event in minutes since the epoch of the propagator.
+    pub tsince: f64,
+    /// Time of the event as a Julian date (UTC).
+    pub jd: f64,
+    /// Whether the function becomes positive.
+    pub rising: bool,
+}
+
+/// Represents a span of time during which an event function is positive.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Interval {
+    /// Start of the interval in minutes since the epoch of the propagator.
+    pub start: f64,
+    /// End of the interval in minutes since the epoch of the propagator.
+    pub end: f64,
+}
+
+impl Interval {
+    /// Returns the duration of the interval in minutes.
+    pub fn duration(&self) -> f64 {
+        self.end - self.start
+    }
+
+    /// Checks whether a time falls within the interval.
+    ///
+    /// # Arguments
+    /// * `tsince` - Time since epoch in minutes.
+    ///
+    /// # Returns
+    /// * `true` if the time is between the start and the end, included.
+    pub fn contains(&self, tsince: f64) -> bool {
+        self.start <= tsince && tsince <= self.end
+    }
+}
+
+/// Finds a root of a function bracketed by a sign change with Brent's method.
+///
+/// # Arguments
+/// * `f` - The function.
+/// * `low` - Lower end of the bracket.
+/// * `high` - Upper end of the bracket, where `f > 0` differs from the lower end.
+/// * `tolerance` - Width of the bracket at which the search stops.
+///
+/// # Returns
+/// * The root, within the tolerance.
+pub fn brent(f: impl Fn(f64) -> f64, low: f64, high: f64, tolerance: f64) -> f64 {
+    let (mut a, mut b): (f64, f64) = (low, high);
+    let (mut fa, mut fb): (f64, f64) = (f(a), f(b));
+    // The crossing is where `f > 0` changes, so a zero counts as negative.
+    if fa == 0.0 {
+        fa = -f64::MIN_POSITIVE;
+    }
+    if fb == 0.0 {
+        fb = -f64::MIN_POSITIVE;
+    }
+    let (mut c, mut fc): (f64, f64) = (a, fa);
+    let (mut d, mut e): (f64, f64) = (b - a, b - a);
+    for _ in 0..MAX_ITERATIONS {
+        if (fb > 0.0) == (fc > 0.0) {
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+        let tol: f64 = 2.0 * f64::EPSILON * b.abs() + 0.5 * tolerance;
+        let m: f64 = 0.5 * (c - b);
+        if m.abs() <= tol || fb == 0.0 {
+            break;
+        }
+        if e.abs() >= tol && fa.abs() > fb.abs() {
+            // Secant or inverse quadratic interpolation.
+            let s: f64 = fb / fa;
+            let (mut p, mut q): (f64, f64) = if a == c {
+                (2.0 * m * s, 1.0 - s)
+            } else {
+                let (q, r): (f64, f64) = (fa / fc, fb / fc);
+                (s * (2.0 * m * q * (q - r) - (b - a) * (r - 1.0)), (q - 1.0) * (r - 1.0) * (s - 1.0))
+            };
+            if p > 0.0 {
+                q = -q;
+            } else {
+                p = -p;
+            }
+            if 2.0 * p < (3.0 * m * q - (tol * q).abs()).min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                d = m;
+                e = m;
+            }
+        } else {
+            d = m;
+            e = m;
+        }
+        a = b;
+        fa = fb;
+        b += if d.abs() > tol { d } else { tol.copysign(m) };
+        fb = f(b);
+        if fb == 0.0 {
+            fb = -f64::MIN_POSITIVE;
+        }
+    }
+    b
+}
+
+/// Finds the sign changes of a function over an interval.
+///
+/// # Arguments
+/// * `f` - The function.
+/// * `start` - Start of the interval.
+/// * `end` - End of the interval.
+/// * `step` - Sampling step, shorter than the time between two sign changes.
+/// * `tolerance` - Time tolerance of the refinement.
+///
+/// # Returns
+/// * The times of the sign changes of `f > 0` in chronological order, with whether it becomes
+///   positive.
+pub(crate) fn crossings(f: impl Fn(f64) -> f64, start: f64, end: f64, step: f64, tolerance: f64) -> Vec<(f64, bool)> {
+    if end <= start || step <= 0.0 {
+        return Vec::new();
+    }
+    let count: usize = ((end - start) / step).ceil() as usize;
+    let mut found: Vec<(f64, bool)> = Vec::new();
+    let mut previous: f64 = start;
+    let mut positive: bool = f(start) > 0.0;
+    for i in 1..=count {
+        let time: f64 = (start + i as f64 * step).min(end);
+        let now: bool = f(time) > 0.0;
+        if now != positive {
+            found.push((brent(&f, previous, time, tolerance), now));
+        }
+        previous = time;
+        positive = now;
+    }
+    found
+}
+
+/// Type of the registered event functions: time since epoch in minutes and state to value.
+type EventFunction<'a> = Box<dyn Fn(f64, &StateVector) -> f64 + 'a>;
+
+/// Holds event functions and the settings of their search.
+pub struct EventDetector<'a> {
+    /// Event functions and the sign changes reported for each.
+    functions: Vec<(EventFunction<'a>, EventDirection)>,
+    /// Sampling step in minutes, shorter than the time between two events of a function.
+    pub step: f64,
+    /// Time tolerance of the refinement in minutes.
+    pub tolerance: f64,
+}
+
+impl<'a> EventDetector<'a> {
+    /// Builds a detector without event functions.
+    ///
+    /// # Arguments
+    /// * `step` - Sampling step in minutes.
+    ///
+    /// # Returns
+    /// * The detector, refining the events to 1e-6 minute.
+    pub fn new(step: f64) -> EventDetector<'a> {
+        EventDetector {
+            functions: Vec::new(),
+            step,
+            tolerance: TIME_TOLERANCE,
+        }
+    }
+
+    /// Registers an event function.
+    ///
+    /// # Arguments
+    /// * `function` - Function of the time since epoch in minutes and of the state.
+    /// * `direction` - Sign changes reported.
+    ///
+    /// # Returns
+    /// * The index of the function, reported in its events.
+    pub fn register(&mut self, function: impl Fn(f64, &StateVector) -> f64 + 'a, direction: EventDirection) -> usize {
+        self.functions.push((Box::new(function), direction));
+        self.functions.len() - 1
+    }
+
+    /// Finds the events of all the registered functions over a window.
+    ///
+    /// # Arguments
+    /// * `propagator` - Propagator of the trajectory.
+    /// * `start` - Start of the window in minutes since epoch.
+    /// * `end` - End of the window in minutes since epoch.
+    ///
+    /// # Returns
+    /// * The events in chronological order, or the first propagation error on the sampling grid.
+    pub fn find<P: Propagator>(&self, propagator: &P, start: f64, end: f64) -> Result<Vec<Event>, PropagationError> {
+        if end <= start || self.step <= 0.0 {
+            return Ok(Vec::new());
+        }
+        self.check_grid(propagator, start, end)?;
+
+        let mut events: Vec<Event> = Vec::new();
+        for (index, (function, direction)) in self.functions.iter().enumerate() {
+            let g = |tsince: f64| propagator.propagate(tsince).map_or(f64::NAN, |state| function(tsince, &state));
+            for (tsince, rising) in crossings(g, start, end, self.step, self.tolerance) {
+                let wanted: bool = match direction {
+                    EventDirection::Rising => rising,
+                    EventDirection::Falling => !rising,
+                    EventDirection::Either => true,
+                };
+                if wanted {
+                    events.push(Event {
+                        index,
+                        tsince,
+                        jd: propagator.epoch_jd() + tsince / 1440.0,
+                        rising,
+                    });
+                }
+            }
+        }
+        events.sort_by(|a, b| a.tsince.total_cmp(&b.tsince));
+        Ok(events)
+    }
+
+    /// Finds the intervals during which each registered function is positive, whatever its
+    /// direction.
+    ///
+    /// # Arguments
+    /// * `propagator` - Propagator of the trajectory.
+    /// * `start` - Start of the window in minutes since epoch.
+    /// * `end` - End of the window in minutes since epoch.
+    ///
+    /// # Returns
+    /// * The intervals of each function in the order of registration, chronological and clipped to
+    ///   the window, or the first propagation error on the sampling grid.
+    pub fn intervals<P: Propagator>(&self, propagator: &P, start: f64, end: f64) -> Result<Vec<Vec<Interval>>, PropagationError> {
+        if end <= start || self.step <= 0.0 {
+            return Ok(self.functions.iter().map(|_| Vec::new()).collect());
+        }
+        self.check_grid(propagator, start, end)?;
+
+        let initial: StateVector = propagator.propagate(start)?;
+        let mut intervals: Vec<Vec<Interval>> = Vec::new();
+        for (function, _) in &self.functions {
+            let g = |tsince: f64| propagator.propagate(tsince).map_or(f64::NAN, |state| function(tsince, &state));
+            let mut found: Vec<Interval> = Vec::new();
+            let mut rise: Option<f64> = (function(start, &initial) > 0.0).then_some(start);
+            for (tsince, rising) in crossings(g, start, end, self.step, self.tolerance) {
+                if rising {
+                    rise = Some(tsince);
+                } else if let Some(begin) = rise.take() {
+                    found.push(Interval { start: begin, end: tsince });
+                }
+            }
+            if let Some(begin) = rise {
+                found.push(Interval { start: begin, end });
+            }
+            intervals.push(found);
+        }
+        Ok(intervals)
+    }
+
+    /// Propagates to every time of the sampling grid once, so that the refinement only meets times
+    /// between valid samples.
+    fn check_grid<P: Propagator>(&self, propagator: &P, start: f64, end: f64) -> Result<(), PropagationError> {
+        let count: usize = ((end - start) / self.step).ceil() as usize;
+        for i in 0..=count {
+            propagator.propagate((start + i as f64 * self.step).min(end))?;
+        }
+        Ok(())
+    }
+}
+
+/// Computes the sampling step of the ready-made detectors from the period at the start.
+pub(crate) fn revolution_step<P: Propagator>(propagator: &P, start: f64) -> Result<f64, PropagationError> {
+    let semi_major_axis: f64 = KeplerianElements::from_state(&propagator.propagate(start)?).semi_major_axis;
+    if semi_major_axis > 0.0 {
+        Ok(TWOPI * (semi_major_axis * semi_major_axis * semi_major_axis / EARTH_MU).sqrt() / 60.0 / SAMPLES_PER_REVOLUTION)
+    } else {
+        Ok(1.0)
+    }
+}
+
+/// Represents a crossing of the equator.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NodeCrossing {
+    /// Time of the crossing in minutes since the epoch of the propagator.
+    pub tsince: f64,
+    /// Time of the crossing as a Julian date (UTC).
+    pub jd: f64,
+    /// Geodetic longitude of the crossing in radians, in [-π, π].
+    pub longitude: f64,
+    /// Whether the satellite crosses northwards, at the ascending node.
+    pub ascending: bool,
+}
+
+/// Finds the crossings of the equator over a window.
+///
+/// # Arguments
+/// * `propagator` - Propagator of the satellite.
+/// * `start` - Start of the window in minutes since epoch.
+/// * `end` - End of the window in minutes since epoch.
+///
+/// # Returns
+/// * The ascending and descending nodes in chronological order, or the first propagation error.
+pub fn node_crossings<P: Propagator>(propagator: &P, start: f64, end: f64) -> Result<Vec<NodeCrossing>, PropagationError> {
+    let mut detector: EventDetector = EventDetector::new(revolution_step(propagator, start)?);
+    detector.register(|_, state| state.position[2], EventDirection::Either);
+    detector
+        .find(propagator, start, end)?
+        .into_iter()
+        .map(|event| {
+            let state: StateVector = propagator.propagate(event.tsince)?;
+            Ok(NodeCrossing {
+                tsince: event.tsince,
+                jd: event.jd,
+                longitude: state.to_geodetic(time::gmst(event.jd)).longitude,
+                ascending: event.rising,
+            })
+        })
+        .collect()
+}
+
+/// Represents a passage at an apsis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ApsisPassage {
+    /// Time of the passage in minutes since the epoch of the propagator.
+    pub tsince: f64,
+    /// Time of the passage as a Julian date (UTC).
+    pub jd: f64,
+    /// Distance from the center of the Earth in kilometers.
+    pub radius: f64,
+    /// Geodetic altitude in kilometers.
+    pub altitude: f64,
+    /// Whether the passage is at the apogee rather than the perigee.
+    pub apogee: bool,
+}
+
+/// Finds the apsis passages over a window, where the radial velocity vanishes.
+///
+/// # Arguments
+/// * `propagator` - Propagator of the satellite.
+/// * `start` - Start of the window in minutes since epoch.
+/// * `end` - End of the window in minutes since epoch.
+///
+/// # Returns
+/// * The perigee and apogee passages in chronological order, or the first propagation error. On
+///   near-circular orbits, the short-period perturbations may add passages.
+pub fn apsis_passages<P: Propagator>(propagator: &P, start: f64, end: f64) -> Result<Vec<ApsisPassage>, PropagationError> {
+    let mut detector: EventDetector = EventDetector::new(revolution_step(propagator, start)?);
+    detector.register(
+        |_, state| state.position[0] * state.velocity[0] + state.position[1] * state.velocity[1] + state.position[2] * state.velocity[2],
+        EventDirection::Either,
+    );
+    detector
+        .find(propagator, start, end)?
+        .into_iter()
+        .map(|event| {
+            let state: StateVector = propagator.propagate(event.tsince)?;
+            let position: [f64; 3] = state.position;
+            Ok(ApsisPassage {
+                tsince: event.tsince,
+                jd: event.jd,
+                radius: (position[0] * position[0] + position[1] * position[1] + position[2] * position[2]).sqrt(),
+                altitude: state.to_geodetic(time::gmst(event.jd)).altitude,
+                // The radius stops decreasing at the perigee.
+                apogee: !event.rising,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{OrbitalElements, Sgp4, Tle, convert_satellite_data};
+    use crate::test_support::iss_elements;
+
+    /// Tests Brent's method on functions with simple and flat roots.
+    #[test]
+    fn test_brent() {
+        let root: f64 = brent(|x| x * x - 2.0, 0.0, 2.0, 1e-12);
+        assert!((root - 2.0_f64.sqrt()).abs() < 1e-12);
+        let root: f64 = brent(|x| (x - 1.0).powi(3), 3.0, -2.0, 1e-9);
+        assert!((root - 1.0).abs() < 1e-8, "{}", root);
+        // A zero at the end of the bracket counts as negative.
+        assert!((brent(|x| x, -1.0, 0.0, 1e-12)).abs() < 1e-12);
+    }
+
+    /// Tests node crossings of the ISS, found as sign changes of the Z coordinate.
+    #[test]
+    fn test_node_events() {
+        let elements: OrbitalElements = iss_elements();
+        let sgp4: Sgp4 = Sgp4::new(&elements);
+        let period: f64 = elements.period_minutes();
+
+        let mut detector: EventDetector = EventDetector::new(5.0);
+        let ascending: usize = detector.register(|_, state| state.position[2], EventDirection::Rising);
+        let either: usize = detector.register(|_, state| state.position[2], EventDirection::Either);
+        let events: Vec<Event> = detector.find(&sgp4, 0.0, 3.0 * period).unwrap();
+
+        let nodes: Vec<&Event> = events.iter().filter(|event| event.index == ascending).collect();
+        assert_eq!(nodes.len(), 3);
+        for pair in nodes.windows(2) {
+            assert!(((pair[1].tsince - pair[0].tsince) / period - 1.0).abs() < 0.01);
+        }
+        for event in &nodes {
+            let state: StateVector = sgp4.propagate(event.tsince).unwrap();
+            assert!(state.position[2].abs() < 1e-3 && state.velocity[2] > 0.0);
+            assert!((event.jd - elements.jd_at(event.tsince)).abs() < 1e-9);
+        }
+        assert_eq!(events.iter().filter(|event| event.index == either).count(), 6);
+        assert!(events.windows(2).all(|pair| pair[0].tsince <= pair[1].tsince));
+    }
+
+    /// Tests the ready-made node detector: alternating nodes drifting westwards.
+    #[test]
+    fn test_node_crossings() {
+        let elements: OrbitalElements = iss_elements();
+        let sgp4: Sgp4 = Sgp4::new(&elements);
+        let nodes: Vec<NodeCrossing> = node_crossings(&sgp4, 0.0, 1440.0).unwrap();
+        assert!((30..=32).contains(&nodes.len()), "{}", nodes.len());
+        assert!(nodes.windows(2).all(|pair| pair[0].ascending != pair[1].ascending));
+
+        let ascending: Vec<&NodeCrossing> = nodes.iter().filter(|node| node.ascending).collect();
+        for pair in ascending.windows(2) {
+            // The Earth turns by about 22.9° during a revolution of 91.6 minutes.
+            let shift: f64 = (pair[0].longitude - pair[1].longitude).rem_euclid(TWOPI).to_degrees();
+            assert!((shift - 22.9).abs() < 0.5, "{}", shift);
+        }
+        let node: &NodeCrossing = ascending[0];
+        let subpoint = elements.subpoint(node.tsince).unwrap();
+        assert!(subpoint.latitude.abs() < 1e-6);
+        assert!((subpoint.longitude - node.longitude).abs() < 1e-6);
+    }
+
+    /// Tests the apsis passages of an eccentric orbit against its mean elements.
+    #[test]
+    fn test_apsis_passages() {
+        let elements: OrbitalElements = convert_satellite_data(
+            &Tle::from_lines(&[
+                "1 00005U 58002B   00179.78495062  .00000023  00000-0  28098-4 0  4753",
+                "2 00005  34.2682 348.7242 1859667 331.7664  19.3264 10.82419157413667",
+            ])
+            .unwrap(),
+        );
+        let sgp4: Sgp4 = Sgp4::new(&elements);
+        let passages: Vec<ApsisPassage> = apsis_passages(&sgp4, 0.0, 1440.0).unwrap();
+        // About 10.8 revolutions, with a perigee and an apogee each.
+        assert!((21..=22).contains(&passages.len()), "{}", passages.len());
+        assert!(passages.windows(2).all(|pair| pair[0].apogee != pair[1].apogee));
+
+        let a: f64 = elements.semi_major_axis_km();
+        let e: f64 = elements.eccentricity;
+        for passage in &passages {
+            let expected: f64 = if passage.apogee { a * (1.0 + e) } else { a * (1.0 - e) };
+            assert!((passage.radius - expected).abs() < 20.0, "{:?} {}", passage, expected);
+            assert!(passage.altitude > 600.0 && passage.altitude < passage.radius - 6300.0);
+            let state: StateVector = sgp4.propagate(passage.tsince).unwrap();
+            let radial: f64 = (0..3).map(|k| state.position[k] * state.velocity[k]).sum::<f64>() / passage.radius;
+            assert!(radial.abs() < 1e-6, "{}", radial);
+        }
+    }
+}
@@ -0,0 +1,60 @@
+//! Export of propagation results to standard file formats.
+//! Each submodule writes one format to any `std::io::Write`, so ephemerides can be streamed to
+//! files, sockets or in-memory buffers.
+
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod cdm;
+pub mod csv;
+#[cfg(feature = "czml")]
+pub mod czml;
+pub mod geojson;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod kml;
+pub mod oem;
+
+use crate::time::calendar_date;
+
+/// Formats a Julian date as an ISO 8601 date and time, to the millisecond.
+///
+/// # Arguments
+/// * `jd` - The Julian date, in days.
+///
+/// # Returns
+/// * The date as `YYYY-MM-DDThh:mm:ss.sss`, without a time zone designator.
+pub fn iso_date(jd: f64) -> String {
+    // Round the time of day to the millisecond before splitting it, so that 59.9996 s is written as
+    // the next minute rather than as 60.000 s.
+    let mut midnight: f64 = (jd - 0.5).floor() + 0.5;
+    let mut milliseconds: u64 = ((jd - midnight) * 86400000.0).round() as u64;
+    if milliseconds >= 86400000 {
+        midnight += 1.0;
+        milliseconds -= 86400000;
+    }
+    let (year, month, day, _, _, _) = calendar_date(midnight + 0.5);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}",
+        year,
+        month,
+        day,
+        milliseconds / 3600000,
+        milliseconds / 60000 % 60,
+        milliseconds / 1000 % 60,
+        milliseconds % 1000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::julian_date;
+
+    /// Tests the formatting of dates, including the rounding at the end of a day.
+    #[test]
+    fn test_iso_date() {
+        assert_eq!(iso_date(julian_date(2008, 9, 20, 12, 25, 40.104)), "2008-09-20T12:25:40.104");
+        assert_eq!(iso_date(julian_date(2000, 1, 1, 0, 0, 0.0)), "2000-01-01T00:00:00.000");
+        assert_eq!(iso_date(julian_date(2023, 12, 31, 23, 59, 59.9999)), "2024-01-01T00:00:00.000");
+    }
+}
@@ -0,0 +1,119 @@
+//! Apache Arrow and Parquet output of ephemerides.
+//! States are laid out in columns (catalog number, time, position, velocity), so the ephemerides of
+//! a whole catalog over days can be written as Parquet and queried with pandas, Polars or DuckDB.
+
+use crate::StateVector;
+use crate::time::JD_UNIX_EPOCH;
+use arrow_array::{ArrayRef, Float64Array, RecordBatch, TimestampMicrosecondArray, UInt32Array};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef, TimeUnit};
+use parquet::arrow::ArrowWriter;
+use parquet::errors::ParquetError;
+use std::io::Write;
+use std::sync::Arc;
+
+/// Names of the position and velocity columns, in km and km/s.
+const STATE_COLUMNS: [&str; 6] = ["x_km", "y_km", "z_km", "vx_km_s", "vy_km_s", "vz_km_s"];
+
+/// Returns the schema of the ephemeris record batches.
+///
+/// The columns are `norad_id` (UInt32), `time` (microsecond UTC timestamp), then the TEME position
+/// and velocity components (Float64, km and km/s).
+pub fn schema() -> SchemaRef {
+    let mut fields: Vec<Field> = vec![
+        Field::new("norad_id", DataType::UInt32, false),
+        Field::new("time", DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())), false),
+    ];
+    fields.extend(STATE_COLUMNS.map(|name| Field::new(name, DataType::Float64, false)));
+    Arc::new(Schema::new(fields))
+}
+
+/// Builds a record batch from the ephemeris of a satellite.
+///
+/// # Arguments
+/// * `norad_id` - Catalog number of the satellite, repeated on every row.
+/// * `states` - `(jd, state)` pairs with TEME states, such as an `Ephemeris`.
+///
+/// # Returns
+/// * The record batch with the `schema()` columns, one row per state.
+pub fn record_batch(norad_id: u32, states: impl IntoIterator<Item = (f64, StateVector)>) -> Result<RecordBatch, ArrowError> {
+    let mut times: Vec<i64> = Vec::new();
+    let mut components: [Vec<f64>; 6] = Default::default();
+    for (jd, state) in states {
+        times.push(((jd - JD_UNIX_EPOCH) * 86400e6).round() as i64);
+        for k in 0..3 {
+            components[k].push(state.position[k]);
+            components[3 + k].push(state.velocity[k]);
+        }
+    }
+
+    let mut columns: Vec<ArrayRef> = vec![
+        Arc::new(UInt32Array::from(vec![norad_id; times.len()])),
+        Arc::new(TimestampMicrosecondArray::from(times).with_timezone("UTC")),
+    ];
+    columns.extend(components.map(|values| Arc::new(Float64Array::from(values)) as ArrayRef));
+    RecordBatch::try_new(schema(), columns)
+}
+
+/// Writes record batches as a Parquet file.
+///
+/// # Arguments
+/// * `writer` - Destination of the file, e.g. a `std::fs::File`.
+/// * `batches` - Record batches with the `schema()` columns, e.g. one per satellite.
+///
+/// # Returns
+/// * An error if a batch does not match the schema or writing fails.
+pub fn write_parquet<W: Write + Send>(writer: W, batches: impl IntoIterator<Item = RecordBatch>) -> Result<(), ParquetError> {
+    let mut parquet: ArrowWriter<W> = ArrowWriter::try_new(writer, schema(), None)?;
+    for batch in batches {
+        parquet.write(&batch)?;
+    }
+    parquet.close()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Ephemeris, Propagator, Sgp4};
+    use arrow_array::Array;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use std::fs::File;
+    use crate::test_support::iss_sgp4;
+
+    /// Tests the columns of a record batch.
+    #[test]
+    fn test_record_batch() {
+        let sgp4: Sgp4 = iss_sgp4();
+        let start: f64 = sgp4.epoch_jd();
+        let batch: RecordBatch = record_batch(25544, Ephemeris::new(&sgp4, start, start + 0.01, 60.0)).unwrap();
+
+        assert_eq!(batch.num_rows(), 15);
+        assert_eq!(batch.num_columns(), 8);
+        let times = batch.column(1).as_any().downcast_ref::<TimestampMicrosecondArray>().unwrap();
+        // 2008-09-20T12:25:40.104192 UTC, within the resolution of Julian dates.
+        assert!((times.value(0) - 1221913540104192).abs() < 20);
+        assert!((times.value(1) - times.value(0) - 60000000).abs() < 20);
+        let x = batch.column_by_name("x_km").unwrap().as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(x.value(0), sgp4.propagate(0.0).unwrap().position[0]);
+        assert_eq!(batch.column(0).null_count(), 0);
+    }
+
+    /// Tests that a Parquet file of two satellites reads back with all rows.
+    #[test]
+    fn test_parquet_round_trip() {
+        let sgp4: Sgp4 = iss_sgp4();
+        let start: f64 = sgp4.epoch_jd();
+        let batches: Vec<RecordBatch> = [25544, 25545]
+            .map(|norad_id| record_batch(norad_id, Ephemeris::new(&sgp4, start, start + 0.1, 60.0)).unwrap())
+            .to_vec();
+        let path = std::env::temp_dir().join(format!("orbitalrs-{}.parquet", std::process::id()));
+        write_parquet(File::create(&path).unwrap(), batches.clone()).unwrap();
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap()).unwrap().build().unwrap();
+        let read: Vec<RecordBatch> = reader.map(|batch| batch.unwrap()).collect();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(read.iter().map(|batch| batch.num_rows()).sum::<usize>(), 2 * 145);
+        assert_eq!(read[0].schema(), schema());
+        assert_eq!(read[0].column(2).as_any().downcast_ref::<Float64Array>().unwrap().value(3), batches[0].column(2).as_any().downcast_ref::<Float64Array>().unwrap().value(3));
+    }
+}
@@ -0,0 +1,245 @@
+//! CCSDS Conjunction Data Message (CDM) output.
+//! This module writes conjunctions in the Keyword-Value Notation (KVN) of CCSDS 508.0-B, the
+//! format of the CDMs issued by 18 SDS. States are Earth-fixed, relative states and covariances
+//! are given in the radial, transverse and normal (RTN) frame of each object, in meters.
+
+use super::iso_date;
+use crate::collision::{CollisionProbability, PcMethod};
+use crate::conjunction::Conjunction;
+use crate::covariance::Covariance;
+use crate::frames::teme_to_ecef;
+use crate::relative::ric_axes;
+use crate::time::{JD_UNIX_EPOCH, gmst};
+use crate::StateVector;
+use std::io::{self, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Keywords of the lower triangle of the RTN covariance, row by row.
+const COVARIANCE_KEYWORDS: [&str; 21] = [
+    "CR_R", "CT_R", "CT_T", "CN_R", "CN_T", "CN_N", "CRDOT_R", "CRDOT_T", "CRDOT_N", "CRDOT_RDOT", "CTDOT_R", "CTDOT_T", "CTDOT_N", "CTDOT_RDOT",
+    "CTDOT_TDOT", "CNDOT_R", "CNDOT_T", "CNDOT_N", "CNDOT_RDOT", "CNDOT_TDOT", "CNDOT_NDOT",
+];
+
+/// Header of a CDM.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CdmHeader {
+    /// Organization that created the message.
+    pub originator: String,
+    /// Identifier of the message, unique for the originator.
+    pub message_id: String,
+    /// Julian date (UTC) at which the message is created.
+    pub creation_date: f64,
+}
+
+impl CdmHeader {
+    /// Builds the header of a message created now.
+    ///
+    /// # Arguments
+    /// * `message_id` - Identifier of the message.
+    ///
+    /// # Returns
+    /// * The header, with `orbitalrs` as the originator.
+    pub fn new(message_id: &str) -> CdmHeader {
+        let seconds: f64 = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs_f64()).unwrap_or(0.0);
+        CdmHeader {
+            originator: "orbitalrs".to_string(),
+            message_id: message_id.to_string(),
+            creation_date: JD_UNIX_EPOCH + seconds / 86400.0,
+        }
+    }
+}
+
+/// Metadata and covariance of one of the objects of a CDM.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CdmObject {
+    /// Catalog number of the object, e.g. `25544`.
+    pub object_designator: String,
+    /// Name of the object, e.g. `ISS (ZARYA)`.
+    pub object_name: String,
+    /// International designator of the object, e.g. `1998-067A`.
+    pub international_designator: String,
+    /// Covariance of the state at the time of closest approach in the TEME frame (km, km/s), if
+    /// known. The covariance section is left out otherwise.
+    pub covariance: Option<Covariance>,
+}
+
+/// Projects a vector onto the RTN axes.
+fn to_rtn(axes: &[[f64; 3]; 3], vector: [f64; 3]) -> [f64; 3] {
+    axes.map(|axis| (0..3).map(|k| axis[k] * vector[k]).sum())
+}
+
+/// Rotates a TEME covariance into the RTN frame and converts it to meters.
+fn covariance_rtn(axes: &[[f64; 3]; 3], covariance: &Covariance) -> Covariance {
+    let rotation: [[f64; 6]; 6] = core::array::from_fn(|i| core::array::from_fn(|j| if i / 3 == j / 3 { axes[i % 3][j % 3] } else { 0.0 }));
+    crate::covariance::transform_covariance(&rotation, covariance).map(|row| row.map(|x| x * 1e6))
+}
+
+/// Formats a number in the exponent notation of CDMs, e.g. `4.1420000E+01`.
+fn scientific(value: f64) -> String {
+    let text: String = format!("{:.7e}", value);
+    let (mantissa, exponent) = text.split_once('e').unwrap_or((&text, "0"));
+    let exponent: i32 = exponent.parse().unwrap_or(0);
+    format!("{}E{}{:02}", mantissa, if exponent < 0 { '-' } else { '+' }, exponent.abs())
+}
+
+/// Writes a conjunction as a CDM.
+///
+/// # Arguments
+/// * `writer` - Destination of the message.
+/// * `header` - Header of the message.
+/// * `conjunction` - Time and geometry of the closest approach, with the states in the TEME frame.
+/// * `objects` - Metadata and covariances of the first and second objects of the conjunction.
+/// * `probability` - Probability of collision, if computed.
+///
+/// # Returns
+/// * An error if writing fails.
+pub fn write_cdm<W: Write>(writer: &mut W, header: &CdmHeader, conjunction: &Conjunction, objects: &[CdmObject; 2], probability: Option<&CollisionProbability>) -> io::Result<()> {
+    let axes: [[f64; 3]; 3] = ric_axes(&conjunction.state_a);
+    let relative_position: [f64; 3] = to_rtn(&axes, conjunction.relative_position());
+    let relative_velocity: [f64; 3] = to_rtn(&axes, conjunction.relative_velocity());
+
+    writeln!(writer, "CCSDS_CDM_VERS = 1.0")?;
+    writeln!(writer, "CREATION_DATE = {}", iso_date(header.creation_date))?;
+    writeln!(writer, "ORIGINATOR = {}", header.originator)?;
+    writeln!(writer, "MESSAGE_ID = {}", header.message_id)?;
+    writeln!(writer, "TCA = {}", iso_date(conjunction.tca_jd))?;
+    writeln!(writer, "MISS_DISTANCE = {:.3} [m]", conjunction.miss_distance * 1e3)?;
+    writeln!(writer, "RELATIVE_SPEED = {:.3} [m/s]", conjunction.relative_speed() * 1e3)?;
+    for (axis, value) in ["R", "T", "N"].iter().zip(relative_position) {
+        writeln!(writer, "RELATIVE_POSITION_{} = {:.3} [m]", axis, value * 1e3)?;
+    }
+    for (axis, value) in ["R", "T", "N"].iter().zip(relative_velocity) {
+        writeln!(writer, "RELATIVE_VELOCITY_{} = {:.3} [m/s]", axis, value * 1e3)?;
+    }
+    if let Some(probability) = probability {
+        writeln!(writer, "COLLISION_PROBABILITY = {}", scientific(probability.probability))?;
+        let method: &str = match probability.method {
+            PcMethod::Foster => "FOSTER-1992",
+            PcMethod::Chan => "CHAN-1997",
+        };
+        writeln!(writer, "COLLISION_PROBABILITY_METHOD = {}", method)?;
+    }
+
+    let gmst: f64 = gmst(conjunction.tca_jd);
+    for (index, (object, state)) in objects.iter().zip([&conjunction.state_a, &conjunction.state_b]).enumerate() {
+        writeln!(writer)?;
+        writeln!(writer, "OBJECT = OBJECT{}", index + 1)?;
+        writeln!(writer, "OBJECT_DESIGNATOR = {}", object.object_designator)?;
+        writeln!(writer, "CATALOG_NAME = SATCAT")?;
+        writeln!(writer, "OBJECT_NAME = {}", object.object_name)?;
+        writeln!(writer, "INTERNATIONAL_DESIGNATOR = {}", object.international_designator)?;
+        writeln!(writer, "EPHEMERIS_NAME = NONE")?;
+        writeln!(writer, "COVARIANCE_METHOD = CALCULATED")?;
+        writeln!(writer, "MANEUVERABLE = N/A")?;
+        writeln!(writer, "REF_FRAME = ITRF")?;
+        writeln!(writer, "COMMENT Propagated with SGP4, TEME rotated to ITRF without polar motion")?;
+
+        let fixed: StateVector = teme_to_ecef(state, gmst);
+        for (keyword, value) in ["X", "Y", "Z"].iter().zip(fixed.position) {
+            writeln!(writer, "{} = {:.6} [km]", keyword, value)?;
+        }
+        for (keyword, value) in ["X_DOT", "Y_DOT", "Z_DOT"].iter().zip(fixed.velocity) {
+            writeln!(writer, "{} = {:.9} [km/s]", keyword, value)?;
+        }
+        if let Some(covariance) = &object.covariance {
+            let rtn: Covariance = covariance_rtn(&ric_axes(state), covariance);
+            let lower = (0..6).flat_map(|i| (0..=i).map(move |j| (i, j)));
+            for (keyword, (i, j)) in COVARIANCE_KEYWORDS.iter().zip(lower) {
+                let unit: &str = match (i >= 3) as u8 + (j >= 3) as u8 {
+                    0 => "m**2",
+                    1 => "m**2/s",
+                    _ => "m**2/s**2",
+                };
+                writeln!(writer, "{} = {} [{}]", keyword, scientific(rtn[i][j]), unit)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::screening::{CatalogConjunction, Screening};
+    use crate::OrbitalElements;
+    use crate::test_support::iss_elements;
+
+    /// Returns the first screened conjunction of the ISS with a copy on a tilted plane.
+    fn conjunction() -> Conjunction {
+        let elements: OrbitalElements = iss_elements();
+        let mut other: OrbitalElements = iss_elements();
+        other.inclination += 0.01;
+        let start_jd: f64 = elements.epoch_jd();
+        let conjunctions: Vec<CatalogConjunction> = Screening { threshold: 10.0, step: 0.2 }.screen(&[elements, other], start_jd, start_jd + 0.1);
+        conjunctions[0].conjunction
+    }
+
+    /// Returns the metadata of an object with a diagonal covariance of 100 m and 10 cm/s.
+    fn object(designator: &str) -> CdmObject {
+        CdmObject {
+            object_designator: designator.to_string(),
+            object_name: "ISS (ZARYA)".to_string(),
+            international_designator: "1998-067A".to_string(),
+            covariance: Some(core::array::from_fn(|i| core::array::from_fn(|j| if i != j { 0.0 } else if i < 3 { 0.01 } else { 1e-8 }))),
+        }
+    }
+
+    /// Tests the relative metadata of the message.
+    #[test]
+    fn test_cdm_relative_state() {
+        let conjunction: Conjunction = conjunction();
+        let mut header: CdmHeader = CdmHeader::new("20080920-25544-99999");
+        header.creation_date = conjunction.tca_jd;
+        let probability: CollisionProbability = conjunction.collision_probability(&object("25544").covariance.unwrap(), &object("99999").covariance.unwrap(), 0.02, PcMethod::Foster);
+        let mut output: Vec<u8> = Vec::new();
+        write_cdm(&mut output, &header, &conjunction, &[object("25544"), object("99999")], Some(&probability)).unwrap();
+        let text: String = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines[0], "CCSDS_CDM_VERS = 1.0");
+        assert_eq!(lines[3], "MESSAGE_ID = 20080920-25544-99999");
+        assert_eq!(lines[4], format!("TCA = {}", iso_date(conjunction.tca_jd)));
+        let value = |keyword: &str| -> f64 {
+            let line: &str = lines.iter().find(|line| line.starts_with(&format!("{} =", keyword))).unwrap();
+            line.split_whitespace().nth(2).unwrap().parse().unwrap()
+        };
+        assert!((value("MISS_DISTANCE") - conjunction.miss_distance * 1e3).abs() < 1e-3);
+        let rtn: [f64; 3] = ["R", "T", "N"].map(|axis| value(&format!("RELATIVE_POSITION_{}", axis)));
+        assert!((rtn.iter().map(|x| x * x).sum::<f64>().sqrt() - value("MISS_DISTANCE")).abs() < 1e-2);
+        // The objects cross at the node: the relative velocity is mostly normal to the orbit.
+        let velocity: [f64; 3] = ["R", "T", "N"].map(|axis| value(&format!("RELATIVE_VELOCITY_{}", axis)));
+        assert!(velocity[2].abs() > 10.0 * velocity[0].abs(), "{:?}", velocity);
+        assert!((value("COLLISION_PROBABILITY") / probability.probability - 1.0).abs() < 1e-6);
+        assert!(lines.contains(&"COLLISION_PROBABILITY_METHOD = FOSTER-1992"));
+    }
+
+    /// Tests the object sections, with and without a covariance.
+    #[test]
+    fn test_cdm_objects() {
+        let conjunction: Conjunction = conjunction();
+        let mut second: CdmObject = object("99999");
+        second.covariance = None;
+        let mut output: Vec<u8> = Vec::new();
+        write_cdm(&mut output, &CdmHeader::new("1"), &conjunction, &[object("25544"), second], None).unwrap();
+        let text: String = String::from_utf8(output).unwrap();
+
+        let (first, second) = text.split_once("OBJECT = OBJECT2").unwrap();
+        assert!(first.contains("OBJECT = OBJECT1\nOBJECT_DESIGNATOR = 25544\nCATALOG_NAME = SATCAT\n"));
+        assert!(!text.contains("COLLISION_PROBABILITY"));
+        // The diagonal covariance is the same in any frame: 1e4 m² and 1e-2 m²/s².
+        assert!(first.contains("CR_R = 1.0000000E+04 [m**2]"));
+        assert!(first.contains("CNDOT_NDOT = 1.0000000E-02 [m**2/s**2]"));
+        assert_eq!(first.lines().filter(|line| line.starts_with('C') && line.contains("[m**2")).count(), 21);
+        assert!(second.contains("OBJECT_DESIGNATOR = 99999") && !second.contains("CR_R"));
+
+        let radius = |section: &str| -> f64 {
+            let components: Vec<f64> = ["X", "Y", "Z"]
+                .iter()
+                .map(|keyword| section.lines().find(|line| line.starts_with(&format!("{} =", keyword))).unwrap().split_whitespace().nth(2).unwrap().parse().unwrap())
+                .collect();
+            components.iter().map(|x| x * x).sum::<f64>().sqrt()
+        };
+        let expected: f64 = conjunction.state_a.position.iter().map(|x| x * x).sum::<f64>().sqrt();
+        assert!((radius(first) - expected).abs() < 1e-5);
+    }
+}
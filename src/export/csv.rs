@@ -0,0 +1,150 @@
+//! Comma-separated values (CSV) output.
+//! This module writes one row per state with the date, position and velocity, in a frame and
+//! units chosen by the caller, ready to be loaded by pandas or a spreadsheet.
+
+use super::iso_date;
+use crate::StateVector;
+use crate::frames::Frame;
+use std::io::{self, Write};
+
+/// Represents the length unit of the position and velocity columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LengthUnit {
+    /// Kilometers and kilometers per second.
+    #[default]
+    Kilometers,
+    /// Meters and meters per second.
+    Meters,
+}
+
+impl LengthUnit {
+    /// Returns the symbol of the unit, used in the column names.
+    fn symbol(&self) -> &'static str {
+        match self {
+            LengthUnit::Kilometers => "km",
+            LengthUnit::Meters => "m",
+        }
+    }
+
+    /// Returns the number of units in a kilometer.
+    fn per_kilometer(&self) -> f64 {
+        match self {
+            LengthUnit::Kilometers => 1.0,
+            LengthUnit::Meters => 1000.0,
+        }
+    }
+}
+
+/// Layout of the CSV output.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CsvOptions {
+    /// Frame of the written states, converted from TEME.
+    pub frame: Frame,
+    /// Unit of the positions and velocities.
+    pub unit: LengthUnit,
+    /// Field separator, `,` by default.
+    pub delimiter: char,
+}
+
+impl Default for CsvOptions {
+    fn default() -> CsvOptions {
+        CsvOptions {
+            frame: Frame::Teme,
+            unit: LengthUnit::Kilometers,
+            delimiter: ',',
+        }
+    }
+}
+
+/// Writes an ephemeris as CSV, with a header row.
+///
+/// The columns are `time` (ISO 8601, UTC), then `x`, `y`, `z`, `vx`, `vy` and `vz` suffixed with
+/// their unit, e.g. `x_km` and `vx_km_s`.
+///
+/// # Arguments
+/// * `writer` - Destination of the table.
+/// * `options` - Frame, unit and separator of the table.
+/// * `states` - `(jd, state)` pairs with TEME states, such as an `Ephemeris`.
+///
+/// # Returns
+/// * An error if writing fails.
+pub fn write_csv<W: Write>(
+    writer: &mut W,
+    options: &CsvOptions,
+    states: impl IntoIterator<Item = (f64, StateVector)>,
+) -> io::Result<()> {
+    let separator: String = options.delimiter.to_string();
+    let unit: &str = options.unit.symbol();
+    let mut header: Vec<String> = vec!["time".to_string()];
+    header.extend(["x", "y", "z"].map(|axis| format!("{}_{}", axis, unit)));
+    header.extend(["vx", "vy", "vz"].map(|axis| format!("{}_{}_s", axis, unit)));
+    writeln!(writer, "{}", header.join(&separator))?;
+
+    // Keep micrometer and nanometer-per-second resolution whatever the unit.
+    let scale: f64 = options.unit.per_kilometer();
+    let decimals: usize = if options.unit == LengthUnit::Kilometers { 6 } else { 3 };
+    for (jd, state) in states {
+        let state: StateVector = options.frame.from_teme(&state, jd);
+        let mut row: Vec<String> = vec![iso_date(jd)];
+        row.extend(state.position.map(|x| format!("{:.*}", decimals, x * scale)));
+        row.extend(state.velocity.map(|v| format!("{:.*}", decimals + 3, v * scale)));
+        writeln!(writer, "{}", row.join(&separator))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frames::teme_to_ecef;
+    use crate::time::gmst;
+    use crate::{Ephemeris, Propagator, Sgp4};
+    use crate::test_support::iss_sgp4;
+
+    /// Tests the default table: header, TEME states in kilometers and one row per state.
+    #[test]
+    fn test_csv_default() {
+        let sgp4: Sgp4 = iss_sgp4();
+        let start: f64 = sgp4.epoch_jd();
+        let mut output: Vec<u8> = Vec::new();
+        write_csv(&mut output, &CsvOptions::default(), Ephemeris::new(&sgp4, start, start + 0.01, 60.0)).unwrap();
+        let text: String = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines[0], "time,x_km,y_km,z_km,vx_km_s,vy_km_s,vz_km_s");
+        assert_eq!(lines.len(), 1 + 15);
+        let fields: Vec<&str> = lines[1].split(',').collect();
+        assert_eq!(fields[0], "2008-09-20T12:25:40.104");
+        let state: StateVector = sgp4.propagate(0.0).unwrap();
+        for k in 0..3 {
+            assert!((fields[1 + k].parse::<f64>().unwrap() - state.position[k]).abs() < 1e-6);
+            assert!((fields[4 + k].parse::<f64>().unwrap() - state.velocity[k]).abs() < 1e-9);
+        }
+    }
+
+    /// Tests the conversion to the Earth-fixed frame in meters, with another separator.
+    #[test]
+    fn test_csv_ecef_meters() {
+        let sgp4: Sgp4 = iss_sgp4();
+        let start: f64 = sgp4.epoch_jd();
+        let options: CsvOptions = CsvOptions {
+            frame: Frame::Ecef,
+            unit: LengthUnit::Meters,
+            delimiter: ';',
+        };
+        let mut output: Vec<u8> = Vec::new();
+        write_csv(&mut output, &options, Ephemeris::new(&sgp4, start, start, 60.0)).unwrap();
+        let text: String = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines[0], "time;x_m;y_m;z_m;vx_m_s;vy_m_s;vz_m_s");
+        let fields: Vec<f64> = lines[1].split(';').skip(1).map(|field| field.parse::<f64>().unwrap()).collect();
+        let state: StateVector = teme_to_ecef(&sgp4.propagate(0.0).unwrap(), gmst(start));
+        for k in 0..3 {
+            assert!((fields[k] - state.position[k] * 1000.0).abs() < 1e-3);
+            assert!((fields[3 + k] - state.velocity[k] * 1000.0).abs() < 1e-6);
+        }
+    }
+}
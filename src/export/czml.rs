@@ -0,0 +1,180 @@
+//! CZML output for CesiumJS.
+//! This module turns an ephemeris into a CZML document with a clock spanning the ephemeris and a
+//! satellite packet whose sampled positions Cesium interpolates between the states.
+
+use super::iso_date;
+use crate::StateVector;
+use crate::frames::Frame;
+use serde_json::{Value, json};
+use std::io::{self, Write};
+
+/// Appearance and sampling of the satellite in the CZML document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CzmlOptions {
+    /// Identifier of the satellite packet.
+    pub id: String,
+    /// Name shown in the Cesium label and info box.
+    pub name: String,
+    /// Writes J2000 (`INERTIAL`) positions instead of Earth-fixed (`FIXED`) ones. Inertial positions
+    /// give smoother interpolation but need Cesium to load the ICRF transformation data.
+    pub inertial: bool,
+    /// Degree of the Lagrange polynomials interpolating the positions.
+    pub interpolation_degree: u32,
+    /// Color of the point and path, as RGBA.
+    pub color: [u8; 4],
+}
+
+impl CzmlOptions {
+    /// Builds the options of a satellite with Earth-fixed positions, drawn in yellow.
+    ///
+    /// # Arguments
+    /// * `id` - Identifier of the satellite packet.
+    /// * `name` - Name of the satellite.
+    ///
+    /// # Returns
+    /// * The options, interpolating with degree 5 polynomials.
+    pub fn new(id: &str, name: &str) -> CzmlOptions {
+        CzmlOptions {
+            id: id.to_string(),
+            name: name.to_string(),
+            inertial: false,
+            interpolation_degree: 5,
+            color: [255, 255, 0, 255],
+        }
+    }
+}
+
+/// Builds the CZML document of an ephemeris.
+///
+/// The document holds the `document` packet with a clock over the ephemeris and, unless the
+/// ephemeris is empty, one satellite packet with a point, a label, a path and the sampled
+/// positions in meters.
+///
+/// # Arguments
+/// * `options` - Identity, frame and appearance of the satellite.
+/// * `states` - `(jd, state)` pairs with TEME states, in chronological order.
+///
+/// # Returns
+/// * The CZML document, a JSON array of packets.
+pub fn czml_document(options: &CzmlOptions, states: impl IntoIterator<Item = (f64, StateVector)>) -> Value {
+    let frame: Frame = if options.inertial { Frame::J2000 } else { Frame::Ecef };
+    let mut epoch: Option<f64> = None;
+    let mut last: f64 = 0.0;
+    let mut samples: Vec<f64> = Vec::new();
+    for (jd, state) in states {
+        let start: f64 = *epoch.get_or_insert(jd);
+        let state: StateVector = frame.from_teme(&state, jd);
+        samples.push((jd - start) * 86400.0);
+        samples.extend(state.position.map(|x| x * 1000.0));
+        last = jd;
+    }
+
+    let mut document: Vec<Value> = vec![json!({
+        "id": "document",
+        "name": options.name,
+        "version": "1.0",
+    })];
+    let Some(epoch) = epoch else {
+        return Value::Array(document);
+    };
+
+    let start: String = format!("{}Z", iso_date(epoch));
+    let interval: String = format!("{}/{}Z", start, iso_date(last));
+    document[0]["clock"] = json!({
+        "interval": interval,
+        "currentTime": start,
+        "multiplier": 60,
+        "range": "LOOP_STOP",
+        "step": "SYSTEM_CLOCK_MULTIPLIER",
+    });
+    let color: Value = json!({ "rgba": options.color });
+    document.push(json!({
+        "id": options.id,
+        "name": options.name,
+        "availability": interval,
+        "label": {
+            "text": options.name,
+            "font": "11pt sans-serif",
+            "fillColor": color,
+            "horizontalOrigin": "LEFT",
+            "pixelOffset": { "cartesian2": [12, 0] },
+        },
+        "point": {
+            "color": color,
+            "pixelSize": 8,
+        },
+        "path": {
+            "material": { "solidColor": { "color": color } },
+            "width": 1,
+            "resolution": 120,
+        },
+        "position": {
+            "interpolationAlgorithm": "LAGRANGE",
+            "interpolationDegree": options.interpolation_degree,
+            "referenceFrame": if options.inertial { "INERTIAL" } else { "FIXED" },
+            "epoch": start,
+            "cartesian": samples,
+        },
+    }));
+    Value::Array(document)
+}
+
+/// Writes the CZML document of an ephemeris.
+///
+/// # Arguments
+/// * `writer` - Destination of the document.
+/// * `options` - Identity, frame and appearance of the satellite.
+/// * `states` - `(jd, state)` pairs with TEME states, in chronological order.
+///
+/// # Returns
+/// * An error if writing fails.
+pub fn write_czml<W: Write>(
+    writer: &mut W,
+    options: &CzmlOptions,
+    states: impl IntoIterator<Item = (f64, StateVector)>,
+) -> io::Result<()> {
+    serde_json::to_writer(&mut *writer, &czml_document(options, states))?;
+    writeln!(writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::gmst;
+    use crate::{Ephemeris, Propagator, Sgp4};
+    use crate::test_support::iss_sgp4;
+
+    /// Tests the clock and the sampled Earth-fixed positions of the document.
+    #[test]
+    fn test_czml_document() {
+        let sgp4: Sgp4 = iss_sgp4();
+        let start: f64 = sgp4.epoch_jd();
+        let options: CzmlOptions = CzmlOptions::new("25544", "ISS (ZARYA)");
+        let document: Value = czml_document(&options, Ephemeris::new(&sgp4, start, start + 0.01, 60.0));
+
+        assert_eq!(document[0]["id"], "document");
+        assert_eq!(document[0]["clock"]["interval"], "2008-09-20T12:25:40.104Z/2008-09-20T12:39:40.104Z");
+        let position: &Value = &document[1]["position"];
+        assert_eq!(position["referenceFrame"], "FIXED");
+        assert_eq!(position["epoch"], "2008-09-20T12:25:40.104Z");
+
+        let samples: &Vec<Value> = position["cartesian"].as_array().unwrap();
+        assert_eq!(samples.len(), 4 * 15);
+        assert!((samples[4].as_f64().unwrap() - 60.0).abs() < 1e-3);
+        let state: StateVector = sgp4.propagate(0.0).unwrap().to_ecef(gmst(start));
+        for k in 0..3 {
+            assert!((samples[1 + k].as_f64().unwrap() - state.position[k] * 1000.0).abs() < 1e-6);
+        }
+    }
+
+    /// Tests that an empty ephemeris only produces the document packet.
+    #[test]
+    fn test_czml_empty() {
+        let options: CzmlOptions = CzmlOptions::new("25544", "ISS (ZARYA)");
+        let mut output: Vec<u8> = Vec::new();
+        write_czml(&mut output, &options, Vec::new()).unwrap();
+        let document: Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(document.as_array().unwrap().len(), 1);
+        assert!(document[0].get("clock").is_none());
+    }
+}
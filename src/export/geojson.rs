@@ -0,0 +1,317 @@
+//! GeoJSON output of ground tracks, sub-satellite points and coverage footprints.
+//! Geometries are split at the antimeridian as RFC 7946 recommends, so web maps (Leaflet, Mapbox)
+//! do not draw lines and polygons across the whole map when the satellite crosses ±180°.
+
+use super::iso_date;
+use crate::coverage::footprint;
+use crate::geodetic::Geodetic;
+use std::f64::consts::PI;
+use std::io::{self, Write};
+
+/// Content of the GeoJSON feature collection.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoJsonOptions {
+    /// Name of the satellite, written in the properties of every feature.
+    pub name: String,
+    /// Adds a Point feature for each sub-satellite point.
+    pub subpoints: bool,
+    /// Minimum elevation (radians) of the footprints drawn at each point, or `None` for no footprints.
+    pub footprint_elevation: Option<f64>,
+    /// Number of boundary points of each footprint.
+    pub footprint_points: usize,
+}
+
+impl GeoJsonOptions {
+    /// Builds the options of a ground track alone, without sub-satellite points or footprints.
+    ///
+    /// # Arguments
+    /// * `name` - Name of the satellite.
+    ///
+    /// # Returns
+    /// * The options, with 36-point footprints if they are enabled later.
+    pub fn new(name: &str) -> GeoJsonOptions {
+        GeoJsonOptions {
+            name: name.to_string(),
+            subpoints: false,
+            footprint_elevation: None,
+            footprint_points: 36,
+        }
+    }
+}
+
+/// Splits a path at the antimeridian.
+///
+/// A segment whose longitude jumps by more than 180° is taken to cross the antimeridian: the path
+/// is ended on one side and resumed on the other, at the interpolated crossing latitude.
+///
+/// # Arguments
+/// * `points` - The points of the path.
+///
+/// # Returns
+/// * The pieces of the path, as `[longitude, latitude]` pairs in degrees.
+pub fn split_at_antimeridian(points: &[Geodetic]) -> Vec<Vec<[f64; 2]>> {
+    let mut pieces: Vec<Vec<[f64; 2]>> = Vec::new();
+    let mut piece: Vec<[f64; 2]> = Vec::new();
+    for (index, point) in points.iter().enumerate() {
+        if index > 0 {
+            let previous: &Geodetic = &points[index - 1];
+            let jump: f64 = point.longitude - previous.longitude;
+            if jump.abs() > PI {
+                // Longitude of the point continued across the antimeridian, and side of the crossing.
+                let side: f64 = previous.longitude.signum();
+                let unwrapped: f64 = point.longitude + side * 2.0 * PI;
+                let fraction: f64 = (side * PI - previous.longitude) / (unwrapped - previous.longitude);
+                let latitude: f64 = (previous.latitude + fraction * (point.latitude - previous.latitude)).to_degrees();
+                piece.push([side * 180.0, latitude]);
+                pieces.push(std::mem::take(&mut piece));
+                piece.push([-side * 180.0, latitude]);
+            }
+        }
+        piece.push([point.longitude.to_degrees(), point.latitude.to_degrees()]);
+    }
+    if !piece.is_empty() {
+        pieces.push(piece);
+    }
+    pieces
+}
+
+/// Clips a ring of unwrapped `[longitude, latitude]` points to the half-plane on one side of a meridian.
+fn clip(ring: &[[f64; 2]], meridian: f64, keep_west: bool) -> Vec<[f64; 2]> {
+    let inside = |p: &[f64; 2]| -> bool { if keep_west { p[0] <= meridian } else { p[0] >= meridian } };
+    let mut clipped: Vec<[f64; 2]> = Vec::new();
+    for (index, current) in ring.iter().enumerate() {
+        let previous: &[f64; 2] = &ring[(index + ring.len() - 1) % ring.len()];
+        if inside(current) != inside(previous) {
+            let fraction: f64 = (meridian - previous[0]) / (current[0] - previous[0]);
+            clipped.push([meridian, previous[1] + fraction * (current[1] - previous[1])]);
+        }
+        if inside(current) {
+            clipped.push(*current);
+        }
+    }
+    clipped
+}
+
+/// Splits a closed boundary (not repeating its first point) into polygons that do not cross the
+/// antimeridian, closing the rings.
+///
+/// # Arguments
+/// * `boundary` - The boundary points, in order.
+/// * `pole` - Latitude (degrees) of the pole enclosed by the boundary, if it winds around one.
+///
+/// # Returns
+/// * The rings of the polygons, as `[longitude, latitude]` pairs in degrees.
+fn split_polygon(boundary: &[Geodetic], pole: f64) -> Vec<Vec<[f64; 2]>> {
+    // Unwrap the longitudes so that consecutive points are less than 180° apart.
+    let mut ring: Vec<[f64; 2]> = Vec::new();
+    for point in boundary {
+        let mut longitude: f64 = point.longitude.to_degrees();
+        if let Some(previous) = ring.last() {
+            longitude += 360.0 * ((previous[0] - longitude) / 360.0).round();
+        }
+        ring.push([longitude, point.latitude.to_degrees()]);
+    }
+    let (Some(first), Some(last)) = (ring.first().copied(), ring.last().copied()) else {
+        return Vec::new();
+    };
+    let mut closing: f64 = first[0];
+    closing += 360.0 * ((last[0] - closing) / 360.0).round();
+
+    let mut polygons: Vec<Vec<[f64; 2]>> = Vec::new();
+    if (closing - first[0]).abs() > 180.0 {
+        // The boundary winds around a pole: cut it at the antimeridian and close it through the pole.
+        let mut points: Vec<[f64; 2]> = ring.iter().map(|p| [(p[0] + 180.0).rem_euclid(360.0) - 180.0, p[1]]).collect();
+        let start: usize = (0..points.len())
+            .find(|&i| (points[i][0] - points[(i + points.len() - 1) % points.len()][0]).abs() > 180.0)
+            .unwrap_or(0);
+        points.rotate_left(start);
+        // With the ring starting just after the antimeridian, the closed path splits into the whole
+        // boundary up to the crossing, and the crossing on the other side back to the first point.
+        let pieces: Vec<Vec<[f64; 2]>> = split_at_antimeridian(
+            &points
+                .iter()
+                .chain(points.first())
+                .map(|p| Geodetic { latitude: p[1].to_radians(), longitude: p[0].to_radians(), altitude: 0.0 })
+                .collect::<Vec<Geodetic>>(),
+        );
+        let (Some(boundary), Some(entry)) = (pieces.first(), pieces.last().and_then(|piece| piece.first())) else {
+            return polygons;
+        };
+        let Some(exit) = boundary.last() else {
+            return polygons;
+        };
+        let mut closed: Vec<[f64; 2]> = vec![*entry];
+        closed.extend(boundary.iter());
+        closed.push([exit[0], pole]);
+        closed.push([entry[0], pole]);
+        closed.push(*entry);
+        polygons.push(closed);
+        return polygons;
+    }
+
+    // Bring the ring in the [-180°, 180°] window as much as possible, then cut what sticks out.
+    let center: f64 = ring.iter().map(|p| p[0]).sum::<f64>() / ring.len() as f64;
+    let shift: f64 = -360.0 * (center / 360.0).round();
+    let ring: Vec<[f64; 2]> = ring.iter().map(|p| [p[0] + shift, p[1]]).collect();
+    let west: f64 = ring.iter().map(|p| p[0]).fold(f64::INFINITY, f64::min);
+    let east: f64 = ring.iter().map(|p| p[0]).fold(f64::NEG_INFINITY, f64::max);
+    let mut parts: Vec<Vec<[f64; 2]>> = Vec::new();
+    if east > 180.0 {
+        parts.push(clip(&ring, 180.0, true));
+        parts.push(clip(&ring, 180.0, false).iter().map(|p| [p[0] - 360.0, p[1]]).collect());
+    } else if west < -180.0 {
+        parts.push(clip(&ring, -180.0, false));
+        parts.push(clip(&ring, -180.0, true).iter().map(|p| [p[0] + 360.0, p[1]]).collect());
+    } else {
+        parts.push(ring);
+    }
+    for mut part in parts.into_iter().filter(|part| part.len() >= 3) {
+        part.push(part[0]);
+        polygons.push(part);
+    }
+    polygons
+}
+
+/// Escapes a text as the contents of a JSON string.
+fn escape(text: &str) -> String {
+    let mut escaped: String = String::new();
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Formats a `[longitude, latitude]` pair as a GeoJSON position.
+fn position(point: &[f64; 2]) -> String {
+    format!("[{:.6},{:.6}]", point[0], point[1])
+}
+
+/// Formats a list of positions as a GeoJSON array.
+fn positions(points: &[[f64; 2]]) -> String {
+    format!("[{}]", points.iter().map(position).collect::<Vec<String>>().join(","))
+}
+
+/// Writes a ground track as a GeoJSON feature collection.
+///
+/// The collection holds the track as a MultiLineString split at the antimeridian, then optionally
+/// one Point per sub-satellite point (with its time and altitude) and one MultiPolygon per footprint.
+///
+/// # Arguments
+/// * `writer` - Destination of the document.
+/// * `options` - Name, sub-satellite points and footprints of the collection.
+/// * `track` - Julian dates (UTC) and sub-satellite points with the satellite altitude, in
+///   chronological order (see `ground_track::ground_track` and `OrbitalElements::jd_at`).
+///
+/// # Returns
+/// * An error if writing fails.
+pub fn write_geojson<W: Write>(writer: &mut W, options: &GeoJsonOptions, track: &[(f64, Geodetic)]) -> io::Result<()> {
+    let name: String = escape(&options.name);
+    let mut features: Vec<String> = Vec::new();
+
+    if let (Some((start, _)), Some((stop, _))) = (track.first(), track.last()) {
+        let points: Vec<Geodetic> = track.iter().map(|(_, point)| *point).collect();
+        let lines: Vec<String> = split_at_antimeridian(&points).iter().map(|line| positions(line)).collect();
+        features.push(format!(
+            r#"{{"type":"Feature","properties":{{"name":"{}","kind":"track","start":"{}Z","stop":"{}Z"}},"geometry":{{"type":"MultiLineString","coordinates":[{}]}}}}"#,
+            name,
+            iso_date(*start),
+            iso_date(*stop),
+            lines.join(",")
+        ));
+    }
+
+    if options.subpoints {
+        for (jd, point) in track {
+            features.push(format!(
+                r#"{{"type":"Feature","properties":{{"name":"{}","kind":"subpoint","time":"{}Z","altitude_km":{:.3}}},"geometry":{{"type":"Point","coordinates":{}}}}}"#,
+                name,
+                iso_date(*jd),
+                point.altitude,
+                position(&[point.longitude.to_degrees(), point.latitude.to_degrees()])
+            ));
+        }
+    }
+
+    if let Some(min_elevation) = options.footprint_elevation {
+        for (jd, point) in track {
+            let boundary: Vec<Geodetic> = footprint(point, min_elevation, options.footprint_points);
+            let pole: f64 = if point.latitude >= 0.0 { 90.0 } else { -90.0 };
+            let polygons: Vec<String> =
+                split_polygon(&boundary, pole).iter().map(|ring| format!("[{}]", positions(ring))).collect();
+            features.push(format!(
+                r#"{{"type":"Feature","properties":{{"name":"{}","kind":"footprint","time":"{}Z"}},"geometry":{{"type":"MultiPolygon","coordinates":[{}]}}}}"#,
+                name,
+                iso_date(*jd),
+                polygons.join(",")
+            ));
+        }
+    }
+
+    writeln!(writer, r#"{{"type":"FeatureCollection","features":[{}]}}"#, features.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(latitude: f64, longitude: f64, altitude: f64) -> Geodetic {
+        Geodetic {
+            latitude: latitude.to_radians(),
+            longitude: longitude.to_radians(),
+            altitude,
+        }
+    }
+
+    /// Tests that tracks and footprints crossing the antimeridian are split on both sides of it.
+    #[test]
+    fn test_split_at_antimeridian() {
+        let pieces: Vec<Vec<[f64; 2]>> = split_at_antimeridian(&[point(0.0, 170.0, 400.0), point(10.0, -170.0, 400.0), point(12.0, -160.0, 400.0)]);
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(pieces[0].len(), 2);
+        assert_eq!(pieces[0][1], [180.0, 5.0]);
+        assert_eq!(pieces[1][0], [-180.0, 5.0]);
+        assert_eq!(pieces[1].len(), 3);
+
+        let boundary: Vec<Geodetic> = footprint(&point(0.0, 178.0, 800.0), 0.0, 36);
+        let polygons: Vec<Vec<[f64; 2]>> = split_polygon(&boundary, 90.0);
+        assert_eq!(polygons.len(), 2);
+        for ring in &polygons {
+            assert_eq!(ring.first(), ring.last());
+            assert!(ring.iter().all(|p| p[0].abs() <= 180.0 + 1e-9));
+        }
+        assert!(polygons[0].iter().all(|p| p[0] > 0.0) && polygons[1].iter().all(|p| p[0] < 0.0));
+
+        // A footprint over the North Pole is closed along the pole and stays in range.
+        let polar: Vec<Vec<[f64; 2]>> = split_polygon(&footprint(&point(85.0, 30.0, 800.0), 0.0, 36), 90.0);
+        assert_eq!(polar.len(), 1);
+        assert!(polar[0].iter().all(|p| p[0].abs() <= 180.0 + 1e-9));
+        assert_eq!(polar[0].iter().filter(|p| p[1] == 90.0).count(), 2);
+        assert_eq!(polar[0].first(), polar[0].last());
+        assert!(polar[0].windows(2).all(|pair| (pair[1][0] - pair[0][0]).abs() < 180.0 || pair[0][1] == 90.0));
+    }
+
+    /// Tests the features of a collection with sub-satellite points and footprints.
+    #[test]
+    fn test_geojson_collection() {
+        let track: Vec<(f64, Geodetic)> = vec![(2454730.0, point(10.0, 175.0, 400.0)), (2454730.001, point(12.0, -178.0, 401.0))];
+        let mut options: GeoJsonOptions = GeoJsonOptions::new("ISS \"ZARYA\"");
+        options.subpoints = true;
+        options.footprint_elevation = Some(0.0);
+        let mut output: Vec<u8> = Vec::new();
+        write_geojson(&mut output, &options, &track).unwrap();
+        let document: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+        let features: &Vec<serde_json::Value> = document["features"].as_array().unwrap();
+        assert_eq!(features.len(), 1 + 2 + 2);
+        assert_eq!(features[0]["properties"]["name"], "ISS \"ZARYA\"");
+        assert_eq!(features[0]["geometry"]["coordinates"].as_array().unwrap().len(), 2);
+        assert_eq!(features[1]["properties"]["time"], "2008-09-20T12:00:00.000Z");
+        assert_eq!(features[2]["geometry"]["coordinates"][0], -178.0);
+        assert_eq!(features[3]["geometry"]["coordinates"].as_array().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,165 @@
+//! JSON output of ephemerides and comparison reports.
+//! This module writes propagation results as plain JSON documents, with units in the field names,
+//! so dashboards and scripts can consume them without parsing the console tables.
+
+use super::iso_date;
+use crate::StateVector;
+use crate::vector::norm;
+use serde_json::{Value, json};
+use std::io::{self, Write};
+
+/// Represents a computed state next to its reference, as printed by the comparison tables.
+#[derive(Debug, Clone, Copy)]
+pub struct Comparison {
+    /// Time since epoch in minutes.
+    pub tsince: f64,
+    /// Reference state (km, km/s).
+    pub reference: StateVector,
+    /// Computed state (km, km/s).
+    pub computed: StateVector,
+}
+
+impl Comparison {
+    /// Returns the computed position minus the reference position in kilometers.
+    pub fn position_delta(&self) -> [f64; 3] {
+        [0, 1, 2].map(|k| self.computed.position[k] - self.reference.position[k])
+    }
+
+    /// Returns the computed velocity minus the reference velocity in kilometers per second.
+    pub fn velocity_delta(&self) -> [f64; 3] {
+        [0, 1, 2].map(|k| self.computed.velocity[k] - self.reference.velocity[k])
+    }
+}
+
+/// Builds the JSON object of a state vector.
+fn state_value(state: &StateVector) -> Value {
+    json!({
+        "position_km": state.position,
+        "velocity_km_s": state.velocity,
+    })
+}
+
+/// Writes an ephemeris as a JSON array of states.
+///
+/// Each element holds `time` (ISO 8601, UTC), `jd`, `position_km` and `velocity_km_s`. The array is
+/// written element by element, so long ephemerides are streamed.
+///
+/// # Arguments
+/// * `writer` - Destination of the document.
+/// * `states` - `(jd, state)` pairs, such as an `Ephemeris`.
+///
+/// # Returns
+/// * An error if writing fails.
+pub fn write_ephemeris<W: Write>(writer: &mut W, states: impl IntoIterator<Item = (f64, StateVector)>) -> io::Result<()> {
+    write!(writer, "[")?;
+    for (index, (jd, state)) in states.into_iter().enumerate() {
+        if index > 0 {
+            write!(writer, ",")?;
+        }
+        let mut record: Value = state_value(&state);
+        record["time"] = json!(iso_date(jd));
+        record["jd"] = json!(jd);
+        serde_json::to_writer(&mut *writer, &record)?;
+    }
+    writeln!(writer, "]")
+}
+
+/// Writes a comparison of computed states with reference states as a JSON report.
+///
+/// The report holds the epoch, one record per comparison with both states and their differences,
+/// and the largest position and velocity differences.
+///
+/// # Arguments
+/// * `writer` - Destination of the document.
+/// * `epoch_jd` - Julian date (UTC) of the element set epoch.
+/// * `comparisons` - The compared states.
+///
+/// # Returns
+/// * An error if writing fails.
+pub fn write_report<W: Write>(writer: &mut W, epoch_jd: f64, comparisons: &[Comparison]) -> io::Result<()> {
+    let records: Vec<Value> = comparisons
+        .iter()
+        .map(|comparison| {
+            let position_delta: [f64; 3] = comparison.position_delta();
+            let velocity_delta: [f64; 3] = comparison.velocity_delta();
+            json!({
+                "tsince_min": comparison.tsince,
+                "time": iso_date(epoch_jd + comparison.tsince / 1440.0),
+                "reference": state_value(&comparison.reference),
+                "computed": state_value(&comparison.computed),
+                "delta": {
+                    "position_km": position_delta,
+                    "velocity_km_s": velocity_delta,
+                    "position_norm_km": norm(position_delta),
+                    "velocity_norm_km_s": norm(velocity_delta),
+                },
+            })
+        })
+        .collect();
+    let max_position: f64 = comparisons.iter().map(|c| norm(c.position_delta())).fold(0.0, f64::max);
+    let max_velocity: f64 = comparisons.iter().map(|c| norm(c.velocity_delta())).fold(0.0, f64::max);
+
+    let report: Value = json!({
+        "epoch": iso_date(epoch_jd),
+        "records": records,
+        "max_position_delta_km": max_position,
+        "max_velocity_delta_km_s": max_velocity,
+    });
+    serde_json::to_writer_pretty(&mut *writer, &report)?;
+    writeln!(writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Ephemeris, Propagator, Sgp4};
+    use crate::test_support::iss_sgp4;
+
+    /// Tests that the ephemeris document is an array holding the propagated states.
+    #[test]
+    fn test_json_ephemeris() {
+        let sgp4: Sgp4 = iss_sgp4();
+        let start: f64 = sgp4.epoch_jd();
+        let mut output: Vec<u8> = Vec::new();
+        write_ephemeris(&mut output, Ephemeris::new(&sgp4, start, start + 0.01, 60.0)).unwrap();
+        let document: Value = serde_json::from_slice(&output).unwrap();
+
+        let records: &Vec<Value> = document.as_array().unwrap();
+        assert_eq!(records.len(), 15);
+        assert_eq!(records[0]["time"], "2008-09-20T12:25:40.104");
+        assert_eq!(records[0]["jd"], start);
+        let state: StateVector = sgp4.propagate(0.0).unwrap();
+        assert_eq!(records[0]["position_km"][2], state.position[2]);
+        assert_eq!(records[0]["velocity_km_s"][0], state.velocity[0]);
+
+        let mut empty: Vec<u8> = Vec::new();
+        write_ephemeris(&mut empty, Vec::new()).unwrap();
+        assert_eq!(String::from_utf8(empty).unwrap(), "[]\n");
+    }
+
+    /// Tests the deltas and maxima of a comparison report.
+    #[test]
+    fn test_json_report() {
+        let sgp4: Sgp4 = iss_sgp4();
+        let comparisons: Vec<Comparison> = [0.0, 60.0]
+            .iter()
+            .map(|&tsince| {
+                let computed: StateVector = sgp4.propagate(tsince).unwrap();
+                let mut reference: StateVector = computed;
+                reference.position[0] -= 3.0 * (1.0 + tsince / 60.0);
+                reference.velocity[2] += 4e-3;
+                Comparison { tsince, reference, computed }
+            })
+            .collect();
+        let mut output: Vec<u8> = Vec::new();
+        write_report(&mut output, sgp4.epoch_jd(), &comparisons).unwrap();
+        let report: Value = serde_json::from_slice(&output).unwrap();
+
+        assert_eq!(report["epoch"], "2008-09-20T12:25:40.104");
+        assert_eq!(report["records"][1]["time"], "2008-09-20T13:25:40.104");
+        assert!((report["records"][0]["delta"]["position_km"][0].as_f64().unwrap() - 3.0).abs() < 1e-9);
+        assert!((report["records"][1]["delta"]["position_norm_km"].as_f64().unwrap() - 6.0).abs() < 1e-9);
+        assert!((report["max_position_delta_km"].as_f64().unwrap() - 6.0).abs() < 1e-9);
+        assert!((report["max_velocity_delta_km_s"].as_f64().unwrap() - 4e-3).abs() < 1e-12);
+    }
+}
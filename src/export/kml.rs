@@ -0,0 +1,177 @@
+//! KML output of ground tracks for Google Earth.
+//! The ground track is written as a clamped-to-ground LineString, optionally split into one
+//! segment per step with a time span so the time slider replays it, and optionally with the
+//! coverage footprint of each point as a polygon.
+
+use super::iso_date;
+use crate::coverage::footprint;
+use crate::geodetic::Geodetic;
+use std::io::{self, Write};
+
+/// Content of the KML document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KmlOptions {
+    /// Name of the document, usually the satellite name.
+    pub name: String,
+    /// Splits the track into one placemark per step with a `TimeSpan`, for the time slider.
+    pub time_spans: bool,
+    /// Minimum elevation (radians) of the footprints drawn at each point, or `None` for no footprints.
+    pub footprint_elevation: Option<f64>,
+    /// Number of boundary points of each footprint.
+    pub footprint_points: usize,
+}
+
+impl KmlOptions {
+    /// Builds the options of a plain ground track, without time spans or footprints.
+    ///
+    /// # Arguments
+    /// * `name` - Name of the document.
+    ///
+    /// # Returns
+    /// * The options, with 36-point footprints if they are enabled later.
+    pub fn new(name: &str) -> KmlOptions {
+        KmlOptions {
+            name: name.to_string(),
+            time_spans: false,
+            footprint_elevation: None,
+            footprint_points: 36,
+        }
+    }
+}
+
+/// Escapes the XML special characters of a text.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Formats geodetic points as KML coordinates on the ground (`longitude,latitude,0` in degrees).
+fn coordinates<'a>(points: impl IntoIterator<Item = &'a Geodetic>) -> String {
+    points
+        .into_iter()
+        .map(|point| format!("{:.6},{:.6},0", point.longitude.to_degrees(), point.latitude.to_degrees()))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Formats the `TimeSpan` element of a placemark.
+fn time_span(begin: f64, end: f64) -> String {
+    format!("<TimeSpan><begin>{}Z</begin><end>{}Z</end></TimeSpan>", iso_date(begin), iso_date(end))
+}
+
+/// Writes a ground track as a KML document.
+///
+/// # Arguments
+/// * `writer` - Destination of the document.
+/// * `options` - Name, time spans and footprints of the document.
+/// * `track` - Julian dates (UTC) and sub-satellite points with the satellite altitude, in
+///   chronological order (see `ground_track::ground_track` and `OrbitalElements::jd_at`).
+///
+/// # Returns
+/// * An error if writing fails.
+pub fn write_kml<W: Write>(writer: &mut W, options: &KmlOptions, track: &[(f64, Geodetic)]) -> io::Result<()> {
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(writer, r#"<kml xmlns="http://www.opengis.net/kml/2.2">"#)?;
+    writeln!(writer, "<Document>")?;
+    writeln!(writer, "<name>{}</name>", escape(&options.name))?;
+    writeln!(writer, r#"<Style id="track"><LineStyle><color>ff00ffff</color><width>2</width></LineStyle></Style>"#)?;
+    writeln!(
+        writer,
+        r#"<Style id="footprint"><LineStyle><color>ff00ffff</color></LineStyle><PolyStyle><color>4000ffff</color></PolyStyle></Style>"#
+    )?;
+
+    if options.time_spans {
+        for pair in track.windows(2) {
+            writeln!(
+                writer,
+                "<Placemark><styleUrl>#track</styleUrl>{}<LineString><tessellate>1</tessellate><coordinates>{}</coordinates></LineString></Placemark>",
+                time_span(pair[0].0, pair[1].0),
+                coordinates([&pair[0].1, &pair[1].1])
+            )?;
+        }
+    } else if !track.is_empty() {
+        writeln!(
+            writer,
+            "<Placemark><name>{}</name><styleUrl>#track</styleUrl><LineString><tessellate>1</tessellate><coordinates>{}</coordinates></LineString></Placemark>",
+            escape(&options.name),
+            coordinates(track.iter().map(|(_, point)| point))
+        )?;
+    }
+
+    if let Some(min_elevation) = options.footprint_elevation {
+        for (index, (jd, subpoint)) in track.iter().enumerate() {
+            let mut boundary: Vec<Geodetic> = footprint(subpoint, min_elevation, options.footprint_points);
+            if let Some(&first) = boundary.first() {
+                boundary.push(first);
+            }
+            // Each footprint lasts until the next point, or is instantaneous at the end of the track.
+            let span: String = if options.time_spans {
+                time_span(*jd, track.get(index + 1).map_or(*jd, |next| next.0))
+            } else {
+                String::new()
+            };
+            writeln!(
+                writer,
+                "<Placemark><styleUrl>#footprint</styleUrl>{}<Polygon><tessellate>1</tessellate><outerBoundaryIs><LinearRing><coordinates>{}</coordinates></LinearRing></outerBoundaryIs></Polygon></Placemark>",
+                span,
+                coordinates(&boundary)
+            )?;
+        }
+    }
+
+    writeln!(writer, "</Document>")?;
+    writeln!(writer, "</kml>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ground_track::ground_track;
+    use crate::OrbitalElements;
+    use crate::test_support::iss_elements;
+
+    fn iss_track() -> Vec<(f64, Geodetic)> {
+        let elements: OrbitalElements = iss_elements();
+        ground_track(&elements, 0.0, 10.0, 5.0)
+            .unwrap()
+            .into_iter()
+            .map(|(tsince, point)| (elements.jd_at(tsince), point))
+            .collect()
+    }
+
+    /// Tests a plain ground track: one LineString with every point and an escaped name.
+    #[test]
+    fn test_kml_track() {
+        let track: Vec<(f64, Geodetic)> = iss_track();
+        let mut output: Vec<u8> = Vec::new();
+        write_kml(&mut output, &KmlOptions::new("ISS & friends"), &track).unwrap();
+        let text: String = String::from_utf8(output).unwrap();
+
+        assert!(text.contains("<name>ISS &amp; friends</name>"));
+        assert_eq!(text.matches("<LineString>").count(), 1);
+        assert!(!text.contains("<TimeSpan>") && !text.contains("<Polygon>"));
+        let first: String = format!("{:.6},{:.6},0", track[0].1.longitude.to_degrees(), track[0].1.latitude.to_degrees());
+        assert!(text.contains(&format!("<coordinates>{} ", first)));
+        assert!(text.trim_end().ends_with("</kml>"));
+    }
+
+    /// Tests the time spans of the segments and the closed footprint polygons.
+    #[test]
+    fn test_kml_time_spans_and_footprints() {
+        let track: Vec<(f64, Geodetic)> = iss_track();
+        let mut options: KmlOptions = KmlOptions::new("ISS");
+        options.time_spans = true;
+        options.footprint_elevation = Some(10.0_f64.to_radians());
+        options.footprint_points = 8;
+        let mut output: Vec<u8> = Vec::new();
+        write_kml(&mut output, &options, &track).unwrap();
+        let text: String = String::from_utf8(output).unwrap();
+
+        assert_eq!(text.matches("<LineString>").count(), 2);
+        assert_eq!(text.matches("<Polygon>").count(), 3);
+        assert!(text.contains("<TimeSpan><begin>2008-09-20T12:25:40.104Z</begin><end>2008-09-20T12:30:40.104Z</end></TimeSpan>"));
+        let ring: &str = text.split("<LinearRing><coordinates>").nth(1).unwrap().split("</coordinates>").next().unwrap();
+        let points: Vec<&str> = ring.split(' ').collect();
+        assert_eq!(points.len(), 9);
+        assert_eq!(points[0], points[8]);
+    }
+}
@@ -0,0 +1,153 @@
+//! CCSDS Orbit Ephemeris Message (OEM) output.
+//! This module writes ephemerides in the Keyword-Value Notation (KVN) of CCSDS 502.0-B, which STK,
+//! GMAT and most ground systems can load directly.
+
+use super::iso_date;
+use crate::StateVector;
+use crate::time::JD_UNIX_EPOCH;
+use std::io::{self, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Header and metadata of an OEM.
+///
+/// The message has a single segment centered on the Earth, with UTC dates and states in km and km/s.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OemMetadata {
+    /// Name of the object, e.g. `ISS (ZARYA)`.
+    pub object_name: String,
+    /// International designator of the object, e.g. `1998-067A`.
+    pub object_id: String,
+    /// Organization that created the message.
+    pub originator: String,
+    /// Reference frame of the states, `TEME` for SGP4 output.
+    pub ref_frame: String,
+    /// Julian date (UTC) at which the message is created.
+    pub creation_date: f64,
+    /// Julian date (UTC) of the first state.
+    pub start: f64,
+    /// Julian date (UTC) of the last state.
+    pub stop: f64,
+}
+
+impl OemMetadata {
+    /// Builds the metadata of TEME states, created now.
+    ///
+    /// # Arguments
+    /// * `object_name` - Name of the object.
+    /// * `object_id` - International designator of the object.
+    /// * `start` - Julian date (UTC) of the first state.
+    /// * `stop` - Julian date (UTC) of the last state.
+    ///
+    /// # Returns
+    /// * The metadata, with `orbitalrs` as the originator.
+    pub fn new(object_name: &str, object_id: &str, start: f64, stop: f64) -> OemMetadata {
+        let seconds: f64 = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs_f64()).unwrap_or(0.0);
+        OemMetadata {
+            object_name: object_name.to_string(),
+            object_id: object_id.to_string(),
+            originator: "orbitalrs".to_string(),
+            ref_frame: "TEME".to_string(),
+            creation_date: JD_UNIX_EPOCH + seconds / 86400.0,
+            start,
+            stop,
+        }
+    }
+}
+
+/// Writes an ephemeris as an OEM.
+///
+/// # Arguments
+/// * `writer` - Destination of the message.
+/// * `metadata` - Header and metadata of the message.
+/// * `states` - `(jd, state)` pairs in chronological order, such as an `Ephemeris`.
+///
+/// # Returns
+/// * An error if writing fails.
+pub fn write_oem<W: Write>(
+    writer: &mut W,
+    metadata: &OemMetadata,
+    states: impl IntoIterator<Item = (f64, StateVector)>,
+) -> io::Result<()> {
+    writeln!(writer, "CCSDS_OEM_VERS = 2.0")?;
+    writeln!(writer, "CREATION_DATE = {}", iso_date(metadata.creation_date))?;
+    writeln!(writer, "ORIGINATOR = {}", metadata.originator)?;
+    writeln!(writer)?;
+    writeln!(writer, "META_START")?;
+    writeln!(writer, "OBJECT_NAME = {}", metadata.object_name)?;
+    writeln!(writer, "OBJECT_ID = {}", metadata.object_id)?;
+    writeln!(writer, "CENTER_NAME = EARTH")?;
+    writeln!(writer, "REF_FRAME = {}", metadata.ref_frame)?;
+    writeln!(writer, "TIME_SYSTEM = UTC")?;
+    writeln!(writer, "START_TIME = {}", iso_date(metadata.start))?;
+    writeln!(writer, "STOP_TIME = {}", iso_date(metadata.stop))?;
+    writeln!(writer, "META_STOP")?;
+    writeln!(writer)?;
+
+    for (jd, state) in states {
+        let [x, y, z] = state.position;
+        let [vx, vy, vz] = state.velocity;
+        writeln!(
+            writer,
+            "{} {:.6} {:.6} {:.6} {:.9} {:.9} {:.9}",
+            iso_date(jd),
+            x,
+            y,
+            z,
+            vx,
+            vy,
+            vz
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Ephemeris, Propagator, Sgp4};
+    use crate::test_support::iss_sgp4;
+
+    /// Tests the header and metadata of the message.
+    #[test]
+    fn test_oem_header() {
+        let sgp4: Sgp4 = iss_sgp4();
+        let start: f64 = sgp4.epoch_jd();
+        let mut metadata: OemMetadata = OemMetadata::new("ISS (ZARYA)", "1998-067A", start, start + 0.01);
+        metadata.creation_date = start;
+        let mut output: Vec<u8> = Vec::new();
+        write_oem(&mut output, &metadata, Ephemeris::new(&sgp4, start, start + 0.01, 60.0)).unwrap();
+        let text: String = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines[0], "CCSDS_OEM_VERS = 2.0");
+        assert_eq!(lines[1], "CREATION_DATE = 2008-09-20T12:25:40.104");
+        assert_eq!(lines[2], "ORIGINATOR = orbitalrs");
+        assert_eq!(&lines[4..9], ["META_START", "OBJECT_NAME = ISS (ZARYA)", "OBJECT_ID = 1998-067A", "CENTER_NAME = EARTH", "REF_FRAME = TEME"]);
+        assert_eq!(lines[10], "START_TIME = 2008-09-20T12:25:40.104");
+        assert_eq!(lines[11], "STOP_TIME = 2008-09-20T12:40:04.104");
+        assert_eq!(lines[12], "META_STOP");
+        assert_eq!(lines.len(), 14 + 15);
+    }
+
+    /// Tests that the state lines hold the propagated states.
+    #[test]
+    fn test_oem_states() {
+        let sgp4: Sgp4 = iss_sgp4();
+        let start: f64 = sgp4.epoch_jd();
+        let metadata: OemMetadata = OemMetadata::new("ISS (ZARYA)", "1998-067A", start, start + 1.0 / 24.0);
+        let mut output: Vec<u8> = Vec::new();
+        write_oem(&mut output, &metadata, Ephemeris::new(&sgp4, start, start + 1.0 / 24.0, 600.0)).unwrap();
+        let text: String = String::from_utf8(output).unwrap();
+        let data: Vec<&str> = text.lines().skip(14).collect();
+        assert_eq!(data.len(), 7);
+
+        let fields: Vec<&str> = data[3].split_whitespace().collect();
+        assert_eq!(fields[0], "2008-09-20T12:55:40.104");
+        let state: StateVector = sgp4.propagate(30.0).unwrap();
+        // The Julian date of the step is only resolved to about 10 µs, i.e. 0.1 m along the orbit.
+        for k in 0..3 {
+            assert!((fields[1 + k].parse::<f64>().unwrap() - state.position[k]).abs() < 1e-3);
+            assert!((fields[4 + k].parse::<f64>().unwrap() - state.velocity[k]).abs() < 1e-6);
+        }
+    }
+}
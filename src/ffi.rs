@@ -0,0 +1,263 @@
+//! C interface for embedding the propagator in C and C++ ground-station software.
+//! The header `include/sgp4_rust.h` is generated from this module by cbindgen (see
+//! `cbindgen.toml`). Propagators are opaque heap handles; states are written into arrays owned by
+//! the caller.
+
+use crate::propagator::PropagationError;
+use crate::{OrbitalElements, Sgp4, StateVector, Tle, convert_satellite_data};
+use core::ffi::{CStr, c_char};
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
+/// Result codes of the C functions.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sgp4Status {
+    /// The call succeeded.
+    Ok = 0,
+    /// A required pointer was null.
+    NullPointer = 1,
+    /// The lines are not valid UTF-8 or do not form a TLE set.
+    InvalidTle = 2,
+    /// The mean eccentricity left the range [-0.001, 1).
+    MeanEccentricity = 3,
+    /// The mean motion became negative.
+    MeanMotion = 4,
+    /// The eccentricity perturbed by the lunar-solar periodics left the range [0, 1].
+    PerturbedEccentricity = 5,
+    /// The semi-latus rectum became negative.
+    SemiLatusRectum = 6,
+    /// Kepler's equation could not be solved.
+    Kepler = 7,
+    /// The satellite is below the Earth's surface.
+    Decayed = 8,
+}
+
+impl From<&PropagationError> for Sgp4Status {
+    fn from(error: &PropagationError) -> Sgp4Status {
+        match error {
+            PropagationError::MeanEccentricity(_) => Sgp4Status::MeanEccentricity,
+            PropagationError::MeanMotion(_) => Sgp4Status::MeanMotion,
+            PropagationError::PerturbedEccentricity(_) => Sgp4Status::PerturbedEccentricity,
+            PropagationError::SemiLatusRectum(_) => Sgp4Status::SemiLatusRectum,
+            PropagationError::Kepler(_) => Sgp4Status::Kepler,
+            PropagationError::Decayed { .. } => Sgp4Status::Decayed,
+        }
+    }
+}
+
+/// Parses the two data lines of a TLE into orbital elements.
+///
+/// # Arguments
+/// * `line1` - First data line, NUL-terminated.
+/// * `line2` - Second data line, NUL-terminated.
+/// * `elements` - Receives the orbital elements on success.
+///
+/// # Returns
+/// * `Ok`, `NullPointer` or `InvalidTle`.
+///
+/// # Safety
+/// The lines must be null or valid NUL-terminated strings, and `elements` null or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sgp4_parse_tle(line1: *const c_char, line2: *const c_char, elements: *mut OrbitalElements) -> Sgp4Status {
+    if line1.is_null() || line2.is_null() || elements.is_null() {
+        return Sgp4Status::NullPointer;
+    }
+    // SAFETY: the pointers are non-null and the caller guarantees they are valid.
+    let (line1, line2) = unsafe { (CStr::from_ptr(line1).to_str(), CStr::from_ptr(line2).to_str()) };
+    let (Ok(line1), Ok(line2)) = (line1, line2) else {
+        return Sgp4Status::InvalidTle;
+    };
+    match Tle::from_lines(&[line1, line2]) {
+        Ok(tle) => {
+            // SAFETY: `elements` is non-null and valid for writes.
+            unsafe { elements.write(convert_satellite_data(&tle)) };
+            Sgp4Status::Ok
+        }
+        Err(_) => Sgp4Status::InvalidTle,
+    }
+}
+
+/// Performs the SGP4 initialization of a set of orbital elements.
+///
+/// # Arguments
+/// * `elements` - Orbital elements, as filled by `sgp4_parse_tle`.
+///
+/// # Returns
+/// * A propagator handle to release with `sgp4_free`, or null if `elements` is null.
+///
+/// # Safety
+/// `elements` must be null or point to valid orbital elements.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sgp4_new(elements: *const OrbitalElements) -> *mut Sgp4 {
+    // SAFETY: the caller guarantees that a non-null pointer is valid.
+    match unsafe { elements.as_ref() } {
+        Some(elements) => Box::into_raw(Box::new(Sgp4::new(elements))),
+        None => core::ptr::null_mut(),
+    }
+}
+
+/// Releases a propagator handle. Null handles are ignored.
+///
+/// # Safety
+/// `sgp4` must be null or a handle returned by `sgp4_new` that was not released yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sgp4_free(sgp4: *mut Sgp4) {
+    if !sgp4.is_null() {
+        // SAFETY: the handle comes from `Box::into_raw` and is released once.
+        drop(unsafe { Box::from_raw(sgp4) });
+    }
+}
+
+/// Returns the Julian date (UTC) of the epoch of a propagator, or NaN for a null handle.
+///
+/// # Safety
+/// `sgp4` must be null or a live handle returned by `sgp4_new`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sgp4_epoch_jd(sgp4: *const Sgp4) -> f64 {
+    // SAFETY: the caller guarantees that a non-null handle is live.
+    unsafe { sgp4.as_ref() }.map_or(f64::NAN, |sgp4| sgp4.epoch_jd)
+}
+
+/// Computes the TEME state vector of the satellite at a time since epoch.
+///
+/// # Arguments
+/// * `sgp4` - Propagator handle.
+/// * `tsince` - Time since epoch in minutes.
+/// * `position` - Receives the three position components in kilometers.
+/// * `velocity` - Receives the three velocity components in kilometers per second.
+///
+/// # Returns
+/// * `Ok`, `NullPointer` or the propagation error; the arrays are left untouched on error.
+///
+/// # Safety
+/// `sgp4` must be null or a live handle, and the arrays null or valid for three writes each.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sgp4_propagate(sgp4: *const Sgp4, tsince: f64, position: *mut f64, velocity: *mut f64) -> Sgp4Status {
+    // SAFETY: the caller guarantees that a non-null handle is live.
+    let Some(sgp4) = (unsafe { sgp4.as_ref() }) else {
+        return Sgp4Status::NullPointer;
+    };
+    if position.is_null() || velocity.is_null() {
+        return Sgp4Status::NullPointer;
+    }
+    match sgp4.propagate(tsince) {
+        Ok(state) => {
+            // SAFETY: both arrays are non-null and hold three values.
+            unsafe { write_state(&state, position, velocity) };
+            Sgp4Status::Ok
+        }
+        Err(error) => Sgp4Status::from(&error),
+    }
+}
+
+/// Computes the TEME state vectors of the satellite at several times since epoch.
+///
+/// # Arguments
+/// * `sgp4` - Propagator handle.
+/// * `times` - Times since epoch in minutes.
+/// * `count` - Number of times.
+/// * `positions` - Receives `3 * count` position components in kilometers.
+/// * `velocities` - Receives `3 * count` velocity components in kilometers per second.
+///
+/// # Returns
+/// * `Ok`, `NullPointer` or the error of the first state that could not be computed. Such states
+///   are filled with NaN and the following times are still propagated.
+///
+/// # Safety
+/// `sgp4` must be null or a live handle, `times` null or valid for `count` reads, and the output
+/// arrays null or valid for `3 * count` writes each.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sgp4_propagate_many(sgp4: *const Sgp4, times: *const f64, count: usize, positions: *mut f64, velocities: *mut f64) -> Sgp4Status {
+    // SAFETY: the caller guarantees that a non-null handle is live.
+    let Some(sgp4) = (unsafe { sgp4.as_ref() }) else {
+        return Sgp4Status::NullPointer;
+    };
+    if times.is_null() || positions.is_null() || velocities.is_null() {
+        return Sgp4Status::NullPointer;
+    }
+    let mut status: Sgp4Status = Sgp4Status::Ok;
+    for k in 0..count {
+        // SAFETY: `times` holds `count` values.
+        let tsince: f64 = unsafe { *times.add(k) };
+        let state: StateVector = sgp4.propagate(tsince).unwrap_or_else(|error| {
+            if status == Sgp4Status::Ok {
+                status = Sgp4Status::from(&error);
+            }
+            crate::INVALID_STATE
+        });
+        // SAFETY: the output arrays hold `3 * count` values.
+        unsafe { write_state(&state, positions.add(3 * k), velocities.add(3 * k)) };
+    }
+    status
+}
+
+/// Writes a state vector into two arrays of three values.
+///
+/// # Safety
+/// Both pointers must be valid for three writes.
+unsafe fn write_state(state: &StateVector, position: *mut f64, velocity: *mut f64) {
+    for k in 0..3 {
+        // SAFETY: guaranteed by the caller.
+        unsafe {
+            position.add(k).write(state.position[k]);
+            velocity.add(k).write(state.velocity[k]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::ISS_LINES;
+    use alloc::ffi::CString;
+
+    /// Tests the parse, init, propagate and free sequence of a C caller.
+    #[test]
+    fn test_ffi_propagate() {
+        let line1: CString = CString::new(ISS_LINES[0]).unwrap();
+        let line2: CString = CString::new(ISS_LINES[1]).unwrap();
+        let mut elements = core::mem::MaybeUninit::<OrbitalElements>::uninit();
+        let status = unsafe { sgp4_parse_tle(line1.as_ptr(), line2.as_ptr(), elements.as_mut_ptr()) };
+        assert_eq!(status, Sgp4Status::Ok);
+        let elements: OrbitalElements = unsafe { elements.assume_init() };
+
+        let sgp4: *mut Sgp4 = unsafe { sgp4_new(&elements) };
+        assert!(!sgp4.is_null());
+        assert_eq!(unsafe { sgp4_epoch_jd(sgp4) }, elements.epoch_jd());
+
+        let mut position: [f64; 3] = [0.0; 3];
+        let mut velocity: [f64; 3] = [0.0; 3];
+        let status = unsafe { sgp4_propagate(sgp4, 90.0, position.as_mut_ptr(), velocity.as_mut_ptr()) };
+        assert_eq!(status, Sgp4Status::Ok);
+        let expected: StateVector = crate::sgp4(90.0, &elements);
+        assert_eq!(position, expected.position);
+        assert_eq!(velocity, expected.velocity);
+
+        let times: [f64; 2] = [0.0, 90.0];
+        let mut positions: [f64; 6] = [0.0; 6];
+        let mut velocities: [f64; 6] = [0.0; 6];
+        let status = unsafe { sgp4_propagate_many(sgp4, times.as_ptr(), 2, positions.as_mut_ptr(), velocities.as_mut_ptr()) };
+        assert_eq!(status, Sgp4Status::Ok);
+        assert_eq!(positions[3..], position);
+        unsafe { sgp4_free(sgp4) };
+    }
+
+    /// Tests the status codes of invalid arguments.
+    #[test]
+    fn test_ffi_errors() {
+        let line1: CString = CString::new(ISS_LINES[0]).unwrap();
+        let mut elements = core::mem::MaybeUninit::<OrbitalElements>::uninit();
+        let status = unsafe { sgp4_parse_tle(line1.as_ptr(), line1.as_ptr(), elements.as_mut_ptr()) };
+        assert_eq!(status, Sgp4Status::InvalidTle);
+        let status = unsafe { sgp4_parse_tle(core::ptr::null(), line1.as_ptr(), elements.as_mut_ptr()) };
+        assert_eq!(status, Sgp4Status::NullPointer);
+
+        let mut position: [f64; 3] = [0.0; 3];
+        let status = unsafe { sgp4_propagate(core::ptr::null(), 0.0, position.as_mut_ptr(), position.as_mut_ptr()) };
+        assert_eq!(status, Sgp4Status::NullPointer);
+        assert!(unsafe { sgp4_new(core::ptr::null()) }.is_null());
+        assert!(unsafe { sgp4_epoch_jd(core::ptr::null()) }.is_nan());
+        unsafe { sgp4_free(core::ptr::null_mut()) };
+    }
+}
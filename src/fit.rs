@@ -0,0 +1,274 @@
+//! Fitting of SGP4 mean elements to state vectors (differential correction).
+//! The fit adjusts the mean state at epoch, and optionally the drag term, by Gauss-Newton iterations
+//! on the residuals between the propagated and observed states. Working on a Cartesian mean state
+//! keeps the problem well conditioned for circular and equatorial orbits.
+
+use crate::keplerian::KeplerianElements;
+use crate::mean_elements::{mean_from_keplerian, osculating_to_mean};
+use crate::propagator::PropagationError;
+use crate::{OrbitalElements, Sgp4, StateVector, time};
+use core::fmt;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Maximum number of Gauss-Newton iterations.
+const MAX_ITERATIONS: usize = 25;
+/// Relative change of the RMS below which the fit has converged.
+const CONVERGENCE: f64 = 1e-8;
+/// Weight of velocity residuals: a velocity error of 1 km/s counts as a position error of this many km.
+const VELOCITY_WEIGHT: f64 = 60.0;
+/// Finite difference steps of the position (km), velocity (km/s) and drag term parameters.
+const STEPS: [f64; 7] = [1e-3, 1e-3, 1e-3, 1e-6, 1e-6, 1e-6, 1e-6];
+
+/// Represents an observed state of a satellite.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Observation {
+    /// Julian date (UTC) of the observation.
+    pub jd: f64,
+    /// Observed state vector in the TEME frame (km, km/s).
+    pub state: StateVector,
+}
+
+/// Represents the result of a fit.
+pub struct Fit {
+    /// The fitted mean elements.
+    pub elements: OrbitalElements,
+    /// Weighted root mean square of the residuals in kilometers.
+    pub rms: f64,
+    /// Number of iterations performed.
+    pub iterations: usize,
+}
+
+/// Errors that can occur while fitting elements.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FitError {
+    /// Fewer observations than needed to determine the fitted parameters.
+    NotEnoughObservations(usize),
+    /// The iterations left the domain of elliptical orbits.
+    Diverged,
+    /// The normal equations cannot be solved.
+    Singular,
+    /// The elements of an iteration cannot be propagated to an observation.
+    Propagation(PropagationError),
+}
+
+impl fmt::Display for FitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FitError::NotEnoughObservations(count) => write!(f, "{} observations are not enough for a fit", count),
+            FitError::Diverged => write!(f, "the fit diverged"),
+            FitError::Singular => write!(f, "the fit is singular"),
+            FitError::Propagation(error) => write!(f, "the fit cannot propagate its elements: {}", error),
+        }
+    }
+}
+
+impl core::error::Error for FitError {}
+
+/// Builds mean elements from the fitted parameters.
+///
+/// # Arguments
+/// * `parameters` - Mean position (km), mean velocity (km/s) and drag term.
+/// * `epoch_jd` - Julian date (UTC) of the epoch.
+///
+/// # Returns
+/// * The mean elements, or `None` if the parameters do not describe an elliptical orbit.
+fn elements_from_parameters(parameters: &[f64; 7], epoch_jd: f64) -> Option<OrbitalElements> {
+    let state = StateVector {
+        position: [parameters[0], parameters[1], parameters[2]],
+        velocity: [parameters[3], parameters[4], parameters[5]],
+    };
+    let keplerian = KeplerianElements::from_state(&state);
+    if !(0.0..1.0).contains(&keplerian.eccentricity) {
+        return None;
+    }
+    let (year, day) = time::day_of_year(epoch_jd);
+    Some(mean_from_keplerian(&keplerian, year, day, parameters[6]))
+}
+
+/// Computes the weighted residuals of elements against observations.
+///
+/// # Arguments
+/// * `elements` - The mean elements.
+/// * `observations` - The observed states.
+///
+/// # Returns
+/// * Six residuals per observation: position (km), then weighted velocity, or the first
+///   propagation error.
+fn residuals(elements: &OrbitalElements, observations: &[Observation]) -> Result<Vec<f64>, FitError> {
+    let epoch_jd: f64 = elements.epoch_jd();
+    let propagator: Sgp4 = Sgp4::new(elements);
+    let mut residuals: Vec<f64> = Vec::with_capacity(6 * observations.len());
+    for observation in observations {
+        let computed: StateVector = propagator.propagate((observation.jd - epoch_jd) * 1440.0).map_err(FitError::Propagation)?;
+        residuals.extend([0, 1, 2].map(|k| observation.state.position[k] - computed.position[k]));
+        residuals.extend([0, 1, 2].map(|k| (observation.state.velocity[k] - computed.velocity[k]) * VELOCITY_WEIGHT));
+    }
+    Ok(residuals)
+}
+
+/// Computes the root mean square of residuals.
+fn rms(residuals: &[f64]) -> f64 {
+    (residuals.iter().map(|r| r * r).sum::<f64>() / residuals.len() as f64).sqrt()
+}
+
+/// Solves a linear system by Gaussian elimination with partial pivoting.
+///
+/// # Arguments
+/// * `a` - The matrix of the system, with the right-hand side as last column.
+/// * `n` - The number of unknowns.
+///
+/// # Returns
+/// * The solution, or `None` if the matrix is singular.
+fn solve(mut a: Vec<Vec<f64>>, n: usize) -> Option<Vec<f64>> {
+    for col in 0..n {
+        let pivot: usize = (col..n).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < 1e-300 {
+            return None;
+        }
+        a.swap(col, pivot);
+        let pivot_row: Vec<f64> = a[col].clone();
+        for row in a.iter_mut().take(n).skip(col + 1) {
+            let factor: f64 = row[col] / pivot_row[col];
+            for (value, reference) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                *value -= factor * reference;
+            }
+        }
+    }
+    let mut x: Vec<f64> = vec![0.0; n];
+    for row in (0..n).rev() {
+        let sum: f64 = (row + 1..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (a[row][n] - sum) / a[row][row];
+    }
+    Some(x)
+}
+
+/// Fits SGP4 mean elements to observed states by differential correction.
+///
+/// The resulting elements can be written as a TLE with `Tle::from_elements`.
+///
+/// # Arguments
+/// * `observations` - Observed states, at least two (three when fitting the drag term).
+/// * `epoch_jd` - Julian date (UTC) of the epoch of the fitted elements.
+/// * `bstar` - Initial drag term in 1/earth radii.
+/// * `fit_bstar` - Whether the drag term is adjusted or kept at its initial value.
+///
+/// # Returns
+/// * The fitted elements with the RMS of the residuals, or an error if the fit fails.
+pub fn fit_elements(observations: &[Observation], epoch_jd: f64, bstar: f64, fit_bstar: bool) -> Result<Fit, FitError> {
+    let count: usize = if fit_bstar { 7 } else { 6 };
+    if observations.len() < 2 + fit_bstar as usize {
+        return Err(FitError::NotEnoughObservations(observations.len()));
+    }
+
+    // Initial guess: the observation closest to the epoch, propagated to the epoch.
+    let closest: &Observation = observations
+        .iter()
+        .min_by(|a, b| (a.jd - epoch_jd).abs().total_cmp(&(b.jd - epoch_jd).abs()))
+        .ok_or(FitError::NotEnoughObservations(0))?;
+    let (year, day) = time::day_of_year(closest.jd);
+    let guess = osculating_to_mean(&closest.state, year, day, bstar).ok_or(FitError::Diverged)?;
+    let start: StateVector = Sgp4::new(&guess).propagate((epoch_jd - closest.jd) * 1440.0).map_err(FitError::Propagation)?;
+    let mut parameters: [f64; 7] = [
+        start.position[0],
+        start.position[1],
+        start.position[2],
+        start.velocity[0],
+        start.velocity[1],
+        start.velocity[2],
+        bstar,
+    ];
+
+    let mut elements = elements_from_parameters(&parameters, epoch_jd).ok_or(FitError::Diverged)?;
+    let mut current: Vec<f64> = residuals(&elements, observations)?;
+    let mut iterations: usize = 0;
+    while iterations < MAX_ITERATIONS {
+        iterations += 1;
+
+        // Jacobian of the residuals by forward differences.
+        let mut jacobian: Vec<Vec<f64>> = Vec::with_capacity(count);
+        for (p, step) in STEPS.iter().enumerate().take(count) {
+            let mut perturbed: [f64; 7] = parameters;
+            perturbed[p] += step;
+            let shifted = elements_from_parameters(&perturbed, epoch_jd).ok_or(FitError::Diverged)?;
+            let column: Vec<f64> = residuals(&shifted, observations)?;
+            jacobian.push(current.iter().zip(&column).map(|(r0, r1)| (r0 - r1) / step).collect());
+        }
+
+        // Normal equations (JᵀJ) Δ = Jᵀr, with the right-hand side in the last column.
+        let system: Vec<Vec<f64>> = (0..count)
+            .map(|i| {
+                let mut row: Vec<f64> = (0..count)
+                    .map(|j| jacobian[i].iter().zip(&jacobian[j]).map(|(a, b)| a * b).sum())
+                    .collect();
+                row.push(jacobian[i].iter().zip(&current).map(|(a, r)| a * r).sum());
+                row
+            })
+            .collect();
+        let delta: Vec<f64> = solve(system, count).ok_or(FitError::Singular)?;
+        for (parameter, change) in parameters.iter_mut().zip(&delta) {
+            *parameter += change;
+        }
+
+        let previous: f64 = rms(&current);
+        elements = elements_from_parameters(&parameters, epoch_jd).ok_or(FitError::Diverged)?;
+        current = residuals(&elements, observations)?;
+        if (previous - rms(&current)).abs() <= CONVERGENCE * previous.max(1e-9) {
+            break;
+        }
+    }
+
+    Ok(Fit {
+        elements,
+        rms: rms(&current),
+        iterations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::iss_elements;
+
+    /// Tests that the fit recovers the elements that generated the observations.
+    #[test]
+    fn test_fit_elements() {
+        let truth = iss_elements();
+        let observations: Vec<Observation> = (0..20)
+            .map(|i| {
+                let tsince: f64 = 30.0 + i as f64 * 10.0;
+                Observation {
+                    jd: truth.jd_at(tsince),
+                    state: crate::sgp4(tsince, &truth),
+                }
+            })
+            .collect();
+
+        let fit = fit_elements(&observations, truth.epoch_jd(), truth.bstar, false).unwrap();
+
+        // Julian dates resolve time to a few microseconds, i.e. centimeters along the orbit.
+        assert!(fit.rms < 1e-3);
+        assert!((fit.elements.mean_motion - truth.mean_motion).abs() < 1e-10);
+        assert!((fit.elements.inclination - truth.inclination).abs() < 1e-8);
+        assert!((fit.elements.raan - truth.raan).abs() < 1e-8);
+        assert!((fit.elements.epoch_day - truth.epoch_day).abs() < 1e-8);
+    }
+
+    /// Tests that too few observations are rejected.
+    #[test]
+    fn test_fit_not_enough_observations() {
+        let observation = Observation {
+            jd: 2451545.0,
+            state: StateVector {
+                position: [7000.0, 0.0, 0.0],
+                velocity: [0.0, 7.5, 0.0],
+            },
+        };
+
+        assert_eq!(
+            fit_elements(&[observation], 2451545.0, 0.0, false).err(),
+            Some(FitError::NotEnoughObservations(1))
+        );
+    }
+}
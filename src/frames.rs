@@ -0,0 +1,404 @@
+//! Reference frame conversions for SGP4 state vectors.
+//! SGP4 produces states in the True Equator Mean Equinox (TEME) frame; this module rotates them
+//! into the Earth-fixed frame (ECEF) using the Greenwich Mean Sidereal Time, and into the
+//! inertial J2000 frame using the IAU-76/FK5 precession-nutation model.
+
+use crate::StateVector;
+use crate::time::{DAYS_PER_CENTURY, JD_J2000, TimeScale, TimeScales, gmst};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Earth's rotation rate in radians per second.
+pub const EARTH_ROTATION_RATE: f64 = 7.292115146706979e-5;
+/// Arcseconds to radians.
+const ARCSEC: f64 = core::f64::consts::PI / (180.0 * 3600.0);
+
+/// Largest terms of the IAU 1980 nutation series: multipliers of the fundamental arguments
+/// (D, M, M', F, Ω), then Δψ and Δε coefficients and their rates, in 0.0001 arcseconds.
+#[rustfmt::skip]
+const NUTATION_TERMS: [([f64; 5], f64, f64, f64, f64); 30] = [
+    ([ 0.0,  0.0,  0.0, 0.0, 1.0], -171996.0, -174.2, 92025.0,  8.9),
+    ([-2.0,  0.0,  0.0, 2.0, 2.0],  -13187.0,   -1.6,  5736.0, -3.1),
+    ([ 0.0,  0.0,  0.0, 2.0, 2.0],   -2274.0,   -0.2,   977.0, -0.5),
+    ([ 0.0,  0.0,  0.0, 0.0, 2.0],    2062.0,    0.2,  -895.0,  0.5),
+    ([ 0.0,  1.0,  0.0, 0.0, 0.0],    1426.0,   -3.4,    54.0, -0.1),
+    ([ 0.0,  0.0,  1.0, 0.0, 0.0],     712.0,    0.1,    -7.0,  0.0),
+    ([-2.0,  1.0,  0.0, 2.0, 2.0],    -517.0,    1.2,   224.0, -0.6),
+    ([ 0.0,  0.0,  0.0, 2.0, 1.0],    -386.0,   -0.4,   200.0,  0.0),
+    ([ 0.0,  0.0,  1.0, 2.0, 2.0],    -301.0,    0.0,   129.0, -0.1),
+    ([-2.0, -1.0,  0.0, 2.0, 2.0],     217.0,   -0.5,   -95.0,  0.3),
+    ([-2.0,  0.0,  1.0, 0.0, 0.0],    -158.0,    0.0,     0.0,  0.0),
+    ([-2.0,  0.0,  0.0, 2.0, 1.0],     129.0,    0.1,   -70.0,  0.0),
+    ([ 0.0,  0.0, -1.0, 2.0, 2.0],     123.0,    0.0,   -53.0,  0.0),
+    ([ 2.0,  0.0,  0.0, 0.0, 0.0],      63.0,    0.0,     0.0,  0.0),
+    ([ 0.0,  0.0,  1.0, 0.0, 1.0],      63.0,    0.1,   -33.0,  0.0),
+    ([ 2.0,  0.0, -1.0, 2.0, 2.0],     -59.0,    0.0,    26.0,  0.0),
+    ([ 0.0,  0.0, -1.0, 0.0, 1.0],     -58.0,   -0.1,    32.0,  0.0),
+    ([ 0.0,  0.0,  1.0, 2.0, 1.0],     -51.0,    0.0,    27.0,  0.0),
+    ([-2.0,  0.0,  2.0, 0.0, 0.0],      48.0,    0.0,     0.0,  0.0),
+    ([ 0.0,  0.0, -2.0, 2.0, 1.0],      46.0,    0.0,   -24.0,  0.0),
+    ([ 2.0,  0.0,  0.0, 2.0, 2.0],     -38.0,    0.0,    16.0,  0.0),
+    ([ 0.0,  0.0,  2.0, 2.0, 2.0],     -31.0,    0.0,    13.0,  0.0),
+    ([ 0.0,  0.0,  2.0, 0.0, 0.0],      29.0,    0.0,     0.0,  0.0),
+    ([-2.0,  0.0,  1.0, 2.0, 2.0],      29.0,    0.0,   -12.0,  0.0),
+    ([ 0.0,  0.0,  0.0, 2.0, 0.0],      26.0,    0.0,     0.0,  0.0),
+    ([-2.0,  0.0,  0.0, 2.0, 0.0],     -22.0,    0.0,     0.0,  0.0),
+    ([ 0.0,  0.0, -1.0, 2.0, 1.0],      21.0,    0.0,   -10.0,  0.0),
+    ([ 0.0,  2.0,  0.0, 0.0, 0.0],      17.0,   -0.1,     0.0,  0.0),
+    ([ 2.0,  0.0, -1.0, 0.0, 1.0],      16.0,    0.0,    -8.0,  0.0),
+    ([-2.0,  2.0,  0.0, 2.0, 2.0],     -16.0,    0.1,     7.0,  0.0),
+];
+
+/// Builds the frame rotation matrix about the X axis (R1).
+///
+/// # Arguments
+/// * `angle` - The rotation angle in radians.
+///
+/// # Returns
+/// * The rotation matrix.
+fn rotation_x(angle: f64) -> [[f64; 3]; 3] {
+    let (sin, cos) = angle.sin_cos();
+    [[1.0, 0.0, 0.0], [0.0, cos, sin], [0.0, -sin, cos]]
+}
+
+/// Builds the frame rotation matrix about the Y axis (R2).
+///
+/// # Arguments
+/// * `angle` - The rotation angle in radians.
+///
+/// # Returns
+/// * The rotation matrix.
+fn rotation_y(angle: f64) -> [[f64; 3]; 3] {
+    let (sin, cos) = angle.sin_cos();
+    [[cos, 0.0, -sin], [0.0, 1.0, 0.0], [sin, 0.0, cos]]
+}
+
+/// Builds the frame rotation matrix about the Z axis (R3).
+///
+/// # Arguments
+/// * `angle` - The rotation angle in radians.
+///
+/// # Returns
+/// * The rotation matrix.
+fn rotation_z(angle: f64) -> [[f64; 3]; 3] {
+    let (sin, cos) = angle.sin_cos();
+    [[cos, sin, 0.0], [-sin, cos, 0.0], [0.0, 0.0, 1.0]]
+}
+
+/// Multiplies two 3x3 matrices.
+fn multiply(a: &[[f64; 3]; 3], b: &[[f64; 3]; 3]) -> [[f64; 3]; 3] {
+    let mut m: [[f64; 3]; 3] = [[0.0; 3]; 3];
+    for (i, row) in m.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = (0..3).map(|k| a[i][k] * b[k][j]).sum();
+        }
+    }
+    m
+}
+
+/// Transposes a 3x3 matrix, which inverts a rotation.
+fn transpose(m: &[[f64; 3]; 3]) -> [[f64; 3]; 3] {
+    [[m[0][0], m[1][0], m[2][0]], [m[0][1], m[1][1], m[2][1]], [m[0][2], m[1][2], m[2][2]]]
+}
+
+/// Applies a 3x3 matrix to a vector.
+fn apply(m: &[[f64; 3]; 3], v: [f64; 3]) -> [f64; 3] {
+    [
+        m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
+        m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
+        m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2],
+    ]
+}
+
+/// Rotates a vector about the Z axis by a given angle (frame rotation, R3).
+///
+/// # Arguments
+/// * `v` - The vector to rotate.
+/// * `angle` - The rotation angle in radians.
+///
+/// # Returns
+/// * The vector expressed in the rotated frame.
+fn rotate_z(v: [f64; 3], angle: f64) -> [f64; 3] {
+    let (sin, cos) = angle.sin_cos();
+    [cos * v[0] + sin * v[1], -sin * v[0] + cos * v[1], v[2]]
+}
+
+/// Converts a TEME state vector to the Earth-fixed frame.
+///
+/// Polar motion is neglected, so the result is strictly in the pseudo Earth-fixed frame (PEF),
+/// which differs from ITRF by a few meters.
+///
+/// # Arguments
+/// * `state` - State vector in the TEME frame (km, km/s).
+/// * `gmst` - Greenwich Mean Sidereal Time in radians (see `time::gmst`).
+///
+/// # Returns
+/// * State vector in the Earth-fixed frame (km, km/s).
+pub fn teme_to_ecef(state: &StateVector, gmst: f64) -> StateVector {
+    let position: [f64; 3] = rotate_z(state.position, gmst);
+    let rotated: [f64; 3] = rotate_z(state.velocity, gmst);
+
+    // Remove the transport term ω × r of the rotating frame.
+    let velocity: [f64; 3] = [
+        rotated[0] + EARTH_ROTATION_RATE * position[1],
+        rotated[1] - EARTH_ROTATION_RATE * position[0],
+        rotated[2],
+    ];
+
+    StateVector { position, velocity }
+}
+
+/// Converts an Earth-fixed state vector back to the TEME frame, the inverse of `teme_to_ecef`.
+///
+/// # Arguments
+/// * `state` - State vector in the Earth-fixed frame (km, km/s).
+/// * `gmst` - Greenwich Mean Sidereal Time in radians.
+///
+/// # Returns
+/// * State vector in the TEME frame (km, km/s).
+pub fn ecef_to_teme(state: &StateVector, gmst: f64) -> StateVector {
+    let velocity: [f64; 3] = [
+        state.velocity[0] - EARTH_ROTATION_RATE * state.position[1],
+        state.velocity[1] + EARTH_ROTATION_RATE * state.position[0],
+        state.velocity[2],
+    ];
+
+    StateVector {
+        position: rotate_z(state.position, -gmst),
+        velocity: rotate_z(velocity, -gmst),
+    }
+}
+
+/// Computes the IAU-76 precession matrix, rotating J2000 vectors into the mean-of-date frame.
+///
+/// # Arguments
+/// * `jd_tt` - The Julian date in the Terrestrial Time scale.
+///
+/// # Returns
+/// * The rotation matrix from J2000 to mean of date.
+pub fn precession_matrix(jd_tt: f64) -> [[f64; 3]; 3] {
+    let t: f64 = (jd_tt - JD_J2000) / DAYS_PER_CENTURY;
+    let zeta: f64 = (2306.2181 * t + 0.30188 * t * t + 0.017998 * t.powi(3)) * ARCSEC;
+    let theta: f64 = (2004.3109 * t - 0.42665 * t * t - 0.041833 * t.powi(3)) * ARCSEC;
+    let z: f64 = (2306.2181 * t + 1.09468 * t * t + 0.018203 * t.powi(3)) * ARCSEC;
+
+    multiply(&rotation_z(-z), &multiply(&rotation_y(theta), &rotation_z(-zeta)))
+}
+
+/// Computes the IAU-1980 nutation angles from the largest terms of the series
+/// (accurate to a few milliarcseconds).
+///
+/// # Arguments
+/// * `jd_tt` - The Julian date in the Terrestrial Time scale.
+///
+/// # Returns
+/// * Nutation in longitude Δψ, nutation in obliquity Δε and mean obliquity ε, in radians.
+pub fn nutation(jd_tt: f64) -> (f64, f64, f64) {
+    let t: f64 = (jd_tt - JD_J2000) / DAYS_PER_CENTURY;
+    let t2: f64 = t * t;
+    let t3: f64 = t2 * t;
+
+    // Fundamental arguments (Delaunay variables) in degrees.
+    let arguments: [f64; 5] = [
+        297.85036 + 445267.111480 * t - 0.0019142 * t2 + t3 / 189474.0,
+        357.52772 + 35999.050340 * t - 0.0001603 * t2 - t3 / 300000.0,
+        134.96298 + 477198.867398 * t + 0.0086972 * t2 + t3 / 56250.0,
+        93.27191 + 483202.017538 * t - 0.0036825 * t2 + t3 / 327270.0,
+        125.04452 - 1934.136261 * t + 0.0020708 * t2 + t3 / 450000.0,
+    ];
+
+    let mut dpsi: f64 = 0.0;
+    let mut deps: f64 = 0.0;
+    for (multipliers, psi, psi_rate, eps, eps_rate) in NUTATION_TERMS {
+        let angle: f64 = (0..5).map(|k| multipliers[k] * arguments[k]).sum::<f64>().to_radians();
+        dpsi += (psi + psi_rate * t) * angle.sin();
+        deps += (eps + eps_rate * t) * angle.cos();
+    }
+
+    let mean_obliquity: f64 = 84381.448 - 46.8150 * t - 0.00059 * t2 + 0.001813 * t3;
+    (dpsi * 1e-4 * ARCSEC, deps * 1e-4 * ARCSEC, mean_obliquity * ARCSEC)
+}
+
+/// Computes the rotation matrix from TEME to J2000 (IAU-76/FK5).
+///
+/// The chain is TEME → true of date (equation of the equinoxes) → mean of date (nutation)
+/// → J2000 (precession). The small frame bias between J2000 and GCRF is neglected.
+///
+/// # Arguments
+/// * `jd_tt` - The Julian date in the Terrestrial Time scale.
+///
+/// # Returns
+/// * The rotation matrix from TEME to J2000.
+pub fn teme_to_j2000_matrix(jd_tt: f64) -> [[f64; 3]; 3] {
+    let (dpsi, deps, mean_obliquity) = nutation(jd_tt);
+    let equation_of_equinoxes: f64 = dpsi * mean_obliquity.cos();
+
+    let teme_to_tod: [[f64; 3]; 3] = rotation_z(-equation_of_equinoxes);
+    let mod_to_tod: [[f64; 3]; 3] = multiply(
+        &rotation_x(-(mean_obliquity + deps)),
+        &multiply(&rotation_z(-dpsi), &rotation_x(mean_obliquity)),
+    );
+    let j2000_to_mod: [[f64; 3]; 3] = precession_matrix(jd_tt);
+
+    multiply(&transpose(&j2000_to_mod), &multiply(&transpose(&mod_to_tod), &teme_to_tod))
+}
+
+/// Converts a TEME state vector to the J2000 inertial frame.
+///
+/// # Arguments
+/// * `state` - State vector in the TEME frame (km, km/s).
+/// * `jd_tt` - The Julian date in the Terrestrial Time scale (see `time::TimeScales`).
+///
+/// # Returns
+/// * State vector in the J2000 frame (km, km/s).
+pub fn teme_to_j2000(state: &StateVector, jd_tt: f64) -> StateVector {
+    let m: [[f64; 3]; 3] = teme_to_j2000_matrix(jd_tt);
+    StateVector {
+        position: apply(&m, state.position),
+        velocity: apply(&m, state.velocity),
+    }
+}
+
+/// Converts a J2000 state vector to the TEME frame, the inverse of `teme_to_j2000`.
+///
+/// # Arguments
+/// * `state` - State vector in the J2000 frame (km, km/s).
+/// * `jd_tt` - The Julian date in the Terrestrial Time scale.
+///
+/// # Returns
+/// * State vector in the TEME frame (km, km/s).
+pub fn j2000_to_teme(state: &StateVector, jd_tt: f64) -> StateVector {
+    let m: [[f64; 3]; 3] = transpose(&teme_to_j2000_matrix(jd_tt));
+    StateVector {
+        position: apply(&m, state.position),
+        velocity: apply(&m, state.velocity),
+    }
+}
+
+/// Represents a reference frame into which TEME states can be converted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Frame {
+    /// True Equator Mean Equinox, the frame of SGP4.
+    #[default]
+    Teme,
+    /// Earth-fixed frame (pseudo Earth-fixed, without polar motion).
+    Ecef,
+    /// J2000 inertial frame (IAU-76/FK5).
+    J2000,
+}
+
+impl Frame {
+    /// Returns the name of the frame written in exported files. The Earth-fixed states do not
+    /// include polar motion, so they are labelled PEF rather than ITRF.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Frame::Teme => "TEME",
+            Frame::Ecef => "PEF",
+            Frame::J2000 => "EME2000",
+        }
+    }
+
+    /// Converts a TEME state vector into this frame.
+    ///
+    /// UT1 is approximated by UTC and the built-in leap second table gives TT.
+    ///
+    /// # Arguments
+    /// * `state` - State vector in the TEME frame (km, km/s).
+    /// * `jd` - The Julian date (UTC) of the state.
+    ///
+    /// # Returns
+    /// * State vector in this frame (km, km/s).
+    pub fn from_teme(&self, state: &StateVector, jd: f64) -> StateVector {
+        match self {
+            Frame::Teme => *state,
+            Frame::Ecef => teme_to_ecef(state, gmst(jd)),
+            Frame::J2000 => teme_to_j2000(state, TimeScales::new(0.0).convert(jd, TimeScale::Utc, TimeScale::Tt)),
+        }
+    }
+}
+
+impl StateVector {
+    /// Converts this TEME state vector to the Earth-fixed frame.
+    ///
+    /// # Arguments
+    /// * `gmst` - Greenwich Mean Sidereal Time in radians.
+    ///
+    /// # Returns
+    /// * State vector in the Earth-fixed frame (km, km/s).
+    pub fn to_ecef(&self, gmst: f64) -> StateVector {
+        teme_to_ecef(self, gmst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::f64::consts::FRAC_PI_2;
+
+    /// Tests that a point at rest on the rotating Earth has no Earth-fixed velocity.
+    #[test]
+    fn test_teme_to_ecef() {
+        let gmst: f64 = FRAC_PI_2;
+        let r: f64 = 6378.135;
+        // Point on the equator at the Greenwich meridian, seen from TEME when GMST is 90°.
+        let state = StateVector {
+            position: [0.0, r, 0.0],
+            velocity: [-EARTH_ROTATION_RATE * r, 0.0, 0.0],
+        };
+
+        let ecef = state.to_ecef(gmst);
+
+        assert!((ecef.position[0] - r).abs() < 1e-9);
+        assert!(ecef.position[1].abs() < 1e-9);
+        assert!(ecef.velocity.iter().all(|v| v.abs() < 1e-12));
+    }
+
+    /// Tests that the Earth-fixed conversion round-trips.
+    #[test]
+    fn test_ecef_round_trip() {
+        let state = StateVector {
+            position: [7473.37, 428.95, 5828.75],
+            velocity: [5.107, 6.444, -0.186],
+        };
+
+        let back = ecef_to_teme(&teme_to_ecef(&state, 1.234), 1.234);
+
+        for k in 0..3 {
+            assert!((back.position[k] - state.position[k]).abs() < 1e-9);
+            assert!((back.velocity[k] - state.velocity[k]).abs() < 1e-12);
+        }
+    }
+
+    /// Tests TEME to J2000 against Vallado's example (AIAA 2006-6753, 2004-04-06 07:51:28.386 UTC).
+    #[test]
+    fn test_teme_to_j2000() {
+        let jd_tt: f64 = crate::time::julian_date(2004, 4, 6, 7, 51, 28.386009 + 32.0 + 32.184);
+        let state = StateVector {
+            position: [5094.18016210, 6127.64465950, 6380.34453270],
+            velocity: [-4.746131487, 0.785818041, 5.531931288],
+        };
+
+        let j2000 = teme_to_j2000(&state, jd_tt);
+
+        let expected_position: [f64; 3] = [5102.5096, 6123.01152, 6378.1363];
+        let expected_velocity: [f64; 3] = [-4.7432196, 0.7905366, 5.53375619];
+        for k in 0..3 {
+            assert!((j2000.position[k] - expected_position[k]).abs() < 2e-3);
+            assert!((j2000.velocity[k] - expected_velocity[k]).abs() < 2e-6);
+        }
+
+        let back = j2000_to_teme(&j2000, jd_tt);
+        for k in 0..3 {
+            assert!((back.position[k] - state.position[k]).abs() < 1e-8);
+        }
+    }
+
+    /// Tests the names written for each frame.
+    #[test]
+    fn test_frame_names() {
+        assert_eq!(Frame::default().name(), "TEME");
+        assert_eq!(Frame::Ecef.name(), "PEF");
+        assert_eq!(Frame::J2000.name(), "EME2000");
+    }
+}
@@ -0,0 +1,126 @@
+//! Geodetic coordinates on the WGS84 ellipsoid.
+//! This module converts Earth-fixed positions into latitude, longitude and altitude, which is
+//! the basis of ground tracks and pass predictions.
+
+use crate::StateVector;
+use crate::frames::teme_to_ecef;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// WGS84 equatorial radius in kilometers.
+pub const WGS84_A: f64 = 6378.137;
+/// WGS84 flattening.
+pub const WGS84_F: f64 = 1.0 / 298.257223563;
+/// WGS84 first eccentricity squared.
+const WGS84_E2: f64 = WGS84_F * (2.0 - WGS84_F);
+
+/// Represents a geodetic position on the WGS84 ellipsoid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Geodetic {
+    /// Geodetic latitude in radians, positive north.
+    pub latitude: f64,
+    /// Longitude in radians, positive east, in [-π, π].
+    pub longitude: f64,
+    /// Altitude above the ellipsoid in kilometers.
+    pub altitude: f64,
+}
+
+/// Converts an Earth-fixed position into geodetic coordinates by fixed-point iteration on the latitude.
+///
+/// # Arguments
+/// * `position` - Earth-fixed position in kilometers.
+///
+/// # Returns
+/// * The geodetic latitude, longitude and altitude.
+pub fn ecef_to_geodetic(position: [f64; 3]) -> Geodetic {
+    let [x, y, z] = position;
+    let p: f64 = (x * x + y * y).sqrt();
+    let longitude: f64 = y.atan2(x);
+
+    let mut latitude: f64 = z.atan2(p * (1.0 - WGS84_E2));
+    let mut n: f64 = WGS84_A;
+    for _ in 0..10 {
+        let sin_lat: f64 = latitude.sin();
+        n = WGS84_A / (1.0 - WGS84_E2 * sin_lat * sin_lat).sqrt();
+        let next: f64 = (z + WGS84_E2 * n * sin_lat).atan2(p);
+        let converged: bool = (next - latitude).abs() < 1e-12;
+        latitude = next;
+        if converged {
+            break;
+        }
+    }
+
+    // This form stays well conditioned at the poles, unlike p / cos(latitude) - n.
+    let (sin_lat, cos_lat) = latitude.sin_cos();
+    let altitude: f64 = p * cos_lat + (z + WGS84_E2 * n * sin_lat) * sin_lat - n;
+
+    Geodetic { latitude, longitude, altitude }
+}
+
+/// Converts geodetic coordinates into an Earth-fixed position.
+///
+/// # Arguments
+/// * `geodetic` - The geodetic latitude, longitude and altitude.
+///
+/// # Returns
+/// * Earth-fixed position in kilometers.
+pub fn geodetic_to_ecef(geodetic: &Geodetic) -> [f64; 3] {
+    let (sin_lat, cos_lat) = geodetic.latitude.sin_cos();
+    let (sin_lon, cos_lon) = geodetic.longitude.sin_cos();
+    let n: f64 = WGS84_A / (1.0 - WGS84_E2 * sin_lat * sin_lat).sqrt();
+
+    [
+        (n + geodetic.altitude) * cos_lat * cos_lon,
+        (n + geodetic.altitude) * cos_lat * sin_lon,
+        (n * (1.0 - WGS84_E2) + geodetic.altitude) * sin_lat,
+    ]
+}
+
+impl StateVector {
+    /// Converts the position of this TEME state vector into geodetic coordinates.
+    ///
+    /// # Arguments
+    /// * `gmst` - Greenwich Mean Sidereal Time in radians.
+    ///
+    /// # Returns
+    /// * The geodetic latitude, longitude and altitude of the satellite.
+    pub fn to_geodetic(&self, gmst: f64) -> Geodetic {
+        ecef_to_geodetic(teme_to_ecef(self, gmst).position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests the geodetic conversion against Vallado's example 3-3.
+    #[test]
+    fn test_ecef_to_geodetic() {
+        let geodetic = ecef_to_geodetic([6524.834, 6862.875, 6448.296]);
+
+        assert!((geodetic.latitude.to_degrees() - 34.352496).abs() < 1e-5);
+        assert!((geodetic.longitude.to_degrees() - 46.4464).abs() < 1e-4);
+        assert!((geodetic.altitude - 5085.22).abs() < 1e-2);
+
+        let pole = ecef_to_geodetic([0.0, 0.0, 6356.752314 + 1.0]);
+        assert!((pole.latitude.to_degrees() - 90.0).abs() < 1e-9);
+        assert!((pole.altitude - 1.0).abs() < 1e-5);
+    }
+
+    /// Tests that geodetic and Earth-fixed conversions round-trip.
+    #[test]
+    fn test_geodetic_round_trip() {
+        let geodetic = Geodetic {
+            latitude: (-33.9_f64).to_radians(),
+            longitude: 151.2_f64.to_radians(),
+            altitude: 420.0,
+        };
+
+        let back = ecef_to_geodetic(geodetic_to_ecef(&geodetic));
+
+        assert!((back.latitude - geodetic.latitude).abs() < 1e-12);
+        assert!((back.longitude - geodetic.longitude).abs() < 1e-12);
+        assert!((back.altitude - geodetic.altitude).abs() < 1e-8);
+    }
+}
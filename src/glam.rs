@@ -0,0 +1,108 @@
+//! Conversions to `glam` types for game and visualization engines.
+//! State vectors convert to `DVec3` in the units of the crate, and to single-precision `Vec3` in
+//! the usual rendering convention of engines such as Bevy: right-handed, Y up and meters, with the
+//! Z axis of the inertial frame pointing up and its Y axis towards the viewer.
+
+use crate::StateVector;
+use ::glam::{DVec3, Vec3};
+
+/// Meters per kilometer.
+const METERS_PER_KILOMETER: f64 = 1000.0;
+
+/// Rotates a vector from the Z-up frames of the crate to a Y-up frame, keeping it right-handed.
+///
+/// # Arguments
+/// * `vector` - The vector with Z up.
+///
+/// # Returns
+/// * The same vector with Y up: (x, y, z) becomes (x, z, -y).
+pub fn z_up_to_y_up(vector: [f64; 3]) -> DVec3 {
+    DVec3::new(vector[0], vector[2], -vector[1])
+}
+
+impl From<StateVector> for (DVec3, DVec3) {
+    fn from(state: StateVector) -> (DVec3, DVec3) {
+        (state.position_dvec3(), state.velocity_dvec3())
+    }
+}
+
+impl From<(DVec3, DVec3)> for StateVector {
+    fn from((position, velocity): (DVec3, DVec3)) -> StateVector {
+        StateVector {
+            position: position.to_array(),
+            velocity: velocity.to_array(),
+        }
+    }
+}
+
+impl StateVector {
+    /// Returns the position in kilometers as a double-precision vector.
+    pub fn position_dvec3(&self) -> DVec3 {
+        DVec3::from_array(self.position)
+    }
+
+    /// Returns the velocity in km/s as a double-precision vector.
+    pub fn velocity_dvec3(&self) -> DVec3 {
+        DVec3::from_array(self.velocity)
+    }
+
+    /// Returns the position for rendering, Y up.
+    ///
+    /// # Arguments
+    /// * `scale` - Rendering units per meter, 1 for meters. Smaller scales keep the precision of
+    ///   `f32` around the Earth.
+    ///
+    /// # Returns
+    /// * The scaled position with Y up.
+    pub fn render_position(&self, scale: f64) -> Vec3 {
+        (z_up_to_y_up(self.position) * METERS_PER_KILOMETER * scale).as_vec3()
+    }
+
+    /// Returns the velocity for rendering, Y up.
+    ///
+    /// # Arguments
+    /// * `scale` - Rendering units per meter, 1 for meters per second.
+    ///
+    /// # Returns
+    /// * The scaled velocity per second with Y up.
+    pub fn render_velocity(&self, scale: f64) -> Vec3 {
+        (z_up_to_y_up(self.velocity) * METERS_PER_KILOMETER * scale).as_vec3()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests the round trip through double-precision vectors.
+    #[test]
+    fn test_dvec3() {
+        let state = StateVector {
+            position: [6524.834, 6862.875, 6448.296],
+            velocity: [4.901327, 5.533756, -1.976341],
+        };
+        let (position, velocity): (DVec3, DVec3) = state.into();
+        assert_eq!(position.y, 6862.875);
+        assert_eq!(velocity.z, -1.976341);
+        let back: StateVector = (position, velocity).into();
+        assert_eq!(back.position, state.position);
+        assert_eq!(back.velocity, state.velocity);
+    }
+
+    /// Tests the orientation and scale of the rendering vectors.
+    #[test]
+    fn test_render() {
+        let state = StateVector {
+            position: [7000.0, 0.0, 0.0],
+            velocity: [0.0, 5.0, 5.0],
+        };
+        assert_eq!(state.render_position(1.0), Vec3::new(7.0e6, 0.0, 0.0));
+        assert_eq!(state.render_position(1e-6), Vec3::new(7.0, 0.0, 0.0));
+        // North is up, and the Y axis of the inertial frame points to -Z.
+        assert_eq!(state.render_velocity(1.0), Vec3::new(0.0, 5000.0, -5000.0));
+
+        // The rotation keeps the frame right-handed.
+        let (x, y, z) = (z_up_to_y_up([1.0, 0.0, 0.0]), z_up_to_y_up([0.0, 1.0, 0.0]), z_up_to_y_up([0.0, 0.0, 1.0]));
+        assert_eq!(x.cross(y), z);
+    }
+}
@@ -0,0 +1,135 @@
+//! Gravity model constants of SGP4 and of the two-body and numerical propagators.
+//! The reference implementations differ by the Earth constant set they use; selecting the same set
+//! is needed to reproduce their results to the millimeter, e.g. Vallado's verification cases, which
+//! use WGS-72. Custom sets describe other central bodies, such as the Moon or Mars.
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Selects the set of Earth constants used by the SGP4 initialization and propagation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GravityModel {
+    /// WGS-72 with the rounded XKE of the original SPACETRACK REPORT #3 code.
+    #[default]
+    Wgs72Old,
+    /// WGS-72, the constants of the element sets distributed by Space-Track.
+    Wgs72,
+    /// WGS-84.
+    Wgs84,
+}
+
+/// Holds the constants of the gravity field of a central body.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GravityConstants {
+    /// Gravitational parameter in km³/s².
+    pub mu: f64,
+    /// Equatorial radius in kilometers.
+    pub radius: f64,
+    /// sqrt(μ) in body radii^1.5 per minute.
+    pub xke: f64,
+    /// Second zonal harmonic.
+    pub j2: f64,
+    /// Third zonal harmonic.
+    pub j3: f64,
+    /// Fourth zonal harmonic.
+    pub j4: f64,
+    /// Length of the day of the body in minutes, the time unit of mean motions in revolutions per day.
+    pub minutes_per_day: f64,
+}
+
+impl GravityModel {
+    /// Returns the constants of the model.
+    pub fn constants(&self) -> GravityConstants {
+        match self {
+            // μ is derived from the rounded XKE, as in the rest of the crate.
+            GravityModel::Wgs72Old => GravityConstants {
+                mu: 0.0743669161 * 0.0743669161 * 6378.135 * 6378.135 * 6378.135 / 3600.0,
+                radius: 6378.135,
+                xke: 0.0743669161,
+                j2: 0.001082616,
+                j3: -0.00000253881,
+                j4: -0.00000165597,
+                minutes_per_day: 1440.0,
+            },
+            GravityModel::Wgs72 => GravityConstants::new(398600.8, 6378.135, 0.001082616, -0.00000253881, -0.00000165597, 1440.0),
+            GravityModel::Wgs84 => GravityConstants::new(398600.5, 6378.137, 0.00108262998905, -0.00000253215306, -0.00000161098761, 1440.0),
+        }
+    }
+}
+
+impl GravityConstants {
+    /// Builds a set of constants, deriving XKE from the gravitational parameter and the radius.
+    ///
+    /// # Arguments
+    /// * `mu` - Gravitational parameter in km³/s².
+    /// * `radius` - Equatorial radius in kilometers.
+    /// * `j2` - Second zonal harmonic.
+    /// * `j3` - Third zonal harmonic.
+    /// * `j4` - Fourth zonal harmonic.
+    /// * `minutes_per_day` - Length of the day of the body in minutes.
+    ///
+    /// # Returns
+    /// * The constants.
+    pub fn new(mu: f64, radius: f64, j2: f64, j3: f64, j4: f64, minutes_per_day: f64) -> GravityConstants {
+        GravityConstants {
+            mu,
+            radius,
+            xke: 60.0 / (radius * radius * radius / mu).sqrt(),
+            j2,
+            j3,
+            j4,
+            minutes_per_day,
+        }
+    }
+
+    /// Converts a mean motion in revolutions per day of the body to radians per minute.
+    ///
+    /// # Arguments
+    /// * `revolutions_per_day` - Mean motion in revolutions per day.
+    ///
+    /// # Returns
+    /// * Mean motion in radians per minute.
+    pub fn mean_motion(&self, revolutions_per_day: f64) -> f64 {
+        revolutions_per_day * crate::TWOPI / self.minutes_per_day
+    }
+
+    /// Returns the ratio J3 / J2.
+    pub fn j3oj2(&self) -> f64 {
+        self.j3 / self.j2
+    }
+
+    /// Returns the conversion factor from earth radii per minute to kilometers per second.
+    pub fn vkmpersec(&self) -> f64 {
+        self.radius * self.xke / 60.0
+    }
+}
+
+impl Default for GravityConstants {
+    fn default() -> GravityConstants {
+        GravityModel::default().constants()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests that the default model matches the historical constants of the crate.
+    #[test]
+    fn test_default_constants() {
+        let constants: GravityConstants = GravityConstants::default();
+        assert_eq!(constants.xke, crate::XKE);
+        assert_eq!(constants.j2, 2.0 * crate::CK2);
+        assert_eq!(constants.radius, crate::XKMPER);
+        assert_eq!(constants.mu, crate::keplerian::EARTH_MU);
+    }
+
+    /// Tests the XKE derived from μ and the radius against the published values.
+    #[test]
+    fn test_derived_xke() {
+        assert!((GravityModel::Wgs72.constants().xke - 0.07436691613317).abs() < 1e-13);
+        assert!((GravityModel::Wgs84.constants().xke - 0.07436685316871).abs() < 1e-13);
+    }
+}
@@ -0,0 +1,129 @@
+//! Ground track and sub-satellite point computation.
+//! The sub-satellite point is obtained by propagating the satellite, rotating the TEME state into
+//! the Earth-fixed frame with the sidereal time of each instant, and converting it to geodetic
+//! coordinates.
+
+use crate::geodetic::Geodetic;
+use crate::propagator::PropagationError;
+use crate::{OrbitalElements, Sgp4, StateVector, time};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Computes the geodetic point directly under the satellite, with the satellite altitude.
+///
+/// # Arguments
+/// * `elements` - Orbital elements of the satellite.
+/// * `tsince` - Time since epoch in minutes.
+///
+/// # Returns
+/// * The latitude and longitude of the sub-satellite point and the altitude of the satellite, or
+///   the propagation error.
+pub fn subpoint(elements: &OrbitalElements, tsince: f64) -> Result<Geodetic, PropagationError> {
+    let state: StateVector = Sgp4::new(elements).propagate(tsince)?;
+    let gmst: f64 = time::gmst(elements.jd_at(tsince));
+    Ok(state.to_geodetic(gmst))
+}
+
+impl OrbitalElements {
+    /// Computes the sub-satellite point at a time since epoch (see `subpoint`).
+    ///
+    /// # Arguments
+    /// * `tsince` - Time since epoch in minutes.
+    ///
+    /// # Returns
+    /// * The latitude and longitude of the sub-satellite point and the altitude of the satellite, or
+    ///   the propagation error.
+    pub fn subpoint(&self, tsince: f64) -> Result<Geodetic, PropagationError> {
+        subpoint(self, tsince)
+    }
+
+    /// Computes the sub-satellite point at an absolute instant.
+    ///
+    /// # Arguments
+    /// * `when` - The instant, in UTC.
+    ///
+    /// # Returns
+    /// * The latitude and longitude of the sub-satellite point and the altitude of the satellite, or
+    ///   the propagation error.
+    #[cfg(feature = "chrono")]
+    pub fn subpoint_at(&self, when: chrono::DateTime<chrono::Utc>) -> Result<Geodetic, PropagationError> {
+        subpoint(self, self.minutes_since_epoch(when))
+    }
+}
+
+/// Computes the sub-satellite points over a time window.
+///
+/// # Arguments
+/// * `elements` - Orbital elements of the satellite.
+/// * `start` - Start of the window, in minutes since epoch.
+/// * `end` - End of the window, in minutes since epoch (included if it falls on a step).
+/// * `step` - Time between points in minutes, strictly positive.
+///
+/// # Returns
+/// * Pairs of time since epoch (minutes) and geodetic point under the satellite, or the first
+///   propagation error.
+pub fn ground_track(elements: &OrbitalElements, start: f64, end: f64, step: f64) -> Result<Vec<(f64, Geodetic)>, PropagationError> {
+    if step <= 0.0 || end < start {
+        return Ok(Vec::new());
+    }
+
+    // Computing each time from its index avoids accumulating rounding errors over long windows.
+    let count: usize = ((end - start) / step + 1e-9).floor() as usize + 1;
+    let propagator: Sgp4 = Sgp4::new(elements);
+    (0..count)
+        .map(|i| {
+            let tsince: f64 = start + i as f64 * step;
+            let state: StateVector = propagator.propagate(tsince)?;
+            Ok((tsince, state.to_geodetic(time::gmst(elements.jd_at(tsince)))))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::iss_elements;
+
+    /// Tests the ground track of the ISS over one orbit.
+    #[test]
+    fn test_ground_track() {
+        let elements = iss_elements();
+        let period: f64 = 1440.0 / 15.72125391;
+
+        let track = ground_track(&elements, 0.0, period, period / 100.0).unwrap();
+
+        assert_eq!(track.len(), 101);
+        assert!((track[100].0 - period).abs() < 1e-9);
+        for (_, point) in &track {
+            assert!(point.latitude.to_degrees().abs() < 52.0);
+            assert!(point.altitude > 300.0 && point.altitude < 450.0);
+        }
+
+        // The Earth turns east under the orbit, so the track shifts west by about 23° per revolution.
+        let shift: f64 = (track[100].1.longitude - track[0].1.longitude).to_degrees();
+        assert!((shift + 22.9).abs() < 1.0);
+
+        assert!(ground_track(&elements, 10.0, 0.0, 1.0).unwrap().is_empty());
+
+        let mut decayed = elements;
+        decayed.eccentricity = 0.065;
+        assert!(matches!(ground_track(&decayed, 0.0, period, period / 100.0), Err(PropagationError::Decayed { .. })));
+    }
+
+    /// Tests that the sub-satellite point matches the chained frame conversions.
+    #[test]
+    fn test_subpoint() {
+        let elements = iss_elements();
+
+        let point = elements.subpoint(42.0).unwrap();
+
+        let state = crate::sgp4(42.0, &elements);
+        let expected = crate::geodetic::ecef_to_geodetic(state.to_ecef(time::gmst(elements.jd_at(42.0))).position);
+        assert_eq!(point, expected);
+        assert_eq!(subpoint(&elements, 42.0).unwrap(), point);
+
+        let mut decayed = elements;
+        decayed.eccentricity = 0.065;
+        assert!(matches!(decayed.subpoint(0.0), Err(PropagationError::Decayed { .. })));
+    }
+}
@@ -0,0 +1,150 @@
+//! Spherical-harmonic gravity field of the Earth.
+//! The acceleration is computed in the Earth-fixed frame with the recursions of Montenbruck and
+//! Gill, "Satellite Orbits" (2000), section 3.2.5, from the EGM96 coefficients up to degree and
+//! order 4.
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Gravitational parameter of the EGM96 model in km³/s².
+const EGM96_MU: f64 = 398600.4415;
+/// Reference radius of the EGM96 model in kilometers.
+const EGM96_RADIUS: f64 = 6378.1363;
+/// Highest degree and order of the tabulated coefficients.
+pub(crate) const MAX_DEGREE: usize = 4;
+
+/// Fully normalized EGM96 coefficients (degree, order, C, S).
+#[rustfmt::skip]
+const EGM96_COEFFICIENTS: [(usize, usize, f64, f64); 12] = [
+    (2, 0, -0.484165371736e-3, 0.0),
+    (2, 1, -0.186987635955e-9, 0.119528012031e-8),
+    (2, 2, 0.243914352398e-5, -0.140016683654e-5),
+    (3, 0, 0.957254173792e-6, 0.0),
+    (3, 1, 0.202998882184e-5, 0.248513158716e-6),
+    (3, 2, 0.904627768605e-6, -0.619025944205e-6),
+    (3, 3, 0.721072657057e-6, 0.141435626958e-5),
+    (4, 0, 0.539873863789e-6, 0.0),
+    (4, 1, -0.536321616971e-6, -0.473440265853e-6),
+    (4, 2, 0.350694105785e-6, 0.662671572540e-6),
+    (4, 3, 0.990771803829e-6, -0.200928369177e-6),
+    (4, 4, -0.188560802735e-6, 0.308853169333e-6),
+];
+
+/// Returns the unnormalized coefficients C and S of a degree and order.
+fn coefficients(n: usize, m: usize) -> (f64, f64) {
+    if n == 0 {
+        return (1.0, 0.0);
+    }
+    let Some(&(_, _, c, s)) = EGM96_COEFFICIENTS.iter().find(|&&(degree, order, _, _)| degree == n && order == m) else {
+        return (0.0, 0.0);
+    };
+    // Normalization factor sqrt((2 - δ0m) (2n + 1) (n - m)! / (n + m)!).
+    let ratio: f64 = ((n - m + 1)..=(n + m)).fold(1.0, |product, k| product / k as f64);
+    let factor: f64 = ((if m == 0 { 1.0 } else { 2.0 }) * (2 * n + 1) as f64 * ratio).sqrt();
+    (c * factor, s * factor)
+}
+
+/// Computes the gravitational acceleration of the truncated field.
+///
+/// # Arguments
+/// * `position` - Earth-fixed position in kilometers.
+/// * `degree` - Highest degree of the field, at most `MAX_DEGREE`.
+/// * `order` - Highest order of the field, at most the degree.
+///
+/// # Returns
+/// * Earth-fixed acceleration in km/s², including the central term.
+pub(crate) fn acceleration(position: [f64; 3], degree: usize, order: usize) -> [f64; 3] {
+    let degree: usize = degree.min(MAX_DEGREE);
+    let order: usize = order.min(degree);
+    let size: usize = degree + 2;
+
+    let r2: f64 = position.iter().map(|x| x * x).sum();
+    let rho: f64 = EGM96_RADIUS * EGM96_RADIUS / r2;
+    let [x0, y0, z0] = position.map(|x| EGM96_RADIUS * x / r2);
+
+    // Harmonic functions V and W up to degree + 1, indexed [n][m].
+    let mut v: Vec<Vec<f64>> = vec![vec![0.0; size + 1]; size + 1];
+    let mut w: Vec<Vec<f64>> = vec![vec![0.0; size + 1]; size + 1];
+    v[0][0] = EGM96_RADIUS / r2.sqrt();
+    for m in 0..size {
+        if m > 0 {
+            let k: f64 = (2 * m - 1) as f64;
+            v[m][m] = k * (x0 * v[m - 1][m - 1] - y0 * w[m - 1][m - 1]);
+            w[m][m] = k * (x0 * w[m - 1][m - 1] + y0 * v[m - 1][m - 1]);
+        }
+        if m + 1 < size {
+            let k: f64 = (2 * m + 1) as f64;
+            v[m + 1][m] = k * z0 * v[m][m];
+            w[m + 1][m] = k * z0 * w[m][m];
+        }
+        for n in (m + 2)..size {
+            let a: f64 = (2 * n - 1) as f64;
+            let b: f64 = (n + m - 1) as f64;
+            let c: f64 = (n - m) as f64;
+            v[n][m] = (a * z0 * v[n - 1][m] - b * rho * v[n - 2][m]) / c;
+            w[n][m] = (a * z0 * w[n - 1][m] - b * rho * w[n - 2][m]) / c;
+        }
+    }
+
+    let mut acceleration: [f64; 3] = [0.0; 3];
+    for n in 0..=degree {
+        for m in 0..=order.min(n) {
+            let (c, s) = coefficients(n, m);
+            if m == 0 {
+                acceleration[0] -= c * v[n + 1][1];
+                acceleration[1] -= c * w[n + 1][1];
+            } else {
+                let factor: f64 = 0.5 * ((n - m + 1) * (n - m + 2)) as f64;
+                acceleration[0] += 0.5 * (-c * v[n + 1][m + 1] - s * w[n + 1][m + 1])
+                    + factor * (c * v[n + 1][m - 1] + s * w[n + 1][m - 1]);
+                acceleration[1] += 0.5 * (-c * w[n + 1][m + 1] + s * v[n + 1][m + 1])
+                    + factor * (-c * w[n + 1][m - 1] + s * v[n + 1][m - 1]);
+            }
+            acceleration[2] += (n - m + 1) as f64 * (-c * v[n + 1][m] - s * w[n + 1][m]);
+        }
+    }
+    acceleration.map(|a| a * EGM96_MU / (EGM96_RADIUS * EGM96_RADIUS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests that the degree 2 zonal field matches the closed-form J2 acceleration.
+    #[test]
+    fn test_zonal_acceleration() {
+        let position: [f64; 3] = [4000.0, -3000.0, 5000.0];
+        let r2: f64 = position.iter().map(|x| x * x).sum();
+        let r: f64 = r2.sqrt();
+        let (c20, _) = coefficients(2, 0);
+        let j2: f64 = -c20;
+        let mu_r3: f64 = EGM96_MU / (r2 * r);
+        let factor: f64 = 1.5 * j2 * mu_r3 * EGM96_RADIUS * EGM96_RADIUS / r2;
+        let z2_r2: f64 = 5.0 * position[2] * position[2] / r2;
+        let expected: [f64; 3] = [
+            -mu_r3 * position[0] + factor * position[0] * (z2_r2 - 1.0),
+            -mu_r3 * position[1] + factor * position[1] * (z2_r2 - 1.0),
+            -mu_r3 * position[2] + factor * position[2] * (z2_r2 - 3.0),
+        ];
+
+        assert!((j2 - 1.0826267e-3).abs() < 1e-9);
+        let computed: [f64; 3] = acceleration(position, 2, 0);
+        for k in 0..3 {
+            assert!((computed[k] - expected[k]).abs() < 1e-15, "{:?} {:?}", computed, expected);
+        }
+    }
+
+    /// Tests that the tesseral terms are a small correction at GEO and that the degree is capped.
+    #[test]
+    fn test_tesseral_acceleration() {
+        let position: [f64; 3] = [42164.0, 0.0, 0.0];
+        let zonal: [f64; 3] = acceleration(position, 4, 0);
+        let full: [f64; 3] = acceleration(position, 4, 4);
+        let difference: f64 = (0..3).map(|k| (full[k] - zonal[k]).powi(2)).sum::<f64>().sqrt();
+        let central: f64 = EGM96_MU / (42164.0 * 42164.0);
+
+        // The C22/S22 terms dominate: about 9 C22 (R/r)², i.e. 3e-7 of the central attraction at GEO.
+        assert!(difference / central > 2e-7 && difference / central < 5e-7, "{}", difference / central);
+        assert_eq!(acceleration(position, 9, 9), full);
+    }
+}
@@ -0,0 +1,237 @@
+//! History of the element sets of one object.
+//! Long arcs are reconstructed piecewise: each date is propagated from the element set whose epoch
+//! is nearest (or nearest before it), which keeps the SGP4 prediction errors small since they grow
+//! with the age of the set.
+
+use crate::maneuver_detection::{ManeuverDetection, ManeuverEvent};
+use crate::propagator::PropagationError;
+use crate::{OrbitalElements, Sgp4, StateVector};
+use core::fmt;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Selects the element set used at a date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EpochSelection {
+    /// The set with the nearest epoch, before or after the date.
+    #[default]
+    Nearest,
+    /// The latest set with an epoch at or before the date, as available in real time.
+    Preceding,
+}
+
+/// Represents the reasons why a history cannot provide a state.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HistoryError {
+    /// No element set qualifies: the history is empty, or all sets are after the date.
+    NoElementSet,
+    /// The selected element set cannot be propagated to the date.
+    Propagation(PropagationError),
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistoryError::NoElementSet => write!(f, "No element set for the requested date"),
+            HistoryError::Propagation(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl core::error::Error for HistoryError {}
+
+/// Represents a state propagated from a history.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HistoryState {
+    /// State vector in the TEME frame (km, km/s).
+    pub state: StateVector,
+    /// Index of the element set used.
+    pub index: usize,
+    /// Age of the element set at the date in days, negative if its epoch is after the date.
+    pub age: f64,
+}
+
+/// Holds the element sets of one object in chronological order.
+pub struct TleHistory {
+    /// Element sets sorted by epoch.
+    elements: Vec<OrbitalElements>,
+    /// Propagators of the element sets, in the same order.
+    propagators: Vec<Sgp4>,
+    /// Selection of the element set at a date.
+    pub selection: EpochSelection,
+}
+
+impl TleHistory {
+    /// Builds a history from element sets in any order.
+    ///
+    /// # Arguments
+    /// * `elements` - Element sets of a single object. Of sets with the same epoch, the last one
+    ///   is kept, as a correction of the earlier ones.
+    ///
+    /// # Returns
+    /// * The history, selecting the nearest set.
+    pub fn new(elements: Vec<OrbitalElements>) -> TleHistory {
+        let mut history = TleHistory {
+            elements: Vec::new(),
+            propagators: Vec::new(),
+            selection: EpochSelection::default(),
+        };
+        for set in elements {
+            history.push(set);
+        }
+        history
+    }
+
+    /// Adds an element set, keeping the chronological order.
+    ///
+    /// # Arguments
+    /// * `elements` - The element set. It replaces a set with the same epoch.
+    pub fn push(&mut self, elements: OrbitalElements) {
+        let epoch: f64 = elements.epoch_jd();
+        let index: usize = self.elements.partition_point(|set| set.epoch_jd() < epoch);
+        let propagator: Sgp4 = Sgp4::new(&elements);
+        if self.elements.get(index).is_some_and(|set| set.epoch_jd() == epoch) {
+            self.elements[index] = elements;
+            self.propagators[index] = propagator;
+        } else {
+            self.elements.insert(index, elements);
+            self.propagators.insert(index, propagator);
+        }
+    }
+
+    /// Returns the number of element sets.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Returns whether the history holds no element set.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Returns the element sets in chronological order.
+    pub fn elements(&self) -> &[OrbitalElements] {
+        &self.elements
+    }
+
+    /// Selects the element set used at a date.
+    ///
+    /// # Arguments
+    /// * `jd` - The date as a Julian date (UTC).
+    ///
+    /// # Returns
+    /// * The index of the element set, or `None` if no set qualifies.
+    pub fn select(&self, jd: f64) -> Option<usize> {
+        let after: usize = self.elements.partition_point(|set| set.epoch_jd() <= jd);
+        match self.selection {
+            EpochSelection::Preceding => after.checked_sub(1),
+            EpochSelection::Nearest => {
+                let candidates = [after.checked_sub(1), (after < self.elements.len()).then_some(after)];
+                candidates.into_iter().flatten().min_by(|&a, &b| (self.elements[a].epoch_jd() - jd).abs().total_cmp(&(self.elements[b].epoch_jd() - jd).abs()))
+            }
+        }
+    }
+
+    /// Returns the age of the element set used at a date.
+    ///
+    /// # Arguments
+    /// * `jd` - The date as a Julian date (UTC).
+    ///
+    /// # Returns
+    /// * The age in days, negative if the epoch of the set is after the date, or `None` if no set
+    ///   qualifies.
+    pub fn age(&self, jd: f64) -> Option<f64> {
+        self.select(jd).map(|index| jd - self.elements[index].epoch_jd())
+    }
+
+    /// Propagates the element set selected at a date.
+    ///
+    /// # Arguments
+    /// * `jd` - The date as a Julian date (UTC).
+    ///
+    /// # Returns
+    /// * The state, with the set used and its age.
+    pub fn propagate(&self, jd: f64) -> Result<HistoryState, HistoryError> {
+        let index: usize = self.select(jd).ok_or(HistoryError::NoElementSet)?;
+        let age: f64 = jd - self.elements[index].epoch_jd();
+        let state: StateVector = self.propagators[index].propagate(age * 1440.0).map_err(HistoryError::Propagation)?;
+        Ok(HistoryState { state, index, age })
+    }
+
+    /// Finds the likely maneuvers between consecutive element sets.
+    ///
+    /// # Arguments
+    /// * `detection` - Thresholds of the detection.
+    ///
+    /// # Returns
+    /// * The maneuvers in chronological order.
+    pub fn maneuvers(&self, detection: &ManeuverDetection) -> Vec<ManeuverEvent> {
+        detection.detect(&self.elements)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maneuver::{Impulse, ImpulseFrame, apply_to_elements};
+    use crate::test_support::iss_elements;
+
+    /// Returns three element sets of the ISS half a day apart, in reverse order.
+    fn sets() -> Vec<OrbitalElements> {
+        let first: OrbitalElements = iss_elements();
+        let coast = Impulse {
+            delta_v: [0.0; 3],
+            frame: ImpulseFrame::Ric,
+        };
+        let second: OrbitalElements = apply_to_elements(&first, 720.0, &coast).unwrap();
+        let third: OrbitalElements = apply_to_elements(&second, 720.0, &coast).unwrap();
+        vec![third, first, second]
+    }
+
+    /// Tests the selection of the nearest and preceding element sets.
+    #[test]
+    fn test_history_selection() {
+        let mut history: TleHistory = TleHistory::new(sets());
+        assert_eq!(history.len(), 3);
+        let epochs: Vec<f64> = history.elements().iter().map(|set| set.epoch_jd()).collect();
+        assert!(epochs.windows(2).all(|pair| pair[0] < pair[1]));
+
+        assert_eq!(history.select(epochs[0] - 1.0), Some(0));
+        assert_eq!(history.select(epochs[0] + 0.2), Some(0));
+        assert_eq!(history.select(epochs[0] + 0.3), Some(1));
+        assert_eq!(history.select(epochs[2] + 5.0), Some(2));
+        assert!((history.age(epochs[0] + 0.3).unwrap() + 0.2).abs() < 1e-6);
+
+        history.selection = EpochSelection::Preceding;
+        assert_eq!(history.select(epochs[0] - 1.0), None);
+        assert_eq!(history.select(epochs[0] + 0.3), Some(0));
+        assert_eq!(history.select(epochs[1]), Some(1));
+        assert!(matches!(history.propagate(epochs[0] - 1.0), Err(HistoryError::NoElementSet)));
+        assert!(TleHistory::new(Vec::new()).propagate(epochs[0]).is_err());
+    }
+
+    /// Tests the propagation from the selected sets and the replacement of a set.
+    #[test]
+    fn test_history_propagation() {
+        let mut history: TleHistory = TleHistory::new(sets());
+        let epoch: f64 = history.elements()[1].epoch_jd();
+        let result: HistoryState = history.propagate(epoch + 0.1).unwrap();
+        assert_eq!(result.index, 1);
+        let expected: StateVector = Sgp4::new(&history.elements()[1]).propagate(0.1 * 1440.0).unwrap();
+        // The date is only resolved to about 10 µs.
+        for k in 0..3 {
+            assert!((result.state.position[k] - expected.position[k]).abs() < 1e-3);
+        }
+        assert!(history.maneuvers(&ManeuverDetection::default()).is_empty());
+
+        // A set with the same epoch replaces the previous one.
+        let mut replacement: OrbitalElements = sets().remove(2);
+        replacement.inclination += 0.01;
+        history.push(replacement);
+        assert_eq!(history.len(), 3);
+        assert!(history.elements()[1].inclination > history.elements()[0].inclination + 0.005);
+        assert_eq!(history.maneuvers(&ManeuverDetection::default()).len(), 2);
+    }
+}
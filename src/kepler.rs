@@ -0,0 +1,232 @@
+//! Configurable solution of Kepler's equation M = E - e sin(E).
+//! The solver iterates from a chosen starting guess with Newton's or Halley's method, falls back to
+//! bisection when a step leaves the interval known to hold the root, and stops with an error
+//! instead of looping when the iteration cap is reached.
+
+use core::f64::consts::PI;
+use core::fmt;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Errors that can occur while solving Kepler's equation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeplerError {
+    /// The eccentricity is negative or not below 1 (parabolic or hyperbolic orbit).
+    Eccentricity(f64),
+    /// The iteration did not reach the tolerance.
+    NotConverged {
+        /// Number of iterations performed.
+        iterations: u32,
+        /// Residual of Kepler's equation at the last iterate, in radians.
+        residual: f64,
+    },
+}
+
+impl fmt::Display for KeplerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeplerError::Eccentricity(e) => write!(f, "eccentricity {} is not elliptical (0 <= e < 1)", e),
+            KeplerError::NotConverged { iterations, residual } => {
+                write!(f, "Kepler's equation did not converge after {} iterations (residual {})", iterations, residual)
+            }
+        }
+    }
+}
+
+impl core::error::Error for KeplerError {}
+
+/// Selects the iteration scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KeplerMethod {
+    /// Newton-Raphson, quadratic convergence.
+    #[default]
+    Newton,
+    /// Halley, cubic convergence for one more evaluation of the second derivative.
+    Halley,
+}
+
+/// Selects the starting value of the eccentric anomaly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InitialGuess {
+    /// E₀ = M.
+    MeanAnomaly,
+    /// Danby's E₀ = M + 0.85 e sign(sin M).
+    Danby,
+    /// Markley's cubic approximation, accurate to about 1e-3 rad over all eccentricities.
+    #[default]
+    Markley,
+}
+
+/// Holds the settings of the solution of Kepler's equation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeplerSolver {
+    /// Iteration scheme.
+    pub method: KeplerMethod,
+    /// Starting value of the eccentric anomaly.
+    pub initial_guess: InitialGuess,
+    /// Largest correction of the last iteration, in radians.
+    pub tolerance: f64,
+    /// Number of iterations after which the solver gives up.
+    pub max_iterations: u32,
+}
+
+impl Default for KeplerSolver {
+    fn default() -> KeplerSolver {
+        KeplerSolver {
+            method: KeplerMethod::Newton,
+            initial_guess: InitialGuess::Markley,
+            tolerance: 1e-12,
+            max_iterations: 50,
+        }
+    }
+}
+
+impl KeplerSolver {
+    /// Solves Kepler's equation.
+    ///
+    /// # Arguments
+    /// * `mean_anomaly` - Mean anomaly in radians.
+    /// * `eccentricity` - Eccentricity of the orbit.
+    ///
+    /// # Returns
+    /// * Eccentric anomaly in radians, in the same revolution as the mean anomaly, or an error if
+    ///   the orbit is not elliptical or the iteration did not converge.
+    pub fn solve(&self, mean_anomaly: f64, eccentricity: f64) -> Result<f64, KeplerError> {
+        if !(0.0..1.0).contains(&eccentricity) {
+            return Err(KeplerError::Eccentricity(eccentricity));
+        }
+        if !mean_anomaly.is_finite() {
+            return Err(KeplerError::NotConverged { iterations: 0, residual: mean_anomaly });
+        }
+        // Work in [-π, π], where the starting guesses are valid, and restore the revolution at the end.
+        let revolutions: f64 = (mean_anomaly / (2.0 * PI)).round() * 2.0 * PI;
+        let m: f64 = mean_anomaly - revolutions;
+
+        // E - M = e sin(E) has the sign of M and is at most e: the root lies between M and M ± e.
+        let (mut low, mut high) = if m < 0.0 { ((m - eccentricity).max(-PI), m) } else { (m, (m + eccentricity).min(PI)) };
+        let mut e_anomaly: f64 = self.initial_guess(m, eccentricity).clamp(low, high);
+
+        for _ in 0..self.max_iterations {
+            let (sin_e, cos_e) = e_anomaly.sin_cos();
+            let f: f64 = e_anomaly - eccentricity * sin_e - m;
+            if f > 0.0 {
+                high = e_anomaly;
+            } else {
+                low = e_anomaly;
+            }
+            let f_prime: f64 = 1.0 - eccentricity * cos_e;
+            let mut next: f64 = e_anomaly
+                - match self.method {
+                    KeplerMethod::Newton => f / f_prime,
+                    KeplerMethod::Halley => f * f_prime / (f_prime * f_prime - 0.5 * f * eccentricity * sin_e),
+                };
+            // Near e = 1 the derivative vanishes at perigee and the steps overshoot: bisect instead.
+            if !(low..=high).contains(&next) {
+                next = 0.5 * (low + high);
+            }
+            let delta: f64 = next - e_anomaly;
+            e_anomaly = next;
+            if delta.abs() <= self.tolerance {
+                return Ok(e_anomaly + revolutions);
+            }
+        }
+
+        let residual: f64 = (e_anomaly - eccentricity * e_anomaly.sin() - m).abs();
+        Err(KeplerError::NotConverged {
+            iterations: self.max_iterations,
+            residual,
+        })
+    }
+
+    /// Computes the starting value of the iteration.
+    ///
+    /// # Arguments
+    /// * `m` - Mean anomaly in radians, in [-π, π].
+    /// * `e` - Eccentricity of the orbit.
+    ///
+    /// # Returns
+    /// * Starting eccentric anomaly in radians.
+    fn initial_guess(&self, m: f64, e: f64) -> f64 {
+        match self.initial_guess {
+            InitialGuess::MeanAnomaly => m,
+            InitialGuess::Danby => m + 0.85 * e * if m < 0.0 { -1.0 } else { 1.0 },
+            InitialGuess::Markley => {
+                // F. L. Markley, "Kepler equation solver", Celestial Mechanics 63 (1995).
+                let alpha: f64 = (3.0 * PI * PI + 1.6 * PI * (PI - m.abs()) / (1.0 + e)) / (PI * PI - 6.0);
+                let d: f64 = 3.0 * (1.0 - e) + alpha * e;
+                let q: f64 = 2.0 * alpha * d * (1.0 - e) - m * m;
+                let r: f64 = 3.0 * alpha * d * (d - 1.0 + e) * m + m * m * m;
+                let w: f64 = (r.abs() + (q * q * q + r * r).sqrt()).powf(2.0 / 3.0);
+                (2.0 * r * w / (w * w + w * q + q * q) + m) / d
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests every combination of method and starting guess over a range of anomalies.
+    #[test]
+    fn test_kepler_solver() {
+        let methods: [KeplerMethod; 2] = [KeplerMethod::Newton, KeplerMethod::Halley];
+        let guesses: [InitialGuess; 3] = [InitialGuess::MeanAnomaly, InitialGuess::Danby, InitialGuess::Markley];
+        for method in methods {
+            for initial_guess in guesses {
+                let solver = KeplerSolver {
+                    method,
+                    initial_guess,
+                    ..KeplerSolver::default()
+                };
+                for eccentricity in [0.0, 0.1, 0.7, 0.95] {
+                    for k in -20..=20 {
+                        let mean_anomaly: f64 = 0.5 * k as f64;
+                        let e_anomaly: f64 = solver.solve(mean_anomaly, eccentricity).unwrap();
+                        assert!((e_anomaly - eccentricity * e_anomaly.sin() - mean_anomaly).abs() < 1e-12, "{:?} {:?} {} {}", method, initial_guess, eccentricity, mean_anomaly);
+                    }
+                }
+            }
+        }
+
+        let markley = KeplerSolver {
+            initial_guess: InitialGuess::Markley,
+            ..KeplerSolver::default()
+        };
+        assert!((markley.initial_guess(1.0, 0.1) - 1.0885977523978936).abs() < 1e-3);
+    }
+
+    /// Tests orbits close to parabolic, where Newton's method started at M overshoots.
+    #[test]
+    fn test_kepler_near_parabolic() {
+        let guesses: [InitialGuess; 3] = [InitialGuess::MeanAnomaly, InitialGuess::Danby, InitialGuess::Markley];
+        for initial_guess in guesses {
+            let solver = KeplerSolver {
+                initial_guess,
+                ..KeplerSolver::default()
+            };
+            for eccentricity in [0.99, 0.9999, 0.999999] {
+                for mean_anomaly in [1e-6, 1e-3, 0.05, -0.2, 3.1] {
+                    let e_anomaly: f64 = solver.solve(mean_anomaly, eccentricity).unwrap();
+                    assert!((e_anomaly - eccentricity * e_anomaly.sin() - mean_anomaly).abs() < 1e-12, "{:?} {} {}", initial_guess, eccentricity, mean_anomaly);
+                }
+            }
+        }
+    }
+
+    /// Tests that the iteration cap and invalid inputs give an error instead of looping.
+    #[test]
+    fn test_kepler_not_converged() {
+        let solver = KeplerSolver {
+            max_iterations: 2,
+            ..KeplerSolver::default()
+        };
+        assert!(matches!(solver.solve(0.1, 0.99), Err(KeplerError::NotConverged { iterations: 2, .. })));
+        assert!(KeplerSolver::default().solve(f64::NAN, 0.1).is_err());
+        assert_eq!(KeplerSolver::default().solve(1.0, 1.0), Err(KeplerError::Eccentricity(1.0)));
+        assert!(KeplerSolver::default().solve(1.0, 1.5).is_err());
+    }
+}
@@ -0,0 +1,183 @@
+//! Osculating Keplerian elements.
+//! Unlike the SGP4 mean elements of `OrbitalElements`, these elements describe the two-body orbit
+//! tangent to a single state vector. Circular and equatorial orbits, where the node or the perigee
+//! are undefined, use the conventions of Vallado's RV2COE algorithm.
+
+use crate::{StateVector, TWOPI, XKE, XKMPER};
+use crate::vector::{cross, dot};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Earth's gravitational parameter consistent with the SGP4 constants, in km³/s².
+pub const EARTH_MU: f64 = XKE * XKE * XKMPER * XKMPER * XKMPER / 3600.0;
+/// Eccentricity and inclination below which the orbit is treated as circular or equatorial.
+const SINGULARITY_TOLERANCE: f64 = 1e-10;
+
+/// Represents the osculating Keplerian elements of an orbit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeplerianElements {
+    /// Semi-major axis in kilometers, negative for hyperbolic orbits.
+    pub semi_major_axis: f64,
+    /// Eccentricity, unitless.
+    pub eccentricity: f64,
+    /// Inclination in radians.
+    pub inclination: f64,
+    /// Right Ascension of the Ascending Node in radians, zero for equatorial orbits.
+    pub raan: f64,
+    /// Argument of perigee in radians. Zero for circular orbits; measured from the X axis
+    /// (longitude of perigee) for equatorial ones.
+    pub arg_perigee: f64,
+    /// True anomaly in radians. Measured from the node (argument of latitude) for circular orbits,
+    /// and from the X axis (true longitude) for circular equatorial ones.
+    pub true_anomaly: f64,
+}
+
+/// Computes the angle between two vectors, in [0, π].
+fn angle(a: [f64; 3], b: [f64; 3]) -> f64 {
+    (dot(a, b) / (dot(a, a) * dot(b, b)).sqrt()).clamp(-1.0, 1.0).acos()
+}
+
+impl KeplerianElements {
+    /// Computes the osculating elements of a state vector.
+    ///
+    /// # Arguments
+    /// * `state` - Inertial state vector (km, km/s).
+    ///
+    /// # Returns
+    /// * The osculating Keplerian elements.
+    pub fn from_state(state: &StateVector) -> KeplerianElements {
+        let r: [f64; 3] = state.position;
+        let v: [f64; 3] = state.velocity;
+        let r_norm: f64 = dot(r, r).sqrt();
+        let v2: f64 = dot(v, v);
+
+        let h: [f64; 3] = cross(r, v);
+        let node: [f64; 3] = [-h[1], h[0], 0.0];
+        let rv: f64 = dot(r, v);
+        let e_vector: [f64; 3] = [
+            ((v2 - EARTH_MU / r_norm) * r[0] - rv * v[0]) / EARTH_MU,
+            ((v2 - EARTH_MU / r_norm) * r[1] - rv * v[1]) / EARTH_MU,
+            ((v2 - EARTH_MU / r_norm) * r[2] - rv * v[2]) / EARTH_MU,
+        ];
+        let eccentricity: f64 = dot(e_vector, e_vector).sqrt();
+        let semi_major_axis: f64 = 1.0 / (2.0 / r_norm - v2 / EARTH_MU);
+        let inclination: f64 = (h[2] / dot(h, h).sqrt()).clamp(-1.0, 1.0).acos();
+
+        let circular: bool = eccentricity < SINGULARITY_TOLERANCE;
+        let equatorial: bool = inclination < SINGULARITY_TOLERANCE || core::f64::consts::PI - inclination < SINGULARITY_TOLERANCE;
+
+        // Angles in [0, π] from `angle` are moved to the other half-plane by the sign of a component.
+        let mirror = |value: f64, negative: bool| if negative { TWOPI - value } else { value };
+        let raan: f64 = if equatorial { 0.0 } else { mirror(angle([1.0, 0.0, 0.0], node), node[1] < 0.0) };
+        let (arg_perigee, true_anomaly) = match (circular, equatorial) {
+            (false, false) => (
+                mirror(angle(node, e_vector), e_vector[2] < 0.0),
+                mirror(angle(e_vector, r), rv < 0.0),
+            ),
+            (false, true) => (
+                mirror(e_vector[1].atan2(e_vector[0]).rem_euclid(TWOPI), h[2] < 0.0),
+                mirror(angle(e_vector, r), rv < 0.0),
+            ),
+            (true, false) => (0.0, mirror(angle(node, r), r[2] < 0.0)),
+            (true, true) => (0.0, mirror(r[1].atan2(r[0]).rem_euclid(TWOPI), h[2] < 0.0)),
+        };
+
+        KeplerianElements {
+            semi_major_axis,
+            eccentricity,
+            inclination,
+            raan,
+            arg_perigee,
+            true_anomaly,
+        }
+    }
+
+    /// Computes the state vector described by the elements.
+    ///
+    /// # Returns
+    /// * Inertial state vector (km, km/s).
+    pub fn to_state(&self) -> StateVector {
+        let e: f64 = self.eccentricity;
+        let p: f64 = self.semi_major_axis * (1.0 - e * e);
+        let (sin_nu, cos_nu) = self.true_anomaly.sin_cos();
+        let r: f64 = p / (1.0 + e * cos_nu);
+        let speed: f64 = (EARTH_MU / p).sqrt();
+
+        let position: [f64; 2] = [r * cos_nu, r * sin_nu];
+        let velocity: [f64; 2] = [-speed * sin_nu, speed * (e + cos_nu)];
+
+        // Perifocal to inertial rotation R3(-Ω) R1(-i) R3(-ω), applied column by column.
+        let (sin_raan, cos_raan) = self.raan.sin_cos();
+        let (sin_i, cos_i) = self.inclination.sin_cos();
+        let (sin_w, cos_w) = self.arg_perigee.sin_cos();
+        let p_axis: [f64; 3] = [
+            cos_raan * cos_w - sin_raan * sin_w * cos_i,
+            sin_raan * cos_w + cos_raan * sin_w * cos_i,
+            sin_w * sin_i,
+        ];
+        let q_axis: [f64; 3] = [
+            -cos_raan * sin_w - sin_raan * cos_w * cos_i,
+            -sin_raan * sin_w + cos_raan * cos_w * cos_i,
+            cos_w * sin_i,
+        ];
+
+        StateVector {
+            position: [0, 1, 2].map(|k| position[0] * p_axis[k] + position[1] * q_axis[k]),
+            velocity: [0, 1, 2].map(|k| velocity[0] * p_axis[k] + velocity[1] * q_axis[k]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests the conversion against Vallado's example 2-5.
+    #[test]
+    fn test_from_state() {
+        let state = StateVector {
+            position: [6524.834, 6862.875, 6448.296],
+            velocity: [4.901327, 5.533756, -1.976341],
+        };
+
+        let elements = KeplerianElements::from_state(&state);
+
+        assert!((elements.semi_major_axis - 36127.343).abs() < 0.5);
+        assert!((elements.eccentricity - 0.832853).abs() < 1e-5);
+        assert!((elements.inclination.to_degrees() - 87.870).abs() < 1e-3);
+        assert!((elements.raan.to_degrees() - 227.898).abs() < 1e-3);
+        assert!((elements.arg_perigee.to_degrees() - 53.38).abs() < 1e-2);
+        assert!((elements.true_anomaly.to_degrees() - 92.335).abs() < 1e-3);
+    }
+
+    /// Tests round trips through the state vector, including the singular cases.
+    #[test]
+    fn test_state_round_trip() {
+        let cases: [(f64, f64, f64, f64, f64); 4] = [
+            (0.1, 0.9, 4.0, 1.0, 2.5),
+            (0.2, 0.0, 0.0, 1.0, 5.5),
+            (0.0, 1.7, 3.0, 0.0, 0.5),
+            (0.0, 0.0, 0.0, 0.0, 4.0),
+        ];
+        for (eccentricity, inclination, raan, arg_perigee, true_anomaly) in cases {
+            let elements = KeplerianElements {
+                semi_major_axis: 7000.0,
+                eccentricity,
+                inclination,
+                raan,
+                arg_perigee,
+                true_anomaly,
+            };
+
+            let back = KeplerianElements::from_state(&elements.to_state());
+
+            assert!((back.semi_major_axis - 7000.0).abs() < 1e-6);
+            assert!((back.eccentricity - eccentricity).abs() < 1e-10);
+            assert!((back.inclination - inclination).abs() < 1e-10);
+            assert!((back.raan - raan).abs() < 1e-9);
+            assert!((back.arg_perigee - arg_perigee).abs() < 1e-6);
+            assert!((back.true_anomaly - true_anomaly).abs() < 1e-6);
+        }
+    }
+}
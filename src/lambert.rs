@@ -0,0 +1,177 @@
+//! Lambert's problem: the two-body orbit joining two positions in a given time.
+//! The universal-variable formulation covers elliptical, parabolic and hyperbolic transfers
+//! alike. The time of flight grows monotonically with the universal variable ψ over a single
+//! revolution, so ψ is found by bisection, which cannot diverge.
+
+use crate::keplerian::EARTH_MU;
+use core::f64::consts::PI;
+use core::fmt;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Maximum number of bisection steps.
+const MAX_ITERATIONS: usize = 200;
+/// Relative tolerance on the time of flight.
+const TOLERANCE: f64 = 1e-12;
+
+/// Direction of motion of the transfer orbit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TransferDirection {
+    /// Angular momentum towards +Z, like most Earth satellites.
+    #[default]
+    Prograde,
+    /// Angular momentum towards -Z.
+    Retrograde,
+}
+
+/// Represents the reasons why Lambert's problem has no solution.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LambertError {
+    /// The time of flight is not positive.
+    TimeOfFlight(f64),
+    /// The positions are collinear with the center of the Earth, so the plane of the transfer is
+    /// undefined.
+    Collinear,
+    /// The bisection did not reach the time of flight.
+    NotConverged,
+}
+
+impl fmt::Display for LambertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LambertError::TimeOfFlight(t) => write!(f, "Time of flight is not positive: {}", t),
+            LambertError::Collinear => write!(f, "Positions are collinear with the center of the Earth"),
+            LambertError::NotConverged => write!(f, "Lambert solver did not converge"),
+        }
+    }
+}
+
+impl core::error::Error for LambertError {}
+
+/// Represents the velocities at both ends of a transfer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LambertSolution {
+    /// Velocity at the first position in km/s.
+    pub departure: [f64; 3],
+    /// Velocity at the second position in km/s.
+    pub arrival: [f64; 3],
+}
+
+/// Computes the Stumpff functions C(ψ) and S(ψ).
+fn stumpff(psi: f64) -> (f64, f64) {
+    if psi > 1e-6 {
+        let root: f64 = psi.sqrt();
+        ((1.0 - root.cos()) / psi, (root - root.sin()) / (root * psi))
+    } else if psi < -1e-6 {
+        let root: f64 = (-psi).sqrt();
+        let (sinh, cosh) = (0.5 * (root.exp() - (-root).exp()), 0.5 * (root.exp() + (-root).exp()));
+        ((1.0 - cosh) / psi, (sinh - root) / (root * -psi))
+    } else {
+        (0.5 - psi / 24.0, 1.0 / 6.0 - psi / 120.0)
+    }
+}
+
+/// Solves Lambert's problem for a transfer of less than one revolution.
+///
+/// # Arguments
+/// * `r1` - First position in kilometers.
+/// * `r2` - Second position in kilometers, in the same inertial frame.
+/// * `tof` - Time of flight in minutes.
+/// * `direction` - Direction of motion, which selects the short or long way around.
+///
+/// # Returns
+/// * The velocities at both positions, or the reason there is no solution.
+pub fn lambert(r1: [f64; 3], r2: [f64; 3], tof: f64, direction: TransferDirection) -> Result<LambertSolution, LambertError> {
+    if tof <= 0.0 || !tof.is_finite() {
+        return Err(LambertError::TimeOfFlight(tof));
+    }
+    let norm = |v: [f64; 3]| -> f64 { (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt() };
+    let (n1, n2) = (norm(r1), norm(r2));
+    let cross_z: f64 = r1[0] * r2[1] - r1[1] * r2[0];
+    let cos_angle: f64 = ((r1[0] * r2[0] + r1[1] * r2[1] + r1[2] * r2[2]) / (n1 * n2)).clamp(-1.0, 1.0);
+    let mut angle: f64 = cos_angle.acos();
+    if (direction == TransferDirection::Prograde) != (cross_z >= 0.0) {
+        angle = 2.0 * PI - angle;
+    }
+    let a: f64 = angle.sin() * (n1 * n2 / (1.0 - cos_angle)).sqrt();
+    if a.abs() < 1e-9 || !a.is_finite() {
+        return Err(LambertError::Collinear);
+    }
+
+    let seconds: f64 = tof * 60.0;
+    let y_of = |psi: f64| -> (f64, f64, f64) {
+        let (c, s) = stumpff(psi);
+        (n1 + n2 + a * (psi * s - 1.0) / c.sqrt(), c, s)
+    };
+    let (mut low, mut high): (f64, f64) = (-4.0 * PI, 4.0 * PI * PI);
+    for _ in 0..MAX_ITERATIONS {
+        let psi: f64 = 0.5 * (low + high);
+        let (y, c, s) = y_of(psi);
+        // A negative y means the time of flight at ψ is below any reachable one.
+        let flight: f64 = if y < 0.0 { f64::NEG_INFINITY } else { ((y / c).powf(1.5) * s + a * y.sqrt()) / EARTH_MU.sqrt() };
+        if (flight - seconds).abs() <= TOLERANCE * seconds {
+            let f: f64 = 1.0 - y / n1;
+            let g: f64 = a * (y / EARTH_MU).sqrt();
+            let g_dot: f64 = 1.0 - y / n2;
+            return Ok(LambertSolution {
+                departure: [0, 1, 2].map(|k| (r2[k] - f * r1[k]) / g),
+                arrival: [0, 1, 2].map(|k| (g_dot * r2[k] - r1[k]) / g),
+            });
+        }
+        if flight < seconds {
+            low = psi;
+        } else {
+            high = psi;
+        }
+    }
+    Err(LambertError::NotConverged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::anomaly::true_to_mean;
+    use crate::keplerian::KeplerianElements;
+
+    /// Tests the solver on Example 5.2 of Curtis, Orbital Mechanics for Engineering Students.
+    #[test]
+    fn test_lambert_curtis() {
+        let solution = lambert([5000.0, 10000.0, 2100.0], [-14600.0, 2500.0, 7000.0], 60.0, TransferDirection::Prograde).unwrap();
+        let departure: [f64; 3] = [-5.9925, 1.9254, 3.2456];
+        let arrival: [f64; 3] = [-3.3125, -4.1966, -0.38529];
+        for k in 0..3 {
+            assert!((solution.departure[k] - departure[k]).abs() < 1e-3, "{:?}", solution.departure);
+            assert!((solution.arrival[k] - arrival[k]).abs() < 1e-3, "{:?}", solution.arrival);
+        }
+        assert!(matches!(lambert([7000.0, 0.0, 0.0], [-8000.0, 0.0, 0.0], 60.0, TransferDirection::Prograde), Err(LambertError::Collinear)));
+        assert!(matches!(lambert([7000.0, 0.0, 0.0], [0.0, 8000.0, 0.0], 0.0, TransferDirection::Prograde), Err(LambertError::TimeOfFlight(_))));
+    }
+
+    /// Tests that the solver recovers the velocities of a known eccentric orbit, the long way round.
+    #[test]
+    fn test_lambert_known_orbit() {
+        let orbit = |true_anomaly: f64| KeplerianElements {
+            semi_major_axis: 9000.0,
+            eccentricity: 0.2,
+            inclination: 0.7,
+            raan: 1.0,
+            arg_perigee: 0.5,
+            true_anomaly,
+        };
+        let (start, end) = (orbit(0.3).to_state(), orbit(4.0).to_state());
+        let mean_motion: f64 = (EARTH_MU / 9000.0_f64.powi(3)).sqrt();
+        let tof: f64 = (true_to_mean(4.0, 0.2).unwrap() - true_to_mean(0.3, 0.2).unwrap()).rem_euclid(2.0 * PI) / mean_motion / 60.0;
+
+        let solution = lambert(start.position, end.position, tof, TransferDirection::Prograde).unwrap();
+        for k in 0..3 {
+            assert!((solution.departure[k] - start.velocity[k]).abs() < 1e-8, "{:?} {:?}", solution.departure, start.velocity);
+            assert!((solution.arrival[k] - end.velocity[k]).abs() < 1e-8);
+        }
+        // The retrograde transfer goes the other way around.
+        let retrograde = lambert(start.position, end.position, tof, TransferDirection::Retrograde).unwrap();
+        let dot: f64 = (0..3).map(|k| retrograde.departure[k] * start.velocity[k]).sum();
+        assert!(dot < 0.0);
+    }
+}
@@ -1,48 +1,328 @@
 //! A library for performing orbital mechanics calculations using the SGP4 model.
 //! This library provides functionality to convert Two-Line Element (TLE) data into orbital elements
 //! and compute the state vector (position and velocity) of a satellite at a given time.
-
-use std::f64::consts::PI;
-
-/// Represents a Two-Line Element set (TLE) for a satellite.
-/// TLEs are used to describe the orbits of Earth-orbiting objects.
-pub struct Tle {
-    /// First line of the TLE data.
-    pub line1: String,
-    /// Second line of the TLE data.
-    pub line2: String,
-}
+//!
+//! Without the default `std` feature the crate is `no_std` (it still needs `alloc`) and takes its
+//! math functions from `libm`, which the `libm` feature enables.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("sgp4_rust needs either the `std` or the `libm` feature for its math functions");
+
+use core::f64::consts::PI;
+use core::fmt;
+#[cfg(not(feature = "std"))]
+use prelude::*;
+
+pub mod anomaly;
+pub mod astro;
+pub mod atmosphere;
+pub mod beta_angle;
+pub mod builder;
+#[cfg(feature = "std")]
+pub mod cache;
+#[cfg(feature = "http")]
+pub mod celestrak;
+pub mod covariance;
+#[cfg(feature = "std")]
+pub mod binary;
+pub mod collision;
+pub mod conjunction;
+pub mod coverage;
+mod deep_space;
+pub mod doppler;
+pub mod eclipse;
+pub mod ephemeris;
+pub mod events;
+#[cfg(feature = "std")]
+pub mod export;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod fit;
+pub mod frames;
+pub mod geodetic;
+#[cfg(feature = "glam")]
+pub mod glam;
+pub mod gravity;
+pub mod ground_track;
+mod harmonics;
+pub mod history;
+pub mod kepler;
+pub mod keplerian;
+pub mod lambert;
+pub mod lifetime;
+pub mod maneuver;
+pub mod maneuver_detection;
+#[cfg(not(feature = "std"))]
+mod math;
+pub mod mean_elements;
+pub mod mission_analysis;
+pub mod monte_carlo;
+#[cfg(feature = "nalgebra")]
+pub mod nalgebra;
+pub mod numerical;
+#[cfg(feature = "std")]
+pub mod omm;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+pub mod passes;
+mod prelude;
+pub mod propagator;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod reentry;
+pub mod regime;
+pub mod relative;
+pub mod satellite;
+#[cfg(feature = "std")]
+pub mod screening;
+#[cfg(feature = "serde")]
+mod serde_units;
+#[cfg(feature = "f32")]
+pub mod sgp4_f32;
+#[cfg(feature = "simd")]
+pub mod simd;
+#[cfg(feature = "http")]
+pub mod spacetrack;
+pub mod time;
+pub mod tle;
+pub mod topocentric;
+pub mod two_body;
+#[cfg(feature = "uom")]
+pub mod uom;
+pub mod vector;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use ephemeris::{Ephemeris, InterpolatedEphemeris};
+pub use gravity::{GravityConstants, GravityModel};
+pub use propagator::{PropagationError, Propagator, Sgp4};
+pub use satellite::Satellite;
+pub use two_body::TwoBody;
+pub use tle::{Tle, TleError, TleFields, TleMetadata, decode_alpha5, encode_alpha5, full_year, parse_tle};
+#[cfg(feature = "std")]
+pub use tle::parse_tle_file;
 
 /// Represents the state vector of a satellite, including its position and velocity.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StateVector {
     /// Position of the satellite in kilometers (X, Y, Z).
+    #[cfg_attr(feature = "serde", serde(rename = "position_km"))]
     pub position: [f64; 3],
     /// Velocity of the satellite in kilometers per second (XDOT, YDOT, ZDOT).
+    #[cfg_attr(feature = "serde", serde(rename = "velocity_km_s"))]
     pub velocity: [f64; 3],
 }
 
 /// Represents the orbital elements of a satellite.
+///
+/// With the `serde` feature, angles are serialized in degrees and the mean motion in
+/// revolutions per day, under field names carrying the unit. The layout is the one of a C struct,
+/// so the elements can be passed through the `ffi` functions.
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OrbitalElements {
     /// Inclination of the orbit in radians.
+    #[cfg_attr(feature = "serde", serde(rename = "inclination_deg", with = "serde_units::degrees"))]
     pub inclination: f64,
     /// Right Ascension of the Ascending Node (RAAN) in radians.
+    #[cfg_attr(feature = "serde", serde(rename = "raan_deg", with = "serde_units::degrees"))]
     pub raan: f64,
     /// Eccentricity of the orbit, unitless.
     pub eccentricity: f64,
     /// Argument of perigee in radians.
+    #[cfg_attr(feature = "serde", serde(rename = "arg_perigee_deg", with = "serde_units::degrees"))]
     pub arg_perigee: f64,
     /// Mean anomaly in radians.
+    #[cfg_attr(feature = "serde", serde(rename = "mean_anomaly_deg", with = "serde_units::degrees"))]
     pub mean_anomaly: f64,
     /// Mean motion in radians per minute.
+    #[cfg_attr(feature = "serde", serde(rename = "mean_motion_rev_per_day", with = "serde_units::rev_per_day"))]
     pub mean_motion: f64,
     /// Bstar drag term in 1/earth radii.
     pub bstar: f64,
     /// Flag indicating if the orbit is in deep space.
     pub deep_space: bool,
+    /// Four-digit year of the epoch.
+    pub epoch_year: i32,
+    /// Day of the year of the epoch, including the fractional part of the day.
+    pub epoch_day: f64,
+}
+
+impl OrbitalElements {
+    /// Returns the epoch of the elements as a Julian date (UTC).
+    pub fn epoch_jd(&self) -> f64 {
+        time::julian_date_from_day_of_year(self.epoch_year, self.epoch_day)
+    }
+
+    /// Returns the Julian date (UTC) at a given time since epoch.
+    ///
+    /// # Arguments
+    /// * `tsince` - Time since epoch in minutes.
+    ///
+    /// # Returns
+    /// * The Julian date, in days.
+    pub fn jd_at(&self, tsince: f64) -> f64 {
+        self.epoch_jd() + tsince / XMNPDA
+    }
+
+    /// Returns the orbital period in minutes, from the Brouwer mean motion.
+    pub fn period_minutes(&self) -> f64 {
+        TWOPI / self.mean_motion
+    }
+
+    /// Returns the mean semi-major axis in kilometers.
+    pub fn semi_major_axis_km(&self) -> f64 {
+        (XKE / self.mean_motion).powf(TOTHIRD) * XKMPER
+    }
+
+    /// Returns the altitude of the apogee above the Earth's equatorial radius in kilometers.
+    pub fn apogee_altitude_km(&self) -> f64 {
+        self.semi_major_axis_km() * (1.0 + self.eccentricity) - XKMPER
+    }
+
+    /// Returns the altitude of the perigee above the Earth's equatorial radius in kilometers.
+    pub fn perigee_altitude_km(&self) -> f64 {
+        self.semi_major_axis_km() * (1.0 - self.eccentricity) - XKMPER
+    }
+
+    /// Returns the epoch of the elements as a UTC date and time.
+    #[cfg(feature = "chrono")]
+    pub fn epoch(&self) -> chrono::DateTime<chrono::Utc> {
+        tle::epoch_to_datetime(self.epoch_year, self.epoch_day)
+    }
+
+    /// Computes the time elapsed between the epoch of the elements and a given instant.
+    ///
+    /// # Arguments
+    /// * `when` - The instant, in UTC.
+    ///
+    /// # Returns
+    /// * Time since epoch in minutes (negative before the epoch).
+    #[cfg(feature = "chrono")]
+    pub fn minutes_since_epoch(&self, when: chrono::DateTime<chrono::Utc>) -> f64 {
+        let elapsed: chrono::TimeDelta = when - self.epoch();
+        elapsed.num_seconds() as f64 / 60.0 + elapsed.subsec_nanos() as f64 / 60e9
+    }
+
+    /// Computes the state vector of the satellite at an absolute instant.
+    ///
+    /// # Arguments
+    /// * `when` - The instant, in UTC.
+    ///
+    /// # Returns
+    /// * State vector containing the position and velocity of the satellite, or the reason why the
+    ///   orbit cannot be propagated.
+    #[cfg(feature = "chrono")]
+    pub fn propagate_at(&self, when: chrono::DateTime<chrono::Utc>) -> Result<StateVector, PropagationError> {
+        Sgp4::new(self).propagate(self.minutes_since_epoch(when))
+    }
+
+    /// Returns the epoch of the elements as a `hifitime` epoch in UTC, rounded to the microsecond.
+    #[cfg(feature = "hifitime")]
+    pub fn hifitime_epoch(&self) -> hifitime::Epoch {
+        let whole: f64 = self.epoch_day.floor();
+        let micros: i128 = ((self.epoch_day - whole) * 86400e6).round() as i128;
+        hifitime::Epoch::from_gregorian_utc_at_midnight(self.epoch_year, 1, 1)
+            + hifitime::Unit::Day * (whole - 1.0)
+            + hifitime::Duration::from_total_nanoseconds(micros * 1000)
+    }
+
+    /// Computes the time elapsed between the epoch of the elements and a `hifitime` epoch.
+    /// Leap seconds between the two instants are accounted for.
+    ///
+    /// # Arguments
+    /// * `when` - The instant, in any time scale.
+    ///
+    /// # Returns
+    /// * Time since epoch in minutes (negative before the epoch).
+    #[cfg(feature = "hifitime")]
+    pub fn minutes_since_hifitime(&self, when: hifitime::Epoch) -> f64 {
+        let tai = hifitime::TimeScale::TAI;
+        let elapsed: hifitime::Duration = when.to_time_scale(tai) - self.hifitime_epoch().to_time_scale(tai);
+        elapsed.total_nanoseconds() as f64 / 60e9
+    }
+
+    /// Converts a time since epoch into a `hifitime` epoch, the inverse of `minutes_since_hifitime`
+    /// to the nanosecond.
+    ///
+    /// # Arguments
+    /// * `tsince` - Time since epoch in minutes.
+    ///
+    /// # Returns
+    /// * The corresponding instant, in the TAI time scale.
+    #[cfg(feature = "hifitime")]
+    pub fn hifitime_after(&self, tsince: f64) -> hifitime::Epoch {
+        let elapsed = hifitime::Duration::from_total_nanoseconds((tsince * 60e9).round() as i128);
+        self.hifitime_epoch().to_time_scale(hifitime::TimeScale::TAI) + elapsed
+    }
+
+    /// Computes the state vector of the satellite at a `hifitime` epoch.
+    ///
+    /// # Arguments
+    /// * `when` - The instant, in any time scale.
+    ///
+    /// # Returns
+    /// * State vector containing the position and velocity of the satellite, or the reason why the
+    ///   orbit cannot be propagated.
+    #[cfg(feature = "hifitime")]
+    pub fn propagate_hifitime(&self, when: hifitime::Epoch) -> Result<StateVector, PropagationError> {
+        Sgp4::new(self).propagate(self.minutes_since_hifitime(when))
+    }
+}
+
+/// Formats the position in kilometers and the velocity in km/s with fixed widths, on two lines,
+/// or on one line with the alternate flag (`{:#}`).
+impl fmt::Display for StateVector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [x, y, z] = self.position;
+        let [vx, vy, vz] = self.velocity;
+        if f.alternate() {
+            write!(f, "r = ({:.6}, {:.6}, {:.6}) km, v = ({:.9}, {:.9}, {:.9}) km/s", x, y, z, vx, vy, vz)
+        } else {
+            writeln!(f, "Position [km]:   {:>16.6} {:>16.6} {:>16.6}", x, y, z)?;
+            write!(f, "Velocity [km/s]: {:>16.9} {:>16.9} {:>16.9}", vx, vy, vz)
+        }
+    }
+}
+
+/// Formats the elements with angles in degrees and the (Brouwer) mean motion in revolutions per
+/// day, one per line, or on one line with the alternate flag (`{:#}`).
+impl fmt::Display for OrbitalElements {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let revolutions_per_day: f64 = self.mean_motion * XMNPDA / TWOPI;
+        if f.alternate() {
+            return write!(
+                f,
+                "epoch {}/{:012.8} i={:.4}° Ω={:.4}° e={:.7} ω={:.4}° M={:.4}° n={:.8} rev/day B*={:.4e}",
+                self.epoch_year,
+                self.epoch_day,
+                degrees(self.inclination),
+                degrees(self.raan),
+                self.eccentricity,
+                degrees(self.arg_perigee),
+                degrees(self.mean_anomaly),
+                revolutions_per_day,
+                self.bstar,
+            );
+        }
+        writeln!(f, "Epoch:               {} day {:.8} (JD {:.8})", self.epoch_year, self.epoch_day, self.epoch_jd())?;
+        writeln!(f, "Inclination:         {:>12.4}°", degrees(self.inclination))?;
+        writeln!(f, "RAAN:                {:>12.4}°", degrees(self.raan))?;
+        writeln!(f, "Eccentricity:        {:>12.7}", self.eccentricity)?;
+        writeln!(f, "Argument of perigee: {:>12.4}°", degrees(self.arg_perigee))?;
+        writeln!(f, "Mean anomaly:        {:>12.4}°", degrees(self.mean_anomaly))?;
+        writeln!(f, "Mean motion:         {:>12.8} rev/day", revolutions_per_day)?;
+        write!(f, "B*:                  {:>12.4e} 1/ER", self.bstar)
+    }
 }
 
 /// Constant representing 2 * PI.
-const TWOPI: f64 = 2.0 * std::f64::consts::PI;
+const TWOPI: f64 = 2.0 * core::f64::consts::PI;
 /// Earth's gravitational constant.
 const XKE: f64 = 0.0743669161;
 /// Second zonal harmonic coefficient for Earth.
@@ -65,23 +345,53 @@ fn radians(deg: f64) -> f64 {
     deg * PI / 180.0
 }
 
-/// Parses a substring from a TLE line and converts it to a real number.
+/// Converts radians to degrees.
+///
+/// # Arguments
+/// * `rad` - Angle in radians.
+///
+/// # Returns
+/// * Angle in degrees.
+fn degrees(rad: f64) -> f64 {
+    rad * 180.0 / PI
+}
+
+/// Recovers the original (Brouwer) mean motion from the Kozai mean motion stored in TLEs.
 ///
 /// # Arguments
-/// * `line` - The TLE line to parse.
-/// * `start` - The starting index of the substring (1-based).
-/// * `len` - The length of the substring.
+/// * `xno` - Kozai mean motion in radians per minute.
+/// * `inclination` - Inclination of the orbit in radians.
+/// * `eccentricity` - Eccentricity of the orbit.
+/// * `gravity` - Earth constants of the gravity model.
 ///
 /// # Returns
-/// * The parsed real number.
-fn parse_real(line: &str, start: usize, len: usize) -> f64 {
-    line.get(start - 1..start - 1 + len)
-        .unwrap_or("0")
-        .trim()
-        .replace("-", "e-")
-        .replace("+", "e+")
-        .parse::<f64>()
-        .unwrap_or(0.0)
+/// * Brouwer mean motion in radians per minute.
+pub(crate) fn brouwer_mean_motion(xno: f64, inclination: f64, eccentricity: f64, gravity: &GravityConstants) -> f64 {
+    let a1: f64 = (gravity.xke / xno).powf(TOTHIRD);
+    let temp: f64 = 0.75 * gravity.j2 * (3.0 * inclination.cos().powi(2) - 1.0) / (1.0 - eccentricity.powi(2)).powf(1.5);
+    let del1: f64 = temp / (a1 * a1);
+    let ao: f64 = a1 * (1.0 - del1 * (0.5 * TOTHIRD + del1 * (1.0 + 134.0 / 81.0 * del1)));
+    let delo: f64 = temp / (ao * ao);
+    xno / (1.0 + delo)
+}
+
+/// Converts a Brouwer mean motion back to the Kozai mean motion stored in TLEs.
+/// This inverts `brouwer_mean_motion` by fixed-point iteration.
+///
+/// # Arguments
+/// * `xnodp` - Brouwer mean motion in radians per minute.
+/// * `inclination` - Inclination of the orbit in radians.
+/// * `eccentricity` - Eccentricity of the orbit.
+/// * `gravity` - Earth constants of the gravity model.
+///
+/// # Returns
+/// * Kozai mean motion in radians per minute.
+pub(crate) fn kozai_mean_motion(xnodp: f64, inclination: f64, eccentricity: f64, gravity: &GravityConstants) -> f64 {
+    let mut xno: f64 = xnodp;
+    for _ in 0..20 {
+        xno *= xnodp / brouwer_mean_motion(xno, inclination, eccentricity, gravity);
+    }
+    xno
 }
 
 /// Converts satellite TLE data into orbital elements.
@@ -92,30 +402,28 @@ fn parse_real(line: &str, start: usize, len: usize) -> f64 {
 /// # Returns
 /// * Orbital elements derived from the TLE data.
 pub fn convert_satellite_data(tle: &Tle) -> OrbitalElements {
-    let line1: &String = &tle.line1;
-    let line2: &String = &tle.line2;
-
-    let _epoch: f64 = parse_real(line1, 19, 14);
-    let _xndt2o: f64 = parse_real(line1, 34, 10);
-    let _xndd6o: f64 = parse_real(line1, 45, 6);
-    let _iexp: f64 = parse_real(line1, 51, 2);
-    let bstar: f64 = parse_real(line1, 54, 6) * 1e-5 * 10f64.powf(parse_real(line1, 60, 2));
-
-    let inclination: f64 = radians(parse_real(line2, 9, 8));
-    let raan: f64 = radians(parse_real(line2, 18, 8));
-    let eccentricity: f64 = parse_real(line2, 27, 7) * 1e-7;
-    let arg_perigee: f64 = radians(parse_real(line2, 35, 8));
-    let mean_anomaly: f64 = radians(parse_real(line2, 44, 8));
-    let mean_motion: f64 = parse_real(line2, 53, 11);
+    convert_fields(&parse_tle(tle))
+}
+
+/// Converts parsed element set fields (from a TLE or an OMM) into orbital elements.
+///
+/// # Arguments
+/// * `fields` - The element set fields, in TLE units.
+///
+/// # Returns
+/// * Orbital elements derived from the fields.
+pub fn convert_fields(fields: &TleFields) -> OrbitalElements {
+    let bstar: f64 = fields.bstar;
+    let inclination: f64 = radians(fields.inclination);
+    let raan: f64 = radians(fields.raan);
+    let eccentricity: f64 = fields.eccentricity;
+    let arg_perigee: f64 = radians(fields.arg_perigee);
+    let mean_anomaly: f64 = radians(fields.mean_anomaly);
+    let mean_motion: f64 = fields.mean_motion;
 
     // Convert mean motion to radians per minute
     let xno: f64 = mean_motion * TWOPI / XMNPDA;
-    let a1: f64 = (XKE / xno).powf(TOTHIRD);
-    let temp: f64 = 1.5 * CK2 * (3.0 * inclination.cos().powi(2) - 1.0) / (1.0 - eccentricity.powi(2)).powf(1.5);
-    let del1: f64 = temp / (a1 * a1);
-    let ao: f64 = a1 * (1.0 - del1 * (0.5 * TOTHIRD + del1 * (1.0 + 134.0 / 81.0 * del1)));
-    let delo: f64 = temp / (ao * ao);
-    let xnodp: f64 = xno / (1.0 + delo);
+    let xnodp: f64 = brouwer_mean_motion(xno, inclination, eccentricity, &GravityConstants::default());
 
     let deep_space: bool = (TWOPI / xnodp) >= 225.0;
 
@@ -128,6 +436,8 @@ pub fn convert_satellite_data(tle: &Tle) -> OrbitalElements {
         mean_motion: xnodp,
         bstar,
         deep_space,
+        epoch_year: fields.epoch_full_year(),
+        epoch_day: fields.epoch_day,
     }
 }
 
@@ -139,95 +449,96 @@ pub fn convert_satellite_data(tle: &Tle) -> OrbitalElements {
 /// * `tol` - Tolerance for the solution.
 ///
 /// # Returns
-/// * Eccentric anomaly in radians.
-fn solve_kepler(mean_anomaly: f64, eccentricity: f64, tol: f64) -> f64 {
-    let mut e: f64 = mean_anomaly; // Initial estimate
-    let mut delta: f64 = 1.0;
-
-    while delta.abs() > tol {
-        let f: f64 = e - eccentricity * e.sin() - mean_anomaly;
-        let f_prime: f64 = 1.0 - eccentricity * e.cos();
-        delta = f / f_prime;
-        e -= delta;
-    }
-
-    e
+/// * Eccentric anomaly in radians, or an error if the iteration did not converge.
+fn solve_kepler(mean_anomaly: f64, eccentricity: f64, tol: f64) -> Result<f64, kepler::KeplerError> {
+    let solver = kepler::KeplerSolver {
+        tolerance: tol,
+        ..kepler::KeplerSolver::default()
+    };
+    solver.solve(mean_anomaly, eccentricity)
 }
 
+/// State returned by `sgp4()` when the elements no longer describe a valid orbit.
+const INVALID_STATE: StateVector = StateVector {
+    position: [f64::NAN; 3],
+    velocity: [f64::NAN; 3],
+};
+
 /// Computes the state vector (position and velocity) of a satellite using the SGP4 model.
 ///
+/// Deep-space elements are propagated with SDP4. Use `Sgp4` directly to initialize the model once
+/// and to get the reason of a failed propagation.
+///
 /// # Arguments
 /// * `tsince` - Time since epoch in minutes.
 /// * `elements` - Orbital elements of the satellite.
 ///
 /// # Returns
-/// * State vector containing the position and velocity of the satellite.
+/// * State vector in the TEME frame (km, km/s). Its components are NaN when the elements have
+///   decayed to a meaningless orbit.
 pub fn sgp4(tsince: f64, elements: &OrbitalElements) -> StateVector {
-    let a: f64 = (XKE / elements.mean_motion).powf(2.0 / 3.0); // Semi-major axis (earth radii)
-    let e: f64 = elements.eccentricity;
-    let i: f64 = elements.inclination;
-    let omega: f64 = elements.arg_perigee;
-    let raan: f64 = elements.raan;
-
-    // Mean anomaly + Kepler's equation solution
-    let m: f64 = (elements.mean_anomaly + elements.mean_motion * tsince) % TWOPI;
-    let e_anomaly = solve_kepler(m, e, 1e-8);
-
-    // True anomaly
-    let v: f64 = 2.0 * ((1.0 + e).sqrt() * (e_anomaly / 2.0).sin())
-        .atan2((1.0 - e).sqrt() * (e_anomaly / 2.0).cos());
-
-    // Distance (earth radii)
-    let r: f64 = a * (1.0 - e * e_anomaly.cos());
-
-    // Coordinates in the orbital plane
-    let x_orb: f64 = r * v.cos();
-    let y_orb: f64 = r * v.sin();
-
-    // Velocity in the orbital plane
-    let p: f64 = a * (1.0 - e * e); // Semi-latus rectum
-    let r_dot: f64 = XKE * a.sqrt() * e * e_anomaly.sin() / r;
-    let r_fi_dot: f64 = XKE * (p).sqrt() / (r * r);
-
-    let vx_orb: f64 = r_dot * v.cos() - r * r_fi_dot * v.sin();
-    let vy_orb: f64 = r_dot * v.sin() + r * r_fi_dot * v.cos();
-
-    // Pre-calculations for inertial transformation
-    let cos_omega: f64 = omega.cos();
-    let sin_omega: f64 = omega.sin();
-    let cos_raan: f64 = raan.cos();
-    let sin_raan: f64 = raan.sin();
-    let cos_i: f64 = i.cos();
-    let sin_i: f64 = i.sin();
-
-    // Inertial position ECI (earth radii)
-    let x: f64 = x_orb * (cos_raan * cos_omega - sin_raan * sin_omega * cos_i)
-        - y_orb * (cos_raan * sin_omega + sin_raan * cos_omega * cos_i);
-    let y: f64 = x_orb * (sin_raan * cos_omega + cos_raan * sin_omega * cos_i)
-        - y_orb * (sin_raan * sin_omega - cos_raan * cos_omega * cos_i);
-    let z: f64 = x_orb * sin_omega * sin_i + y_orb * cos_omega * sin_i;
-
-    // Inertial velocity ECI (earth radii per minute)
-    let vx: f64 = vx_orb * (cos_raan * cos_omega - sin_raan * sin_omega * cos_i)
-        - vy_orb * (cos_raan * sin_omega + sin_raan * cos_omega * cos_i);
-    let vy: f64 = vx_orb * (sin_raan * cos_omega + cos_raan * sin_omega * cos_i)
-        - vy_orb * (sin_raan * sin_omega - cos_raan * cos_omega * cos_i);
-    let vz: f64 = vx_orb * sin_omega * sin_i + vy_orb * cos_omega * sin_i;
-
-    StateVector {
-        position: [x * XKMPER, y * XKMPER, z * XKMPER], // km
-        velocity: [vx * XKMPER / 60.0, vy * XKMPER / 60.0, vz * XKMPER / 60.0], // km/s
+    Sgp4::new(elements).propagate(tsince).unwrap_or(INVALID_STATE)
+}
+
+/// Computes the state vectors of a satellite at several times, sharing the time-independent
+/// computations between them.
+///
+/// # Arguments
+/// * `times` - Times since epoch in minutes.
+/// * `elements` - Orbital elements of the satellite.
+///
+/// # Returns
+/// * One state vector per requested time, in the same order.
+pub fn sgp4_batch(times: &[f64], elements: &OrbitalElements) -> Vec<StateVector> {
+    let propagator = Sgp4::new(elements);
+    times.iter().map(|&tsince| propagator.propagate(tsince).unwrap_or(INVALID_STATE)).collect()
+}
+
+/// Fixtures shared by the tests of the modules.
+#[cfg(test)]
+pub(crate) mod test_support {
+    use super::*;
+
+    /// Title line of the ISS element set.
+    pub(crate) const ISS_NAME: &str = "ISS (ZARYA)";
+    /// Data lines of the ISS element set of the SGP4 verification cases.
+    pub(crate) const ISS_LINES: [&str; 2] = [
+        "1 25544U 98067A   08264.51782528 -.00002182  00000-0 -11606-4 0  2927",
+        "2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537",
+    ];
+
+    /// Returns the ISS element set with its title line, as found in a TLE file.
+    #[cfg(any(feature = "http", feature = "wasm"))]
+    pub(crate) fn iss_text() -> String {
+        format!("{}\n{}\n{}\n", ISS_NAME, ISS_LINES[0], ISS_LINES[1])
+    }
+
+    /// Returns the TLE set of the ISS.
+    pub(crate) fn iss_tle() -> Tle {
+        Tle::from_lines(&ISS_LINES).unwrap()
+    }
+
+    /// Returns the orbital elements of the ISS.
+    pub(crate) fn iss_elements() -> OrbitalElements {
+        convert_satellite_data(&iss_tle())
+    }
+
+    /// Returns an SGP4 propagator initialized with the elements of the ISS.
+    pub(crate) fn iss_sgp4() -> Sgp4 {
+        Sgp4::new(&iss_elements())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use test_support::iss_elements;
 
     /// Tests the conversion of TLE data to orbital elements.
     #[test]
     fn test_convert_satellite_data() {
         let tle = Tle {
+            name: None,
             line1: "1 25544U 98067A   21135.57634567  .00002418  00000-0  50843-4 0  9993".to_string(),
             line2: "2 25544  51.6443 126.6639 0006738  34.7758 325.3542 15.48913328283873".to_string(),
         };
@@ -242,13 +553,49 @@ mod tests {
         assert!(elements.mean_motion > 0.0);
     }
 
+    /// Tests the readable and compact formats of the elements and state vectors.
+    #[test]
+    fn test_display() {
+        let elements = iss_elements();
+        let text: String = elements.to_string();
+        assert_eq!(text.lines().count(), 8);
+        assert!(text.contains("Epoch:               2008 day 264.51782528"), "{}", text);
+        assert!(text.contains("Inclination:              51.6416°"), "{}", text);
+        assert!(text.contains("Eccentricity:           0.0006703"), "{}", text);
+        let compact: String = format!("{:#}", elements);
+        assert!(compact.starts_with("epoch 2008/264.51782528 i=51.6416° Ω=247.4627° e=0.0006703 ω=130.5360° M=325.0288° n=15.7"), "{}", compact);
+        assert!(!compact.contains('\n'));
+
+        let state = StateVector {
+            position: [6524.834, -6862.875, 6448.296],
+            velocity: [4.901327, 5.533756, -1.976341],
+        };
+        assert_eq!(
+            state.to_string(),
+            "Position [km]:        6524.834000     -6862.875000      6448.296000\nVelocity [km/s]:      4.901327000      5.533756000     -1.976341000"
+        );
+        assert_eq!(format!("{:#}", state), "r = (6524.834000, -6862.875000, 6448.296000) km, v = (4.901327000, 5.533756000, -1.976341000) km/s");
+    }
+
+    /// Tests the period, semi-major axis and apsis altitudes derived from the elements.
+    #[test]
+    fn test_derived_quantities() {
+        let elements = iss_elements();
+
+        assert!((elements.period_minutes() - 1440.0 / 15.72125391).abs() < 0.05);
+        assert!((elements.semi_major_axis_km() - 6730.0).abs() < 10.0);
+        let mean_altitude: f64 = elements.semi_major_axis_km() - XKMPER;
+        assert!(elements.perigee_altitude_km() < mean_altitude && mean_altitude < elements.apogee_altitude_km());
+        assert!((elements.apogee_altitude_km() - elements.perigee_altitude_km() - 2.0 * elements.semi_major_axis_km() * 0.0006703).abs() < 1e-9);
+    }
+
     /// Tests the solution of Kepler's equation.
     #[test]
     fn test_solve_kepler() {
         let mean_anomaly: f64 = 1.0;
         let eccentricity: f64 = 0.1;
         let tol: f64 = 1e-8;
-        let e: f64 = solve_kepler(mean_anomaly, eccentricity, tol);
+        let e: f64 = solve_kepler(mean_anomaly, eccentricity, tol).unwrap();
         let expected_e: f64 = 1.0885977523978936;
         assert!((e - expected_e).abs() < tol, "Kepler's equation solution is not within the expected tolerance");
     }
@@ -257,6 +604,7 @@ mod tests {
     #[test]
     fn test_sgp4() {
         let tle = Tle {
+            name: None,
             line1: "1 25544U 98067A   21135.57634567  .00002418  00000-0  50843-4 0  9993".to_string(),
             line2: "2 25544  51.6443 126.6639 0006738  34.7758 325.3542 15.48913328283873".to_string(),
         };
@@ -268,4 +616,105 @@ mod tests {
         assert!(state.position.iter().all(|&x| x.abs() < 10000.0)); // Check if position values are reasonable
         assert!(state.velocity.iter().all(|&x| x.abs() < 10.0)); // Check if velocity values are reasonable
     }
+
+    /// Tests that batch propagation matches individual propagation.
+    #[test]
+    fn test_sgp4_batch() {
+        let tle = Tle {
+            name: None,
+            line1: "1 25544U 98067A   21135.57634567  .00002418  00000-0  50843-4 0  9993".to_string(),
+            line2: "2 25544  51.6443 126.6639 0006738  34.7758 325.3542 15.48913328283873".to_string(),
+        };
+
+        let elements = convert_satellite_data(&tle);
+        let times: Vec<f64> = (0..10).map(|i| i as f64 * 37.5 - 100.0).collect();
+        let states = sgp4_batch(&times, &elements);
+
+        assert_eq!(states.len(), times.len());
+        for (state, &tsince) in states.iter().zip(&times) {
+            assert_eq!(state.position, sgp4(tsince, &elements).position);
+            assert_eq!(state.velocity, sgp4(tsince, &elements).velocity);
+        }
+    }
+
+    /// Tests that orbital elements serialize in degrees and revolutions per day and round-trip.
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_units() {
+        let tle = Tle {
+            name: None,
+            line1: "1 25544U 98067A   21135.57634567  .00002418  00000-0  50843-4 0  9993".to_string(),
+            line2: "2 25544  51.6443 126.6639 0006738  34.7758 325.3542 15.48913328283873".to_string(),
+        };
+
+        let elements = convert_satellite_data(&tle);
+        let json: serde_json::Value = serde_json::to_value(&elements).unwrap();
+        assert!((json["inclination_deg"].as_f64().unwrap() - 51.6443).abs() < 1e-9);
+        assert!((json["mean_motion_rev_per_day"].as_f64().unwrap() - 15.489).abs() < 0.01);
+
+        let restored: OrbitalElements = serde_json::from_value(json).unwrap();
+        assert!((restored.inclination - elements.inclination).abs() < 1e-12);
+        assert!((restored.mean_motion - elements.mean_motion).abs() < 1e-12);
+
+        let state = sgp4(0.0, &elements);
+        let json: serde_json::Value = serde_json::to_value(state).unwrap();
+        assert_eq!(json["position_km"].as_array().unwrap().len(), 3);
+    }
+
+    /// Tests propagating to absolute instants around the epoch.
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_propagate_at() {
+        let tle = Tle {
+            name: None,
+            line1: "1 25544U 98067A   21135.57634567  .00002418  00000-0  50843-4 0  9993".to_string(),
+            line2: "2 25544  51.6443 126.6639 0006738  34.7758 325.3542 15.48913328283873".to_string(),
+        };
+
+        let elements = convert_satellite_data(&tle);
+        let when = elements.epoch() + chrono::Duration::minutes(90);
+
+        assert!((elements.minutes_since_epoch(when) - 90.0).abs() < 1e-9);
+        let state = elements.propagate_at(when).unwrap();
+        let expected = sgp4(90.0, &elements);
+        for k in 0..3 {
+            assert!((state.position[k] - expected.position[k]).abs() < 1e-6);
+            assert!((state.velocity[k] - expected.velocity[k]).abs() < 1e-9);
+        }
+
+        let before = elements.epoch() - chrono::Duration::milliseconds(1500);
+        assert!((elements.minutes_since_epoch(before) + 0.025).abs() < 1e-12);
+        assert!(elements.minutes_since_epoch(chrono::DateTime::<chrono::Utc>::MIN_UTC) < -1e11);
+        assert!(elements.propagate_at(chrono::DateTime::<chrono::Utc>::MAX_UTC).is_err());
+    }
+
+    /// Tests conversions between `hifitime` epochs and minutes since epoch.
+    #[cfg(feature = "hifitime")]
+    #[test]
+    fn test_hifitime_conversions() {
+        let elements = iss_elements();
+        let epoch = elements.hifitime_epoch();
+        assert_eq!(epoch, hifitime::Epoch::from_gregorian_utc(2008, 9, 20, 12, 25, 40, 104_192_000));
+
+        for tsince in [-1440.0, 0.0, 97.123456789, 1e5] {
+            let when = elements.hifitime_after(tsince);
+            assert!((elements.minutes_since_hifitime(when) - tsince).abs() < 1e-10);
+        }
+
+        // The leap second at the end of 2008 makes this interval one second longer than in UTC days.
+        let after_leap = hifitime::Epoch::from_gregorian_utc(2009, 1, 1, 0, 0, 0, 0);
+        let before_leap = hifitime::Epoch::from_gregorian_utc(2008, 12, 31, 0, 0, 0, 0);
+        let delta = elements.minutes_since_hifitime(after_leap) - elements.minutes_since_hifitime(before_leap);
+        assert!((delta - (1440.0 + 1.0 / 60.0)).abs() < 1e-9);
+
+        let state = elements.propagate_hifitime(elements.hifitime_after(90.0)).unwrap();
+        let expected = sgp4(90.0, &elements);
+        for k in 0..3 {
+            assert!((state.position[k] - expected.position[k]).abs() < 1e-6);
+        }
+        let mut decayed = iss_elements();
+        // Perigee below the surface.
+        decayed.eccentricity = 0.065;
+        assert!((0..92).any(|k| decayed.propagate_hifitime(decayed.hifitime_after(k as f64)).is_err()));
+    }
 }
@@ -0,0 +1,150 @@
+//! Orbital lifetime estimation.
+//! The decay of a near-circular orbit is integrated over its mean altitude, da/dt = -(Cd·A/m) ρ
+//! √(μa), with the exponential atmosphere scaled for the solar activity. This is a quick estimate
+//! for planning, typically within a factor of two; `reentry` predicts a dated re-entry with SGP4.
+
+use crate::atmosphere;
+use crate::keplerian::EARTH_MU;
+use crate::numerical::Drag;
+use crate::{OrbitalElements, XKMPER};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Altitude step of the integration in kilometers.
+const ALTITUDE_STEP: f64 = 1.0;
+
+/// Level of solar activity assumed over the lifetime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SolarActivity {
+    /// Solar minimum: the thermosphere contracts.
+    Low,
+    /// Average conditions of the reference atmosphere.
+    #[default]
+    Moderate,
+    /// Solar maximum: the thermosphere expands.
+    High,
+}
+
+impl SolarActivity {
+    /// Returns the factor applied to the density of the reference atmosphere.
+    pub fn density_factor(&self) -> f64 {
+        match self {
+            SolarActivity::Low => 0.5,
+            SolarActivity::Moderate => 1.0,
+            SolarActivity::High => 2.5,
+        }
+    }
+}
+
+/// Holds the settings of a lifetime estimation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LifetimeConfig {
+    /// Solar activity assumed over the lifetime.
+    pub solar_activity: SolarActivity,
+    /// Drag coefficient times area over mass (Cd·A/m) in m²/kg, instead of the one derived from B*.
+    pub ballistic_coefficient: Option<f64>,
+    /// Mean altitude at which the object is considered re-entered, in kilometers.
+    pub end_altitude: f64,
+    /// Longest lifetime in years, beyond which the orbit is considered stable.
+    pub max_years: f64,
+}
+
+impl Default for LifetimeConfig {
+    fn default() -> LifetimeConfig {
+        LifetimeConfig {
+            solar_activity: SolarActivity::default(),
+            ballistic_coefficient: None,
+            end_altitude: 100.0,
+            max_years: 200.0,
+        }
+    }
+}
+
+impl OrbitalElements {
+    /// Estimates the remaining orbital lifetime.
+    ///
+    /// # Arguments
+    /// * `config` - Solar activity, drag and limits of the estimation.
+    ///
+    /// # Returns
+    /// * The lifetime in days from the epoch, or `None` if the object has no drag or stays up
+    ///   longer than the longest lifetime.
+    pub fn estimate_lifetime(&self, config: &LifetimeConfig) -> Option<f64> {
+        let ballistic: f64 = config.ballistic_coefficient.unwrap_or_else(|| Drag::from_bstar(self.bstar).ballistic_coefficient);
+        if ballistic <= 0.0 {
+            return None;
+        }
+        let max_seconds: f64 = config.max_years * 365.25 * 86400.0;
+        let mut altitude: f64 = self.semi_major_axis_km() - XKMPER;
+        let mut seconds: f64 = 0.0;
+        while altitude > config.end_altitude {
+            let step: f64 = ALTITUDE_STEP.min(altitude - config.end_altitude);
+            let middle: f64 = altitude - 0.5 * step;
+            // Cd·A/m ρ is in 1/m, hence the factor to 1/km.
+            let density: f64 = atmosphere::density(middle) * config.solar_activity.density_factor();
+            let rate: f64 = ballistic * density * 1e3 * (EARTH_MU * (XKMPER + middle)).sqrt();
+            seconds += step / rate;
+            if seconds > max_seconds {
+                return None;
+            }
+            altitude -= step;
+        }
+        Some(seconds / 86400.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reentry::Reentry;
+    use crate::test_support::iss_elements;
+
+    /// Returns the elements of the ISS with a drag term.
+    fn iss(bstar: f64) -> OrbitalElements {
+        let mut elements: OrbitalElements = iss_elements();
+        elements.bstar = bstar;
+        elements
+    }
+
+    /// Tests the effect of the solar activity and of the drag, and compares with the SGP4 decay.
+    #[test]
+    fn test_lifetime() {
+        let elements: OrbitalElements = iss(2e-3);
+        let lifetime = |solar_activity: SolarActivity| {
+            elements
+                .estimate_lifetime(&LifetimeConfig {
+                    solar_activity,
+                    ..LifetimeConfig::default()
+                })
+                .unwrap()
+        };
+        let moderate: f64 = lifetime(SolarActivity::Moderate);
+        assert!(lifetime(SolarActivity::High) < moderate && moderate < lifetime(SolarActivity::Low));
+        assert!((lifetime(SolarActivity::Low) / moderate - 2.0).abs() < 1e-6);
+
+        let sgp4: f64 = Reentry::default().estimate(&elements).unwrap().epoch_jd - elements.epoch_jd();
+        assert!(moderate > 0.5 * sgp4 && moderate < 2.0 * sgp4, "{} {}", moderate, sgp4);
+
+        // Doubling the drag halves the lifetime.
+        let doubled: f64 = iss(4e-3).estimate_lifetime(&LifetimeConfig::default()).unwrap();
+        assert!((moderate / doubled - 2.0).abs() < 1e-6);
+    }
+
+    /// Tests the limits of the estimation.
+    #[test]
+    fn test_lifetime_limits() {
+        assert_eq!(iss(0.0).estimate_lifetime(&LifetimeConfig::default()), None);
+        assert_eq!(iss(-1e-4).estimate_lifetime(&LifetimeConfig::default()), None);
+        let config = LifetimeConfig {
+            ballistic_coefficient: Some(0.01),
+            max_years: 0.1,
+            ..LifetimeConfig::default()
+        };
+        assert_eq!(iss(0.0).estimate_lifetime(&config), None);
+        let lifetime: f64 = iss(0.0).estimate_lifetime(&LifetimeConfig { max_years: 100.0, ..config }).unwrap();
+        // A few months at 350 km for 0.01 m²/kg.
+        assert!(lifetime > 0.1 * 365.25 && lifetime < 100.0 * 365.25, "{}", lifetime);
+    }
+}
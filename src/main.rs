@@ -1,10 +1,514 @@
-//! Main application for comparing satellite positions and velocities using SGP4 model.
-//! This application reads Two-Line Element (TLE) data and compares the computed satellite state vectors
-//! with reference data.
+//! Command-line interface of the library.
+//! `orbitalrs <subcommand>` reads element sets from a file or a URL, selects satellites and
+//! works over a time range. The `compare` subcommand checks the SGP4 states against the reference
+//! vectors of a test file, as the application always did.
 
-use sgp4_rust::{Tle, convert_satellite_data, sgp4, OrbitalElements};
-use std::fs::read_to_string;
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use colored::*;
+use sgp4_rust::export::iso_date;
+use sgp4_rust::frames::Frame;
+use sgp4_rust::passes::{Pass, passes_above};
+use sgp4_rust::topocentric::{LookAngles, Observer};
+use sgp4_rust::{Ephemeris, OrbitalElements, Satellite, StateVector, Tle, convert_satellite_data, sgp4, time};
+use std::error::Error;
+use std::fs::{File, read_to_string};
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Longest pass prediction, in days: the search samples every satellite along the whole span.
+const MAX_PASS_DAYS: f64 = 366.0;
+
+/// Propagates satellites from TLE and OMM element sets with SGP4/SDP4.
+#[derive(Parser)]
+#[command(name = "orbitalrs", version, about)]
+struct Cli {
+    /// The subcommand to run.
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// Subcommands of the CLI.
+#[derive(Subcommand)]
+enum Command {
+    /// Compares SGP4 states with the reference vectors of a test file.
+    Compare(CompareArgs),
+    /// Prints the element sets of the selected satellites.
+    Info(InfoArgs),
+    /// Propagates the selected satellites over a time range.
+    Propagate(PropagateArgs),
+    /// Predicts the passes of the selected satellites over a ground station.
+    Passes(PassesArgs),
+    /// Follows the selected satellites from a ground station in real time.
+    Track(TrackArgs),
+}
+
+/// Arguments selecting satellites from an element set file or URL.
+#[derive(Args)]
+struct InputArgs {
+    /// File or http(s) URL holding TLE sets or GP JSON records.
+    #[arg(long = "tle", value_name = "FILE|URL")]
+    source: String,
+    /// Keeps the satellites whose name contains this text, ignoring case.
+    #[arg(long)]
+    name: Option<String>,
+    /// Keeps the satellites with this NORAD catalog number; can be repeated.
+    #[arg(long = "norad", value_name = "ID")]
+    norad_ids: Vec<u32>,
+}
+
+/// Time of a range, absolute or relative to the epoch of an element set.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TimeSpec {
+    /// A Julian date (UTC).
+    Date(f64),
+    /// Minutes since the epoch of the element set.
+    SinceEpoch(f64),
+}
+
+impl TimeSpec {
+    /// Converts the time into minutes since the epoch of an element set.
+    ///
+    /// # Arguments
+    /// * `epoch_jd` - The epoch of the element set as a Julian date (UTC).
+    ///
+    /// # Returns
+    /// * The time since epoch in minutes.
+    fn tsince(&self, epoch_jd: f64) -> f64 {
+        match *self {
+            TimeSpec::Date(jd) => (jd - epoch_jd) * 1440.0,
+            TimeSpec::SinceEpoch(minutes) => minutes,
+        }
+    }
+}
+
+/// Arguments bounding a time range.
+#[derive(Args)]
+struct TimeRangeArgs {
+    /// Start of the range: an ISO 8601 UTC date such as 2024-03-01T12:00:00, or minutes since
+    /// the epoch such as -90.
+    #[arg(long, value_parser = parse_time, allow_hyphen_values = true)]
+    from: Option<TimeSpec>,
+    /// End of the range, in the same forms as the start.
+    #[arg(long, value_parser = parse_time, allow_hyphen_values = true)]
+    to: Option<TimeSpec>,
+}
+
+impl TimeRangeArgs {
+    /// Checks whether a time is within the range, open on the missing bounds.
+    ///
+    /// # Arguments
+    /// * `tsince` - Time since epoch in minutes.
+    /// * `epoch_jd` - The epoch of the element set as a Julian date (UTC).
+    fn contains(&self, tsince: f64, epoch_jd: f64) -> bool {
+        self.from.is_none_or(|from| tsince >= from.tsince(epoch_jd)) && self.to.is_none_or(|to| tsince <= to.tsince(epoch_jd))
+    }
+
+    /// Returns the bounds of the range, starting at the epoch and lasting a default span when they
+    /// are missing.
+    ///
+    /// # Arguments
+    /// * `epoch_jd` - The epoch of the element set as a Julian date (UTC).
+    /// * `span` - Duration of the range in minutes when the end is missing.
+    ///
+    /// # Returns
+    /// * The start and the end of the range as Julian dates (UTC).
+    fn bounds(&self, epoch_jd: f64, span: f64) -> (f64, f64) {
+        let start: f64 = epoch_jd + self.from.map_or(0.0, |from| from.tsince(epoch_jd)) / 1440.0;
+        let end: f64 = self.to.map_or(start + span / 1440.0, |to| epoch_jd + to.tsince(epoch_jd) / 1440.0);
+        (start, end)
+    }
+}
+
+/// Frame of the propagated states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFrame {
+    /// True Equator Mean Equinox, the frame of SGP4.
+    Teme,
+    /// Earth-fixed frame.
+    Ecef,
+    /// J2000 inertial frame.
+    J2000,
+    /// Geodetic latitude, longitude and altitude on the WGS84 ellipsoid.
+    Geodetic,
+}
+
+impl OutputFrame {
+    /// Returns the frame of the state vectors, or `None` for geodetic coordinates.
+    fn frame(&self) -> Option<Frame> {
+        match self {
+            OutputFrame::Teme => Some(Frame::Teme),
+            OutputFrame::Ecef => Some(Frame::Ecef),
+            OutputFrame::J2000 => Some(Frame::J2000),
+            OutputFrame::Geodetic => None,
+        }
+    }
+
+    /// Returns the name of the frame written in the output.
+    fn name(&self) -> &'static str {
+        self.frame().map_or("GEODETIC", |frame| frame.name())
+    }
+
+    /// Returns the names of the columns, with their units.
+    fn columns(&self) -> &'static [&'static str] {
+        match self {
+            OutputFrame::Geodetic => &["latitude_deg", "longitude_deg", "altitude_km"],
+            _ => &["x_km", "y_km", "z_km", "vx_km_s", "vy_km_s", "vz_km_s"],
+        }
+    }
+
+    /// Converts a TEME state into the values of the columns.
+    ///
+    /// # Arguments
+    /// * `jd` - The Julian date (UTC) of the state.
+    /// * `state` - State vector in the TEME frame (km, km/s).
+    ///
+    /// # Returns
+    /// * The values, in the order of `columns`.
+    fn values(&self, jd: f64, state: &StateVector) -> Vec<f64> {
+        let Some(frame) = self.frame() else {
+            let geodetic = state.to_geodetic(time::gmst(jd));
+            return vec![geodetic.latitude.to_degrees(), geodetic.longitude.to_degrees(), geodetic.altitude];
+        };
+        let state: StateVector = frame.from_teme(state, jd);
+        state.position.iter().chain(&state.velocity).copied().collect()
+    }
+}
+
+/// Format of the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// Aligned table for the terminal.
+    Table,
+    /// Comma-separated values with a header row.
+    Csv,
+    /// JSON document.
+    Json,
+}
+
+/// Arguments choosing the format and the destination of the output.
+#[derive(Args)]
+struct OutputArgs {
+    /// Format of the output.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+    /// Writes the output to a file instead of the standard output.
+    #[arg(long, short = 'o', value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+impl OutputArgs {
+    /// Opens the destination of the output.
+    fn writer(&self) -> io::Result<Box<dyn Write>> {
+        open_output(self.output.as_ref())
+    }
+}
+
+/// Opens the destination of an output, without colors when it is a file.
+///
+/// # Arguments
+/// * `path` - The file to create, or `None` for the standard output.
+///
+/// # Returns
+/// * The writer, or the error creating the file.
+fn open_output(path: Option<&PathBuf>) -> io::Result<Box<dyn Write>> {
+    match path {
+        Some(path) => {
+            colored::control::set_override(false);
+            Ok(Box::new(BufWriter::new(File::create(path)?)))
+        }
+        None => Ok(Box::new(io::stdout().lock())),
+    }
+}
+
+/// Format of a pass prediction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum PassFormat {
+    /// Aligned table for the terminal.
+    Table,
+    /// Comma-separated values with a header row.
+    Csv,
+    /// JSON document.
+    Json,
+    /// iCalendar file with one event per pass.
+    Ics,
+}
+
+/// Arguments locating a ground station.
+#[derive(Args)]
+struct StationArgs {
+    /// Geodetic latitude of the station in degrees, positive north.
+    #[arg(long, allow_hyphen_values = true)]
+    lat: f64,
+    /// Longitude of the station in degrees, positive east.
+    #[arg(long, allow_hyphen_values = true)]
+    lon: f64,
+    /// Altitude of the station above the WGS84 ellipsoid in kilometers.
+    #[arg(long, default_value_t = 0.0, allow_hyphen_values = true)]
+    alt: f64,
+}
+
+impl StationArgs {
+    /// Builds the observer at the station.
+    fn observer(&self) -> Result<Observer, Box<dyn Error>> {
+        if !(-90.0..=90.0).contains(&self.lat) || !(-180.0..=360.0).contains(&self.lon) {
+            return Err(format!("invalid station coordinates {}, {}", self.lat, self.lon).into());
+        }
+        Ok(Observer::new(self.lat, self.lon, self.alt))
+    }
+}
+
+/// Arguments of the `compare` subcommand.
+#[derive(Args)]
+struct CompareArgs {
+    /// Test file with a TLE followed by the reference positions and velocities.
+    #[arg(default_value = "data/sample.txt")]
+    file: PathBuf,
+    /// Range of the compared times.
+    #[command(flatten)]
+    range: TimeRangeArgs,
+    /// Format and destination of the comparison.
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+/// Arguments of the `propagate` subcommand.
+#[derive(Args)]
+struct PropagateArgs {
+    /// Element sets and selected satellites.
+    #[command(flatten)]
+    input: InputArgs,
+    /// Range of the ephemeris, from the epoch over one day by default.
+    #[command(flatten)]
+    range: TimeRangeArgs,
+    /// Time between two states, in seconds or with a unit: 30s, 10min, 1h or 1d.
+    #[arg(long, value_parser = parse_duration, default_value = "60s")]
+    step: f64,
+    /// Frame of the states.
+    #[arg(long, value_enum, default_value_t = OutputFrame::Teme)]
+    frame: OutputFrame,
+    /// Format and destination of the ephemeris.
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+/// Arguments of the `passes` subcommand.
+#[derive(Args)]
+struct PassesArgs {
+    /// Element sets and selected satellites.
+    #[command(flatten)]
+    input: InputArgs,
+    /// Location of the ground station.
+    #[command(flatten)]
+    station: StationArgs,
+    /// Elevation mask in degrees: passes start and end at this elevation.
+    #[arg(long, value_parser = parse_elevation, default_value_t = 0.0, allow_hyphen_values = true)]
+    min_elevation: f64,
+    /// Start of the prediction, in the forms of the time ranges; now by default.
+    #[arg(long, value_parser = parse_time, allow_hyphen_values = true)]
+    from: Option<TimeSpec>,
+    /// Number of days of the prediction, at most one year.
+    #[arg(long, value_parser = parse_days, default_value_t = 1.0)]
+    days: f64,
+    /// Format of the passes.
+    #[arg(long, value_enum, default_value_t = PassFormat::Table)]
+    format: PassFormat,
+    /// Writes the passes to a file instead of the standard output.
+    #[arg(long, short = 'o', value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+/// Arguments of the `track` subcommand.
+#[derive(Args)]
+struct TrackArgs {
+    /// Element sets and selected satellites.
+    #[command(flatten)]
+    input: InputArgs,
+    /// Location of the ground station.
+    #[command(flatten)]
+    station: StationArgs,
+    /// Time between two updates, in seconds or with a unit: 1s, 10s or 1min.
+    #[arg(long, value_parser = parse_duration, default_value = "1s")]
+    rate: f64,
+    /// Stops after this number of updates instead of running until interrupted.
+    #[arg(long)]
+    count: Option<u64>,
+    /// Redraws the table in place instead of appending rows; only for the table format.
+    #[arg(long)]
+    refresh: bool,
+    /// Format and destination of the samples.
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+/// Arguments of the `info` subcommand.
+#[derive(Args)]
+struct InfoArgs {
+    /// Element sets and selected satellites.
+    #[command(flatten)]
+    input: InputArgs,
+    /// Format and destination of the element sets.
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+/// Parses a time given on the command line.
+///
+/// # Arguments
+/// * `text` - An ISO 8601 UTC date (`YYYY-MM-DD`, optionally followed by `Thh:mm` or
+///   `Thh:mm:ss` and `Z`), or a number of minutes since epoch.
+///
+/// # Returns
+/// * The time, or a message explaining the expected forms.
+fn parse_time(text: &str) -> Result<TimeSpec, String> {
+    if let Ok(minutes) = text.parse::<f64>() {
+        if !minutes.is_finite() {
+            return Err(format!("invalid time '{}', minutes since epoch must be finite", text));
+        }
+        return Ok(TimeSpec::SinceEpoch(minutes));
+    }
+    let invalid = || format!("invalid time '{}', expected YYYY-MM-DDThh:mm:ss or minutes since epoch", text);
+    let text: &str = text.trim_end_matches('Z');
+    let (date, clock): (&str, &str) = text.split_once(['T', ' ']).unwrap_or((text, "00:00"));
+    let date: Vec<&str> = date.split('-').collect();
+    let clock: Vec<&str> = clock.split(':').collect();
+    if date.len() != 3 || !(2..=3).contains(&clock.len()) {
+        return Err(invalid());
+    }
+    let year: i32 = date[0].parse().map_err(|_| invalid())?;
+    let month: u32 = date[1].parse().map_err(|_| invalid())?;
+    let day: u32 = date[2].parse().map_err(|_| invalid())?;
+    let hour: u32 = clock[0].parse().map_err(|_| invalid())?;
+    let minute: u32 = clock[1].parse().map_err(|_| invalid())?;
+    let second: f64 = clock.get(2).map_or(Ok(0.0), |second| second.parse()).map_err(|_| invalid())?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 || minute > 59 || !(0.0..61.0).contains(&second) {
+        return Err(invalid());
+    }
+    Ok(TimeSpec::Date(time::julian_date(year, month, day, hour, minute, second)))
+}
+
+/// Returns the current time of the system clock.
+///
+/// # Returns
+/// * The current Julian date (UTC).
+fn now_jd() -> f64 {
+    let unix: f64 = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).map_or(0.0, |elapsed| elapsed.as_secs_f64());
+    time::JD_UNIX_EPOCH + unix / 86400.0
+}
+
+/// Parses a duration given on the command line.
+///
+/// # Arguments
+/// * `text` - A positive number of seconds, optionally followed by `s`, `min`, `h` or `d`.
+///
+/// # Returns
+/// * The duration in seconds, or a message explaining the expected form.
+fn parse_duration(text: &str) -> Result<f64, String> {
+    let split: usize = text.find(|c: char| c.is_ascii_alphabetic()).unwrap_or(text.len());
+    let (value, unit): (&str, &str) = text.split_at(split);
+    let scale: f64 = match unit {
+        "" | "s" => 1.0,
+        "min" => 60.0,
+        "h" => 3600.0,
+        "d" => 86400.0,
+        _ => return Err(format!("invalid unit '{}', expected s, min, h or d", unit)),
+    };
+    match value.trim().parse::<f64>() {
+        // The scaled value is checked too, so that a huge number of days cannot overflow.
+        Ok(value) if value > 0.0 && (value * scale).is_finite() => Ok(value * scale),
+        _ => Err(format!("invalid duration '{}', expected a positive number such as 60s", text)),
+    }
+}
+
+/// Parses the number of days of a pass prediction.
+///
+/// # Arguments
+/// * `text` - A positive number of days, at most `MAX_PASS_DAYS`.
+///
+/// # Returns
+/// * The number of days, or a message explaining the accepted range.
+fn parse_days(text: &str) -> Result<f64, String> {
+    match text.trim().parse::<f64>() {
+        Ok(days) if days > 0.0 && days <= MAX_PASS_DAYS => Ok(days),
+        _ => Err(format!("invalid number of days '{}', expected a number in (0, {}]", text, MAX_PASS_DAYS)),
+    }
+}
+
+/// Parses an elevation mask given on the command line.
+///
+/// # Arguments
+/// * `text` - An elevation in degrees, between -90 and 90.
+///
+/// # Returns
+/// * The elevation in degrees, or a message explaining the accepted range.
+fn parse_elevation(text: &str) -> Result<f64, String> {
+    match text.trim().parse::<f64>() {
+        Ok(elevation) if (-90.0..=90.0).contains(&elevation) => Ok(elevation),
+        _ => Err(format!("invalid elevation '{}', expected degrees in [-90, 90]", text)),
+    }
+}
+
+/// Reads the contents of an element set source.
+///
+/// # Arguments
+/// * `source` - A file path, or an http(s) URL with the `http` feature.
+///
+/// # Returns
+/// * The contents, or the reason why they cannot be read.
+fn read_source(source: &str) -> Result<String, Box<dyn Error>> {
+    if source.starts_with("http://") || source.starts_with("https://") {
+        #[cfg(feature = "http")]
+        return Ok(ureq::get(source).call()?.body_mut().read_to_string()?);
+        #[cfg(not(feature = "http"))]
+        return Err("reading a URL requires the http feature".into());
+    }
+    Ok(read_to_string(source).map_err(|e| format!("cannot read {}: {}", source, e))?)
+}
+
+/// Parses element sets given as TLE text or GP JSON records.
+///
+/// # Arguments
+/// * `text` - The element sets.
+///
+/// # Returns
+/// * The satellites, or the reason why the records cannot be parsed.
+fn parse_satellites(text: &str) -> Result<Vec<Satellite>, Box<dyn Error>> {
+    if text.trim_start().starts_with(['[', '{']) {
+        return Ok(sgp4_rust::omm::parse_json(text)?.iter().map(Satellite::from_fields).collect());
+    }
+    Ok(sgp4_rust::tle::parse_tle_file(text.as_bytes())?.iter().map(Satellite::from_tle).collect())
+}
+
+/// Keeps the satellites matching the selection arguments.
+///
+/// # Arguments
+/// * `satellites` - The satellites read from the source.
+/// * `input` - The selection arguments.
+///
+/// # Returns
+/// * The selected satellites, or an error if none is selected.
+fn select(satellites: Vec<Satellite>, input: &InputArgs) -> Result<Vec<Satellite>, Box<dyn Error>> {
+    let name: Option<String> = input.name.as_ref().map(|name| name.to_lowercase());
+    let selected: Vec<Satellite> = satellites
+        .into_iter()
+        .filter(|satellite| input.norad_ids.is_empty() || input.norad_ids.contains(&satellite.norad_id))
+        .filter(|satellite| match &name {
+            Some(name) => satellite.name.as_ref().is_some_and(|own| own.to_lowercase().contains(name)),
+            None => true,
+        })
+        .collect();
+    if selected.is_empty() {
+        return Err(format!("no satellite of {} matches the selection", input.source).into());
+    }
+    Ok(selected)
+}
+
+/// Reads the element sets of a source and keeps the selected satellites.
+fn load_satellites(input: &InputArgs) -> Result<Vec<Satellite>, Box<dyn Error>> {
+    select(parse_satellites(&read_source(&input.source)?)?, input)
+}
 
 /// Displays a comparison of satellite positions.
 ///
@@ -12,12 +516,13 @@ use colored::*;
 /// at various times since epoch (TSINCE). The differences are color-coded based on their magnitude.
 ///
 /// # Arguments
+/// * `out` - Destination of the table.
 /// * `tsince_values` - A slice of time values since epoch in minutes.
 /// * `positions` - A slice of reference positions corresponding to the time values.
 /// * `elements` - The orbital elements of the satellite.
-fn afficher_positions(tsince_values: &[f64], positions: &[[f64; 3]], elements: &OrbitalElements) {
-    println!("\nComparaison des positions :");
-    println!("┌── TSINCE ──┬───────────── REF POSITION [km] ────────┬───────────── SIMULATED [km] ───────────┬──────────── DELTA [km] ──────────┬─ Total Δ [km] ─┐");
+fn afficher_positions(out: &mut dyn Write, tsince_values: &[f64], positions: &[[f64; 3]], elements: &OrbitalElements) -> io::Result<()> {
+    writeln!(out, "\nComparaison des positions :")?;
+    writeln!(out, "┌── TSINCE ──┬───────────── REF POSITION [km] ────────┬───────────── SIMULATED [km] ───────────┬──────────── DELTA [km] ──────────┬─ Total Δ [km] ─┐")?;
 
     for (i, &tsince) in tsince_values.iter().enumerate() {
         let expected = positions[i];
@@ -47,17 +552,18 @@ fn afficher_positions(tsince_values: &[f64], positions: &[[f64; 3]], elements: &
             }
         };
 
-        println!(
+        writeln!(
+            out,
             "│ {:>10.3} │ X={:>10.2} Y={:>10.2} Z={:>10.2} │ X={:>10.2} Y={:>10.2} Z={:>10.2} │ ΔX={} ΔY={} ΔZ={} │   ΔR={}   │",
             tsince,
             expected[0], expected[1], expected[2],
             state.position[0], state.position[1], state.position[2],
             fmt(dx), fmt(dy), fmt(dz),
             fmt_total(dx + dy + dz),
-        );
+        )?;
     }
 
-    println!("└────────────┴────────────────────────────────────────┴────────────────────────────────────────┴──────────────────────────────────┴────────────────┘");
+    writeln!(out, "└────────────┴────────────────────────────────────────┴────────────────────────────────────────┴──────────────────────────────────┴────────────────┘")
 }
 
 /// Displays a comparison of satellite velocities.
@@ -66,12 +572,13 @@ fn afficher_positions(tsince_values: &[f64], positions: &[[f64; 3]], elements: &
 /// at various times since epoch (TSINCE). The differences are color-coded based on their magnitude.
 ///
 /// # Arguments
+/// * `out` - Destination of the table.
 /// * `tsince_values` - A slice of time values since epoch in minutes.
 /// * `velocities` - A slice of reference velocities corresponding to the time values.
 /// * `elements` - The orbital elements of the satellite.
-fn afficher_vitesses(tsince_values: &[f64], velocities: &[[f64; 3]], elements: &OrbitalElements) {
-    println!("\nComparaison des vitesses :");
-    println!("┌─── TSINCE ────┬──────── REF VELOCITY [km/s] ──────┬──────────── SIMULATED [km/s] ───────────┬───────── DELTA [km/s] ───────┬─ Total Δ [km] ─┐");
+fn afficher_vitesses(out: &mut dyn Write, tsince_values: &[f64], velocities: &[[f64; 3]], elements: &OrbitalElements) -> io::Result<()> {
+    writeln!(out, "\nComparaison des vitesses :")?;
+    writeln!(out, "┌─── TSINCE ────┬──────── REF VELOCITY [km/s] ──────┬──────────── SIMULATED [km/s] ───────────┬───────── DELTA [km/s] ───────┬─ Total Δ [km] ─┐")?;
 
     for (i, &tsince) in tsince_values.iter().enumerate() {
         let reference = velocities[i];
@@ -101,34 +608,82 @@ fn afficher_vitesses(tsince_values: &[f64], velocities: &[[f64; 3]], elements: &
             }
         };
 
-        println!(
+        writeln!(
+            out,
             "│ t = {:>5} min │ Ref = [{:>7.4}, {:>7.4}, {:>7.4}] │ Simulated = [{:>7.4}, {:>7.4}, {:>7.4}] │ Δ = [{}, {}, {}] │    ΔR={}   │",
             tsince,
             reference[0], reference[1], reference[2],
             state.velocity[0], state.velocity[1], state.velocity[2],
             fmt(dvx), fmt(dvy), fmt(dvz),
             fmt_total(dvx + dvy + dvz),
-        );
+        )?;
     }
 
-    println!("└───────────────┴───────────────────────────────────┴─────────────────────────────────────────┴──────────────────────────────┴────────────────┘");
+    writeln!(out, "└───────────────┴───────────────────────────────────┴─────────────────────────────────────────┴──────────────────────────────┴────────────────┘")
 }
 
-/// Main function to read TLE data, compute satellite state vectors, and display comparisons.
+/// Writes the comparison of positions and velocities as CSV, one row per reference time.
 ///
-/// This function reads the TLE data from a file, converts it to orbital elements, and then
-/// extracts the reference positions and velocities. It then displays the comparisons of these
-/// positions and velocities with the computed values.
-fn main() {
-    let input = read_to_string("data/sample.txt").expect("Could not read file");
+/// # Arguments
+/// * `out` - Destination of the table.
+/// * `tsince_values` - A slice of time values since epoch in minutes.
+/// * `positions` - A slice of reference positions corresponding to the time values.
+/// * `velocities` - A slice of reference velocities corresponding to the time values.
+/// * `elements` - The orbital elements of the satellite.
+fn afficher_csv(out: &mut dyn Write, tsince_values: &[f64], positions: &[[f64; 3]], velocities: &[[f64; 3]], elements: &OrbitalElements) -> io::Result<()> {
+    writeln!(out, "tsince_min,x_ref_km,y_ref_km,z_ref_km,vx_ref_km_s,vy_ref_km_s,vz_ref_km_s,x_km,y_km,z_km,vx_km_s,vy_km_s,vz_km_s")?;
+    for ((&tsince, position), velocity) in tsince_values.iter().zip(positions).zip(velocities) {
+        let state = sgp4(tsince, elements);
+        let values: Vec<String> = [tsince]
+            .iter()
+            .chain(position)
+            .chain(velocity)
+            .chain(&state.position)
+            .chain(&state.velocity)
+            .map(|value| value.to_string())
+            .collect();
+        writeln!(out, "{}", values.join(","))?;
+    }
+    Ok(())
+}
+
+/// Prints the comparison of positions and velocities as a JSON report.
+///
+/// # Arguments
+/// * `out` - Destination of the report.
+/// * `tsince_values` - A slice of time values since epoch in minutes.
+/// * `positions` - A slice of reference positions corresponding to the time values.
+/// * `velocities` - A slice of reference velocities corresponding to the time values.
+/// * `elements` - The orbital elements of the satellite.
+fn afficher_json(out: &mut dyn Write, tsince_values: &[f64], positions: &[[f64; 3]], velocities: &[[f64; 3]], elements: &OrbitalElements) -> io::Result<()> {
+    use sgp4_rust::StateVector;
+    use sgp4_rust::export::json::{Comparison, write_report};
+
+    let comparisons: Vec<Comparison> = tsince_values
+        .iter()
+        .zip(positions.iter().zip(velocities))
+        .map(|(&tsince, (&position, &velocity))| Comparison {
+            tsince,
+            reference: StateVector { position, velocity },
+            computed: sgp4(tsince, elements),
+        })
+        .collect();
+    write_report(&mut &mut *out, elements.epoch_jd(), &comparisons)
+}
+
+/// Runs the `compare` subcommand.
+///
+/// This function reads the TLE data from the test file, converts it to orbital elements, and
+/// then extracts the reference positions and velocities within the time range. It then displays
+/// the comparisons of these positions and velocities with the computed values.
+fn compare(args: &CompareArgs) -> Result<(), Box<dyn Error>> {
+    let input = read_to_string(&args.file).map_err(|e| format!("cannot read {}: {}", args.file.display(), e))?;
     let lines: Vec<&str> = input.lines().collect();
 
-    let tle = Tle {
-        line1: lines[0].to_string(),
-        line2: lines[1].to_string(),
-    };
+    let tle = Tle::from_lines(&lines[..2.min(lines.len())])?;
 
     let elements = convert_satellite_data(&tle);
+    let epoch_jd: f64 = elements.epoch_jd();
 
     // Extract TSINCE, positions, and velocities
     let mut tsince_values: Vec<f64> = Vec::new();
@@ -136,7 +691,7 @@ fn main() {
     let mut velocities: Vec<[f64; 3]> = Vec::new();
 
     let mut mode = "";
-    for line in &lines[3..] {
+    for line in lines.iter().skip(3) {
         let line = line.trim();
         if line.is_empty() {
             continue;
@@ -165,6 +720,713 @@ fn main() {
         }
     }
 
-    afficher_positions(&tsince_values, &positions, &elements);
-    afficher_vitesses(&tsince_values, &velocities, &elements);
+    // Velocities are listed in the order of the positions, so both are filtered by index.
+    let kept: Vec<usize> = (0..tsince_values.len().min(velocities.len())).filter(|&i| args.range.contains(tsince_values[i], epoch_jd)).collect();
+    let tsince_values: Vec<f64> = kept.iter().map(|&i| tsince_values[i]).collect();
+    let positions: Vec<[f64; 3]> = kept.iter().map(|&i| positions[i]).collect();
+    let velocities: Vec<[f64; 3]> = kept.iter().map(|&i| velocities[i]).collect();
+
+    let mut out: Box<dyn Write> = args.output.writer()?;
+    match args.output.format {
+        OutputFormat::Table => {
+            afficher_positions(&mut out, &tsince_values, &positions, &elements)?;
+            afficher_vitesses(&mut out, &tsince_values, &velocities, &elements)?;
+        }
+        OutputFormat::Csv => afficher_csv(&mut out, &tsince_values, &positions, &velocities, &elements)?,
+        OutputFormat::Json => afficher_json(&mut out, &tsince_values, &positions, &velocities, &elements)?,
+    }
+    Ok(out.flush()?)
+}
+
+/// Runs the `info` subcommand, printing the identity and the main elements of each satellite.
+fn info(args: &InfoArgs) -> Result<(), Box<dyn Error>> {
+    let satellites: Vec<Satellite> = load_satellites(&args.input)?;
+    let mut out: Box<dyn Write> = args.output.writer()?;
+    match args.output.format {
+        OutputFormat::Table => {
+            writeln!(out, "{:<24} {:>7} {:<9} {:<23} {:>9} {:>8} {:>9} {:>9} {:>9}", "NAME", "NORAD", "INTLDES", "EPOCH (UTC)", "PERIOD", "INCL", "ECC", "PERIGEE", "APOGEE")?;
+            for satellite in &satellites {
+                let elements: &OrbitalElements = &satellite.elements;
+                writeln!(
+                    out,
+                    "{:<24} {:>7} {:<9} {:<23} {:>9.2} {:>8.4} {:>9.7} {:>9.1} {:>9.1}",
+                    satellite.name.as_deref().unwrap_or("-"),
+                    satellite.norad_id,
+                    satellite.intl_designator,
+                    iso_date(satellite.epoch_jd),
+                    satellite.period(),
+                    elements.inclination.to_degrees(),
+                    elements.eccentricity,
+                    elements.perigee_altitude_km(),
+                    elements.apogee_altitude_km(),
+                )?;
+            }
+        }
+        OutputFormat::Csv => {
+            writeln!(out, "name,norad_id,intl_designator,epoch,period_min,inclination_deg,eccentricity,perigee_km,apogee_km")?;
+            for satellite in &satellites {
+                let elements: &OrbitalElements = &satellite.elements;
+                writeln!(
+                    out,
+                    "\"{}\",{},{},{},{},{},{},{},{}",
+                    satellite.name.as_deref().unwrap_or("").replace('"', "\"\""),
+                    satellite.norad_id,
+                    satellite.intl_designator,
+                    iso_date(satellite.epoch_jd),
+                    satellite.period(),
+                    elements.inclination.to_degrees(),
+                    elements.eccentricity,
+                    elements.perigee_altitude_km(),
+                    elements.apogee_altitude_km(),
+                )?;
+            }
+        }
+        OutputFormat::Json => {
+            let records: Vec<serde_json::Value> = satellites
+                .iter()
+                .map(|satellite| {
+                    serde_json::json!({
+                        "name": satellite.name,
+                        "norad_id": satellite.norad_id,
+                        "intl_designator": satellite.intl_designator,
+                        "epoch": iso_date(satellite.epoch_jd),
+                        "period_min": satellite.period(),
+                        "inclination_deg": satellite.elements.inclination.to_degrees(),
+                        "eccentricity": satellite.elements.eccentricity,
+                        "perigee_km": satellite.elements.perigee_altitude_km(),
+                        "apogee_km": satellite.elements.apogee_altitude_km(),
+                    })
+                })
+                .collect();
+            serde_json::to_writer_pretty(&mut out, &records)?;
+            writeln!(out)?;
+        }
+    }
+    Ok(out.flush()?)
+}
+
+/// Writes the states of a satellite in the output of the `propagate` subcommand, followed by the
+/// reason why the propagation stopped early.
+///
+/// # Arguments
+/// * `out` - The destination of the table and CSV output.
+/// * `args` - The arguments of the subcommand.
+/// * `satellite` - The satellite.
+/// * `states` - The Julian dates (UTC) and TEME states of the satellite.
+/// * `error` - The propagation error, if the ephemeris stopped before its end.
+/// * `records` - The JSON records, written once every satellite is propagated.
+///
+/// # Returns
+/// * Nothing, or the write error.
+fn write_states(out: &mut dyn Write, args: &PropagateArgs, satellite: &Satellite, states: &[(f64, StateVector)], error: Option<&str>, records: &mut Vec<serde_json::Value>) -> Result<(), Box<dyn Error>> {
+    let columns: &[&str] = args.frame.columns();
+    match args.output.format {
+        OutputFormat::Table => {
+            for (jd, state) in states {
+                let values: Vec<String> = args.frame.values(*jd, state).iter().map(|value| format!("{:>14.6}", value)).collect();
+                writeln!(out, "{:>7} {:<23} {}", satellite.norad_id, iso_date(*jd), values.join(" "))?;
+            }
+            if let Some(error) = error {
+                writeln!(out, "{:>7} {}", satellite.norad_id, error.red())?;
+            }
+        }
+        OutputFormat::Csv => {
+            for (jd, state) in states {
+                let values: Vec<String> = args.frame.values(*jd, state).iter().map(|value| format!("{:.6}", value)).collect();
+                writeln!(out, "{},{},{},", satellite.norad_id, iso_date(*jd), values.join(","))?;
+            }
+            if let Some(error) = error {
+                writeln!(out, "{},{}\"{}\"", satellite.norad_id, ",".repeat(columns.len() + 1), error.replace('"', "\"\""))?;
+            }
+        }
+        OutputFormat::Json => {
+            let records_of_satellite: Vec<serde_json::Value> = states
+                .iter()
+                .map(|(jd, state)| {
+                    let mut record = serde_json::json!({ "time": iso_date(*jd), "jd": jd });
+                    for (column, value) in columns.iter().zip(args.frame.values(*jd, state)) {
+                        record[*column] = serde_json::json!(value);
+                    }
+                    record
+                })
+                .collect();
+            let mut record = serde_json::json!({
+                "name": satellite.name,
+                "norad_id": satellite.norad_id,
+                "frame": args.frame.name(),
+                "states": records_of_satellite,
+            });
+            if let Some(error) = error {
+                record["error"] = serde_json::json!(error);
+            }
+            records.push(record);
+        }
+    }
+    Ok(())
+}
+
+/// Runs the `propagate` subcommand, writing the states of each satellite at regular steps.
+fn propagate(args: &PropagateArgs) -> Result<(), Box<dyn Error>> {
+    let satellites: Vec<Satellite> = load_satellites(&args.input)?;
+    let columns: &[&str] = args.frame.columns();
+    let mut out: Box<dyn Write> = args.output.writer()?;
+    match args.output.format {
+        OutputFormat::Table => {
+            let header: Vec<String> = columns.iter().map(|column| format!("{:>14}", column.to_uppercase())).collect();
+            writeln!(out, "{:>7} {:<23} {}", "NORAD", "TIME (UTC)", header.join(" "))?;
+        }
+        OutputFormat::Csv => writeln!(out, "norad_id,time,{},error", columns.join(","))?,
+        OutputFormat::Json => {}
+    }
+    let mut records: Vec<serde_json::Value> = Vec::new();
+
+    for satellite in &satellites {
+        let (start, end) = args.range.bounds(satellite.epoch_jd, 1440.0);
+        if end < start {
+            return Err(format!("the range of {} ends before it starts", satellite.norad_id).into());
+        }
+        let mut ephemeris = Ephemeris::new(satellite, start, end, args.step);
+        let states: Vec<(f64, StateVector)> = ephemeris.by_ref().collect();
+        // A satellite that cannot be propagated keeps its states so far and an error, and the
+        // others are still written.
+        let error: Option<String> = ephemeris.error().map(|error| format!("cannot propagate {} after {}: {}", satellite.norad_id, states.last().map_or(iso_date(start), |(jd, _)| iso_date(*jd)), error));
+        write_states(&mut *out, args, satellite, &states, error.as_deref(), &mut records)?;
+    }
+
+    if args.output.format == OutputFormat::Json {
+        serde_json::to_writer_pretty(&mut out, &records)?;
+        writeln!(out)?;
+    }
+    Ok(out.flush()?)
+}
+
+/// Represents a pass of a satellite, with its dates and the azimuths at its ends.
+struct StationPass<'a> {
+    /// The satellite.
+    satellite: &'a Satellite,
+    /// The pass, in minutes since the epoch of the satellite.
+    pass: Pass,
+    /// Look angles at the start of the pass.
+    rise: LookAngles,
+    /// Look angles at the end of the pass.
+    set: LookAngles,
+}
+
+impl StationPass<'_> {
+    /// Returns the Julian date (UTC) of a time of the pass.
+    fn jd(&self, tsince: f64) -> f64 {
+        self.satellite.epoch_jd + tsince / 1440.0
+    }
+
+    /// Returns the name of the satellite, or its catalog number.
+    fn name(&self) -> String {
+        self.satellite.name.clone().unwrap_or_else(|| self.satellite.norad_id.to_string())
+    }
+}
+
+/// Formats a Julian date as an iCalendar UTC date-time, such as `20240301T123456Z`.
+fn ics_date(jd: f64) -> String {
+    let date: String = iso_date(jd).chars().take(19).filter(|c| *c != '-' && *c != ':').collect();
+    format!("{}Z", date)
+}
+
+/// Writes passes as an iCalendar file, with one event per pass.
+///
+/// # Arguments
+/// * `out` - Destination of the calendar.
+/// * `passes` - The passes, in chronological order.
+fn write_ics(out: &mut dyn Write, passes: &[StationPass]) -> io::Result<()> {
+    // iCalendar lines end with CRLF (RFC 5545).
+    write!(out, "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//orbitalrs//passes//EN\r\n")?;
+    let stamp: String = ics_date(now_jd());
+    for pass in passes {
+        write!(out, "BEGIN:VEVENT\r\n")?;
+        write!(out, "UID:{}-{}@orbitalrs\r\n", pass.satellite.norad_id, ics_date(pass.jd(pass.pass.aos)))?;
+        write!(out, "DTSTAMP:{}\r\n", stamp)?;
+        write!(out, "DTSTART:{}\r\n", ics_date(pass.jd(pass.pass.aos)))?;
+        write!(out, "DTEND:{}\r\n", ics_date(pass.jd(pass.pass.los)))?;
+        write!(out, "SUMMARY:{} pass\\, max {:.0}°\r\n", pass.name(), pass.pass.max_elevation.to_degrees())?;
+        write!(
+            out,
+            "DESCRIPTION:AOS azimuth {:.0}°\\, maximum elevation {:.1}° at {}\\, LOS azimuth {:.0}°\r\n",
+            pass.rise.azimuth.to_degrees(),
+            pass.pass.max_elevation.to_degrees(),
+            iso_date(pass.jd(pass.pass.max_elevation_time)),
+            pass.set.azimuth.to_degrees()
+        )?;
+        write!(out, "END:VEVENT\r\n")?;
+    }
+    write!(out, "END:VCALENDAR\r\n")
+}
+
+/// Runs the `passes` subcommand, listing the passes of every satellite in chronological order.
+fn predict_passes(args: &PassesArgs) -> Result<(), Box<dyn Error>> {
+    let satellites: Vec<Satellite> = load_satellites(&args.input)?;
+    let observer: Observer = args.station.observer()?;
+    let start_jd: f64 = match args.from {
+        Some(TimeSpec::Date(jd)) => jd,
+        Some(TimeSpec::SinceEpoch(_)) => return Err("the start of a prediction must be a date".into()),
+        None => now_jd(),
+    };
+
+    let mut found: Vec<StationPass> = Vec::new();
+    // A satellite that cannot be propagated is reported after the passes of the others.
+    let mut failures: Vec<(&Satellite, String)> = Vec::new();
+    for satellite in &satellites {
+        let start: f64 = (start_jd - satellite.epoch_jd) * 1440.0;
+        let look = |tsince: f64| -> Result<LookAngles, Box<dyn Error>> {
+            let state: StateVector = satellite.propagate(tsince).map_err(|e| format!("cannot propagate {} at {}: {}", satellite.norad_id, iso_date(satellite.epoch_jd + tsince / 1440.0), e))?;
+            Ok(observer.look_angles(&state, time::gmst(satellite.epoch_jd + tsince / 1440.0)))
+        };
+        let passes: Result<Vec<StationPass>, Box<dyn Error>> = passes_above(&observer, &satellite.elements, start, start + args.days * 1440.0, args.min_elevation.to_radians())
+            .map_err(|e| format!("cannot predict the passes of {}: {}", satellite.norad_id, e).into())
+            .and_then(|passes| passes.into_iter().map(|pass| Ok(StationPass { satellite, pass, rise: look(pass.aos)?, set: look(pass.los)? })).collect());
+        match passes {
+            Ok(passes) => found.extend(passes),
+            Err(error) => failures.push((satellite, error.to_string())),
+        }
+    }
+    found.sort_by(|a, b| a.jd(a.pass.aos).total_cmp(&b.jd(b.pass.aos)));
+
+    let mut out: Box<dyn Write> = open_output(args.output.as_ref())?;
+    write_passes(&mut *out, args.format, &found, &failures)?;
+    Ok(out.flush()?)
+}
+
+/// Writes the passes found by the `passes` subcommand, followed by the satellites that could not
+/// be propagated.
+///
+/// # Arguments
+/// * `out` - The destination of the output.
+/// * `format` - The format of the output.
+/// * `found` - The passes, in chronological order.
+/// * `failures` - The satellites that could not be propagated, with the reason.
+///
+/// # Returns
+/// * Nothing, or the write error.
+fn write_passes(out: &mut dyn Write, format: PassFormat, found: &[StationPass], failures: &[(&Satellite, String)]) -> Result<(), Box<dyn Error>> {
+    let name = |satellite: &Satellite| satellite.name.clone().unwrap_or_else(|| satellite.norad_id.to_string());
+    match format {
+        PassFormat::Table => {
+            writeln!(out, "{:<24} {:>7} {:<23} {:>6} {:<23} {:>6} {:<23} {:>6} {:>8}", "NAME", "NORAD", "AOS (UTC)", "AZ", "MAX EL (UTC)", "EL", "LOS (UTC)", "AZ", "MINUTES")?;
+            for pass in found {
+                writeln!(
+                    out,
+                    "{:<24} {:>7} {:<23} {:>6.1} {:<23} {:>6.1} {:<23} {:>6.1} {:>8.2}",
+                    pass.name(),
+                    pass.satellite.norad_id,
+                    iso_date(pass.jd(pass.pass.aos)),
+                    pass.rise.azimuth.to_degrees(),
+                    iso_date(pass.jd(pass.pass.max_elevation_time)),
+                    pass.pass.max_elevation.to_degrees(),
+                    iso_date(pass.jd(pass.pass.los)),
+                    pass.set.azimuth.to_degrees(),
+                    pass.pass.duration(),
+                )?;
+            }
+            for (satellite, error) in failures {
+                writeln!(out, "{:<24} {:>7} {}", name(satellite), satellite.norad_id, error.red())?;
+            }
+        }
+        PassFormat::Csv => {
+            writeln!(out, "name,norad_id,aos,aos_azimuth_deg,max_elevation_time,max_elevation_deg,los,los_azimuth_deg,duration_min,error")?;
+            for pass in found {
+                writeln!(
+                    out,
+                    "\"{}\",{},{},{:.3},{},{:.3},{},{:.3},{:.4},",
+                    pass.name().replace('"', "\"\""),
+                    pass.satellite.norad_id,
+                    iso_date(pass.jd(pass.pass.aos)),
+                    pass.rise.azimuth.to_degrees(),
+                    iso_date(pass.jd(pass.pass.max_elevation_time)),
+                    pass.pass.max_elevation.to_degrees(),
+                    iso_date(pass.jd(pass.pass.los)),
+                    pass.set.azimuth.to_degrees(),
+                    pass.pass.duration(),
+                )?;
+            }
+            for (satellite, error) in failures {
+                writeln!(out, "\"{}\",{},,,,,,,,\"{}\"", name(satellite).replace('"', "\"\""), satellite.norad_id, error.replace('"', "\"\""))?;
+            }
+        }
+        PassFormat::Json => {
+            let mut records: Vec<serde_json::Value> = found
+                .iter()
+                .map(|pass| {
+                    serde_json::json!({
+                        "name": pass.satellite.name,
+                        "norad_id": pass.satellite.norad_id,
+                        "aos": iso_date(pass.jd(pass.pass.aos)),
+                        "aos_azimuth_deg": pass.rise.azimuth.to_degrees(),
+                        "max_elevation_time": iso_date(pass.jd(pass.pass.max_elevation_time)),
+                        "max_elevation_deg": pass.pass.max_elevation.to_degrees(),
+                        "los": iso_date(pass.jd(pass.pass.los)),
+                        "los_azimuth_deg": pass.set.azimuth.to_degrees(),
+                        "duration_min": pass.pass.duration(),
+                    })
+                })
+                .collect();
+            records.extend(failures.iter().map(|(satellite, error)| serde_json::json!({ "name": satellite.name, "norad_id": satellite.norad_id, "error": error })));
+            serde_json::to_writer_pretty(&mut *out, &records)?;
+            writeln!(out)?;
+        }
+        PassFormat::Ics => {
+            write_ics(out, found)?;
+            // A calendar has no place for errors: they go to the standard error.
+            for (_, error) in failures {
+                eprintln!("{} {}", "warning:".yellow().bold(), error);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Represents the position of a satellite seen from a ground station at an instant.
+struct TrackSample {
+    /// Julian date (UTC) of the sample.
+    jd: f64,
+    /// Azimuth, elevation, range and range rate seen from the station.
+    angles: LookAngles,
+    /// Sub-satellite point and altitude.
+    subpoint: sgp4_rust::geodetic::Geodetic,
+}
+
+/// Computes the position of a satellite seen from a ground station at an instant.
+///
+/// # Arguments
+/// * `satellite` - The satellite.
+/// * `observer` - The ground station.
+/// * `jd` - The instant as a Julian date (UTC).
+///
+/// # Returns
+/// * The sample, or the reason why the satellite cannot be propagated.
+fn track_sample(satellite: &Satellite, observer: &Observer, jd: f64) -> Result<TrackSample, Box<dyn Error>> {
+    let state: StateVector = satellite.propagate((jd - satellite.epoch_jd) * 1440.0).map_err(|e| format!("cannot propagate {}: {}", satellite.norad_id, e))?;
+    let gmst: f64 = time::gmst(jd);
+    Ok(TrackSample {
+        jd,
+        angles: observer.look_angles(&state, gmst),
+        subpoint: state.to_geodetic(gmst),
+    })
+}
+
+/// Writes the row of a satellite in the output of the `track` subcommand, or the reason why it
+/// could not be sampled.
+///
+/// # Arguments
+/// * `out` - The destination of the output.
+/// * `format` - The format of the output.
+/// * `satellite` - The satellite.
+/// * `jd` - The instant of the update as a Julian date (UTC).
+/// * `sample` - The sample of the satellite, or the error that prevented it.
+///
+/// # Returns
+/// * Nothing, or the write error.
+fn write_track_row(out: &mut dyn Write, format: OutputFormat, satellite: &Satellite, jd: f64, sample: &Result<TrackSample, Box<dyn Error>>) -> Result<(), Box<dyn Error>> {
+    let name: String = satellite.name.clone().unwrap_or_default();
+    let sample: &TrackSample = match sample {
+        Ok(sample) => sample,
+        Err(error) => {
+            match format {
+                OutputFormat::Table => writeln!(out, "{:<23} {:<24} {:>7} {}", iso_date(jd), name, satellite.norad_id, error.to_string().red())?,
+                OutputFormat::Csv => writeln!(out, "{},\"{}\",{},,,,,,,,\"{}\"", iso_date(jd), name.replace('"', "\"\""), satellite.norad_id, error.to_string().replace('"', "\"\""))?,
+                OutputFormat::Json => {
+                    let record = serde_json::json!({
+                        "time": iso_date(jd),
+                        "name": satellite.name,
+                        "norad_id": satellite.norad_id,
+                        "error": error.to_string(),
+                    });
+                    writeln!(out, "{}", record)?;
+                }
+            }
+            return Ok(());
+        }
+    };
+    let (azimuth, elevation): (f64, f64) = (sample.angles.azimuth.to_degrees(), sample.angles.elevation.to_degrees());
+    let (latitude, longitude): (f64, f64) = (sample.subpoint.latitude.to_degrees(), sample.subpoint.longitude.to_degrees());
+    match format {
+        OutputFormat::Table => {
+            let elevation_text: String = format!("{:>7.2}", elevation);
+            // Satellites above the horizon stand out.
+            let elevation_text: ColoredString = if elevation > 0.0 { elevation_text.green().bold() } else { elevation_text.normal() };
+            writeln!(
+                out,
+                "{:<23} {:<24} {:>7} {:>7.2} {} {:>10.1} {:>9.3} {:>8.3} {:>9.3} {:>9.1}",
+                iso_date(sample.jd),
+                name,
+                satellite.norad_id,
+                azimuth,
+                elevation_text,
+                sample.angles.range,
+                sample.angles.range_rate,
+                latitude,
+                longitude,
+                sample.subpoint.altitude,
+            )?;
+        }
+        OutputFormat::Csv => writeln!(
+            out,
+            "{},\"{}\",{},{:.4},{:.4},{:.3},{:.5},{:.5},{:.5},{:.3},",
+            iso_date(sample.jd),
+            name.replace('"', "\"\""),
+            satellite.norad_id,
+            azimuth,
+            elevation,
+            sample.angles.range,
+            sample.angles.range_rate,
+            latitude,
+            longitude,
+            sample.subpoint.altitude,
+        )?,
+        // One JSON object per line, so that the samples can be streamed.
+        OutputFormat::Json => {
+            let record = serde_json::json!({
+                "time": iso_date(sample.jd),
+                "name": satellite.name,
+                "norad_id": satellite.norad_id,
+                "azimuth_deg": azimuth,
+                "elevation_deg": elevation,
+                "range_km": sample.angles.range,
+                "range_rate_km_s": sample.angles.range_rate,
+                "latitude_deg": latitude,
+                "longitude_deg": longitude,
+                "altitude_km": sample.subpoint.altitude,
+            });
+            writeln!(out, "{}", record)?;
+        }
+    }
+    Ok(())
+}
+
+/// Runs the `track` subcommand, sampling every satellite at the wall-clock time at a fixed rate.
+fn track(args: &TrackArgs) -> Result<(), Box<dyn Error>> {
+    let satellites: Vec<Satellite> = load_satellites(&args.input)?;
+    let observer: Observer = args.station.observer()?;
+    let header: String = format!(
+        "{:<23} {:<24} {:>7} {:>7} {:>7} {:>10} {:>9} {:>8} {:>9} {:>9}",
+        "TIME (UTC)", "NAME", "NORAD", "AZ", "EL", "RANGE KM", "RATE KM/S", "LAT", "LON", "ALT KM"
+    );
+    let mut out: Box<dyn Write> = args.output.writer()?;
+    match args.output.format {
+        OutputFormat::Table if !args.refresh => writeln!(out, "{}", header)?,
+        OutputFormat::Csv => writeln!(out, "time,name,norad_id,azimuth_deg,elevation_deg,range_km,range_rate_km_s,latitude_deg,longitude_deg,altitude_km,error")?,
+        _ => {}
+    }
+
+    // Updates are scheduled from the start so that the rate does not drift with the output time.
+    let start: Instant = Instant::now();
+    let mut update: u64 = 0;
+    while args.count.is_none_or(|count| update < count) {
+        thread::sleep((start + Duration::from_secs_f64(update as f64 * args.rate)).saturating_duration_since(Instant::now()));
+        let jd: f64 = now_jd();
+        if args.refresh && args.output.format == OutputFormat::Table {
+            // Clear the screen and move the cursor home.
+            writeln!(out, "\x1b[2J\x1b[H{}", header)?;
+        }
+        for satellite in &satellites {
+            // A satellite that cannot be propagated gets an error row instead of ending the track.
+            let sample: Result<TrackSample, Box<dyn Error>> = track_sample(satellite, &observer, jd);
+            write_track_row(&mut *out, args.output.format, satellite, jd, &sample)?;
+        }
+        out.flush()?;
+        update += 1;
+    }
+    Ok(())
+}
+
+/// Parses the command line and runs the subcommand, reporting errors on the standard error.
+fn main() -> ExitCode {
+    let cli: Cli = Cli::parse();
+    let result: Result<(), Box<dyn Error>> = match &cli.command {
+        Command::Compare(args) => compare(args),
+        Command::Info(args) => info(args),
+        Command::Propagate(args) => propagate(args),
+        Command::Passes(args) => predict_passes(args),
+        Command::Track(args) => track(args),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{} {}", "error:".red().bold(), error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    /// Tests the definition of the arguments and the parsing of a command line.
+    #[test]
+    fn test_cli() {
+        Cli::command().debug_assert();
+        let cli: Cli = Cli::try_parse_from(["orbitalrs", "info", "--tle", "stations.txt", "--norad", "25544", "--norad", "48274", "--format", "csv"]).unwrap();
+        let Command::Info(args) = cli.command else { panic!("expected the info subcommand") };
+        assert_eq!(args.input.norad_ids, vec![25544, 48274]);
+        assert_eq!(args.output.format, OutputFormat::Csv);
+        assert!(Cli::try_parse_from(["orbitalrs", "info"]).is_err());
+    }
+
+    /// Tests the parsing of times and the time range.
+    #[test]
+    fn test_parse_time() {
+        assert_eq!(parse_time("-90"), Ok(TimeSpec::SinceEpoch(-90.0)));
+        assert_eq!(parse_time("2008-09-20T12:00:00Z"), Ok(TimeSpec::Date(time::julian_date(2008, 9, 20, 12, 0, 0.0))));
+        assert_eq!(parse_time("2008-09-20"), Ok(TimeSpec::Date(time::julian_date(2008, 9, 20, 0, 0, 0.0))));
+        assert!(parse_time("2008-13-20").is_err());
+        assert!(parse_time("tomorrow").is_err());
+        assert!(parse_time("nan").is_err());
+        assert!(parse_time("-inf").is_err());
+        assert!(parse_time("1e400").is_err());
+        assert!(Cli::try_parse_from(["orbitalrs", "propagate", "--tle", "iss.txt", "--from", "-90"]).is_ok());
+        assert!(Cli::try_parse_from(["orbitalrs", "propagate", "--tle", "iss.txt", "--from", "NaN"]).is_err());
+
+        let range = TimeRangeArgs {
+            from: Some(TimeSpec::SinceEpoch(0.0)),
+            to: Some(TimeSpec::Date(2451545.5)),
+        };
+        assert!(range.contains(720.0, 2451545.0));
+        assert!(!range.contains(-1.0, 2451545.0));
+        assert!(!range.contains(721.0, 2451545.0));
+    }
+
+    /// Tests the parsing of durations and the bounds of a propagation.
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("60"), Ok(60.0));
+        assert_eq!(parse_duration("10min"), Ok(600.0));
+        assert_eq!(parse_duration("1.5h"), Ok(5400.0));
+        assert!(parse_duration("0s").is_err());
+        assert!(parse_duration("5w").is_err());
+        assert!(parse_duration("nan").is_err());
+        assert!(parse_duration("inf").is_err());
+        assert!(parse_duration("1e307d").is_err());
+        assert!(parse_duration("1e400 s").is_err());
+
+        let cli: Cli = Cli::try_parse_from(["orbitalrs", "propagate", "--tle", "iss.txt", "--from", "-90", "--step", "30s", "--frame", "geodetic"]).unwrap();
+        let Command::Propagate(args) = cli.command else { panic!("expected the propagate subcommand") };
+        assert_eq!(args.step, 30.0);
+        assert_eq!(args.frame.columns().len(), 3);
+        assert_eq!(args.frame.name(), "GEODETIC");
+        assert_eq!(OutputFrame::Ecef.name(), "PEF");
+        let (start, end) = args.range.bounds(2451545.0, 1440.0);
+        assert!((start - (2451545.0 - 90.0 / 1440.0)).abs() < 1e-12);
+        assert!((end - (start + 1.0)).abs() < 1e-12);
+    }
+
+    /// Tests that a propagation error is written after the states of its satellite.
+    #[test]
+    fn test_write_states() {
+        let satellite: Satellite = parse_satellites("1 25544U 98067A   08264.51782528 -.00002182  00000-0 -11606-4 0  2927\n2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537\n").unwrap().remove(0);
+        let states: Vec<(f64, StateVector)> = vec![(satellite.epoch_jd, satellite.propagate(0.0).unwrap())];
+        let mut records: Vec<serde_json::Value> = Vec::new();
+
+        let cli: Cli = Cli::try_parse_from(["orbitalrs", "propagate", "--tle", "iss.txt", "--format", "csv"]).unwrap();
+        let Command::Propagate(args) = cli.command else { panic!("expected the propagate subcommand") };
+        let mut out: Vec<u8> = Vec::new();
+        write_states(&mut out, &args, &satellite, &states, Some("cannot propagate 25544: decayed"), &mut records).unwrap();
+        let text: String = String::from_utf8(out).unwrap();
+        let rows: Vec<&str> = text.lines().collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].split(',').count(), 9);
+        assert_eq!(rows[1], "25544,,,,,,,,\"cannot propagate 25544: decayed\"");
+
+        let cli: Cli = Cli::try_parse_from(["orbitalrs", "propagate", "--tle", "iss.txt", "--format", "json"]).unwrap();
+        let Command::Propagate(args) = cli.command else { panic!("expected the propagate subcommand") };
+        write_states(&mut io::sink(), &args, &satellite, &states, Some("decayed"), &mut records).unwrap();
+        write_states(&mut io::sink(), &args, &satellite, &states, None, &mut records).unwrap();
+        assert_eq!(records[0]["error"], "decayed");
+        assert_eq!(records[0]["frame"], "TEME");
+        assert!(records[1].get("error").is_none());
+    }
+
+    /// Tests the pass prediction arguments and the iCalendar output.
+    #[test]
+    fn test_passes() {
+        let cli: Cli = Cli::try_parse_from(["orbitalrs", "passes", "--tle", "iss.txt", "--lat", "-33.9", "--lon", "18.4", "--min-elevation", "10", "--days", "3", "--format", "ics"]).unwrap();
+        let Command::Passes(args) = cli.command else { panic!("expected the passes subcommand") };
+        assert_eq!(args.station.lat, -33.9);
+        assert_eq!(args.format, PassFormat::Ics);
+        for (option, value) in [("--days", "inf"), ("--days", "nan"), ("--days", "1e9"), ("--days", "0"), ("--min-elevation", "95"), ("--min-elevation", "nan")] {
+            assert!(Cli::try_parse_from(["orbitalrs", "passes", "--tle", "iss.txt", "--lat", "0", "--lon", "0", option, value]).is_err(), "{} {}", option, value);
+        }
+        assert_eq!(parse_elevation("-90"), Ok(-90.0));
+        assert_eq!(parse_days("366"), Ok(366.0));
+
+        let satellite: Satellite = parse_satellites("ISS (ZARYA)\n1 25544U 98067A   08264.51782528 -.00002182  00000-0 -11606-4 0  2927\n2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537\n").unwrap().remove(0);
+        let observer: Observer = Observer::new(48.85, 2.35, 0.035);
+        let pass: Pass = passes_above(&observer, &satellite.elements, 0.0, 1440.0, 0.0).unwrap()[0];
+        let angles = |tsince: f64| observer.look_angles(&satellite.propagate(tsince).unwrap(), time::gmst(satellite.epoch_jd + tsince / 1440.0));
+        let found: Vec<StationPass> = vec![StationPass {
+            satellite: &satellite,
+            pass,
+            rise: angles(pass.aos),
+            set: angles(pass.los),
+        }];
+        let mut output: Vec<u8> = Vec::new();
+        write_ics(&mut output, &found).unwrap();
+        let text: String = String::from_utf8(output).unwrap();
+        assert!(text.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert_eq!(text.matches("BEGIN:VEVENT").count(), 1);
+        assert!(text.contains(&format!("DTSTART:{}\r\n", ics_date(satellite.epoch_jd + pass.aos / 1440.0))));
+        assert_eq!(ics_date(2451545.0), "20000101T120000Z");
+
+        let failures: Vec<(&Satellite, String)> = vec![(&satellite, "cannot propagate 25544: decayed".to_string())];
+        let mut output: Vec<u8> = Vec::new();
+        write_passes(&mut output, PassFormat::Csv, &found, &failures).unwrap();
+        let text: String = String::from_utf8(output).unwrap();
+        let rows: Vec<&str> = text.lines().collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[1].split(',').count(), 10);
+        assert_eq!(rows[2], "\"ISS (ZARYA)\",25544,,,,,,,,\"cannot propagate 25544: decayed\"");
+        let mut output: Vec<u8> = Vec::new();
+        write_passes(&mut output, PassFormat::Json, &found, &failures).unwrap();
+        let records: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(records[1]["error"], "cannot propagate 25544: decayed");
+    }
+
+    /// Tests the tracking arguments and a sample at the epoch.
+    #[test]
+    fn test_track() {
+        let cli: Cli = Cli::try_parse_from(["orbitalrs", "track", "--tle", "iss.txt", "--lat", "48.85", "--lon", "2.35", "--rate", "5s", "--count", "3"]).unwrap();
+        let Command::Track(args) = cli.command else { panic!("expected the track subcommand") };
+        assert_eq!(args.rate, 5.0);
+        assert_eq!(args.count, Some(3));
+
+        let satellite: Satellite = parse_satellites("1 25544U 98067A   08264.51782528 -.00002182  00000-0 -11606-4 0  2927\n2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537\n").unwrap().remove(0);
+        let observer: Observer = args.station.observer().unwrap();
+        let sample: TrackSample = track_sample(&satellite, &observer, satellite.epoch_jd).unwrap();
+        let expected: LookAngles = observer.look_angles(&satellite.propagate(0.0).unwrap(), time::gmst(satellite.epoch_jd));
+        assert_eq!(sample.angles, expected);
+        assert_eq!(sample.subpoint, satellite.subpoint(0.0).unwrap());
+        assert!((now_jd() - 2451545.0) > 0.0);
+
+        let mut out: Vec<u8> = Vec::new();
+        write_track_row(&mut out, OutputFormat::Csv, &satellite, satellite.epoch_jd, &Ok(sample)).unwrap();
+        let failed: Result<TrackSample, Box<dyn Error>> = Err("cannot propagate 25544: decayed".into());
+        write_track_row(&mut out, OutputFormat::Csv, &satellite, satellite.epoch_jd, &failed).unwrap();
+        let text: String = String::from_utf8(out).unwrap();
+        let rows: Vec<&str> = text.lines().collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].split(',').count(), 11);
+        assert!(rows[0].ends_with(','));
+        assert_eq!(rows[1], "2008-09-20T12:25:40.104,\"\",25544,,,,,,,,\"cannot propagate 25544: decayed\"");
+    }
+
+    /// Tests the selection of satellites by name and catalog number.
+    #[test]
+    fn test_select() {
+        let text: &str = "ISS (ZARYA)\n1 25544U 98067A   08264.51782528 -.00002182  00000-0 -11606-4 0  2927\n2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537\n";
+        let mut input = InputArgs {
+            source: "stations.txt".to_string(),
+            name: Some("zarya".to_string()),
+            norad_ids: Vec::new(),
+        };
+        assert_eq!(select(parse_satellites(text).unwrap(), &input).unwrap().len(), 1);
+        input.norad_ids = vec![20580];
+        assert!(select(parse_satellites(text).unwrap(), &input).is_err());
+    }
 }
@@ -0,0 +1,368 @@
+//! Impulsive maneuvers.
+//! A burn changes the velocity instantly, given in the inertial frame or in the radial, in-track
+//! and cross-track (RIC) frame of the satellite. Applied to a TLE, the burned state is fitted back
+//! to SGP4 mean elements at the time of the burn: the element set to expect after the maneuver.
+//! Hohmann, bi-elliptic and coaxial ellipse transfers are planned as sequences of such burns.
+
+use crate::keplerian::EARTH_MU;
+use crate::mean_elements::osculating_to_mean;
+use crate::propagator::PropagationError;
+use crate::relative::ric_axes;
+use crate::time::day_of_year;
+use crate::{OrbitalElements, Sgp4, StateVector, XKMPER};
+use core::f64::consts::PI;
+use core::fmt;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Frame of the velocity change of a burn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ImpulseFrame {
+    /// Same inertial frame as the state, TEME for SGP4.
+    Inertial,
+    /// Radial, in-track and cross-track axes of the satellite at the time of the burn.
+    Ric,
+}
+
+/// Represents an impulsive burn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Impulse {
+    /// Velocity change in km/s.
+    pub delta_v: [f64; 3],
+    /// Frame of the velocity change.
+    pub frame: ImpulseFrame,
+}
+
+impl Impulse {
+    /// Returns the magnitude of the velocity change in km/s.
+    pub fn magnitude(&self) -> f64 {
+        self.delta_v.iter().map(|x| x * x).sum::<f64>().sqrt()
+    }
+}
+
+/// Represents the reasons why a burn cannot be applied to an element set.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ManeuverError {
+    /// The elements cannot be propagated to the time of the burn.
+    Propagation(PropagationError),
+    /// No mean elements reproduce the state after the burn, e.g. for an escape orbit.
+    NotConverged,
+}
+
+impl fmt::Display for ManeuverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ManeuverError::Propagation(error) => write!(f, "{}", error),
+            ManeuverError::NotConverged => write!(f, "No mean elements reproduce the state after the burn"),
+        }
+    }
+}
+
+impl core::error::Error for ManeuverError {}
+
+/// Applies a burn to a state.
+///
+/// # Arguments
+/// * `state` - State vector before the burn (km, km/s).
+/// * `impulse` - The burn.
+///
+/// # Returns
+/// * The state vector after the burn, at the same position.
+pub fn apply_impulse(state: &StateVector, impulse: &Impulse) -> StateVector {
+    let delta_v: [f64; 3] = match impulse.frame {
+        ImpulseFrame::Inertial => impulse.delta_v,
+        ImpulseFrame::Ric => {
+            let axes: [[f64; 3]; 3] = ric_axes(state);
+            [0, 1, 2].map(|k| (0..3).map(|axis| axes[axis][k] * impulse.delta_v[axis]).sum())
+        }
+    };
+    StateVector {
+        position: state.position,
+        velocity: [0, 1, 2].map(|k| state.velocity[k] + delta_v[k]),
+    }
+}
+
+/// Applies a burn to an element set.
+///
+/// # Arguments
+/// * `elements` - Mean orbital elements before the burn.
+/// * `tsince` - Time of the burn in minutes since the epoch of the elements.
+/// * `impulse` - The burn.
+///
+/// # Returns
+/// * The mean elements after the burn, with their epoch at the time of the burn and the drag term
+///   of the original elements.
+pub fn apply_to_elements(elements: &OrbitalElements, tsince: f64, impulse: &Impulse) -> Result<OrbitalElements, ManeuverError> {
+    let state: StateVector = Sgp4::new(elements).propagate(tsince).map_err(ManeuverError::Propagation)?;
+    let (epoch_year, epoch_day) = day_of_year(elements.jd_at(tsince));
+    osculating_to_mean(&apply_impulse(&state, impulse), epoch_year, epoch_day, elements.bstar).ok_or(ManeuverError::NotConverged)
+}
+
+/// Computes the tangential burn that moves the opposite apsis to an altitude.
+///
+/// The burn is exact at an apsis, where the velocity is horizontal: at perigee it raises or lowers
+/// the apogee, at apogee the perigee.
+///
+/// # Arguments
+/// * `state` - State vector at the time of the burn (km, km/s).
+/// * `altitude` - Target altitude of the opposite apsis above the equatorial radius in kilometers.
+///
+/// # Returns
+/// * The burn along the velocity, in the RIC frame.
+pub fn apsis_change(state: &StateVector, altitude: f64) -> Impulse {
+    let radius: f64 = state.position.iter().map(|x| x * x).sum::<f64>().sqrt();
+    let target: f64 = altitude + XKMPER;
+    let speed: f64 = state.velocity.iter().map(|x| x * x).sum::<f64>().sqrt();
+    let required: f64 = (2.0 * EARTH_MU * target / (radius * (radius + target))).sqrt();
+    let axes: [[f64; 3]; 3] = ric_axes(state);
+    let scale: f64 = required / speed - 1.0;
+    Impulse {
+        delta_v: axes.map(|axis| scale * (0..3).map(|k| axis[k] * state.velocity[k]).sum::<f64>()),
+        frame: ImpulseFrame::Ric,
+    }
+}
+
+/// Computes the burn that rotates the orbital plane about the radial direction.
+///
+/// The burn changes the inclination when made at a node, and the right ascension of the ascending
+/// node at the highest latitude.
+///
+/// # Arguments
+/// * `state` - State vector at the time of the burn (km, km/s).
+/// * `angle` - Rotation of the plane in radians, positive towards the angular momentum.
+///
+/// # Returns
+/// * The burn keeping the speed and the radial velocity, in the RIC frame.
+pub fn plane_change(state: &StateVector, angle: f64) -> Impulse {
+    let axes: [[f64; 3]; 3] = ric_axes(state);
+    let horizontal: f64 = (0..3).map(|k| axes[1][k] * state.velocity[k]).sum();
+    let (sin, cos) = angle.sin_cos();
+    Impulse {
+        delta_v: [0.0, horizontal * (cos - 1.0), horizontal * sin],
+        frame: ImpulseFrame::Ric,
+    }
+}
+
+/// Represents a burn of a transfer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransferBurn {
+    /// Time of the burn in minutes after the first burn.
+    pub time: f64,
+    /// Radius at which the burn is made in kilometers, at an apsis of the orbits.
+    pub radius: f64,
+    /// The burn, along the in-track axis.
+    pub impulse: Impulse,
+}
+
+/// Represents a sequence of tangential burns between coplanar orbits.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transfer {
+    /// Burns in chronological order.
+    pub burns: Vec<TransferBurn>,
+}
+
+impl Transfer {
+    /// Returns the total velocity change in km/s.
+    pub fn delta_v(&self) -> f64 {
+        self.burns.iter().map(|burn| burn.impulse.magnitude()).sum()
+    }
+
+    /// Returns the time from the first to the last burn in minutes.
+    pub fn duration(&self) -> f64 {
+        self.burns.last().map_or(0.0, |burn| burn.time)
+    }
+}
+
+/// Computes the tangential burn at an apsis between two orbits sharing it.
+fn tangential_burn(time: f64, radius: f64, from_axis: f64, to_axis: f64) -> TransferBurn {
+    let speed = |semi_major_axis: f64| (EARTH_MU * (2.0 / radius - 1.0 / semi_major_axis)).sqrt();
+    TransferBurn {
+        time,
+        radius,
+        impulse: Impulse {
+            delta_v: [0.0, speed(to_axis) - speed(from_axis), 0.0],
+            frame: ImpulseFrame::Ric,
+        },
+    }
+}
+
+/// Computes half the period of an orbit in minutes.
+fn half_period(semi_major_axis: f64) -> f64 {
+    PI * (semi_major_axis * semi_major_axis * semi_major_axis / EARTH_MU).sqrt() / 60.0
+}
+
+/// Plans a Hohmann transfer between circular orbits.
+///
+/// # Arguments
+/// * `from` - Radius of the initial orbit in kilometers.
+/// * `to` - Radius of the final orbit in kilometers.
+///
+/// # Returns
+/// * The two burns, half a transfer orbit apart.
+pub fn hohmann(from: f64, to: f64) -> Transfer {
+    coaxial_transfer([from, from], [to, to])
+}
+
+/// Plans a bi-elliptic transfer between circular orbits, through an intermediate apoapsis.
+///
+/// # Arguments
+/// * `from` - Radius of the initial orbit in kilometers.
+/// * `to` - Radius of the final orbit in kilometers.
+/// * `intermediate` - Radius of the common apoapsis of the two transfer orbits in kilometers.
+///
+/// # Returns
+/// * The three burns. The transfer is cheaper than a Hohmann transfer for radius ratios above
+///   about 11.94 with a high enough intermediate radius, at the cost of a much longer duration.
+pub fn bi_elliptic(from: f64, to: f64, intermediate: f64) -> Transfer {
+    let first: f64 = (from + intermediate) / 2.0;
+    let second: f64 = (intermediate + to) / 2.0;
+    Transfer {
+        burns: vec![
+            tangential_burn(0.0, from, from, first),
+            tangential_burn(half_period(first), intermediate, first, second),
+            tangential_burn(half_period(first) + half_period(second), to, second, to),
+        ],
+    }
+}
+
+/// Plans a two-burn transfer between coplanar ellipses with the same line of apsides.
+///
+/// The transfer orbit joins an apsis of the initial orbit to the opposite apsis of the final one;
+/// both choices are tried and the cheaper one is returned.
+///
+/// # Arguments
+/// * `from` - Periapsis and apoapsis radii of the initial orbit in kilometers.
+/// * `to` - Periapsis and apoapsis radii of the final orbit in kilometers, apoapsis on the side of
+///   the initial apoapsis.
+///
+/// # Returns
+/// * The two burns, half a transfer orbit apart.
+pub fn coaxial_transfer(from: [f64; 2], to: [f64; 2]) -> Transfer {
+    let from_axis: f64 = (from[0] + from[1]) / 2.0;
+    let to_axis: f64 = (to[0] + to[1]) / 2.0;
+    // Departure at the periapsis (arrival at the opposite, apoapsis side) or at the apoapsis.
+    [(from[0], to[1]), (from[1], to[0])]
+        .into_iter()
+        .map(|(departure, arrival)| {
+            let transfer_axis: f64 = (departure + arrival) / 2.0;
+            Transfer {
+                burns: vec![tangential_burn(0.0, departure, from_axis, transfer_axis), tangential_burn(half_period(transfer_axis), arrival, transfer_axis, to_axis)],
+            }
+        })
+        .min_by(|a, b| a.delta_v().total_cmp(&b.delta_v()))
+        .expect("two candidate transfers")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keplerian::KeplerianElements;
+    use crate::test_support::iss_elements;
+
+    /// Tests the apsis and plane change burns on two-body orbits.
+    #[test]
+    fn test_burn_helpers() {
+        let orbit = KeplerianElements {
+            semi_major_axis: XKMPER + 400.0,
+            eccentricity: 0.0,
+            inclination: 0.9,
+            raan: 0.3,
+            arg_perigee: 0.0,
+            true_anomaly: 0.0,
+        };
+        let state: StateVector = orbit.to_state();
+
+        let raise: Impulse = apsis_change(&state, 800.0);
+        // Hohmann first burn from 400 km to 800 km: about 109 m/s.
+        assert!((raise.magnitude() - 0.1091).abs() < 1e-3, "{}", raise.magnitude());
+        let raised = KeplerianElements::from_state(&apply_impulse(&state, &raise));
+        assert!((raised.semi_major_axis * (1.0 + raised.eccentricity) - XKMPER - 800.0).abs() < 1e-6);
+        assert!((raised.semi_major_axis * (1.0 - raised.eccentricity) - XKMPER - 400.0).abs() < 1e-6);
+
+        // At the ascending node, the plane change tilts the orbit.
+        let tilted = KeplerianElements::from_state(&apply_impulse(&state, &plane_change(&state, 0.01)));
+        assert!((tilted.inclination - 0.91).abs() < 1e-9, "{}", tilted.inclination);
+        assert!((tilted.raan - 0.3).abs() < 1e-9);
+        assert!((tilted.semi_major_axis - orbit.semi_major_axis).abs() < 1e-6);
+    }
+
+    /// Tests the element set after a burn: it starts from the burned state and raises the orbit.
+    #[test]
+    fn test_apply_to_elements() {
+        let elements: OrbitalElements = iss_elements();
+        let impulse = Impulse {
+            delta_v: [0.0, 0.01, 0.0],
+            frame: ImpulseFrame::Ric,
+        };
+        let burned: OrbitalElements = apply_to_elements(&elements, 30.0, &impulse).unwrap();
+        assert!((burned.epoch_jd() - elements.jd_at(30.0)).abs() * 86400.0 < 1e-3);
+        assert!(burned.mean_motion < elements.mean_motion);
+        // 10 m/s raises the semi-major axis by about 2a Δv / v: 17 km.
+        assert!((burned.semi_major_axis_km() - elements.semi_major_axis_km() - 17.5).abs() < 1.0, "{}", burned.semi_major_axis_km() - elements.semi_major_axis_km());
+
+        let before: StateVector = Sgp4::new(&elements).propagate(30.0).unwrap();
+        let after: StateVector = Sgp4::new(&burned).propagate(0.0).unwrap();
+        for k in 0..3 {
+            assert!((after.position[k] - before.position[k]).abs() < 1e-3);
+        }
+        let delta_v: f64 = (0..3).map(|k| (after.velocity[k] - before.velocity[k]).powi(2)).sum::<f64>().sqrt();
+        assert!((delta_v - 0.01).abs() < 1e-6);
+    }
+
+    /// Tests the Hohmann and bi-elliptic transfers against textbook values.
+    #[test]
+    fn test_transfers() {
+        // From a 300 km parking orbit to the geostationary radius.
+        let transfer: Transfer = hohmann(XKMPER + 300.0, 42164.0);
+        assert!((transfer.delta_v() - 3.894).abs() < 5e-3, "{}", transfer.delta_v());
+        assert!((transfer.duration() / 60.0 - 5.27).abs() < 0.02, "{}", transfer.duration());
+        let circular = KeplerianElements {
+            semi_major_axis: XKMPER + 300.0,
+            eccentricity: 0.0,
+            inclination: 0.5,
+            raan: 0.0,
+            arg_perigee: 0.0,
+            true_anomaly: 0.0,
+        };
+        let transfer_orbit = KeplerianElements::from_state(&apply_impulse(&circular.to_state(), &transfer.burns[0].impulse));
+        assert!((transfer_orbit.semi_major_axis * (1.0 + transfer_orbit.eccentricity) - 42164.0).abs() < 1e-6);
+
+        // Above a radius ratio of 15.58, any bi-elliptic transfer beats the Hohmann transfer.
+        let (from, to): (f64, f64) = (7000.0, 7000.0 * 16.0);
+        let bi: Transfer = bi_elliptic(from, to, 2.0 * to);
+        assert!(bi.delta_v() < hohmann(from, to).delta_v());
+        assert!(bi.duration() > hohmann(from, to).duration());
+        assert_eq!(bi.burns.len(), 3);
+        // Lowering is the reverse of raising.
+        assert!((hohmann(to, from).delta_v() - hohmann(from, to).delta_v()).abs() < 1e-12);
+        assert!(hohmann(to, from).burns[0].impulse.delta_v[1] < 0.0);
+    }
+
+    /// Tests the coaxial ellipse transfer, which reduces to the Hohmann transfer between circles.
+    #[test]
+    fn test_coaxial_transfer() {
+        let circles: Transfer = coaxial_transfer([7000.0, 7000.0], [9000.0, 9000.0]);
+        assert_eq!(circles, hohmann(7000.0, 9000.0));
+
+        // From an ellipse to a higher circle, leaving from the faster periapsis is cheaper.
+        let transfer: Transfer = coaxial_transfer([7000.0, 8500.0], [9000.0, 9000.0]);
+        assert_eq!(transfer.burns[0].radius, 7000.0);
+        assert!(transfer.delta_v() < hohmann(7000.0, 9000.0).delta_v());
+        // The first burn matches the apsis change helper.
+        let orbit = KeplerianElements {
+            semi_major_axis: 7750.0,
+            eccentricity: 1500.0 / 15500.0,
+            inclination: 0.5,
+            raan: 0.0,
+            arg_perigee: 0.0,
+            true_anomaly: 0.0,
+        };
+        let state: StateVector = orbit.to_state();
+        let helper: Impulse = apsis_change(&state, 9000.0 - XKMPER);
+        assert!((helper.delta_v[1] - transfer.burns[0].impulse.delta_v[1]).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,138 @@
+//! Detection of maneuvers in the element history of an object.
+//! Each element set is propagated with SGP4 to the epoch of the next one, so the secular effects
+//! of drag and J2 are part of the prediction. Semi-major axis or inclination residuals beyond the
+//! noise of the element sets point to a maneuver between the two epochs, and the Gauss equations
+//! turn them into an estimate of the velocity change.
+
+use crate::keplerian::{EARTH_MU, KeplerianElements};
+use crate::{OrbitalElements, Sgp4, StateVector};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Holds the thresholds of the detection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManeuverDetection {
+    /// Smallest semi-major axis residual flagged, in kilometers.
+    pub semi_major_axis_threshold: f64,
+    /// Smallest inclination residual flagged, in radians.
+    pub inclination_threshold: f64,
+}
+
+impl Default for ManeuverDetection {
+    fn default() -> ManeuverDetection {
+        ManeuverDetection {
+            semi_major_axis_threshold: 0.5,
+            inclination_threshold: 2e-4,
+        }
+    }
+}
+
+/// Represents a likely maneuver between two element sets.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManeuverEvent {
+    /// Index in the history of the first element set after the maneuver.
+    pub index: usize,
+    /// Epoch of the last element set before the maneuver, as a Julian date (UTC).
+    pub start_jd: f64,
+    /// Epoch of the first element set after the maneuver, as a Julian date (UTC).
+    pub end_jd: f64,
+    /// Residual of the osculating semi-major axis in kilometers, observed minus predicted.
+    pub delta_semi_major_axis: f64,
+    /// Residual of the osculating inclination in radians, observed minus predicted.
+    pub delta_inclination: f64,
+    /// Estimated in-track velocity change in km/s, positive for a raise.
+    pub delta_v_in_track: f64,
+    /// Estimated cross-track velocity change in km/s, a lower bound reached at a node.
+    pub delta_v_cross_track: f64,
+}
+
+impl ManeuverEvent {
+    /// Returns the magnitude of the estimated velocity change in km/s.
+    pub fn delta_v(&self) -> f64 {
+        (self.delta_v_in_track * self.delta_v_in_track + self.delta_v_cross_track * self.delta_v_cross_track).sqrt()
+    }
+}
+
+impl ManeuverDetection {
+    /// Finds the likely maneuvers in the element history of an object.
+    ///
+    /// # Arguments
+    /// * `history` - Element sets of a single object in chronological order.
+    ///
+    /// # Returns
+    /// * The maneuvers in chronological order. Pairs of sets that cannot be propagated are skipped.
+    pub fn detect(&self, history: &[OrbitalElements]) -> Vec<ManeuverEvent> {
+        history
+            .windows(2)
+            .enumerate()
+            .filter_map(|(index, pair)| {
+                let (before, after) = (&pair[0], &pair[1]);
+                let predicted: StateVector = Sgp4::new(before).propagate((after.epoch_jd() - before.epoch_jd()) * 1440.0).ok()?;
+                let observed: StateVector = Sgp4::new(after).propagate(0.0).ok()?;
+                let (predicted, observed) = (KeplerianElements::from_state(&predicted), KeplerianElements::from_state(&observed));
+                let delta_semi_major_axis: f64 = observed.semi_major_axis - predicted.semi_major_axis;
+                let delta_inclination: f64 = observed.inclination - predicted.inclination;
+                if delta_semi_major_axis.abs() < self.semi_major_axis_threshold && delta_inclination.abs() < self.inclination_threshold {
+                    return None;
+                }
+                // Gauss: Δa = 2a²vΔv/μ for a tangential burn, Δi = Δv/v for a burn at a node.
+                let a: f64 = predicted.semi_major_axis;
+                let speed: f64 = (EARTH_MU / a).sqrt();
+                Some(ManeuverEvent {
+                    index: index + 1,
+                    start_jd: before.epoch_jd(),
+                    end_jd: after.epoch_jd(),
+                    delta_semi_major_axis,
+                    delta_inclination,
+                    delta_v_in_track: EARTH_MU * delta_semi_major_axis / (2.0 * a * a * speed),
+                    delta_v_cross_track: speed * delta_inclination.abs(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maneuver::{Impulse, ImpulseFrame, apply_to_elements, plane_change};
+    use crate::test_support::iss_elements;
+
+    /// Builds the element set of a burn, or of a later epoch without one.
+    fn burn(elements: &OrbitalElements, tsince: f64, delta_v: [f64; 3]) -> OrbitalElements {
+        apply_to_elements(elements, tsince, &Impulse { delta_v, frame: ImpulseFrame::Ric }).unwrap()
+    }
+
+    /// Tests the detection of an in-track burn in a history of element sets.
+    #[test]
+    fn test_detect_raise() {
+        let first: OrbitalElements = iss_elements();
+        let second: OrbitalElements = burn(&first, 720.0, [0.0; 3]);
+        let third: OrbitalElements = burn(&second, 720.0, [0.0, 0.005, 0.0]);
+        let fourth: OrbitalElements = burn(&third, 720.0, [0.0; 3]);
+        let history: Vec<OrbitalElements> = vec![first, second, third, fourth];
+
+        let events: Vec<ManeuverEvent> = ManeuverDetection::default().detect(&history);
+        assert_eq!(events.len(), 1, "{:?}", events);
+        assert_eq!(events[0].index, 2);
+        assert_eq!(events[0].end_jd, history[2].epoch_jd());
+        assert!((events[0].delta_v_in_track - 0.005).abs() < 5e-4, "{:?}", events[0]);
+        assert!(events[0].delta_v_cross_track < 1e-3);
+    }
+
+    /// Tests the detection of a plane change.
+    #[test]
+    fn test_detect_plane_change() {
+        let first: OrbitalElements = iss_elements();
+        let state: StateVector = Sgp4::new(&first).propagate(300.0).unwrap();
+        let impulse: Impulse = plane_change(&state, 0.002);
+        let second: OrbitalElements = burn(&first, 300.0, impulse.delta_v);
+        let events: Vec<ManeuverEvent> = ManeuverDetection::default().detect(&[first, second]);
+        assert_eq!(events.len(), 1);
+        // The estimate is a lower bound, reached when the burn is at a node.
+        assert!(events[0].delta_v() <= impulse.magnitude() * 1.05, "{} {}", events[0].delta_v(), impulse.magnitude());
+        assert!(events[0].delta_v_cross_track > 0.0 && events[0].delta_semi_major_axis.abs() < 0.5);
+    }
+}
@@ -0,0 +1,142 @@
+//! Conversions between osculating elements and SGP4 mean elements.
+//! Mean elements are defined by the propagator: the osculating elements of a set of mean elements
+//! are those of the state it produces at epoch. The inverse conversion iterates on that definition,
+//! correcting the guess in Cartesian space so that circular and equatorial orbits converge too.
+
+use crate::anomaly::true_to_mean;
+use crate::keplerian::KeplerianElements;
+use crate::propagator::{PropagationError, Sgp4};
+use crate::{OrbitalElements, StateVector, TWOPI, XKE, XKMPER};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Maximum number of iterations of the osculating to mean conversion.
+const MAX_ITERATIONS: usize = 50;
+/// Position tolerance of the osculating to mean conversion in kilometers.
+const POSITION_TOLERANCE: f64 = 1e-7;
+
+/// Builds mean elements whose two-body orbit is described by Keplerian elements.
+///
+/// # Arguments
+/// * `keplerian` - The Keplerian elements, taken as mean values.
+/// * `epoch_year` - Four-digit year of the epoch.
+/// * `epoch_day` - Day of the year of the epoch, including the fractional part of the day.
+/// * `bstar` - Drag term in 1/earth radii.
+///
+/// # Returns
+/// * The mean orbital elements.
+pub(crate) fn mean_from_keplerian(keplerian: &KeplerianElements, epoch_year: i32, epoch_day: f64, bstar: f64) -> OrbitalElements {
+    let mean_motion: f64 = XKE * (keplerian.semi_major_axis / XKMPER).powf(-1.5);
+    let mean_anomaly: f64 = true_to_mean(keplerian.true_anomaly, keplerian.eccentricity)
+        .unwrap_or(keplerian.true_anomaly)
+        .rem_euclid(TWOPI);
+
+    OrbitalElements {
+        inclination: keplerian.inclination,
+        raan: keplerian.raan,
+        eccentricity: keplerian.eccentricity,
+        arg_perigee: keplerian.arg_perigee,
+        mean_anomaly,
+        mean_motion,
+        bstar,
+        deep_space: TWOPI / mean_motion >= 225.0,
+        epoch_year,
+        epoch_day,
+    }
+}
+
+/// Computes the osculating elements corresponding to mean elements, at their epoch.
+///
+/// # Arguments
+/// * `elements` - The SGP4 mean elements.
+///
+/// # Returns
+/// * The osculating Keplerian elements of the propagated state at epoch, or the propagation error.
+pub fn mean_to_osculating(elements: &OrbitalElements) -> Result<KeplerianElements, PropagationError> {
+    Ok(KeplerianElements::from_state(&Sgp4::new(elements).propagate(0.0)?))
+}
+
+/// Computes the SGP4 mean elements reproducing an osculating state at their epoch.
+///
+/// # Arguments
+/// * `state` - Osculating state vector in the TEME frame (km, km/s).
+/// * `epoch_year` - Four-digit year of the state.
+/// * `epoch_day` - Day of the year of the state, including the fractional part of the day.
+/// * `bstar` - Drag term of the resulting elements, in 1/earth radii.
+///
+/// # Returns
+/// * The mean orbital elements, or `None` if the iteration does not converge (e.g. for
+///   hyperbolic states).
+pub fn osculating_to_mean(state: &StateVector, epoch_year: i32, epoch_day: f64, bstar: f64) -> Option<OrbitalElements> {
+    let mut guess: StateVector = *state;
+    for _ in 0..MAX_ITERATIONS {
+        let keplerian = KeplerianElements::from_state(&guess);
+        if !(0.0..1.0).contains(&keplerian.eccentricity) {
+            return None;
+        }
+        let elements = mean_from_keplerian(&keplerian, epoch_year, epoch_day, bstar);
+        let computed: StateVector = Sgp4::new(&elements).propagate(0.0).ok()?;
+
+        let dr: [f64; 3] = [0, 1, 2].map(|k| state.position[k] - computed.position[k]);
+        let dv: [f64; 3] = [0, 1, 2].map(|k| state.velocity[k] - computed.velocity[k]);
+        if (dr[0] * dr[0] + dr[1] * dr[1] + dr[2] * dr[2]).sqrt() < POSITION_TOLERANCE {
+            return Some(elements);
+        }
+        guess = StateVector {
+            position: [0, 1, 2].map(|k| guess.position[k] + dr[k]),
+            velocity: [0, 1, 2].map(|k| guess.velocity[k] + dv[k]),
+        };
+    }
+    None
+}
+
+impl OrbitalElements {
+    /// Computes the osculating elements at epoch (see `mean_to_osculating`).
+    ///
+    /// # Returns
+    /// * The osculating Keplerian elements, or the propagation error.
+    pub fn osculating(&self) -> Result<KeplerianElements, PropagationError> {
+        mean_to_osculating(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::iss_elements;
+
+    /// Tests that mean elements survive a round trip through their osculating state.
+    #[test]
+    fn test_mean_round_trip() {
+        let elements = iss_elements();
+
+        let state = crate::sgp4(0.0, &elements);
+        let mean = osculating_to_mean(&state, elements.epoch_year, elements.epoch_day, elements.bstar).unwrap();
+
+        assert!((mean.mean_motion - elements.mean_motion).abs() < 1e-12);
+        assert!((mean.eccentricity - elements.eccentricity).abs() < 1e-9);
+        assert!((mean.inclination - elements.inclination).abs() < 1e-9);
+        assert!((mean.raan - elements.raan).abs() < 1e-9);
+        assert!((mean.arg_perigee + mean.mean_anomaly - elements.arg_perigee - elements.mean_anomaly).abs() < 1e-9);
+        assert_eq!(mean.epoch_day, elements.epoch_day);
+
+        let osculating = elements.osculating().unwrap();
+        let position = osculating.to_state().position;
+        assert!((0..3).all(|k| (position[k] - state.position[k]).abs() < 1e-6));
+
+        let mut decayed = elements;
+        decayed.eccentricity = 0.065;
+        assert!(matches!(decayed.osculating(), Err(PropagationError::Decayed { .. })));
+    }
+
+    /// Tests that hyperbolic states are rejected.
+    #[test]
+    fn test_osculating_to_mean_hyperbolic() {
+        let state = StateVector {
+            position: [7000.0, 0.0, 0.0],
+            velocity: [0.0, 12.0, 0.0],
+        };
+
+        assert!(osculating_to_mean(&state, 2024, 1.0, 0.0).is_none());
+    }
+}
@@ -0,0 +1,233 @@
+//! Sun-synchronous and repeat ground-track analysis.
+//! The J2 secular rates of the mean elements, the ones of SGP4, give the regression of the node, compared with the
+//! apparent motion of the Sun for the local time of the ascending node, and the revolutions per
+//! nodal day, approximated by a ratio of small integers for the repeat cycle of the ground track.
+
+use crate::astro::sun_position;
+use crate::frames::EARTH_ROTATION_RATE;
+use crate::{CK2, OrbitalElements, TWOPI, XKMPER};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Nodal regression rate of a sun-synchronous orbit (one turn per tropical year), in radians per minute.
+pub const SUN_SYNCHRONOUS_RATE: f64 = TWOPI / (365.2422 * 1440.0);
+
+/// Represents the position of the orbital plane relative to the Sun.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SunSynchronism {
+    /// Nodal regression rate in radians per minute.
+    pub nodal_rate: f64,
+    /// Local mean solar time of the ascending node at epoch, in hours in [0, 24).
+    pub ltan: f64,
+    /// Drift of the local time of the ascending node in hours per day, zero for a sun-synchronous
+    /// orbit.
+    pub ltan_drift: f64,
+}
+
+impl SunSynchronism {
+    /// Checks whether the orbit is sun-synchronous.
+    ///
+    /// # Arguments
+    /// * `tolerance` - Largest drift of the local time of the ascending node in hours per year.
+    ///
+    /// # Returns
+    /// * `true` if the local time drifts by less than the tolerance.
+    pub fn is_sun_synchronous(&self, tolerance: f64) -> bool {
+        (self.ltan_drift * 365.2422).abs() < tolerance
+    }
+}
+
+/// Holds the J2 secular rates of the angles of an orbit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SecularRates {
+    /// Rate of the right ascension of the ascending node in radians per minute.
+    pub raan: f64,
+    /// Rate of the argument of perigee in radians per minute.
+    pub arg_perigee: f64,
+    /// Rate of the mean anomaly in radians per minute, the mean motion included.
+    pub mean_anomaly: f64,
+}
+
+impl SecularRates {
+    /// Converts the rates to degrees per day.
+    ///
+    /// # Returns
+    /// * The rates of the node, the argument of perigee and the mean anomaly in degrees per day.
+    pub fn degrees_per_day(&self) -> [f64; 3] {
+        [self.raan, self.arg_perigee, self.mean_anomaly].map(|rate| (rate * 1440.0).to_degrees())
+    }
+}
+
+/// Represents a repeat cycle of the ground track.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RepeatGroundTrack {
+    /// Number of revolutions in a cycle.
+    pub revolutions: u32,
+    /// Number of nodal days in a cycle.
+    pub days: u32,
+}
+
+impl RepeatGroundTrack {
+    /// Returns the number of revolutions per nodal day, e.g. 14 + 1/3.
+    pub fn revolutions_per_day(&self) -> f64 {
+        self.revolutions as f64 / self.days as f64
+    }
+}
+
+/// Computes the inclination of a sun-synchronous orbit.
+///
+/// # Arguments
+/// * `semi_major_axis` - Mean semi-major axis in kilometers.
+/// * `eccentricity` - Mean eccentricity.
+///
+/// # Returns
+/// * The inclination in radians, or `None` if the orbit is too high for J2 to turn its plane fast
+///   enough.
+pub fn sun_synchronous_inclination(semi_major_axis: f64, eccentricity: f64) -> Option<f64> {
+    let a: f64 = semi_major_axis / XKMPER;
+    let p: f64 = a * (1.0 - eccentricity * eccentricity);
+    // Mean motion in radians per minute, from XKE in Earth radii and minutes.
+    let mean_motion: f64 = crate::XKE / (a * a * a).sqrt();
+    let cos_inclination: f64 = -SUN_SYNCHRONOUS_RATE * p * p / (3.0 * CK2 * mean_motion);
+    (cos_inclination >= -1.0).then(|| cos_inclination.acos())
+}
+
+impl OrbitalElements {
+    /// Computes the J2 secular rates of the node, the argument of perigee and the mean anomaly,
+    /// to check the long-term behavior of an orbit without propagating it.
+    ///
+    /// # Returns
+    /// * The rates in radians per minute.
+    pub fn secular_rates(&self) -> SecularRates {
+        let a: f64 = self.semi_major_axis_km() / XKMPER;
+        let beta2: f64 = 1.0 - self.eccentricity * self.eccentricity;
+        let p: f64 = a * beta2;
+        let cos2: f64 = self.inclination.cos() * self.inclination.cos();
+        let factor: f64 = 1.5 * CK2 * self.mean_motion / (p * p);
+        SecularRates {
+            raan: -2.0 * factor * self.inclination.cos(),
+            arg_perigee: factor * (5.0 * cos2 - 1.0),
+            mean_anomaly: self.mean_motion + factor * beta2.sqrt() * (3.0 * cos2 - 1.0),
+        }
+    }
+
+    /// Returns the regression rate of the ascending node due to J2.
+    ///
+    /// # Returns
+    /// * The rate in radians per minute, negative for prograde orbits.
+    pub fn nodal_regression_rate(&self) -> f64 {
+        self.secular_rates().raan
+    }
+
+    /// Computes the local time of the ascending node and its drift.
+    ///
+    /// # Returns
+    /// * The nodal regression rate and the local time of the ascending node at epoch, with its drift.
+    pub fn sun_synchronism(&self) -> SunSynchronism {
+        let sun: [f64; 3] = sun_position(self.epoch_jd());
+        let nodal_rate: f64 = self.nodal_regression_rate();
+        // Noon is when the Sun is in the direction of the node.
+        let hour_angle: f64 = self.raan - sun[1].atan2(sun[0]);
+        SunSynchronism {
+            nodal_rate,
+            ltan: (12.0 + hour_angle.to_degrees() / 15.0).rem_euclid(24.0),
+            ltan_drift: (nodal_rate - SUN_SYNCHRONOUS_RATE) * 1440.0 * 24.0 / TWOPI,
+        }
+    }
+
+    /// Returns the number of revolutions per nodal day, the time the Earth takes to turn once
+    /// relative to the orbital plane.
+    pub fn revolutions_per_nodal_day(&self) -> f64 {
+        let rates: SecularRates = self.secular_rates();
+        (rates.arg_perigee + rates.mean_anomaly) / (EARTH_ROTATION_RATE * 60.0 - rates.raan)
+    }
+
+    /// Finds the shortest repeat cycle of the ground track.
+    ///
+    /// # Arguments
+    /// * `max_days` - Longest cycle in nodal days.
+    /// * `tolerance` - Largest offset of the ground track after a cycle, in revolutions.
+    ///
+    /// # Returns
+    /// * The revolutions and days of the cycle, in lowest terms, or `None` if no cycle within
+    ///   `max_days` repeats within the tolerance.
+    pub fn repeat_ground_track(&self, max_days: u32, tolerance: f64) -> Option<RepeatGroundTrack> {
+        let ratio: f64 = self.revolutions_per_nodal_day();
+        (1..=max_days).find_map(|days| {
+            let revolutions: f64 = (ratio * days as f64).round();
+            ((ratio * days as f64 - revolutions).abs() < tolerance && revolutions >= 1.0).then_some(RepeatGroundTrack {
+                revolutions: revolutions as u32,
+                days,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Tle, convert_satellite_data};
+    use crate::test_support::iss_elements;
+
+    /// Returns the elements of a sun-synchronous weather satellite.
+    fn noaa() -> OrbitalElements {
+        convert_satellite_data(
+            &Tle::from_lines(&[
+                "1 33591U 09005A   24001.50000000  .00000100  00000-0  80000-4 0  9990",
+                "2 33591  99.1900  50.0000 0013000 200.0000 160.0000 14.12500000770000",
+            ])
+            .unwrap(),
+        )
+    }
+
+    /// Tests the local time of the ascending node and the inclination of sun-synchronous orbits.
+    #[test]
+    fn test_sun_synchronism() {
+        let elements: OrbitalElements = noaa();
+        let synchronism: SunSynchronism = elements.sun_synchronism();
+        // The Sun is at a right ascension of about 281.3° on 2024 January 1.
+        assert!((synchronism.ltan - (12.0 + (50.0 - 281.3) / 15.0 + 24.0)).abs() < 0.05, "{}", synchronism.ltan);
+        assert!(synchronism.is_sun_synchronous(2.0), "{}", synchronism.ltan_drift);
+        assert!(!iss_elements().sun_synchronism().is_sun_synchronous(1.0));
+
+        let inclination: f64 = sun_synchronous_inclination(elements.semi_major_axis_km(), elements.eccentricity).unwrap();
+        // The drift of about an hour per year is a few tenths of a degree of inclination.
+        assert!((inclination - elements.inclination).abs().to_degrees() < 0.5, "{}", inclination.to_degrees());
+        assert!((sun_synchronous_inclination(XKMPER + 800.0, 0.0).unwrap().to_degrees() - 98.6).abs() < 0.1);
+        assert_eq!(sun_synchronous_inclination(XKMPER + 10000.0, 0.0), None);
+    }
+
+    /// Tests the detection of a repeat cycle of 14 + 1/3 revolutions per day.
+    #[test]
+    fn test_repeat_ground_track() {
+        let mut elements: OrbitalElements = noaa();
+        for _ in 0..5 {
+            elements.mean_motion *= (43.0 / 3.0) / elements.revolutions_per_nodal_day();
+        }
+        assert!((elements.revolutions_per_nodal_day() - 43.0 / 3.0).abs() < 1e-9);
+        let repeat: RepeatGroundTrack = elements.repeat_ground_track(30, 1e-3).unwrap();
+        assert_eq!(repeat, RepeatGroundTrack { revolutions: 43, days: 3 });
+        assert!((repeat.revolutions_per_day() - elements.revolutions_per_nodal_day()).abs() < 1e-9);
+
+        elements.mean_motion *= 1.0 + 1e-4;
+        assert_eq!(elements.repeat_ground_track(3, 1e-3), None);
+    }
+
+    /// Tests the secular rates against the mean elements propagated by SGP4 over ten days.
+    #[test]
+    fn test_secular_rates() {
+        let elements: OrbitalElements = noaa();
+        let rates: SecularRates = elements.secular_rates();
+        let [raan, arg_perigee, _] = rates.degrees_per_day();
+        // One turn per year for a sun-synchronous orbit, and about -3°/day for the perigee.
+        assert!((raan - 360.0 / 365.2422).abs() < 0.05, "{}", raan);
+        assert!(arg_perigee < -2.5 && arg_perigee > -3.5, "{}", arg_perigee);
+
+        let later: OrbitalElements = crate::mean_elements::osculating_to_mean(&crate::sgp4(14400.0, &elements), elements.epoch_year, elements.epoch_day + 10.0, elements.bstar).unwrap();
+        let drift: f64 = (later.raan - elements.raan + TWOPI).rem_euclid(TWOPI) - rates.raan * 14400.0;
+        assert!(drift.abs() < 1e-3, "{}", drift);
+    }
+}
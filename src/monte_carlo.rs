@@ -0,0 +1,370 @@
+//! Monte Carlo propagation of uncertain orbital elements.
+//! An ensemble of element sets is drawn around the nominal ones, from 1-σ uncertainties on the
+//! elements or from the covariance of the state at epoch, and propagated to a common time; the
+//! statistics of the predicted states describe the uncertainty without linearization. The draws
+//! are reproducible from a seed, and the ensemble is propagated in parallel with the `rayon` feature.
+
+use crate::covariance::Covariance;
+use crate::mean_elements::osculating_to_mean;
+use crate::propagator::{PropagationError, Sgp4};
+use crate::{OrbitalElements, StateVector, TWOPI};
+use core::f64::consts::PI;
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Holds the 1-σ uncertainties of the mean orbital elements, in the units of `OrbitalElements`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElementSigmas {
+    /// Inclination in radians.
+    pub inclination: f64,
+    /// Right ascension of the ascending node in radians.
+    pub raan: f64,
+    /// Eccentricity.
+    pub eccentricity: f64,
+    /// Argument of perigee in radians.
+    pub arg_perigee: f64,
+    /// Mean anomaly in radians.
+    pub mean_anomaly: f64,
+    /// Mean motion in radians per minute.
+    pub mean_motion: f64,
+    /// Drag term in 1/earth radii.
+    pub bstar: f64,
+}
+
+/// Describes the uncertainty of the elements at epoch.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Uncertainty {
+    /// Independent Gaussian errors on the mean elements.
+    Elements(ElementSigmas),
+    /// Gaussian errors on the osculating TEME state at epoch (km, km/s).
+    Covariance(Box<Covariance>),
+}
+
+/// Holds the settings of a Monte Carlo run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MonteCarlo {
+    /// Number of element sets drawn.
+    pub samples: usize,
+    /// Seed of the random draws; the same seed gives the same ensemble.
+    pub seed: u64,
+}
+
+impl Default for MonteCarlo {
+    fn default() -> MonteCarlo {
+        MonteCarlo { samples: 1000, seed: 0x5eed }
+    }
+}
+
+/// Represents an ellipsoid centered on the mean position.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ellipsoid {
+    /// Semi-axes in kilometers, in increasing order.
+    pub semi_axes: [f64; 3],
+    /// Unit vectors of the axes in the TEME frame, in the order of the semi-axes.
+    pub axes: [[f64; 3]; 3],
+}
+
+/// Holds the statistics of the predicted states of an ensemble.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnsembleStatistics {
+    /// Mean state in the TEME frame (km, km/s).
+    pub mean: StateVector,
+    /// Sample covariance of the states.
+    pub covariance: Covariance,
+    /// Number of element sets propagated successfully.
+    pub samples: usize,
+    /// Number of element sets that could not be built or propagated.
+    pub failed: usize,
+    /// Mahalanobis distances of the sample positions, in increasing order.
+    distances: Vec<f64>,
+}
+
+/// Generates pseudo-random numbers with the SplitMix64 algorithm.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    /// Returns a number uniformly distributed in (0, 1).
+    fn uniform(&mut self) -> f64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z: u64 = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^= z >> 31;
+        ((z >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+    }
+
+    /// Returns a number with the standard normal distribution (Box-Muller transform).
+    fn normal(&mut self) -> f64 {
+        let (u1, u2) = (self.uniform(), self.uniform());
+        (-2.0 * u1.ln()).sqrt() * (TWOPI * u2).cos()
+    }
+}
+
+/// Computes the Cholesky factor L of a covariance, P = L Lᵀ. Components without variance get a
+/// zero column.
+fn cholesky(covariance: &Covariance) -> [[f64; 6]; 6] {
+    let mut l: [[f64; 6]; 6] = [[0.0; 6]; 6];
+    for j in 0..6 {
+        let diagonal: f64 = covariance[j][j] - (0..j).map(|k| l[j][k] * l[j][k]).sum::<f64>();
+        if diagonal <= 0.0 {
+            continue;
+        }
+        l[j][j] = diagonal.sqrt();
+        for i in j + 1..6 {
+            l[i][j] = (covariance[i][j] - (0..j).map(|k| l[i][k] * l[j][k]).sum::<f64>()) / l[j][j];
+        }
+    }
+    l
+}
+
+/// Computes the eigenvalues and eigenvectors of a symmetric 3×3 matrix by Jacobi rotations.
+///
+/// # Returns
+/// * The eigenvalues in increasing order, and the unit eigenvectors in the same order.
+fn symmetric_eigen(matrix: [[f64; 3]; 3]) -> ([f64; 3], [[f64; 3]; 3]) {
+    let mut a: [[f64; 3]; 3] = matrix;
+    let mut v: [[f64; 3]; 3] = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    for _ in 0..50 {
+        let off: f64 = a[0][1] * a[0][1] + a[0][2] * a[0][2] + a[1][2] * a[1][2];
+        if off <= 1e-30 * (a[0][0] * a[0][0] + a[1][1] * a[1][1] + a[2][2] * a[2][2]) {
+            break;
+        }
+        for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+            if a[p][q] == 0.0 {
+                continue;
+            }
+            let theta: f64 = 0.5 * (2.0 * a[p][q]).atan2(a[q][q] - a[p][p]);
+            let (s, c) = theta.sin_cos();
+            for row in a.iter_mut() {
+                let (x, y) = (row[p], row[q]);
+                row[p] = c * x - s * y;
+                row[q] = s * x + c * y;
+            }
+            let (row_p, row_q) = (a[p], a[q]);
+            for k in 0..3 {
+                a[p][k] = c * row_p[k] - s * row_q[k];
+                a[q][k] = s * row_p[k] + c * row_q[k];
+            }
+            for row in v.iter_mut() {
+                let (x, y) = (row[p], row[q]);
+                row[p] = c * x - s * y;
+                row[q] = s * x + c * y;
+            }
+        }
+    }
+    let mut order: [usize; 3] = [0, 1, 2];
+    order.sort_by(|&i, &j| a[i][i].total_cmp(&a[j][j]));
+    (order.map(|k| a[k][k]), order.map(|k| [v[0][k], v[1][k], v[2][k]]))
+}
+
+impl MonteCarlo {
+    /// Draws element sets around nominal elements.
+    ///
+    /// # Arguments
+    /// * `elements` - Nominal mean elements.
+    /// * `uncertainty` - Uncertainty of the elements at epoch.
+    ///
+    /// # Returns
+    /// * The element sets that could be built; draws giving an eccentricity outside [0, 1) are
+    ///   dropped. A covariance is drawn around the state at epoch, so the propagation error of the
+    ///   nominal elements is returned if they cannot be propagated to it.
+    pub fn sample(&self, elements: &OrbitalElements, uncertainty: &Uncertainty) -> Result<Vec<OrbitalElements>, PropagationError> {
+        let mut random = SplitMix64(self.seed);
+        let factor: Option<([[f64; 6]; 6], StateVector)> = match uncertainty {
+            Uncertainty::Elements(_) => None,
+            Uncertainty::Covariance(covariance) => Some((cholesky(covariance), Sgp4::new(elements).propagate(0.0)?)),
+        };
+
+        Ok((0..self.samples)
+            .filter_map(|_| match (uncertainty, &factor) {
+                (Uncertainty::Elements(sigmas), _) => {
+                    let mean_motion: f64 = elements.mean_motion + sigmas.mean_motion * random.normal();
+                    let sample = OrbitalElements {
+                        inclination: elements.inclination + sigmas.inclination * random.normal(),
+                        raan: elements.raan + sigmas.raan * random.normal(),
+                        eccentricity: elements.eccentricity + sigmas.eccentricity * random.normal(),
+                        arg_perigee: elements.arg_perigee + sigmas.arg_perigee * random.normal(),
+                        mean_anomaly: elements.mean_anomaly + sigmas.mean_anomaly * random.normal(),
+                        mean_motion,
+                        bstar: elements.bstar + sigmas.bstar * random.normal(),
+                        deep_space: TWOPI / mean_motion >= 225.0,
+                        epoch_year: elements.epoch_year,
+                        epoch_day: elements.epoch_day,
+                    };
+                    ((0.0..1.0).contains(&sample.eccentricity) && (0.0..=PI).contains(&sample.inclination) && mean_motion > 0.0).then_some(sample)
+                }
+                (Uncertainty::Covariance(_), Some((l, nominal))) => {
+                    let z: [f64; 6] = core::array::from_fn(|_| random.normal());
+                    let delta: [f64; 6] = core::array::from_fn(|i| (0..=i).map(|k| l[i][k] * z[k]).sum());
+                    let state = StateVector {
+                        position: [0, 1, 2].map(|k| nominal.position[k] + delta[k]),
+                        velocity: [0, 1, 2].map(|k| nominal.velocity[k] + delta[k + 3]),
+                    };
+                    osculating_to_mean(&state, elements.epoch_year, elements.epoch_day, elements.bstar)
+                }
+                (Uncertainty::Covariance(_), None) => None,
+            })
+            .collect())
+    }
+
+    /// Propagates an ensemble of element sets drawn around nominal elements.
+    ///
+    /// # Arguments
+    /// * `elements` - Nominal mean elements.
+    /// * `uncertainty` - Uncertainty of the elements at epoch.
+    /// * `tsince` - Time since epoch in minutes.
+    ///
+    /// # Returns
+    /// * The statistics of the predicted states, or `None` if fewer than two samples could be
+    ///   propagated, or the propagation error of the nominal elements at epoch (see `sample`).
+    pub fn propagate(&self, elements: &OrbitalElements, uncertainty: &Uncertainty, tsince: f64) -> Result<Option<EnsembleStatistics>, PropagationError> {
+        let samples: Vec<OrbitalElements> = self.sample(elements, uncertainty)?;
+        let propagate = |sample: &OrbitalElements| Sgp4::new(sample).propagate(tsince).ok();
+        #[cfg(feature = "rayon")]
+        let states: Vec<StateVector> = samples.par_iter().filter_map(propagate).collect();
+        #[cfg(not(feature = "rayon"))]
+        let states: Vec<StateVector> = samples.iter().filter_map(propagate).collect();
+
+        Ok(EnsembleStatistics::from_states(&states).map(|statistics| EnsembleStatistics {
+            failed: self.samples - states.len(),
+            ..statistics
+        }))
+    }
+}
+
+impl EnsembleStatistics {
+    /// Computes the statistics of a set of states.
+    ///
+    /// # Arguments
+    /// * `states` - The states of the ensemble.
+    ///
+    /// # Returns
+    /// * The statistics, or `None` if there are fewer than two states.
+    pub fn from_states(states: &[StateVector]) -> Option<EnsembleStatistics> {
+        if states.len() < 2 {
+            return None;
+        }
+        let count: f64 = states.len() as f64;
+        let components: Vec<[f64; 6]> = states
+            .iter()
+            .map(|state| {
+                let [x, y, z] = state.position;
+                let [vx, vy, vz] = state.velocity;
+                [x, y, z, vx, vy, vz]
+            })
+            .collect();
+        let mean: [f64; 6] = core::array::from_fn(|k| components.iter().map(|c| c[k]).sum::<f64>() / count);
+        let covariance: Covariance = core::array::from_fn(|i| {
+            core::array::from_fn(|j| components.iter().map(|c| (c[i] - mean[i]) * (c[j] - mean[j])).sum::<f64>() / (count - 1.0))
+        });
+
+        let (values, vectors) = symmetric_eigen(core::array::from_fn(|i| core::array::from_fn(|j| covariance[i][j])));
+        let mut distances: Vec<f64> = components
+            .iter()
+            .map(|c| {
+                let d: [f64; 3] = [0, 1, 2].map(|k| c[k] - mean[k]);
+                (0..3)
+                    .filter(|&k| values[k] > 0.0)
+                    .map(|k| (d[0] * vectors[k][0] + d[1] * vectors[k][1] + d[2] * vectors[k][2]).powi(2) / values[k])
+                    .sum::<f64>()
+                    .sqrt()
+            })
+            .collect();
+        distances.sort_by(f64::total_cmp);
+
+        Some(EnsembleStatistics {
+            mean: StateVector {
+                position: [mean[0], mean[1], mean[2]],
+                velocity: [mean[3], mean[4], mean[5]],
+            },
+            covariance,
+            samples: states.len(),
+            failed: 0,
+            distances,
+        })
+    }
+
+    /// Computes the ellipsoid of the position covariance that holds a fraction of the samples.
+    ///
+    /// # Arguments
+    /// * `percentile` - Fraction of the sample positions inside the ellipsoid, in [0, 1].
+    ///
+    /// # Returns
+    /// * The ellipsoid, centered on the mean position.
+    pub fn position_ellipsoid(&self, percentile: f64) -> Ellipsoid {
+        let (values, axes) = symmetric_eigen(core::array::from_fn(|i| core::array::from_fn(|j| self.covariance[i][j])));
+        let index: usize = ((percentile.clamp(0.0, 1.0) * self.distances.len() as f64).ceil() as usize).clamp(1, self.distances.len()) - 1;
+        let scale: f64 = self.distances[index];
+        Ellipsoid {
+            semi_axes: values.map(|value| value.max(0.0).sqrt() * scale),
+            axes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::covariance::sgp4_with_covariance;
+    use crate::test_support::iss_elements;
+
+    /// Tests that the drawn elements have the requested spread and are reproducible.
+    #[test]
+    fn test_sample_elements() {
+        let elements: OrbitalElements = iss_elements();
+        let sigmas = ElementSigmas {
+            inclination: 1e-4,
+            mean_anomaly: 1e-3,
+            ..ElementSigmas::default()
+        };
+        let monte_carlo = MonteCarlo { samples: 2000, seed: 42 };
+        let samples: Vec<OrbitalElements> = monte_carlo.sample(&elements, &Uncertainty::Elements(sigmas)).unwrap();
+        assert_eq!(samples.len(), 2000);
+        let mean: f64 = samples.iter().map(|s| s.mean_anomaly).sum::<f64>() / 2000.0;
+        let sigma: f64 = (samples.iter().map(|s| (s.mean_anomaly - mean).powi(2)).sum::<f64>() / 1999.0).sqrt();
+        assert!((mean - elements.mean_anomaly).abs() < 1e-4);
+        assert!((sigma / 1e-3 - 1.0).abs() < 0.1, "{}", sigma);
+        assert!(samples.iter().all(|s| s.raan == elements.raan));
+        assert_eq!(monte_carlo.sample(&elements, &Uncertainty::Elements(sigmas)).unwrap()[7].inclination, samples[7].inclination);
+    }
+
+    /// Tests the ensemble spread of an epoch covariance against the linear covariance propagation.
+    #[test]
+    fn test_monte_carlo_covariance() {
+        let elements: OrbitalElements = iss_elements();
+        let covariance: Covariance = core::array::from_fn(|i| core::array::from_fn(|j| if i != j { 0.0 } else if i < 3 { 0.01 } else { 1e-8 }));
+        let tsince: f64 = 2.0 * elements.period_minutes();
+        let statistics = MonteCarlo { samples: 1000, seed: 7 }.propagate(&elements, &Uncertainty::Covariance(Box::new(covariance)), tsince).unwrap().unwrap();
+        assert_eq!(statistics.samples + statistics.failed, 1000);
+
+        let linear = sgp4_with_covariance(&elements, tsince, &covariance).unwrap();
+        for k in 0..3 {
+            let sigma: f64 = statistics.covariance[k][k].sqrt();
+            assert!((sigma / linear.position_sigma()[k] - 1.0).abs() < 0.15, "{} {}", sigma, linear.position_sigma()[k]);
+            assert!((statistics.mean.position[k] - linear.state.position[k]).abs() < 0.5);
+        }
+
+        let inner: Ellipsoid = statistics.position_ellipsoid(0.5);
+        let outer: Ellipsoid = statistics.position_ellipsoid(0.99);
+        assert!(inner.semi_axes[2] < outer.semi_axes[2]);
+        assert!(inner.semi_axes[0] <= inner.semi_axes[1] && inner.semi_axes[1] <= inner.semi_axes[2]);
+        // For a Gaussian cloud, half of the samples lie within 1.54 σ in three dimensions.
+        let largest: f64 = statistics.position_ellipsoid(0.5).semi_axes[2];
+        let (values, _) = symmetric_eigen(core::array::from_fn(|i| core::array::from_fn(|j| statistics.covariance[i][j])));
+        assert!((largest / values[2].sqrt() - 1.54).abs() < 0.15, "{}", largest / values[2].sqrt());
+
+        let mut decayed: OrbitalElements = elements;
+        decayed.eccentricity = 0.065;
+        let result = MonteCarlo { samples: 10, seed: 7 }.propagate(&decayed, &Uncertainty::Covariance(Box::new(covariance)), tsince);
+        assert!(matches!(result, Err(PropagationError::Decayed { .. })));
+    }
+}
@@ -0,0 +1,157 @@
+//! Conversions to and from `nalgebra` types.
+//! State vectors convert to and from pairs of `Vector3<f64>`, and the frame rotations of the crate
+//! are returned as `Matrix3<f64>`, so GNC code built on nalgebra can use them without unpacking
+//! arrays by hand.
+
+use crate::StateVector;
+use crate::frames::{precession_matrix, teme_to_j2000_matrix};
+use crate::relative::ric_axes;
+use crate::topocentric::Observer;
+use ::nalgebra::{Isometry3, Matrix3, Rotation3, Translation3, UnitQuaternion, Vector3};
+
+/// Converts a row-major 3x3 array into a matrix.
+///
+/// # Arguments
+/// * `m` - The array, indexed by row then column.
+///
+/// # Returns
+/// * The matrix with the same elements.
+pub fn matrix3(m: &[[f64; 3]; 3]) -> Matrix3<f64> {
+    Matrix3::from_fn(|row, column| m[row][column])
+}
+
+impl From<StateVector> for (Vector3<f64>, Vector3<f64>) {
+    fn from(state: StateVector) -> (Vector3<f64>, Vector3<f64>) {
+        (state.position_vector(), state.velocity_vector())
+    }
+}
+
+impl From<(Vector3<f64>, Vector3<f64>)> for StateVector {
+    fn from((position, velocity): (Vector3<f64>, Vector3<f64>)) -> StateVector {
+        StateVector {
+            position: position.into(),
+            velocity: velocity.into(),
+        }
+    }
+}
+
+impl StateVector {
+    /// Returns the position in kilometers as a vector.
+    pub fn position_vector(&self) -> Vector3<f64> {
+        Vector3::from(self.position)
+    }
+
+    /// Returns the velocity in km/s as a vector.
+    pub fn velocity_vector(&self) -> Vector3<f64> {
+        Vector3::from(self.velocity)
+    }
+
+    /// Returns the pose of the RIC frame of this state in its inertial frame.
+    ///
+    /// # Returns
+    /// * The isometry taking RIC coordinates (km) to inertial coordinates (km).
+    pub fn ric_isometry(&self) -> Isometry3<f64> {
+        let rotation: Rotation3<f64> = Rotation3::from_matrix_unchecked(ric_rotation(self).transpose());
+        Isometry3::from_parts(Translation3::from(self.position_vector()), UnitQuaternion::from_rotation_matrix(&rotation))
+    }
+}
+
+/// Computes the rotation from the inertial frame to the RIC frame of a chief (see `relative::ric_axes`).
+///
+/// # Arguments
+/// * `chief` - State vector of the chief in an inertial frame (km, km/s).
+///
+/// # Returns
+/// * The rotation matrix from the inertial frame to the RIC frame.
+pub fn ric_rotation(chief: &StateVector) -> Matrix3<f64> {
+    matrix3(&ric_axes(chief))
+}
+
+/// Computes the rotation from TEME to the Earth-fixed frame (see `frames::teme_to_ecef`).
+///
+/// # Arguments
+/// * `gmst` - Greenwich Mean Sidereal Time in radians.
+///
+/// # Returns
+/// * The rotation matrix applied to the TEME positions.
+pub fn teme_to_ecef_rotation(gmst: f64) -> Matrix3<f64> {
+    let (sin, cos) = gmst.sin_cos();
+    Matrix3::new(cos, sin, 0.0, -sin, cos, 0.0, 0.0, 0.0, 1.0)
+}
+
+/// Computes the rotation from TEME to J2000 (see `frames::teme_to_j2000_matrix`).
+///
+/// # Arguments
+/// * `jd_tt` - The Julian date in the Terrestrial Time scale.
+///
+/// # Returns
+/// * The rotation matrix from TEME to J2000.
+pub fn teme_to_j2000_rotation(jd_tt: f64) -> Matrix3<f64> {
+    matrix3(&teme_to_j2000_matrix(jd_tt))
+}
+
+/// Computes the IAU-76 precession rotation from J2000 to the mean-of-date frame.
+///
+/// # Arguments
+/// * `jd_tt` - The Julian date in the Terrestrial Time scale.
+///
+/// # Returns
+/// * The rotation matrix from J2000 to mean of date.
+pub fn precession_rotation(jd_tt: f64) -> Matrix3<f64> {
+    matrix3(&precession_matrix(jd_tt))
+}
+
+impl Observer {
+    /// Returns the rotation from the Earth-fixed frame to the East, North, Up frame of the observer.
+    pub fn enu_rotation(&self) -> Matrix3<f64> {
+        matrix3(&self.enu_axes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frames::{teme_to_ecef, teme_to_j2000};
+    use crate::relative::to_ric;
+
+    /// Returns a state vector of a low orbit.
+    fn state() -> StateVector {
+        StateVector {
+            position: [6524.834, 6862.875, 6448.296],
+            velocity: [4.901327, 5.533756, -1.976341],
+        }
+    }
+
+    /// Tests the round trip through vectors and the rotations against the array functions.
+    #[test]
+    fn test_rotations() {
+        let state: StateVector = state();
+        let (position, velocity): (Vector3<f64>, Vector3<f64>) = state.into();
+        assert_eq!(position.z, 6448.296);
+        let back: StateVector = (position, velocity).into();
+        assert_eq!(back.position, state.position);
+        assert_eq!(back.velocity, state.velocity);
+
+        let ecef: StateVector = teme_to_ecef(&state, 1.2);
+        assert!((teme_to_ecef_rotation(1.2) * position - ecef.position_vector()).norm() < 1e-9);
+        let j2000: StateVector = teme_to_j2000(&state, 2453101.828154745);
+        assert!((teme_to_j2000_rotation(2453101.828154745) * position - j2000.position_vector()).norm() < 1e-9);
+        assert!((precession_rotation(2453101.828154745).determinant() - 1.0).abs() < 1e-12);
+        let enu: Matrix3<f64> = Observer::new(45.0, 10.0, 0.0).enu_rotation();
+        assert!((enu * enu.transpose() - Matrix3::identity()).norm() < 1e-12);
+    }
+
+    /// Tests that the RIC isometry maps relative positions back to the inertial frame.
+    #[test]
+    fn test_ric_isometry() {
+        let chief: StateVector = state();
+        let deputy = StateVector {
+            position: [6525.0, 6863.5, 6447.9],
+            ..chief
+        };
+        let relative: StateVector = to_ric(&chief, &deputy);
+        let inertial: Vector3<f64> = chief.ric_isometry().transform_point(&relative.position_vector().into()).coords;
+        assert!((inertial - deputy.position_vector()).norm() < 1e-9);
+        assert!((ric_rotation(&chief) * (deputy.position_vector() - chief.position_vector()) - relative.position_vector()).norm() < 1e-9);
+    }
+}
@@ -0,0 +1,676 @@
+//! Numerical orbit propagation.
+//! The equations of motion are integrated in the TEME frame with a Runge-Kutta scheme, from an
+//! initial state vector and under a configurable force model. This gives a higher-fidelity
+//! reference to cross-check the analytical SGP4 model.
+
+use crate::astro::{AU, moon_position, sun_position};
+use crate::eclipse::sunlit_fraction;
+use crate::frames::EARTH_ROTATION_RATE;
+use crate::geodetic::ecef_to_geodetic;
+use crate::propagator::{PropagationError, Propagator};
+use crate::gravity::GravityConstants;
+use crate::{StateVector, atmosphere, harmonics, time};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Smallest step of the adaptive integrator in seconds.
+const MIN_STEP: f64 = 1e-3;
+/// Initial step of the adaptive integrator in seconds.
+const INITIAL_STEP: f64 = 60.0;
+/// Reference density of the SGP4 drag term B* in kg/m² per earth radius.
+const BSTAR_REFERENCE_DENSITY: f64 = 0.15696615;
+/// Solar radiation pressure at 1 AU in N/m².
+const SOLAR_PRESSURE: f64 = 4.56e-6;
+/// Gravitational parameter of the Sun in km³/s².
+const SUN_MU: f64 = 1.32712440018e11;
+/// Gravitational parameter of the Moon in km³/s².
+const MOON_MU: f64 = 4902.800066;
+
+/// Represents the model of the Earth's gravity field.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Gravity {
+    /// Spherical Earth.
+    PointMass,
+    /// Spherical Earth and the oblateness term J2.
+    J2,
+    /// EGM96 field truncated to a degree and order (at most 4), including the tesseral terms that
+    /// drive the longitude drift of geostationary satellites.
+    Harmonics { degree: usize, order: usize },
+}
+
+/// Represents the atmospheric drag on the satellite, in an exponential atmosphere rotating with
+/// the Earth.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Drag {
+    /// Drag coefficient times cross-sectional area over mass (Cd·A/m) in m²/kg.
+    pub ballistic_coefficient: f64,
+}
+
+impl Drag {
+    /// Builds the drag model equivalent to the SGP4 drag term of a TLE.
+    ///
+    /// # Arguments
+    /// * `bstar` - Drag term B* in inverse earth radii.
+    ///
+    /// # Returns
+    /// * The drag model, with Cd·A/m = 2 B* / ρ₀.
+    pub fn from_bstar(bstar: f64) -> Drag {
+        Drag {
+            ballistic_coefficient: 2.0 * bstar / BSTAR_REFERENCE_DENSITY,
+        }
+    }
+
+    /// Computes the drag acceleration.
+    ///
+    /// # Arguments
+    /// * `position` - Position in the TEME frame in kilometers.
+    /// * `velocity` - Velocity in the TEME frame in km/s.
+    ///
+    /// # Returns
+    /// * Acceleration in km/s².
+    pub fn acceleration(&self, position: [f64; 3], velocity: [f64; 3]) -> [f64; 3] {
+        // The altitude does not depend on the longitude, so the TEME position can be used directly.
+        let rho: f64 = atmosphere::density(ecef_to_geodetic(position).altitude);
+        let relative: [f64; 3] = [
+            velocity[0] + EARTH_ROTATION_RATE * position[1],
+            velocity[1] - EARTH_ROTATION_RATE * position[0],
+            velocity[2],
+        ];
+        let speed: f64 = relative.iter().map(|v| v * v).sum::<f64>().sqrt();
+        // ρ Cd·A/m is in 1/m: convert it to 1/km.
+        let factor: f64 = -0.5 * rho * self.ballistic_coefficient * 1000.0 * speed;
+        relative.map(|v| factor * v)
+    }
+}
+
+/// Represents the solar radiation pressure on a spherical (cannonball) satellite, with the conical
+/// shadow of the Earth.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RadiationPressure {
+    /// Reflectivity coefficient Cr, from 1 (absorbing) to 2 (specular reflection).
+    pub reflectivity: f64,
+    /// Cross-sectional area over mass in m²/kg.
+    pub area_to_mass: f64,
+}
+
+impl RadiationPressure {
+    /// Computes the radiation pressure acceleration.
+    ///
+    /// # Arguments
+    /// * `jd` - Julian date of the state.
+    /// * `position` - Position in the TEME frame in kilometers.
+    ///
+    /// # Returns
+    /// * Acceleration in km/s², pointing away from the Sun.
+    pub fn acceleration(&self, jd: f64, position: [f64; 3]) -> [f64; 3] {
+        let sun: [f64; 3] = sun_position(jd);
+        let fraction: f64 = sunlit_fraction(position, sun);
+        if fraction == 0.0 {
+            return [0.0; 3];
+        }
+        let to_sun: [f64; 3] = [sun[0] - position[0], sun[1] - position[1], sun[2] - position[2]];
+        let distance: f64 = to_sun.iter().map(|x| x * x).sum::<f64>().sqrt();
+        // Pressure times Cr·A/m is in m/s²: convert it to km/s².
+        let magnitude: f64 =
+            fraction * SOLAR_PRESSURE * self.reflectivity * self.area_to_mass * (AU / distance).powi(2) / 1000.0;
+        to_sun.map(|x| -magnitude * x / distance)
+    }
+}
+
+/// Computes the point-mass attraction of a third body, relative to the Earth.
+///
+/// # Arguments
+/// * `mu` - Gravitational parameter of the body in km³/s².
+/// * `body` - Geocentric position of the body in kilometers.
+/// * `position` - Geocentric position of the satellite in kilometers.
+///
+/// # Returns
+/// * Acceleration in km/s²: the attraction on the satellite minus the attraction on the Earth.
+pub fn third_body_acceleration(mu: f64, body: [f64; 3], position: [f64; 3]) -> [f64; 3] {
+    let relative: [f64; 3] = [body[0] - position[0], body[1] - position[1], body[2] - position[2]];
+    let relative3: f64 = relative.iter().map(|x| x * x).sum::<f64>().powf(1.5);
+    let body3: f64 = body.iter().map(|x| x * x).sum::<f64>().powf(1.5);
+    [0, 1, 2].map(|k| mu * (relative[k] / relative3 - body[k] / body3))
+}
+
+/// Represents the forces acting on the satellite.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ForceModel {
+    /// Model of the Earth's gravity field.
+    pub gravity: Gravity,
+    /// Atmospheric drag, if any.
+    pub drag: Option<Drag>,
+    /// Solar radiation pressure, if any.
+    pub radiation_pressure: Option<RadiationPressure>,
+    /// Whether to include the point-mass attraction of the Sun.
+    pub sun_gravity: bool,
+    /// Whether to include the point-mass attraction of the Moon.
+    pub moon_gravity: bool,
+    /// Constants of the central body of the point-mass and J2 models. Harmonics, drag and the Sun
+    /// and Moon terms stay specific to the Earth.
+    pub central_body: GravityConstants,
+}
+
+impl Default for ForceModel {
+    fn default() -> ForceModel {
+        ForceModel {
+            gravity: Gravity::J2,
+            drag: None,
+            radiation_pressure: None,
+            sun_gravity: false,
+            moon_gravity: false,
+            central_body: GravityConstants::default(),
+        }
+    }
+}
+
+impl ForceModel {
+    /// Computes the acceleration of the satellite.
+    ///
+    /// # Arguments
+    /// * `jd` - Julian date (UTC) of the state, which orients the Earth-fixed gravity field.
+    /// * `position` - Position in the TEME frame in kilometers.
+    /// * `velocity` - Velocity in the TEME frame in km/s.
+    ///
+    /// # Returns
+    /// * Acceleration in km/s².
+    pub fn acceleration(&self, jd: f64, position: [f64; 3], velocity: [f64; 3]) -> [f64; 3] {
+        let mut acceleration: [f64; 3] = self.gravity_acceleration(jd, position);
+        let mut add = |[ax, ay, az]: [f64; 3]| {
+            acceleration = [acceleration[0] + ax, acceleration[1] + ay, acceleration[2] + az];
+        };
+        if let Some(drag) = &self.drag {
+            add(drag.acceleration(position, velocity));
+        }
+        if let Some(radiation_pressure) = &self.radiation_pressure {
+            add(radiation_pressure.acceleration(jd, position));
+        }
+        if self.sun_gravity {
+            add(third_body_acceleration(SUN_MU, sun_position(jd), position));
+        }
+        if self.moon_gravity {
+            add(third_body_acceleration(MOON_MU, moon_position(jd), position));
+        }
+        acceleration
+    }
+
+    /// Computes the gravitational acceleration of the central body.
+    fn gravity_acceleration(&self, jd: f64, position: [f64; 3]) -> [f64; 3] {
+        match self.gravity {
+            Gravity::PointMass | Gravity::J2 => {
+                let r2: f64 = position.iter().map(|x| x * x).sum();
+                let r: f64 = r2.sqrt();
+                let body: &GravityConstants = &self.central_body;
+                let mu_r3: f64 = body.mu / (r2 * r);
+                let mut acceleration: [f64; 3] = position.map(|x| -mu_r3 * x);
+
+                if let Gravity::J2 = self.gravity {
+                    let factor: f64 = 1.5 * body.j2 * mu_r3 * body.radius * body.radius / r2;
+                    let z2_r2: f64 = 5.0 * position[2] * position[2] / r2;
+                    acceleration[0] += factor * position[0] * (z2_r2 - 1.0);
+                    acceleration[1] += factor * position[1] * (z2_r2 - 1.0);
+                    acceleration[2] += factor * position[2] * (z2_r2 - 3.0);
+                }
+                acceleration
+            }
+            Gravity::Harmonics { degree, order } => {
+                // The field rotates with the Earth: evaluate it in the Earth-fixed frame.
+                let (sin_theta, cos_theta) = time::gmst(jd).sin_cos();
+                let [x, y, z] = position;
+                let fixed: [f64; 3] = [cos_theta * x + sin_theta * y, -sin_theta * x + cos_theta * y, z];
+                let [ax, ay, az] = harmonics::acceleration(fixed, degree, order);
+                [cos_theta * ax - sin_theta * ay, sin_theta * ax + cos_theta * ay, az]
+            }
+        }
+    }
+}
+
+/// Represents the integration scheme.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Integrator {
+    /// Classical fourth-order Runge-Kutta with a fixed step in seconds.
+    Rk4 { step: f64 },
+    /// Dormand-Prince 5(4) with adaptive steps, keeping the local error of each component of the
+    /// state below a tolerance (km and km/s).
+    DormandPrince { tolerance: f64 },
+}
+
+impl Default for Integrator {
+    fn default() -> Integrator {
+        Integrator::DormandPrince { tolerance: 1e-9 }
+    }
+}
+
+/// Propagates a state vector by numerical integration of the equations of motion.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NumericalPropagator {
+    /// State vector at epoch in the TEME frame (km, km/s).
+    pub initial: StateVector,
+    /// Julian date (UTC) of the initial state.
+    pub epoch_jd: f64,
+    /// Forces acting on the satellite.
+    pub forces: ForceModel,
+    /// Integration scheme.
+    pub integrator: Integrator,
+}
+
+/// State of the integrator: position (km) followed by velocity (km/s).
+type State = [f64; 6];
+
+/// Computes `y + h * Σ bᵢ kᵢ`.
+fn combine(y: &State, h: f64, terms: &[(f64, &State)]) -> State {
+    let mut result: State = *y;
+    for (b, k) in terms {
+        for (r, k) in result.iter_mut().zip(k.iter()) {
+            *r += h * b * k;
+        }
+    }
+    result
+}
+
+impl NumericalPropagator {
+    /// Builds a propagator with the J2 force model and the adaptive Dormand-Prince integrator.
+    ///
+    /// # Arguments
+    /// * `initial` - State vector at epoch in the TEME frame (km, km/s).
+    /// * `epoch_jd` - Julian date (UTC) of the initial state.
+    ///
+    /// # Returns
+    /// * The numerical propagator.
+    pub fn new(initial: StateVector, epoch_jd: f64) -> NumericalPropagator {
+        NumericalPropagator {
+            initial,
+            epoch_jd,
+            forces: ForceModel::default(),
+            integrator: Integrator::default(),
+        }
+    }
+
+    /// Computes the time derivative of the state at a time in seconds since epoch.
+    fn derivative(&self, t: f64, y: &State) -> State {
+        let acceleration: [f64; 3] = self.forces.acceleration(self.epoch_jd + t / 86400.0, [y[0], y[1], y[2]], [y[3], y[4], y[5]]);
+        [y[3], y[4], y[5], acceleration[0], acceleration[1], acceleration[2]]
+    }
+
+    /// Performs one step of the classical Runge-Kutta scheme.
+    fn rk4_step(&self, t: f64, y: &State, h: f64) -> State {
+        let k1: State = self.derivative(t, y);
+        let k2: State = self.derivative(t + 0.5 * h, &combine(y, h, &[(0.5, &k1)]));
+        let k3: State = self.derivative(t + 0.5 * h, &combine(y, h, &[(0.5, &k2)]));
+        let k4: State = self.derivative(t + h, &combine(y, h, &[(1.0, &k3)]));
+        combine(y, h, &[(1.0 / 6.0, &k1), (1.0 / 3.0, &k2), (1.0 / 3.0, &k3), (1.0 / 6.0, &k4)])
+    }
+
+    /// Performs one step of the Dormand-Prince scheme.
+    ///
+    /// # Returns
+    /// * The fifth-order solution and the largest component of its estimated local error.
+    fn dormand_prince_step(&self, t: f64, y: &State, h: f64) -> (State, f64) {
+        let k1: State = self.derivative(t, y);
+        let k2: State = self.derivative(t + h / 5.0, &combine(y, h, &[(1.0 / 5.0, &k1)]));
+        let k3: State = self.derivative(t + 0.3 * h, &combine(y, h, &[(3.0 / 40.0, &k1), (9.0 / 40.0, &k2)]));
+        let k4: State =
+            self.derivative(t + 0.8 * h, &combine(y, h, &[(44.0 / 45.0, &k1), (-56.0 / 15.0, &k2), (32.0 / 9.0, &k3)]));
+        let k5: State = self.derivative(
+            t + 8.0 / 9.0 * h,
+            &combine(
+                y,
+                h,
+                &[(19372.0 / 6561.0, &k1), (-25360.0 / 2187.0, &k2), (64448.0 / 6561.0, &k3), (-212.0 / 729.0, &k4)],
+            ),
+        );
+        let k6: State = self.derivative(
+            t + h,
+            &combine(
+                y,
+                h,
+                &[(9017.0 / 3168.0, &k1), (-355.0 / 33.0, &k2), (46732.0 / 5247.0, &k3), (49.0 / 176.0, &k4), (-5103.0 / 18656.0, &k5)],
+            ),
+        );
+        let next: State = combine(
+            y,
+            h,
+            &[(35.0 / 384.0, &k1), (500.0 / 1113.0, &k3), (125.0 / 192.0, &k4), (-2187.0 / 6784.0, &k5), (11.0 / 84.0, &k6)],
+        );
+        let k7: State = self.derivative(t + h, &next);
+
+        // Difference between the fifth- and fourth-order solutions.
+        let error: State = combine(
+            &[0.0; 6],
+            h,
+            &[
+                (71.0 / 57600.0, &k1),
+                (-71.0 / 16695.0, &k3),
+                (71.0 / 1920.0, &k4),
+                (-17253.0 / 339200.0, &k5),
+                (22.0 / 525.0, &k6),
+                (-1.0 / 40.0, &k7),
+            ],
+        );
+        (next, error.iter().fold(0.0, |max: f64, e| max.max(e.abs())))
+    }
+
+    /// Integrates the state between two times.
+    ///
+    /// # Arguments
+    /// * `start` - Time of the state in seconds since epoch.
+    /// * `y` - State at the start time.
+    /// * `end` - Time of the requested state in seconds since epoch.
+    ///
+    /// # Returns
+    /// * The state at the end time.
+    fn integrate(&self, start: f64, y: State, end: f64) -> State {
+        let direction: f64 = if end >= start { 1.0 } else { -1.0 };
+        let mut t: f64 = start;
+        let mut y: State = y;
+
+        match self.integrator {
+            Integrator::Rk4 { step } => {
+                while (end - t) * direction > 0.0 {
+                    let h: f64 = direction * step.min((end - t).abs());
+                    y = self.rk4_step(t, &y, h);
+                    t += h;
+                }
+            }
+            Integrator::DormandPrince { tolerance } => {
+                let mut h: f64 = INITIAL_STEP;
+                while (end - t) * direction > 0.0 {
+                    let last: bool = h >= (end - t).abs();
+                    let step: f64 = direction * h.min((end - t).abs());
+                    let (next, error) = self.dormand_prince_step(t, &y, step);
+                    // Steps are accepted when accurate enough, or when they cannot shrink further.
+                    if error <= tolerance || h <= MIN_STEP {
+                        y = next;
+                        t = if last { end } else { t + step };
+                    }
+                    let scale: f64 = if error > 0.0 { 0.9 * (tolerance / error).powf(0.2) } else { 5.0 };
+                    h = (h * scale.clamp(0.2, 5.0)).max(MIN_STEP);
+                }
+            }
+        }
+        y
+    }
+
+    /// Computes the state vectors at several times, continuing the integration between
+    /// consecutive times on the same side of the epoch.
+    ///
+    /// # Arguments
+    /// * `times` - Times since epoch in minutes.
+    ///
+    /// # Returns
+    /// * One state vector per requested time, in the same order.
+    pub fn propagate_batch(&self, times: &[f64]) -> Vec<StateVector> {
+        let initial: State = to_array(&self.initial);
+        let mut current: (f64, State) = (0.0, initial);
+        times
+            .iter()
+            .map(|&tsince| {
+                let end: f64 = tsince * 60.0;
+                let (start, y) = current;
+                // Restart from the epoch rather than integrating back through it.
+                let (start, y) = if end * start < 0.0 || end.abs() < start.abs() { (0.0, initial) } else { (start, y) };
+                let y: State = self.integrate(start, y, end);
+                current = (end, y);
+                from_array(&y)
+            })
+            .collect()
+    }
+}
+
+impl Propagator for NumericalPropagator {
+    fn propagate(&self, tsince: f64) -> Result<StateVector, PropagationError> {
+        Ok(from_array(&self.integrate(0.0, to_array(&self.initial), tsince * 60.0)))
+    }
+
+    fn epoch_jd(&self) -> f64 {
+        self.epoch_jd
+    }
+}
+
+/// Packs a state vector into the integrator state.
+fn to_array(state: &StateVector) -> State {
+    let [x, y, z] = state.position;
+    let [vx, vy, vz] = state.velocity;
+    [x, y, z, vx, vy, vz]
+}
+
+/// Unpacks the integrator state into a state vector.
+fn from_array(y: &State) -> StateVector {
+    StateVector {
+        position: [y[0], y[1], y[2]],
+        velocity: [y[3], y[4], y[5]],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keplerian::{EARTH_MU, KeplerianElements};
+    use crate::{Sgp4, TWOPI, TwoBody, XKMPER};
+    use crate::test_support::iss_elements;
+
+    /// Tests both integrators against the closed-form two-body solution.
+    #[test]
+    fn test_numerical_two_body() {
+        let elements = iss_elements();
+        let two_body = TwoBody::new(&elements);
+        let forces = ForceModel {
+            gravity: Gravity::PointMass,
+            ..ForceModel::default()
+        };
+        let integrators: [Integrator; 2] = [Integrator::Rk4 { step: 10.0 }, Integrator::default()];
+
+        for integrator in integrators {
+            let numerical = NumericalPropagator {
+                forces,
+                integrator,
+                ..NumericalPropagator::new(two_body.propagate(0.0).unwrap(), elements.epoch_jd())
+            };
+            let times: [f64; 3] = [500.0, -90.0, 1000.0];
+            let states: Vec<StateVector> = numerical.propagate_batch(&times);
+            for (tsince, state) in times.iter().zip(states) {
+                let expected = two_body.propagate(*tsince).unwrap();
+                for k in 0..3 {
+                    assert!((state.position[k] - expected.position[k]).abs() < 1e-3, "{:?} {}", integrator, tsince);
+                    assert!((state.velocity[k] - expected.velocity[k]).abs() < 1e-6, "{:?} {}", integrator, tsince);
+                }
+            }
+        }
+    }
+
+    /// Tests the point-mass model of a lunar orbiter against the two-body solution.
+    #[test]
+    fn test_numerical_custom_body() {
+        let moon: GravityConstants = GravityConstants::new(4902.800066, 1738.0, 0.0002033, 0.0000085, -0.0000096, 1440.0);
+        let mut elements = iss_elements();
+        elements.mean_motion = moon.mean_motion(12.0);
+        let two_body = TwoBody::with_constants(&elements, &moon);
+        let numerical = NumericalPropagator {
+            forces: ForceModel {
+                gravity: Gravity::PointMass,
+                central_body: moon,
+                ..ForceModel::default()
+            },
+            ..NumericalPropagator::new(two_body.propagate(0.0).unwrap(), elements.epoch_jd())
+        };
+
+        let expected = two_body.propagate(240.0).unwrap();
+        let state = Propagator::propagate(&numerical, 240.0).unwrap();
+        for k in 0..3 {
+            assert!((state.position[k] - expected.position[k]).abs() < 1e-3);
+        }
+        // A radius of about 1870 km: 130 km above the lunar surface.
+        let r: f64 = state.position.iter().map(|x| x * x).sum::<f64>().sqrt();
+        assert!(r > 1800.0 && r < 1950.0, "{}", r);
+    }
+
+    /// Tests that the J2 force model follows SGP4 over a few revolutions.
+    #[test]
+    fn test_numerical_j2_against_sgp4() {
+        let elements = iss_elements();
+        let sgp4 = Sgp4::new(&elements);
+        let numerical = NumericalPropagator::new(sgp4.propagate(0.0).unwrap(), elements.epoch_jd());
+
+        let tsince: f64 = 3.0 * elements.period_minutes();
+        let state = Propagator::propagate(&numerical, tsince).unwrap();
+        let expected = sgp4.propagate(tsince).unwrap();
+        let distance: f64 = (0..3).map(|k| (state.position[k] - expected.position[k]).powi(2)).sum::<f64>().sqrt();
+        assert!(distance < 20.0, "{}", distance);
+
+        // Without J2 the node does not regress and the error is much larger.
+        let point_mass = NumericalPropagator {
+            forces: ForceModel {
+                gravity: Gravity::PointMass,
+                ..ForceModel::default()
+            },
+            ..numerical
+        };
+        let state = Propagator::propagate(&point_mass, tsince).unwrap();
+        let distance_point_mass: f64 =
+            (0..3).map(|k| (state.position[k] - expected.position[k]).powi(2)).sum::<f64>().sqrt();
+        assert!(distance_point_mass > 5.0 * distance, "{} {}", distance_point_mass, distance);
+    }
+
+    /// Tests that the tesseral terms make a geostationary satellite drift towards the stable
+    /// longitude near 75°E.
+    #[test]
+    fn test_numerical_geo_drift() {
+        let epoch_jd: f64 = 2451545.0;
+        let radius: f64 = 42164.17;
+        let gmst: f64 = time::gmst(epoch_jd);
+        let longitude: f64 = 30.0_f64.to_radians() + gmst;
+        let speed: f64 = (EARTH_MU / radius).sqrt();
+        let initial = StateVector {
+            position: [radius * longitude.cos(), radius * longitude.sin(), 0.0],
+            velocity: [-speed * longitude.sin(), speed * longitude.cos(), 0.0],
+        };
+        let longitude_at = |gravity: Gravity, days: f64| -> f64 {
+            let numerical = NumericalPropagator {
+                forces: ForceModel {
+                    gravity,
+                    ..ForceModel::default()
+                },
+                ..NumericalPropagator::new(initial, epoch_jd)
+            };
+            let state = Propagator::propagate(&numerical, days * 1440.0).unwrap();
+            let jd: f64 = epoch_jd + days;
+            (state.position[1].atan2(state.position[0]) - time::gmst(jd)).to_degrees()
+        };
+
+        let days: f64 = 30.0;
+        let zonal: f64 = longitude_at(Gravity::Harmonics { degree: 4, order: 0 }, days);
+        let tesseral: f64 = longitude_at(Gravity::Harmonics { degree: 4, order: 4 }, days);
+        let drift: f64 = (tesseral - zonal + 180.0).rem_euclid(360.0) - 180.0;
+        // Longitude acceleration of about 0.0017°/day² at 45° from the stable point: 0.76° in 30 days.
+        assert!(drift > 0.5 && drift < 1.1, "{}", drift);
+    }
+
+    /// Tests the decay of a circular orbit at 400 km against the analytical rate
+    /// Δa = -2π ρ (Cd·A/m) a² per revolution.
+    #[test]
+    fn test_numerical_drag_decay() {
+        let epoch_jd: f64 = 2451545.0;
+        let radius: f64 = XKMPER + 400.0;
+        let speed: f64 = (EARTH_MU / radius).sqrt();
+        let inclination: f64 = 51.6_f64.to_radians();
+        let initial = StateVector {
+            position: [radius, 0.0, 0.0],
+            velocity: [0.0, speed * inclination.cos(), speed * inclination.sin()],
+        };
+        let drag = Drag {
+            ballistic_coefficient: 0.01,
+        };
+        let numerical = NumericalPropagator {
+            forces: ForceModel {
+                gravity: Gravity::PointMass,
+                drag: Some(drag),
+                ..ForceModel::default()
+            },
+            ..NumericalPropagator::new(initial, epoch_jd)
+        };
+
+        let period: f64 = TWOPI * (radius.powi(3) / EARTH_MU).sqrt() / 60.0;
+        let revolutions: f64 = 15.0;
+        let state = Propagator::propagate(&numerical, revolutions * period).unwrap();
+        let decay: f64 = radius - KeplerianElements::from_state(&state).semi_major_axis;
+
+        // The co-rotating atmosphere lowers the relative speed, and the geodetic altitude is higher
+        // than 400 km away from the equator: both lower the drag, by about 15% together.
+        let expected: f64 = revolutions * TWOPI * atmosphere::density(400.0) * 0.01 * 1000.0 * radius * radius;
+        assert!(decay > 0.75 * expected && decay < 0.95 * expected, "{} {}", decay, expected);
+
+        assert!((Drag::from_bstar(3.0e-4).ballistic_coefficient - 3.82e-3).abs() < 1e-5);
+    }
+
+    /// Tests the magnitude and direction of the radiation pressure, and its shadowing.
+    #[test]
+    fn test_numerical_radiation_pressure() {
+        let jd: f64 = 2451545.0;
+        let forces = ForceModel {
+            radiation_pressure: Some(RadiationPressure {
+                reflectivity: 1.5,
+                area_to_mass: 0.02,
+            }),
+            ..ForceModel::default()
+        };
+        let sun: [f64; 3] = sun_position(jd);
+        let sun_distance: f64 = sun.iter().map(|x| x * x).sum::<f64>().sqrt();
+        let toward_sun: [f64; 3] = sun.map(|x| 42164.0 * x / sun_distance);
+        let srp = |position: [f64; 3]| -> [f64; 3] {
+            let with: [f64; 3] = forces.acceleration(jd, position, [0.0; 3]);
+            let without: [f64; 3] = ForceModel::default().acceleration(jd, position, [0.0; 3]);
+            [0, 1, 2].map(|k| with[k] - without[k])
+        };
+
+        let lit: [f64; 3] = srp(toward_sun);
+        let magnitude: f64 = lit.iter().map(|a| a * a).sum::<f64>().sqrt();
+        let expected: f64 = SOLAR_PRESSURE * 1.5 * 0.02 * (AU / (sun_distance - 42164.0)).powi(2) / 1000.0;
+        assert!((magnitude - expected).abs() < 1e-6 * expected, "{} {}", magnitude, expected);
+        let alignment: f64 = (0..3).map(|k| lit[k] * sun[k]).sum::<f64>() / (magnitude * sun_distance);
+        assert!((alignment + 1.0).abs() < 1e-9);
+
+        assert_eq!(srp(toward_sun.map(|x| -x)), [0.0; 3]);
+    }
+
+    /// Tests the tidal third-body acceleration and the inclination growth it causes at GEO.
+    #[test]
+    fn test_numerical_third_body() {
+        let jd: f64 = 2451545.0;
+        let sun: [f64; 3] = sun_position(jd);
+        let sun_distance: f64 = sun.iter().map(|x| x * x).sum::<f64>().sqrt();
+        let toward_sun: [f64; 3] = sun.map(|x| 42164.0 * x / sun_distance);
+        let tidal: [f64; 3] = third_body_acceleration(SUN_MU, sun, toward_sun);
+        let magnitude: f64 = tidal.iter().map(|a| a * a).sum::<f64>().sqrt();
+        assert!((magnitude / (2.0 * SUN_MU * 42164.0 / sun_distance.powi(3)) - 1.0).abs() < 1e-3);
+
+        let radius: f64 = 42164.17;
+        let speed: f64 = (EARTH_MU / radius).sqrt();
+        let initial = StateVector {
+            position: [radius, 0.0, 0.0],
+            velocity: [0.0, speed, 0.0],
+        };
+        let inclination_after = |sun_gravity: bool, moon_gravity: bool| -> f64 {
+            let numerical = NumericalPropagator {
+                forces: ForceModel {
+                    sun_gravity,
+                    moon_gravity,
+                    ..ForceModel::default()
+                },
+                ..NumericalPropagator::new(initial, jd)
+            };
+            let state = Propagator::propagate(&numerical, 30.0 * 1440.0).unwrap();
+            KeplerianElements::from_state(&state).inclination.to_degrees()
+        };
+
+        // The Sun and the Moon tilt geostationary orbits by up to about 0.9° per year.
+        assert!(inclination_after(false, false) < 1e-6);
+        let both: f64 = inclination_after(true, true);
+        assert!(both > 0.03 && both < 0.1, "{}", both);
+    }
+}
@@ -0,0 +1,399 @@
+//! CCSDS Orbit Mean-Elements Message (OMM) parsing.
+//! This module reads OMMs in KVN (`KEY = value`) and XML form into the same element set
+//! fields as a TLE, which `convert_fields` then turns into orbital elements for `sgp4()`.
+//! With the `json` feature, it also reads the JSON form of the same keywords returned by
+//! Celestrak (`gp.php?FORMAT=json`) and Space-Track.
+
+use crate::TleFields;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Keys read from an OMM, as written in both the KVN and XML forms.
+const OMM_KEYS: [&str; 18] = [
+    "OBJECT_NAME",
+    "OBJECT_ID",
+    "MEAN_ELEMENT_THEORY",
+    "EPOCH",
+    "MEAN_MOTION",
+    "ECCENTRICITY",
+    "INCLINATION",
+    "RA_OF_ASC_NODE",
+    "ARG_OF_PERICENTER",
+    "MEAN_ANOMALY",
+    "EPHEMERIS_TYPE",
+    "CLASSIFICATION_TYPE",
+    "NORAD_CAT_ID",
+    "ELEMENT_SET_NO",
+    "REV_AT_EPOCH",
+    "BSTAR",
+    "MEAN_MOTION_DOT",
+    "MEAN_MOTION_DDOT",
+];
+
+/// Errors that can occur while reading an OMM.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OmmError {
+    /// A mandatory keyword is missing.
+    Missing(&'static str),
+    /// A keyword has a value that cannot be parsed.
+    Invalid {
+        /// The keyword.
+        key: &'static str,
+        /// The value found in the message.
+        value: String,
+    },
+    /// The mean elements are not SGP4 mean elements.
+    Theory(String),
+    /// The message is not well-formed.
+    Syntax(String),
+}
+
+impl fmt::Display for OmmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OmmError::Missing(key) => write!(f, "missing OMM keyword {}", key),
+            OmmError::Invalid { key, value } => write!(f, "invalid value '{}' for OMM keyword {}", value, key),
+            OmmError::Theory(theory) => write!(f, "unsupported mean element theory {}", theory),
+            OmmError::Syntax(message) => write!(f, "malformed OMM: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for OmmError {}
+
+/// Parses an OMM in Keyword-Value Notation (KVN).
+///
+/// Comment lines are ignored, as are units written in square brackets after a value.
+///
+/// # Arguments
+/// * `text` - The contents of the KVN message.
+///
+/// # Returns
+/// * The element set fields, or an error if the message is incomplete or malformed.
+pub fn parse_kvn(text: &str) -> Result<TleFields, OmmError> {
+    let mut values: HashMap<String, String> = HashMap::new();
+
+    for line in text.lines() {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value: &str = value.split('[').next().unwrap_or("").trim();
+        values.insert(key.trim().to_string(), value.to_string());
+    }
+
+    fields_from_values(&values)
+}
+
+/// Parses an OMM in XML form.
+///
+/// Only the leaf elements holding the mean elements and TLE parameters are read; the
+/// surrounding structure (header, segment, metadata) is not validated.
+///
+/// # Arguments
+/// * `text` - The contents of the XML message.
+///
+/// # Returns
+/// * The element set fields, or an error if the message is incomplete or malformed.
+pub fn parse_xml(text: &str) -> Result<TleFields, OmmError> {
+    let mut values: HashMap<String, String> = HashMap::new();
+
+    for key in OMM_KEYS {
+        if let Some(value) = xml_value(text, key) {
+            values.insert(key.to_string(), value.trim().to_string());
+        }
+    }
+
+    fields_from_values(&values)
+}
+
+/// Parses a JSON array of GP element sets, as returned by Celestrak's `gp.php?FORMAT=json`.
+///
+/// Values may be JSON numbers (Celestrak) or strings (Space-Track). A single object
+/// instead of an array is also accepted.
+///
+/// # Arguments
+/// * `text` - The JSON document.
+///
+/// # Returns
+/// * The element set fields of every record, or the first error encountered.
+#[cfg(feature = "json")]
+pub fn parse_json(text: &str) -> Result<Vec<TleFields>, OmmError> {
+    let document: serde_json::Value = serde_json::from_str(text).map_err(|e| OmmError::Syntax(e.to_string()))?;
+    let records: Vec<&serde_json::Value> = match &document {
+        serde_json::Value::Array(records) => records.iter().collect(),
+        record => vec![record],
+    };
+
+    records
+        .into_iter()
+        .map(|record| {
+            let object = record.as_object().ok_or_else(|| OmmError::Syntax("expected a JSON object".to_string()))?;
+            let mut values: HashMap<String, String> = HashMap::new();
+            for key in OMM_KEYS {
+                match object.get(key) {
+                    Some(serde_json::Value::String(value)) => values.insert(key.to_string(), value.trim().to_string()),
+                    Some(serde_json::Value::Number(value)) => values.insert(key.to_string(), value.to_string()),
+                    _ => None,
+                };
+            }
+            fields_from_values(&values)
+        })
+        .collect()
+}
+
+/// Extracts the text content of the first XML element with the given tag.
+///
+/// # Arguments
+/// * `text` - The XML document.
+/// * `tag` - The element name.
+///
+/// # Returns
+/// * The text between the opening and closing tags, if the element exists.
+fn xml_value<'a>(text: &'a str, tag: &str) -> Option<&'a str> {
+    let open: String = format!("<{}", tag);
+    let close: String = format!("</{}>", tag);
+
+    let mut search: usize = 0;
+    while let Some(offset) = text[search..].find(&open) {
+        let start: usize = search + offset + open.len();
+        // Skip longer tags sharing the same prefix, e.g. MEAN_MOTION_DOT for MEAN_MOTION.
+        if text[start..].starts_with(['>', ' ', '\t', '\n', '\r']) {
+            let content: usize = start + text[start..].find('>')? + 1;
+            let end: usize = content + text[content..].find(&close)?;
+            return Some(&text[content..end]);
+        }
+        search = start;
+    }
+
+    None
+}
+
+/// Parses an ISO 8601 epoch such as `2008-09-20T12:25:40.104192` into a year and a
+/// fractional day of the year.
+///
+/// # Arguments
+/// * `text` - The epoch string.
+///
+/// # Returns
+/// * The four-digit year and the day of the year (1.0 at January 1st, 0h), if valid.
+pub(crate) fn parse_iso_epoch(text: &str) -> Option<(i32, f64)> {
+    let text: &str = text.trim().trim_end_matches('Z');
+    let (date, time) = text.split_once('T').unwrap_or((text, "00:00:00"));
+
+    let mut date_parts = date.split('-');
+    let year: i32 = date_parts.next()?.parse().ok()?;
+    let month: usize = date_parts.next()?.parse().ok()?;
+    let day: u32 = date_parts.next()?.parse().ok()?;
+
+    let mut time_parts = time.split(':');
+    let hours: f64 = time_parts.next()?.parse().ok()?;
+    let minutes: f64 = time_parts.next().unwrap_or("0").parse().ok()?;
+    let seconds: f64 = time_parts.next().unwrap_or("0").parse().ok()?;
+
+    let leap: bool = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    let days_in_months: [u32; 12] = [31, if leap { 29 } else { 28 }, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_months[month - 1] {
+        return None;
+    }
+
+    let day_of_year: u32 = days_in_months[..month - 1].iter().sum::<u32>() + day;
+    Some((year, day_of_year as f64 + (hours * 3600.0 + minutes * 60.0 + seconds) / 86400.0))
+}
+
+/// Converts an international designator from the OMM form (`1998-067A`) to the TLE form (`98067A`).
+///
+/// # Arguments
+/// * `object_id` - The OMM object identifier.
+///
+/// # Returns
+/// * The designator as written in TLE line 1.
+pub(crate) fn tle_designator(object_id: &str) -> String {
+    match object_id.split_once('-') {
+        Some((year, piece)) if year.len() == 4 => format!("{}{}", &year[2..], piece),
+        _ => object_id.to_string(),
+    }
+}
+
+/// Builds element set fields from the key/value pairs of an OMM.
+///
+/// # Arguments
+/// * `values` - The values found in the message, by keyword.
+///
+/// # Returns
+/// * The element set fields, or an error if a value is missing or malformed.
+fn fields_from_values(values: &HashMap<String, String>) -> Result<TleFields, OmmError> {
+    if let Some(theory) = values.get("MEAN_ELEMENT_THEORY")
+        && !theory.to_uppercase().starts_with("SGP4")
+    {
+        return Err(OmmError::Theory(theory.clone()));
+    }
+
+    let real = |key: &'static str| -> Result<f64, OmmError> {
+        let value: &String = values.get(key).ok_or(OmmError::Missing(key))?;
+        value.parse::<f64>().map_err(|_| OmmError::Invalid { key, value: value.clone() })
+    };
+    let optional_real = |key: &'static str| -> Result<f64, OmmError> {
+        if values.contains_key(key) { real(key) } else { Ok(0.0) }
+    };
+    let optional_int = |key: &'static str| -> Result<u32, OmmError> {
+        match values.get(key) {
+            Some(value) => value.parse::<u32>().map_err(|_| OmmError::Invalid { key, value: value.clone() }),
+            None => Ok(0),
+        }
+    };
+
+    let epoch: &String = values.get("EPOCH").ok_or(OmmError::Missing("EPOCH"))?;
+    let (year, epoch_day) = parse_iso_epoch(epoch).ok_or(OmmError::Invalid { key: "EPOCH", value: epoch.clone() })?;
+
+    Ok(TleFields {
+        name: values.get("OBJECT_NAME").cloned(),
+        catalog_number: optional_int("NORAD_CAT_ID")?,
+        classification: values.get("CLASSIFICATION_TYPE").and_then(|c| c.chars().next()).unwrap_or('U'),
+        international_designator: values.get("OBJECT_ID").map(|id| tle_designator(id)).unwrap_or_default(),
+        epoch_year: year.rem_euclid(100) as u8,
+        epoch_day,
+        mean_motion_dot: optional_real("MEAN_MOTION_DOT")?,
+        mean_motion_ddot: optional_real("MEAN_MOTION_DDOT")?,
+        bstar: optional_real("BSTAR")?,
+        ephemeris_type: optional_int("EPHEMERIS_TYPE")? as u8,
+        element_set_number: optional_int("ELEMENT_SET_NO")?,
+        inclination: real("INCLINATION")?,
+        raan: real("RA_OF_ASC_NODE")?,
+        eccentricity: real("ECCENTRICITY")?,
+        arg_perigee: real("ARG_OF_PERICENTER")?,
+        mean_anomaly: real("MEAN_ANOMALY")?,
+        mean_motion: real("MEAN_MOTION")?,
+        revolution_number: optional_int("REV_AT_EPOCH")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert_fields;
+    use crate::test_support::iss_elements;
+
+    const KVN: &str = "\
+CCSDS_OMM_VERS = 2.0
+CREATION_DATE = 2008-09-21T00:00:00
+ORIGINATOR = 18 SPCS
+COMMENT This is a comment
+OBJECT_NAME = ISS (ZARYA)
+OBJECT_ID = 1998-067A
+CENTER_NAME = EARTH
+REF_FRAME = TEME
+TIME_SYSTEM = UTC
+MEAN_ELEMENT_THEORY = SGP4
+EPOCH = 2008-09-20T12:25:40.104192
+MEAN_MOTION = 15.72125391 [rev/day]
+ECCENTRICITY = 0.0006703
+INCLINATION = 51.6416 [deg]
+RA_OF_ASC_NODE = 247.4627 [deg]
+ARG_OF_PERICENTER = 130.5360 [deg]
+MEAN_ANOMALY = 325.0288 [deg]
+EPHEMERIS_TYPE = 0
+CLASSIFICATION_TYPE = U
+NORAD_CAT_ID = 25544
+ELEMENT_SET_NO = 292
+REV_AT_EPOCH = 56353
+BSTAR = -0.11606E-4 [1/ER]
+MEAN_MOTION_DOT = -0.00002182 [rev/day**2]
+MEAN_MOTION_DDOT = 0.0 [rev/day**3]
+";
+
+    /// Tests that a KVN OMM gives the same elements as the equivalent TLE.
+    #[test]
+    fn test_parse_kvn() {
+        let fields = parse_kvn(KVN).unwrap();
+
+        assert_eq!(fields.name.as_deref(), Some("ISS (ZARYA)"));
+        assert_eq!(fields.catalog_number, 25544);
+        assert_eq!(fields.international_designator, "98067A");
+        assert_eq!(fields.epoch_year, 8);
+        assert!((fields.epoch_day - 264.51782528).abs() < 1e-9);
+        assert_eq!(fields.revolution_number, 56353);
+
+        let expected = iss_elements();
+        let elements = convert_fields(&fields);
+
+        assert!((elements.inclination - expected.inclination).abs() < 1e-12);
+        assert!((elements.mean_motion - expected.mean_motion).abs() < 1e-12);
+        assert!((elements.bstar - expected.bstar).abs() < 1e-12);
+    }
+
+    /// Tests parsing an XML OMM.
+    #[test]
+    fn test_parse_xml() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ndm><omm id="CCSDS_OMM_VERS" version="2.0"><body><segment>
+  <metadata>
+    <OBJECT_NAME>ISS (ZARYA)</OBJECT_NAME>
+    <OBJECT_ID>1998-067A</OBJECT_ID>
+    <MEAN_ELEMENT_THEORY>SGP4</MEAN_ELEMENT_THEORY>
+  </metadata>
+  <data>
+    <meanElements>
+      <EPOCH>2008-09-20T12:25:40.104192</EPOCH>
+      <MEAN_MOTION>15.72125391</MEAN_MOTION>
+      <ECCENTRICITY>.0006703</ECCENTRICITY>
+      <INCLINATION>51.6416</INCLINATION>
+      <RA_OF_ASC_NODE>247.4627</RA_OF_ASC_NODE>
+      <ARG_OF_PERICENTER>130.5360</ARG_OF_PERICENTER>
+      <MEAN_ANOMALY>325.0288</MEAN_ANOMALY>
+    </meanElements>
+    <tleParameters>
+      <NORAD_CAT_ID>25544</NORAD_CAT_ID>
+      <BSTAR>-.11606E-4</BSTAR>
+      <MEAN_MOTION_DOT>-.00002182</MEAN_MOTION_DOT>
+      <MEAN_MOTION_DDOT>0</MEAN_MOTION_DDOT>
+    </tleParameters>
+  </data>
+</segment></body></omm></ndm>"#;
+
+        let fields = parse_xml(xml).unwrap();
+
+        assert_eq!(fields.name.as_deref(), Some("ISS (ZARYA)"));
+        assert!((fields.mean_motion - 15.72125391).abs() < 1e-12);
+        assert!((fields.mean_motion_dot + 0.00002182).abs() < 1e-12);
+        assert!((fields.bstar + 0.11606e-4).abs() < 1e-12);
+        assert!((fields.epoch_day - 264.51782528).abs() < 1e-9);
+    }
+
+    /// Tests the errors reported for incomplete or non-SGP4 messages.
+    #[test]
+    fn test_omm_errors() {
+        let missing = KVN.replace("INCLINATION = 51.6416 [deg]\n", "");
+        assert_eq!(parse_kvn(&missing).unwrap_err(), OmmError::Missing("INCLINATION"));
+
+        let invalid = KVN.replace("ECCENTRICITY = 0.0006703", "ECCENTRICITY = abc");
+        assert!(matches!(parse_kvn(&invalid).unwrap_err(), OmmError::Invalid { key: "ECCENTRICITY", .. }));
+
+        let theory = KVN.replace("MEAN_ELEMENT_THEORY = SGP4", "MEAN_ELEMENT_THEORY = DSST");
+        assert_eq!(parse_kvn(&theory).unwrap_err(), OmmError::Theory("DSST".to_string()));
+    }
+
+    /// Tests parsing Celestrak GP JSON records with numeric and string values.
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_parse_json() {
+        let json = r#"[{"OBJECT_NAME":"ISS (ZARYA)","OBJECT_ID":"1998-067A","EPOCH":"2008-09-20T12:25:40.104192",
+            "MEAN_MOTION":15.72125391,"ECCENTRICITY":0.0006703,"INCLINATION":51.6416,"RA_OF_ASC_NODE":247.4627,
+            "ARG_OF_PERICENTER":130.536,"MEAN_ANOMALY":325.0288,"EPHEMERIS_TYPE":0,"CLASSIFICATION_TYPE":"U",
+            "NORAD_CAT_ID":25544,"ELEMENT_SET_NO":292,"REV_AT_EPOCH":56353,"BSTAR":-1.1606e-5,
+            "MEAN_MOTION_DOT":-2.182e-5,"MEAN_MOTION_DDOT":0},
+            {"OBJECT_NAME":"ISS (ZARYA)","EPOCH":"2008-09-20T12:25:40.104192","MEAN_MOTION":"15.72125391",
+            "ECCENTRICITY":"0.0006703","INCLINATION":"51.6416","RA_OF_ASC_NODE":"247.4627",
+            "ARG_OF_PERICENTER":"130.5360","MEAN_ANOMALY":"325.0288","NORAD_CAT_ID":"25544","BSTAR":"-0.000011606"}]"#;
+
+        let records = parse_json(json).unwrap();
+
+        assert_eq!(records.len(), 2);
+        for fields in &records {
+            assert_eq!(fields.catalog_number, 25544);
+            assert!((fields.bstar + 0.11606e-4).abs() < 1e-12);
+            assert!((fields.mean_motion - 15.72125391).abs() < 1e-12);
+        }
+        assert_eq!(records[0].international_designator, "98067A");
+        assert!(matches!(parse_json("[1, 2]"), Err(OmmError::Syntax(_))));
+    }
+}
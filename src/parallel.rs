@@ -0,0 +1,61 @@
+//! Parallel propagation with rayon.
+//! Results are collected in the order of the inputs, so they do not depend on the scheduling of
+//! the worker threads.
+
+use crate::{OrbitalElements, StateVector, sgp4_batch};
+use rayon::prelude::*;
+
+/// Number of times propagated by one task in `sgp4_batch_par`.
+const CHUNK_SIZE: usize = 1024;
+
+/// Computes the state vectors of a satellite at several times, in parallel.
+///
+/// # Arguments
+/// * `times` - Times since epoch in minutes.
+/// * `elements` - Orbital elements of the satellite.
+///
+/// # Returns
+/// * One state vector per requested time, in the same order.
+pub fn sgp4_batch_par(times: &[f64], elements: &OrbitalElements) -> Vec<StateVector> {
+    times
+        .par_chunks(CHUNK_SIZE)
+        .flat_map_iter(|chunk| sgp4_batch(chunk, elements))
+        .collect()
+}
+
+/// Propagates every satellite of a catalog over a common time grid, one satellite per task.
+///
+/// # Arguments
+/// * `catalog` - Orbital elements of the satellites.
+/// * `times` - Times since the epoch of each satellite in minutes.
+///
+/// # Returns
+/// * For each satellite in catalog order, one state vector per requested time.
+pub fn propagate_catalog(catalog: &[OrbitalElements], times: &[f64]) -> Vec<Vec<StateVector>> {
+    catalog.par_iter().map(|elements| sgp4_batch(times, elements)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sgp4;
+    use crate::test_support::iss_elements;
+
+    /// Tests that parallel propagation matches sequential propagation, in order.
+    #[test]
+    fn test_parallel_propagation() {
+        let iss = iss_elements();
+        let times: Vec<f64> = (0..5000).map(|i| i as f64 * 0.5).collect();
+
+        let states = sgp4_batch_par(&times, &iss);
+        assert_eq!(states.len(), times.len());
+        assert_eq!(states[4321].position, sgp4(times[4321], &iss).position);
+
+        let catalog: Vec<OrbitalElements> = (0..8).map(|k| OrbitalElements { raan: k as f64 * 0.5, ..iss }).collect();
+        let results = propagate_catalog(&catalog, &times[..10]);
+        assert_eq!(results.len(), 8);
+        for (elements, states) in catalog.iter().zip(&results) {
+            assert_eq!(states[9].position, sgp4(times[9], elements).position);
+        }
+    }
+}
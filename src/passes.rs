@@ -0,0 +1,335 @@
+//! Pass prediction for a ground observer.
+//! Passes are found by sampling the satellite elevation a few dozen times per revolution to bracket
+//! horizon crossings, which are then refined with Brent's method (see `events`). The culmination is
+//! refined by a golden-section search between rise and set. Optical visibility additionally requires
+//! the satellite to be sunlit while the observer is in darkness.
+
+use crate::astro::sun_position;
+use crate::eclipse::{Illumination, illumination};
+use crate::events::crossings;
+use crate::propagator::{PropagationError, Propagator};
+use crate::topocentric::Observer;
+use crate::{OrbitalElements, Sgp4, StateVector, TWOPI, time};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Number of elevation samples per revolution used to bracket horizon crossings.
+const SAMPLES_PER_REVOLUTION: f64 = 100.0;
+/// Tolerance on the refined event times in minutes.
+const TIME_TOLERANCE: f64 = 1e-6;
+
+/// Represents a pass of a satellite over a ground observer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pass {
+    /// Acquisition of signal (rise above the horizon), in minutes since epoch.
+    pub aos: f64,
+    /// Loss of signal (set below the horizon), in minutes since epoch.
+    pub los: f64,
+    /// Time of maximum elevation, in minutes since epoch.
+    pub max_elevation_time: f64,
+    /// Maximum elevation in radians.
+    pub max_elevation: f64,
+}
+
+impl Pass {
+    /// Returns the duration of the pass in minutes.
+    pub fn duration(&self) -> f64 {
+        self.los - self.aos
+    }
+}
+
+/// Computes the elevation of a satellite seen by an observer.
+///
+/// # Arguments
+/// * `observer` - The ground observer.
+/// * `propagator` - Propagator of the satellite.
+/// * `tsince` - Time since epoch in minutes.
+///
+/// # Returns
+/// * The elevation above the local horizon in radians, or the propagation error.
+pub fn elevation<P: Propagator>(observer: &Observer, propagator: &P, tsince: f64) -> Result<f64, PropagationError> {
+    let state: StateVector = propagator.propagate(tsince)?;
+    let gmst: f64 = time::gmst(propagator.epoch_jd() + tsince / 1440.0);
+    Ok(observer.look_angles(&state, gmst).elevation)
+}
+
+/// Computes the elevation of a satellite when it is optically visible by an observer.
+///
+/// # Arguments
+/// * `observer` - The ground observer.
+/// * `propagator` - Propagator of the satellite.
+/// * `twilight` - Highest Sun elevation at the observer, in radians, for the sky to be dark enough.
+/// * `tsince` - Time since epoch in minutes.
+///
+/// # Returns
+/// * The elevation in radians if the satellite is at least partly sunlit and the observer in darkness,
+///   -1 otherwise, or the propagation error.
+fn visible_elevation<P: Propagator>(observer: &Observer, propagator: &P, twilight: f64, tsince: f64) -> Result<f64, PropagationError> {
+    let state: StateVector = propagator.propagate(tsince)?;
+    let jd: f64 = propagator.epoch_jd() + tsince / 1440.0;
+    let gmst: f64 = time::gmst(jd);
+    let sun = StateVector {
+        position: sun_position(jd),
+        velocity: [0.0; 3],
+    };
+    if illumination(state.position, sun.position) == Illumination::Umbra || observer.look_angles(&sun, gmst).elevation > twilight {
+        return Ok(-1.0);
+    }
+    Ok(observer.look_angles(&state, gmst).elevation)
+}
+
+/// Refines the maximum of a unimodal function by golden-section search.
+///
+/// # Arguments
+/// * `f` - The function.
+/// * `low` - Start of the search interval.
+/// * `high` - End of the search interval.
+///
+/// # Returns
+/// * The time of the maximum and the maximum value.
+pub(crate) fn refine_maximum(f: impl Fn(f64) -> f64, mut low: f64, mut high: f64) -> (f64, f64) {
+    let ratio: f64 = (5.0_f64.sqrt() - 1.0) / 2.0;
+    let mut left: f64 = high - ratio * (high - low);
+    let mut right: f64 = low + ratio * (high - low);
+    let mut f_left: f64 = f(left);
+    let mut f_right: f64 = f(right);
+    while high - low > TIME_TOLERANCE {
+        if f_left < f_right {
+            low = left;
+            left = right;
+            f_left = f_right;
+            right = low + ratio * (high - low);
+            f_right = f(right);
+        } else {
+            high = right;
+            right = left;
+            f_right = f_left;
+            left = high - ratio * (high - low);
+            f_left = f(left);
+        }
+    }
+    let time: f64 = 0.5 * (low + high);
+    (time, f(time))
+}
+
+/// Finds the intervals of a time window during which a function is positive.
+///
+/// # Arguments
+/// * `f` - The function, sampled `SAMPLES_PER_REVOLUTION` times per revolution.
+/// * `elements` - Orbital elements of the satellite, used to choose the sampling step.
+/// * `start` - Start of the window in minutes since epoch.
+/// * `end` - End of the window in minutes since epoch.
+///
+/// # Returns
+/// * The start and end of each interval, clipped to the window, or the first propagation error on
+///   the sampling grid.
+pub(crate) fn positive_intervals(f: impl Fn(f64) -> Result<f64, PropagationError>, elements: &OrbitalElements, start: f64, end: f64) -> Result<Vec<(f64, f64)>, PropagationError> {
+    if end <= start || elements.mean_motion <= 0.0 {
+        return Ok(Vec::new());
+    }
+    let step: f64 = TWOPI / elements.mean_motion / SAMPLES_PER_REVOLUTION;
+    // Evaluating the whole grid first reports propagation errors, so that the refinement only meets
+    // times between valid samples.
+    let count: usize = ((end - start) / step).ceil() as usize;
+    for i in 0..=count {
+        f((start + i as f64 * step).min(end))?;
+    }
+
+    let g = |tsince: f64| f(tsince).unwrap_or(f64::NAN);
+    let mut intervals: Vec<(f64, f64)> = Vec::new();
+    let mut rise: Option<f64> = (g(start) > 0.0).then_some(start);
+    for (tsince, rising) in crossings(g, start, end, step, TIME_TOLERANCE) {
+        if rising {
+            rise = Some(tsince);
+        } else if let Some(begin) = rise.take() {
+            intervals.push((begin, tsince));
+        }
+    }
+    if let Some(begin) = rise {
+        intervals.push((begin, end));
+    }
+    Ok(intervals)
+}
+
+/// Finds the passes during which an elevation function is positive and refines their culmination.
+///
+/// # Arguments
+/// * `f` - The elevation function, negative when the satellite is not observable.
+/// * `elements` - Orbital elements of the satellite.
+/// * `start` - Start of the search window in minutes since epoch.
+/// * `end` - End of the search window in minutes since epoch.
+///
+/// # Returns
+/// * The passes in chronological order, clipped to the window, or the first propagation error.
+fn find_passes(f: impl Fn(f64) -> Result<f64, PropagationError> + Copy, elements: &OrbitalElements, start: f64, end: f64) -> Result<Vec<Pass>, PropagationError> {
+    Ok(positive_intervals(f, elements, start, end)?
+        .into_iter()
+        .map(|(aos, los)| {
+            let (max_elevation_time, max_elevation) = refine_maximum(|tsince| f(tsince).unwrap_or(f64::NAN), aos, los);
+            Pass {
+                aos,
+                los,
+                max_elevation_time,
+                max_elevation,
+            }
+        })
+        .collect())
+}
+
+/// Predicts the passes of a satellite above the horizon of an observer.
+///
+/// # Arguments
+/// * `observer` - The ground observer.
+/// * `elements` - Orbital elements of the satellite.
+/// * `start` - Start of the search window in minutes since epoch.
+/// * `end` - End of the search window in minutes since epoch.
+///
+/// # Returns
+/// * The passes in chronological order, or the first propagation error. Passes in progress at the
+///   edges of the window are clipped to it.
+pub fn passes(observer: &Observer, elements: &OrbitalElements, start: f64, end: f64) -> Result<Vec<Pass>, PropagationError> {
+    let propagator: Sgp4 = Sgp4::new(elements);
+    let f = |tsince: f64| elevation(observer, &propagator, tsince);
+    find_passes(f, elements, start, end)
+}
+
+/// Predicts the passes of a satellite above an elevation mask, such as the lowest elevation a
+/// ground station antenna can track.
+///
+/// # Arguments
+/// * `observer` - The ground observer.
+/// * `elements` - Orbital elements of the satellite.
+/// * `start` - Start of the search window in minutes since epoch.
+/// * `end` - End of the search window in minutes since epoch.
+/// * `min_elevation` - Elevation of the mask in radians.
+///
+/// # Returns
+/// * The passes in chronological order, starting and ending at the mask and clipped to the window,
+///   or the first propagation error.
+pub fn passes_above(observer: &Observer, elements: &OrbitalElements, start: f64, end: f64, min_elevation: f64) -> Result<Vec<Pass>, PropagationError> {
+    let propagator: Sgp4 = Sgp4::new(elements);
+    let f = |tsince: f64| elevation(observer, &propagator, tsince).map(|elevation| elevation - min_elevation);
+    Ok(find_passes(f, elements, start, end)?
+        .into_iter()
+        .map(|pass| Pass {
+            max_elevation: pass.max_elevation + min_elevation,
+            ..pass
+        })
+        .collect())
+}
+
+/// Predicts the optically visible passes of a satellite: above the horizon, lit by the Sun, while
+/// the Sun is below the twilight threshold at the observer.
+///
+/// # Arguments
+/// * `observer` - The ground observer.
+/// * `elements` - Orbital elements of the satellite.
+/// * `start` - Start of the search window in minutes since epoch.
+/// * `end` - End of the search window in minutes since epoch.
+/// * `twilight` - Highest Sun elevation at the observer in radians, e.g. -6° for civil twilight.
+///
+/// # Returns
+/// * The visible parts of the passes in chronological order, clipped to the window, or the first
+///   propagation error.
+pub fn visible_passes(observer: &Observer, elements: &OrbitalElements, start: f64, end: f64, twilight: f64) -> Result<Vec<Pass>, PropagationError> {
+    let propagator: Sgp4 = Sgp4::new(elements);
+    let f = |tsince: f64| visible_elevation(observer, &propagator, twilight, tsince);
+    find_passes(f, elements, start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{iss_elements, iss_sgp4};
+
+    /// Tests an overhead pass for an observer placed under the satellite.
+    #[test]
+    fn test_passes_overhead() {
+        let elements = iss_elements();
+        let propagator: Sgp4 = iss_sgp4();
+        let under = elements.subpoint(300.0).unwrap();
+        let observer = Observer { location: crate::geodetic::Geodetic { altitude: 0.0, ..under } };
+
+        let found = passes(&observer, &elements, 280.0, 320.0).unwrap();
+
+        assert_eq!(found.len(), 1);
+        let pass = found[0];
+        assert!((pass.max_elevation_time - 300.0).abs() < 0.1);
+        assert!(pass.max_elevation.to_degrees() > 85.0);
+        assert!(pass.duration() > 5.0 && pass.duration() < 15.0);
+        assert!(elevation(&observer, &propagator, pass.aos).unwrap().abs() < 1e-6);
+        assert!(elevation(&observer, &propagator, pass.los).unwrap().abs() < 1e-6);
+    }
+
+    /// Tests that passes over a day are ordered, disjoint and above the horizon.
+    #[test]
+    fn test_passes_day() {
+        let elements = iss_elements();
+        let observer = Observer::new(48.85, 2.35, 0.035);
+
+        let found = passes(&observer, &elements, 0.0, 1440.0).unwrap();
+
+        assert!(found.len() >= 3);
+        for pair in found.windows(2) {
+            assert!(pair[0].los < pair[1].aos);
+        }
+        for pass in &found {
+            assert!(pass.max_elevation > 0.0);
+            assert!(pass.aos <= pass.max_elevation_time && pass.max_elevation_time <= pass.los);
+        }
+        assert!(passes(&observer, &elements, 10.0, 0.0).unwrap().is_empty());
+    }
+
+    /// Tests that the passes above a mask are the high parts of the geometric passes.
+    #[test]
+    fn test_passes_above() {
+        let elements = iss_elements();
+        let propagator: Sgp4 = iss_sgp4();
+        let observer = Observer::new(48.85, 2.35, 0.035);
+        let mask: f64 = 10.0_f64.to_radians();
+
+        let all = passes(&observer, &elements, 0.0, 1440.0).unwrap();
+        let above = passes_above(&observer, &elements, 0.0, 1440.0, mask).unwrap();
+
+        assert!(!above.is_empty());
+        assert_eq!(above.len(), all.iter().filter(|pass| pass.max_elevation > mask).count());
+        for pass in &above {
+            assert!(pass.max_elevation > mask);
+            assert!((elevation(&observer, &propagator, pass.aos).unwrap() - mask).abs() < 1e-6);
+            assert!(all.iter().any(|p| p.aos < pass.aos && pass.los < p.los && (p.max_elevation - pass.max_elevation).abs() < 1e-6));
+        }
+    }
+
+    /// Tests that visible passes are sunlit, in darkness, and part of a geometric pass.
+    #[test]
+    fn test_visible_passes() {
+        let elements = iss_elements();
+        let propagator: Sgp4 = iss_sgp4();
+        let observer = Observer::new(48.85, 2.35, 0.035);
+        let twilight: f64 = (-6.0_f64).to_radians();
+
+        let all = passes(&observer, &elements, 0.0, 4.0 * 1440.0).unwrap();
+        let visible = visible_passes(&observer, &elements, 0.0, 4.0 * 1440.0, twilight).unwrap();
+
+        assert!(!visible.is_empty());
+        assert!(visible.len() < all.len());
+        for pass in &visible {
+            assert!(all.iter().any(|p| p.aos <= pass.aos + 1e-5 && pass.los <= p.los + 1e-5));
+            let middle: f64 = 0.5 * (pass.aos + pass.los);
+            assert!(visible_elevation(&observer, &propagator, twilight, middle).unwrap() > 0.0);
+        }
+    }
+
+    /// Tests that the passes of a satellite that decays during the search report the propagation error.
+    #[test]
+    fn test_passes_decayed() {
+        let mut elements = iss_elements();
+        elements.eccentricity = 0.065;
+        let observer = Observer::new(48.85, 2.35, 0.035);
+
+        assert!(matches!(passes(&observer, &elements, 0.0, 1440.0), Err(PropagationError::Decayed { .. })));
+        assert!(matches!(passes_above(&observer, &elements, 0.0, 1440.0, 0.1), Err(PropagationError::Decayed { .. })));
+        assert!(matches!(visible_passes(&observer, &elements, 0.0, 1440.0, -0.1), Err(PropagationError::Decayed { .. })));
+    }
+}
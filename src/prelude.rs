@@ -0,0 +1,13 @@
+//! Names that `no_std` builds import explicitly.
+//! With `std`, they come from the standard prelude; without it, collections and formatting come
+//! from `alloc` and the float methods from the `libm`-backed `math::Float` trait.
+
+#[cfg(not(feature = "std"))]
+pub(crate) use crate::math::Float;
+#[cfg(not(feature = "std"))]
+pub(crate) use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
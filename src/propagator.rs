@@ -0,0 +1,631 @@
+//! SGP4/SDP4 propagation, split between a one-time initialization and per-step propagation.
+//! The implementation follows the reference code of Vallado et al., "Revisiting Spacetrack
+//! Report #3" (2006). The Earth constants are those of a `GravityModel`, WGS-72 old (`GravityModel::Wgs72Old`) by default.
+
+use crate::deep_space::{DeepSpace, Mean};
+use crate::gravity::{GravityConstants, GravityModel};
+use crate::kepler::KeplerError;
+use crate::{OrbitalElements, StateVector, TWOPI, brouwer_mean_motion, kozai_mean_motion};
+use core::f64::consts::PI;
+use core::fmt;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Two-thirds constant.
+const X2O3: f64 = 2.0 / 3.0;
+/// Guard against a division by zero for retrograde equatorial orbits.
+const TEMP4: f64 = 1.5e-12;
+
+/// Represents the reasons why a set of elements cannot be propagated to a given time.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropagationError {
+    /// The mean eccentricity left the range [-0.001, 1).
+    MeanEccentricity(f64),
+    /// The mean motion became negative.
+    MeanMotion(f64),
+    /// The eccentricity perturbed by the lunar-solar periodics left the range [0, 1].
+    PerturbedEccentricity(f64),
+    /// The semi-latus rectum became negative.
+    SemiLatusRectum(f64),
+    /// Kepler's equation could not be solved.
+    Kepler(KeplerError),
+    /// The satellite is below the Earth's surface: the orbit has decayed before this time.
+    Decayed {
+        /// Time since epoch in minutes.
+        tsince: f64,
+    },
+}
+
+impl fmt::Display for PropagationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PropagationError::MeanEccentricity(e) => write!(f, "Mean eccentricity out of range: {}", e),
+            PropagationError::MeanMotion(n) => write!(f, "Negative mean motion: {}", n),
+            PropagationError::PerturbedEccentricity(e) => write!(f, "Perturbed eccentricity out of range: {}", e),
+            PropagationError::SemiLatusRectum(p) => write!(f, "Negative semi-latus rectum: {}", p),
+            PropagationError::Kepler(error) => write!(f, "{}", error),
+            PropagationError::Decayed { tsince } => write!(f, "Satellite decayed at {} minutes since epoch", tsince),
+        }
+    }
+}
+
+impl core::error::Error for PropagationError {}
+
+/// Common interface of the orbit propagators.
+///
+/// Code written against this trait can switch between propagation models without changes.
+pub trait Propagator {
+    /// Computes the state vector of the satellite at a time since epoch.
+    ///
+    /// # Arguments
+    /// * `tsince` - Time since epoch in minutes.
+    ///
+    /// # Returns
+    /// * State vector in the TEME frame (km, km/s), or the reason why the orbit cannot be propagated.
+    fn propagate(&self, tsince: f64) -> Result<StateVector, PropagationError>;
+
+    /// Returns the epoch of the propagation as a Julian date (UTC).
+    fn epoch_jd(&self) -> f64;
+}
+
+impl<P: Propagator + ?Sized> Propagator for &P {
+    fn propagate(&self, tsince: f64) -> Result<StateVector, PropagationError> {
+        (**self).propagate(tsince)
+    }
+
+    fn epoch_jd(&self) -> f64 {
+        (**self).epoch_jd()
+    }
+}
+
+/// Holds the SGP4 initialization of a set of orbital elements.
+///
+/// `Sgp4::new` performs the time-independent computations once; `propagate` then only performs the
+/// work that depends on the time since epoch. Deep-space elements (period of 225 minutes or more)
+/// are propagated with the lunar-solar and resonance terms of SDP4.
+#[derive(Debug, Clone)]
+pub struct Sgp4 {
+    // Mean elements at epoch.
+    pub(crate) epoch_jd: f64,
+    pub(crate) ecco: f64,
+    pub(crate) inclo: f64,
+    pub(crate) nodeo: f64,
+    pub(crate) argpo: f64,
+    pub(crate) mo: f64,
+    pub(crate) no: f64,
+    pub(crate) bstar: f64,
+    pub(crate) ao: f64,
+    // Trigonometric functions of the inclination.
+    pub(crate) sinio: f64,
+    pub(crate) cosio: f64,
+    pub(crate) con41: f64,
+    pub(crate) x1mth2: f64,
+    pub(crate) x7thm1: f64,
+    // Secular rates and drag coefficients.
+    pub(crate) mdot: f64,
+    pub(crate) argpdot: f64,
+    pub(crate) nodedot: f64,
+    pub(crate) nodecf: f64,
+    pub(crate) cc1: f64,
+    pub(crate) cc4: f64,
+    pub(crate) cc5: f64,
+    pub(crate) t2cof: f64,
+    pub(crate) omgcof: f64,
+    pub(crate) xmcof: f64,
+    pub(crate) eta: f64,
+    pub(crate) delmo: f64,
+    pub(crate) sinmao: f64,
+    // Higher-order drag terms, unused by the simplified drag model of low perigees.
+    pub(crate) isimp: bool,
+    pub(crate) d2: f64,
+    pub(crate) d3: f64,
+    pub(crate) d4: f64,
+    pub(crate) t3cof: f64,
+    pub(crate) t4cof: f64,
+    pub(crate) t5cof: f64,
+    // Long-period periodic coefficients.
+    pub(crate) xlcof: f64,
+    pub(crate) aycof: f64,
+    // Lunar-solar and resonance terms of deep-space orbits.
+    pub(crate) deep_space: Option<DeepSpace>,
+    // Earth constants of the gravity model.
+    pub(crate) gravity: GravityConstants,
+}
+
+impl Sgp4 {
+    /// Performs the one-time SGP4 initialization of a set of orbital elements.
+    ///
+    /// Deep-space elements (period of 225 minutes or more) use the simplified drag model and the
+    /// deep-space terms of SDP4.
+    ///
+    /// # Arguments
+    /// * `elements` - Orbital elements of the satellite (Brouwer mean motion).
+    ///
+    /// # Returns
+    /// * The initialized propagator.
+    pub fn new(elements: &OrbitalElements) -> Sgp4 {
+        Sgp4::with_gravity_model(elements, GravityModel::default())
+    }
+
+    /// Performs the one-time SGP4 initialization with the constants of a gravity model.
+    ///
+    /// The Brouwer mean motion of the elements, recovered with the default constants, is recovered
+    /// again from the TLE mean motion with the constants of the model.
+    ///
+    /// # Arguments
+    /// * `elements` - Orbital elements of the satellite (Brouwer mean motion).
+    /// * `model` - Gravity model whose constants are used.
+    ///
+    /// # Returns
+    /// * The initialized propagator.
+    pub fn with_gravity_model(elements: &OrbitalElements, model: GravityModel) -> Sgp4 {
+        let gravity: GravityConstants = model.constants();
+        let GravityConstants { xke, radius, j2, j4, .. } = gravity;
+        let j3oj2: f64 = gravity.j3oj2();
+
+        let ecco: f64 = elements.eccentricity;
+        let inclo: f64 = elements.inclination;
+        let argpo: f64 = elements.arg_perigee;
+        let mo: f64 = elements.mean_anomaly;
+        let no: f64 = if model == GravityModel::default() {
+            elements.mean_motion
+        } else {
+            let kozai: f64 = kozai_mean_motion(elements.mean_motion, inclo, ecco, &GravityConstants::default());
+            brouwer_mean_motion(kozai, inclo, ecco, &gravity)
+        };
+        let bstar: f64 = elements.bstar;
+
+        let eccsq: f64 = ecco * ecco;
+        let omeosq: f64 = 1.0 - eccsq;
+        let rteosq: f64 = omeosq.sqrt();
+        let (sinio, cosio) = inclo.sin_cos();
+        let cosio2: f64 = cosio * cosio;
+        let ao: f64 = (xke / no).powf(X2O3);
+        let po: f64 = ao * omeosq;
+        let con42: f64 = 1.0 - 5.0 * cosio2;
+        let con41: f64 = -con42 - cosio2 - cosio2;
+        let posq: f64 = po * po;
+        let rp: f64 = ao * (1.0 - ecco);
+
+        // Atmospheric density parameters, adjusted for low perigees.
+        let ss: f64 = 78.0 / radius + 1.0;
+        let qzms2t: f64 = ((120.0 - 78.0) / radius).powi(4);
+        let perige: f64 = (rp - 1.0) * radius;
+        let (sfour, qzms24) = if perige < 156.0 {
+            let s: f64 = if perige < 98.0 { 20.0 } else { perige - 78.0 };
+            (s / radius + 1.0, ((120.0 - s) / radius).powi(4))
+        } else {
+            (ss, qzms2t)
+        };
+        let isimp: bool = rp < 220.0 / radius + 1.0 || elements.deep_space;
+
+        let pinvsq: f64 = 1.0 / posq;
+        let tsi: f64 = 1.0 / (ao - sfour);
+        let eta: f64 = ao * ecco * tsi;
+        let etasq: f64 = eta * eta;
+        let eeta: f64 = ecco * eta;
+        let psisq: f64 = (1.0 - etasq).abs();
+        let coef: f64 = qzms24 * tsi.powi(4);
+        let coef1: f64 = coef / psisq.powf(3.5);
+        let cc2: f64 = coef1
+            * no
+            * (ao * (1.0 + 1.5 * etasq + eeta * (4.0 + etasq))
+                + 0.375 * j2 * tsi / psisq * con41 * (8.0 + 3.0 * etasq * (8.0 + etasq)));
+        let cc1: f64 = bstar * cc2;
+        let cc3: f64 = if ecco > 1.0e-4 { -2.0 * coef * tsi * j3oj2 * no * sinio / ecco } else { 0.0 };
+        let x1mth2: f64 = 1.0 - cosio2;
+        let cc4: f64 = 2.0
+            * no
+            * coef1
+            * ao
+            * omeosq
+            * (eta * (2.0 + 0.5 * etasq) + ecco * (0.5 + 2.0 * etasq)
+                - j2 * tsi / (ao * psisq)
+                    * (-3.0 * con41 * (1.0 - 2.0 * eeta + etasq * (1.5 - 0.5 * eeta))
+                        + 0.75 * x1mth2 * (2.0 * etasq - eeta * (1.0 + etasq)) * (2.0 * argpo).cos()));
+        let cc5: f64 = 2.0 * coef1 * ao * omeosq * (1.0 + 2.75 * (etasq + eeta) + eeta * etasq);
+
+        // Secular rates of the mean anomaly, argument of perigee and node.
+        let cosio4: f64 = cosio2 * cosio2;
+        let temp1: f64 = 1.5 * j2 * pinvsq * no;
+        let temp2: f64 = 0.5 * temp1 * j2 * pinvsq;
+        let temp3: f64 = -0.46875 * j4 * pinvsq * pinvsq * no;
+        let mdot: f64 = no
+            + 0.5 * temp1 * rteosq * con41
+            + 0.0625 * temp2 * rteosq * (13.0 - 78.0 * cosio2 + 137.0 * cosio4);
+        let argpdot: f64 = -0.5 * temp1 * con42
+            + 0.0625 * temp2 * (7.0 - 114.0 * cosio2 + 395.0 * cosio4)
+            + temp3 * (3.0 - 36.0 * cosio2 + 49.0 * cosio4);
+        let xhdot1: f64 = -temp1 * cosio;
+        let nodedot: f64 = xhdot1 + (0.5 * temp2 * (4.0 - 19.0 * cosio2) + 2.0 * temp3 * (3.0 - 7.0 * cosio2)) * cosio;
+
+        let omgcof: f64 = bstar * cc3 * argpo.cos();
+        let xmcof: f64 = if ecco > 1.0e-4 { -X2O3 * coef * bstar / eeta } else { 0.0 };
+        let nodecf: f64 = 3.5 * omeosq * xhdot1 * cc1;
+        let t2cof: f64 = 1.5 * cc1;
+        let xlcof: f64 = -0.25 * j3oj2 * sinio * (3.0 + 5.0 * cosio) / guard_retrograde(cosio);
+        let aycof: f64 = -0.5 * j3oj2 * sinio;
+        let delmo: f64 = (1.0 + eta * mo.cos()).powi(3);
+
+        let (d2, d3, d4, t3cof, t4cof, t5cof) = if isimp {
+            (0.0, 0.0, 0.0, 0.0, 0.0, 0.0)
+        } else {
+            let cc1sq: f64 = cc1 * cc1;
+            let d2: f64 = 4.0 * ao * tsi * cc1sq;
+            let temp: f64 = d2 * tsi * cc1 / 3.0;
+            let d3: f64 = (17.0 * ao + sfour) * temp;
+            let d4: f64 = 0.5 * temp * ao * tsi * (221.0 * ao + 31.0 * sfour) * cc1;
+            (
+                d2,
+                d3,
+                d4,
+                d2 + 2.0 * cc1sq,
+                0.25 * (3.0 * d3 + cc1 * (12.0 * d2 + 10.0 * cc1sq)),
+                0.2 * (3.0 * d4 + 12.0 * cc1 * d3 + 6.0 * d2 * d2 + 15.0 * cc1sq * (2.0 * d2 + cc1sq)),
+            )
+        };
+
+        let deep_space: Option<DeepSpace> = elements.deep_space.then(|| {
+            let mean = Mean {
+                ecc: ecco,
+                incl: inclo,
+                node: elements.raan,
+                argp: argpo,
+                m: mo,
+                n: no,
+            };
+            DeepSpace::new(elements.epoch_jd(), &mean, (mdot, argpdot, nodedot), xke)
+        });
+
+        Sgp4 {
+            epoch_jd: elements.epoch_jd(),
+            ecco,
+            inclo,
+            nodeo: elements.raan,
+            argpo,
+            mo,
+            no,
+            bstar,
+            ao,
+            sinio,
+            cosio,
+            con41,
+            x1mth2,
+            x7thm1: 7.0 * cosio2 - 1.0,
+            mdot,
+            argpdot,
+            nodedot,
+            nodecf,
+            cc1,
+            cc4,
+            cc5,
+            t2cof,
+            omgcof,
+            xmcof,
+            eta,
+            delmo,
+            sinmao: mo.sin(),
+            isimp,
+            d2,
+            d3,
+            d4,
+            t3cof,
+            t4cof,
+            t5cof,
+            xlcof,
+            aycof,
+            deep_space,
+            gravity,
+        }
+    }
+
+    /// Computes the state vector of the satellite at a time since epoch.
+    ///
+    /// # Arguments
+    /// * `tsince` - Time since epoch in minutes.
+    ///
+    /// # Returns
+    /// * State vector in the TEME frame (km, km/s), or an error when the elements have decayed to a
+    ///   meaningless orbit.
+    pub fn propagate(&self, tsince: f64) -> Result<StateVector, PropagationError> {
+        let t: f64 = tsince;
+        let GravityConstants { xke, radius, j2, .. } = self.gravity;
+        let j3oj2: f64 = self.gravity.j3oj2();
+
+        // Secular gravity and atmospheric drag.
+        let xmdf: f64 = self.mo + self.mdot * t;
+        let argpdf: f64 = self.argpo + self.argpdot * t;
+        let nodedf: f64 = self.nodeo + self.nodedot * t;
+        let t2: f64 = t * t;
+        let mut tempa: f64 = 1.0 - self.cc1 * t;
+        let mut tempe: f64 = self.bstar * self.cc4 * t;
+        let mut templ: f64 = self.t2cof * t2;
+        let mut mean = Mean {
+            ecc: self.ecco,
+            incl: self.inclo,
+            node: nodedf + self.nodecf * t2,
+            argp: argpdf,
+            m: xmdf,
+            n: self.no,
+        };
+
+        if !self.isimp {
+            let delomg: f64 = self.omgcof * t;
+            let delm: f64 = self.xmcof * ((1.0 + self.eta * xmdf.cos()).powi(3) - self.delmo);
+            let temp: f64 = delomg + delm;
+            mean.m = xmdf + temp;
+            mean.argp = argpdf - temp;
+            let t3: f64 = t2 * t;
+            let t4: f64 = t3 * t;
+            tempa -= self.d2 * t2 + self.d3 * t3 + self.d4 * t4;
+            tempe += self.bstar * self.cc5 * (mean.m.sin() - self.sinmao);
+            templ += self.t3cof * t3 + t4 * (self.t4cof + t * self.t5cof);
+        }
+
+        // Lunar-solar secular effects and resonances.
+        if let Some(deep_space) = &self.deep_space {
+            mean = deep_space.secular(t, &mean);
+        }
+        if mean.n <= 0.0 {
+            return Err(PropagationError::MeanMotion(mean.n));
+        }
+
+        let am: f64 = (xke / mean.n).powf(X2O3) * tempa * tempa;
+        let nm: f64 = xke / am.powf(1.5);
+        let em: f64 = mean.ecc - tempe;
+        if !(-0.001..1.0).contains(&em) {
+            return Err(PropagationError::MeanEccentricity(em));
+        }
+        mean.ecc = em.max(1.0e-6);
+        mean.m += self.no * templ;
+        let xlm: f64 = (mean.m + mean.argp + mean.node) % TWOPI;
+        mean.node %= TWOPI;
+        mean.argp %= TWOPI;
+        mean.m = (xlm - mean.argp - mean.node) % TWOPI;
+
+        // Lunar-solar periodics, which also change the inclination-dependent coefficients.
+        let (sinip, cosip, xlcof, aycof, con41, x1mth2, x7thm1) = match &self.deep_space {
+            Some(deep_space) => {
+                mean = deep_space.periodics(t, &mean);
+                if mean.incl < 0.0 {
+                    mean.incl = -mean.incl;
+                    mean.node += PI;
+                    mean.argp -= PI;
+                }
+                if !(0.0..=1.0).contains(&mean.ecc) {
+                    return Err(PropagationError::PerturbedEccentricity(mean.ecc));
+                }
+                let (sinip, cosip) = mean.incl.sin_cos();
+                let cosisq: f64 = cosip * cosip;
+                (
+                    sinip,
+                    cosip,
+                    -0.25 * j3oj2 * sinip * (3.0 + 5.0 * cosip) / guard_retrograde(cosip),
+                    -0.5 * j3oj2 * sinip,
+                    3.0 * cosisq - 1.0,
+                    1.0 - cosisq,
+                    7.0 * cosisq - 1.0,
+                )
+            }
+            None => (self.sinio, self.cosio, self.xlcof, self.aycof, self.con41, self.x1mth2, self.x7thm1),
+        };
+        let Mean { ecc: ep, argp: argpp, node: nodep, m: mp, incl: xincp, .. } = mean;
+
+        // Long-period periodics.
+        let axnl: f64 = ep * argpp.cos();
+        let temp: f64 = 1.0 / (am * (1.0 - ep * ep));
+        let aynl: f64 = ep * argpp.sin() + temp * aycof;
+        let xl: f64 = mp + argpp + nodep + temp * xlcof * axnl;
+
+        // Kepler's equation for the eccentric longitude.
+        let u: f64 = (xl - nodep) % TWOPI;
+        let mut eo1: f64 = u;
+        let (mut sineo1, mut coseo1) = (0.0, 1.0);
+        for _ in 0..10 {
+            (sineo1, coseo1) = eo1.sin_cos();
+            let step: f64 = ((u - aynl * coseo1 + axnl * sineo1 - eo1) / (1.0 - coseo1 * axnl - sineo1 * aynl)).clamp(-0.95, 0.95);
+            eo1 += step;
+            if step.abs() < 1.0e-12 {
+                break;
+            }
+        }
+
+        // Short-period preliminary quantities.
+        let ecose: f64 = axnl * coseo1 + aynl * sineo1;
+        let esine: f64 = axnl * sineo1 - aynl * coseo1;
+        let el2: f64 = axnl * axnl + aynl * aynl;
+        let pl: f64 = am * (1.0 - el2);
+        if pl < 0.0 {
+            return Err(PropagationError::SemiLatusRectum(pl));
+        }
+        let rl: f64 = am * (1.0 - ecose);
+        let rdotl: f64 = am.sqrt() * esine / rl;
+        let rvdotl: f64 = pl.sqrt() / rl;
+        let betal: f64 = (1.0 - el2).sqrt();
+        let temp: f64 = esine / (1.0 + betal);
+        let sinu: f64 = am / rl * (sineo1 - aynl - axnl * temp);
+        let cosu: f64 = am / rl * (coseo1 - axnl + aynl * temp);
+        let su: f64 = sinu.atan2(cosu);
+        let sin2u: f64 = (cosu + cosu) * sinu;
+        let cos2u: f64 = 1.0 - 2.0 * sinu * sinu;
+        let temp: f64 = 1.0 / pl;
+        let temp1: f64 = 0.5 * j2 * temp;
+        let temp2: f64 = temp1 * temp;
+
+        // Short-period periodics.
+        let mrt: f64 = rl * (1.0 - 1.5 * temp2 * betal * con41) + 0.5 * temp1 * x1mth2 * cos2u;
+        let su: f64 = su - 0.25 * temp2 * x7thm1 * sin2u;
+        let xnode: f64 = nodep + 1.5 * temp2 * cosip * sin2u;
+        let xinc: f64 = xincp + 1.5 * temp2 * cosip * sinip * cos2u;
+        let mvt: f64 = rdotl - nm * temp1 * x1mth2 * sin2u / xke;
+        let rvdot: f64 = rvdotl + nm * temp1 * (x1mth2 * cos2u + 1.5 * con41) / xke;
+        if mrt < 1.0 {
+            return Err(PropagationError::Decayed { tsince });
+        }
+
+        // Orientation vectors.
+        let (sinsu, cossu) = su.sin_cos();
+        let (snod, cnod) = xnode.sin_cos();
+        let (sini, cosi) = xinc.sin_cos();
+        let xmx: f64 = -snod * cosi;
+        let xmy: f64 = cnod * cosi;
+        let ux: [f64; 3] = [xmx * sinsu + cnod * cossu, xmy * sinsu + snod * cossu, sini * sinsu];
+        let vx: [f64; 3] = [xmx * cossu - cnod * sinsu, xmy * cossu - snod * sinsu, sini * cossu];
+
+        Ok(StateVector {
+            position: ux.map(|c| mrt * c * radius),
+            velocity: [0, 1, 2].map(|k| (mvt * ux[k] + rvdot * vx[k]) * self.gravity.vkmpersec()),
+        })
+    }
+
+    /// Returns the mean semi-major axis at epoch in earth radii.
+    pub fn semi_major_axis(&self) -> f64 {
+        self.ao
+    }
+
+    /// Returns whether the deep-space (SDP4) terms are applied.
+    pub fn is_deep_space(&self) -> bool {
+        self.deep_space.is_some()
+    }
+}
+
+impl Propagator for Sgp4 {
+    fn propagate(&self, tsince: f64) -> Result<StateVector, PropagationError> {
+        Sgp4::propagate(self, tsince)
+    }
+
+    fn epoch_jd(&self) -> f64 {
+        self.epoch_jd
+    }
+}
+
+/// Returns `1 + cos(i)`, kept away from zero for retrograde equatorial orbits.
+fn guard_retrograde(cosio: f64) -> f64 {
+    if (cosio + 1.0).abs() > TEMP4 { 1.0 + cosio } else { TEMP4 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Tle, convert_satellite_data};
+    use crate::test_support::{iss_elements, iss_sgp4};
+
+    /// Tests the propagator against the reference test case 00005 of Vallado's verification set.
+    #[test]
+    fn test_sgp4_reference() {
+        let tle = Tle::from_lines(&[
+            "1 00005U 58002B   00179.78495062  .00000023  00000-0  28098-4 0  4753",
+            "2 00005  34.2682 348.7242 1859667 331.7664  19.3264 10.82419157413667",
+        ])
+        .unwrap();
+        let sgp4 = Sgp4::new(&convert_satellite_data(&tle));
+
+        let expected: [(f64, [f64; 3], [f64; 3]); 3] = [
+            (0.0, [7022.46529266, -1400.08296755, 0.03995155], [1.893841015, 6.405893759, 4.534807250]),
+            (360.0, [-7154.03120202, -3783.17682504, -3536.19412294], [4.741887409, -4.151817765, -2.093935425]),
+            (720.0, [-7134.59340119, 6531.68641334, 3260.27186483], [-4.113793027, -2.911922039, -2.557327851]),
+        ];
+        for (tsince, position, velocity) in expected {
+            let state = sgp4.propagate(tsince).unwrap();
+            for k in 0..3 {
+                assert!((state.position[k] - position[k]).abs() < 1e-3, "{} {:?}", tsince, state.position);
+                assert!((state.velocity[k] - velocity[k]).abs() < 1e-6, "{} {:?}", tsince, state.velocity);
+            }
+        }
+    }
+
+    /// Tests that the WGS-72 constants reproduce the verification set, which was generated with
+    /// them, to the printed precision, and that WGS-84 moves the satellite by meters.
+    #[test]
+    fn test_sgp4_gravity_model() {
+        let elements = convert_satellite_data(
+            &Tle::from_lines(&[
+                "1 00005U 58002B   00179.78495062  .00000023  00000-0  28098-4 0  4753",
+                "2 00005  34.2682 348.7242 1859667 331.7664  19.3264 10.82419157413667",
+            ])
+            .unwrap(),
+        );
+        let wgs72 = Sgp4::with_gravity_model(&elements, GravityModel::Wgs72);
+        let state = wgs72.propagate(4320.0).unwrap();
+        let position: [f64; 3] = [-9060.47373569, 4658.70952502, 813.68673153];
+        for (computed, expected) in state.position.iter().zip(position) {
+            assert!((computed - expected).abs() < 1e-7, "{:?}", state.position);
+        }
+        assert_eq!(Sgp4::with_gravity_model(&elements, GravityModel::Wgs72Old).propagate(4320.0).unwrap().position, Sgp4::new(&elements).propagate(4320.0).unwrap().position);
+
+        let wgs84 = Sgp4::with_gravity_model(&elements, GravityModel::Wgs84).propagate(0.0).unwrap();
+        let distance: f64 = (0..3).map(|k| (wgs84.position[k] - wgs72.propagate(0.0).unwrap().position[k]).powi(2)).sum::<f64>().sqrt();
+        assert!(distance > 1e-3 && distance < 0.1, "{}", distance);
+    }
+
+    /// Tests the deep-space terms against the Molniya (half-day resonance) and geostationary
+    /// (synchronous resonance, low inclination) cases of Vallado's verification set.
+    #[test]
+    fn test_sdp4_reference() {
+        let molniya = Tle::from_lines(&[
+            "1 08195U 75081A   06176.33215444  .00000099  00000-0  11873-3 0   813",
+            "2 08195  64.1586 279.0717 6877146 264.7651  20.2257  2.00491383225656",
+        ])
+        .unwrap();
+        let geostationary = Tle::from_lines(&[
+            "1 28626U 05008A   06176.46683397 -.00000205  00000-0  10000-3 0  2190",
+            "2 28626   0.0019 286.9433 0000335  13.7918  55.6504  1.00270176  4891",
+        ])
+        .unwrap();
+
+        let expected: [(&Tle, f64, [f64; 3], [f64; 3]); 6] = [
+            (&molniya, 0.0, [2349.89483350, -14785.93811562, 0.02119379], [2.721488096, -3.256811655, 4.498416672]),
+            (&molniya, 2880.0, [3417.20931585, -16038.79510664, 1894.74934055], [2.585515864, -2.596818146, 4.456882556]),
+            (&molniya, -720.0, [2074.17053788, -14427.80727238, -474.16310822], [2.753721857, -3.445103511, 4.495891434]),
+            (&geostationary, 0.0, [42080.71852213, -2646.86387436, 0.81851294], [0.193105177, 3.068688251, 0.000438449]),
+            (&geostationary, 7200.0, [42153.60441967, 949.67637227, 6.79355517], [-0.069162883, 3.073961171, -0.000430338]),
+            (&geostationary, -1440.0, [42029.05113437, -3368.15990819, 2.95725566], [0.245704559, 3.064928956, 0.000662227]),
+        ];
+        for (tle, tsince, position, velocity) in expected {
+            let sgp4 = Sgp4::new(&convert_satellite_data(tle));
+            assert!(sgp4.is_deep_space());
+            let state = Propagator::propagate(&sgp4, tsince).unwrap();
+            for k in 0..3 {
+                assert!((state.position[k] - position[k]).abs() < 1e-3, "{} {:?}", tsince, state.position);
+                assert!((state.velocity[k] - velocity[k]).abs() < 1e-6, "{} {:?}", tsince, state.velocity);
+            }
+        }
+    }
+
+    /// Tests that propagation does not change the initialization and is repeatable.
+    #[test]
+    fn test_sgp4_repeatable() {
+        let sgp4 = iss_sgp4();
+
+        let first = sgp4.propagate(1234.5).unwrap();
+        let _ = sgp4.propagate(-60.0);
+        let second = sgp4.propagate(1234.5).unwrap();
+
+        assert_eq!(first.position, second.position);
+        let radius: f64 = first.position.iter().map(|x| x * x).sum::<f64>().sqrt();
+        assert!((radius - 6730.0).abs() < 30.0);
+        assert!((sgp4.semi_major_axis() * crate::XKMPER - 6730.0).abs() < 10.0);
+    }
+
+    /// Tests that states below the Earth's surface are reported as decayed.
+    #[test]
+    fn test_sgp4_decayed() {
+        let mut elements: OrbitalElements = iss_elements();
+        // Perigee about 50 km below the surface.
+        elements.eccentricity = 0.065;
+        let sgp4 = Sgp4::new(&elements);
+
+        let mut decayed: usize = 0;
+        for k in 0..92 {
+            let tsince: f64 = k as f64;
+            match sgp4.propagate(tsince) {
+                Ok(state) => assert!(state.position.iter().map(|x| x * x).sum::<f64>().sqrt() >= crate::XKMPER),
+                Err(error) => {
+                    assert_eq!(error, PropagationError::Decayed { tsince });
+                    decayed += 1;
+                }
+            }
+        }
+        assert!(decayed > 0 && decayed < 92, "{}", decayed);
+    }
+}
@@ -0,0 +1,215 @@
+//! Python bindings with PyO3, built as the `sgp4_rust` extension module by maturin.
+//! States are exchanged as `(position, velocity)` tuples of three-element lists in km and km/s,
+//! angles in degrees and dates as Julian dates (UTC).
+
+use crate::frames::Frame;
+use crate::geodetic::{Geodetic, ecef_to_geodetic};
+use crate::time::gmst;
+use crate::{OrbitalElements, Sgp4, StateVector, Tle, convert_satellite_data};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// Position and velocity of a state vector, as exchanged with Python.
+type PyState = ([f64; 3], [f64; 3]);
+
+/// Holds a TLE set, its orbital elements and its SGP4 initialization.
+#[pyclass(name = "Tle", module = "sgp4_rust", frozen)]
+pub struct PyTle {
+    tle: Tle,
+    elements: OrbitalElements,
+    sgp4: Sgp4,
+}
+
+#[pymethods]
+impl PyTle {
+    /// Parses a TLE set and initializes the propagator.
+    ///
+    /// # Arguments
+    /// * `line1` - First data line.
+    /// * `line2` - Second data line.
+    /// * `name` - Optional name line.
+    ///
+    /// # Returns
+    /// * The TLE, or a `ValueError` if the lines do not form a TLE set.
+    #[new]
+    #[pyo3(signature = (line1, line2, name = None))]
+    fn new(line1: &str, line2: &str, name: Option<&str>) -> PyResult<PyTle> {
+        let lines: Vec<&str> = name.into_iter().chain([line1, line2]).collect();
+        let tle: Tle = Tle::from_lines(&lines).map_err(|error| PyValueError::new_err(error.to_string()))?;
+        let elements: OrbitalElements = convert_satellite_data(&tle);
+        Ok(PyTle {
+            sgp4: Sgp4::new(&elements),
+            elements,
+            tle,
+        })
+    }
+
+    /// Returns the name line of the TLE, if it had one.
+    #[getter]
+    fn name(&self) -> Option<String> {
+        self.tle.name.clone()
+    }
+
+    /// Returns the first data line.
+    #[getter]
+    fn line1(&self) -> String {
+        self.tle.line1.clone()
+    }
+
+    /// Returns the second data line.
+    #[getter]
+    fn line2(&self) -> String {
+        self.tle.line2.clone()
+    }
+
+    /// Returns the Julian date (UTC) of the epoch.
+    #[getter]
+    fn epoch_jd(&self) -> f64 {
+        self.elements.epoch_jd()
+    }
+
+    /// Returns the orbital period in minutes.
+    #[getter]
+    fn period_minutes(&self) -> f64 {
+        self.elements.period_minutes()
+    }
+
+    /// Computes the TEME state vector of the satellite at a time since epoch.
+    ///
+    /// # Arguments
+    /// * `tsince` - Time since epoch in minutes.
+    ///
+    /// # Returns
+    /// * Position (km) and velocity (km/s), or a `ValueError` when the orbit has decayed.
+    fn propagate(&self, tsince: f64) -> PyResult<PyState> {
+        let state: StateVector = self.sgp4.propagate(tsince).map_err(|error| PyValueError::new_err(error.to_string()))?;
+        Ok((state.position, state.velocity))
+    }
+
+    /// Computes the TEME state vectors of the satellite at several times since epoch.
+    ///
+    /// # Arguments
+    /// * `times` - Times since epoch in minutes.
+    ///
+    /// # Returns
+    /// * One position and velocity per time, or a `ValueError` at the first decayed state.
+    fn propagate_many(&self, times: Vec<f64>) -> PyResult<Vec<PyState>> {
+        times.into_iter().map(|tsince| self.propagate(tsince)).collect()
+    }
+
+    /// Computes the TEME state vector of the satellite at a date.
+    ///
+    /// # Arguments
+    /// * `jd` - Julian date (UTC).
+    ///
+    /// # Returns
+    /// * Position (km) and velocity (km/s), or a `ValueError` when the orbit has decayed.
+    fn propagate_jd(&self, jd: f64) -> PyResult<PyState> {
+        self.propagate((jd - self.elements.epoch_jd()) * 1440.0)
+    }
+
+    /// Returns the Python representation of the TLE.
+    fn __repr__(&self) -> String {
+        match &self.tle.name {
+            Some(name) => format!("Tle({:?}, {:?}, name={:?})", self.tle.line1, self.tle.line2, name),
+            None => format!("Tle({:?}, {:?})", self.tle.line1, self.tle.line2),
+        }
+    }
+}
+
+/// Converts a TEME state vector into another frame.
+///
+/// # Arguments
+/// * `position` - TEME position in kilometers.
+/// * `velocity` - TEME velocity in kilometers per second.
+/// * `jd` - Julian date (UTC) of the state.
+/// * `frame` - Target frame: `"TEME"`, `"ECEF"` or `"J2000"`.
+///
+/// # Returns
+/// * Position and velocity in the target frame, or a `ValueError` for an unknown frame.
+#[pyfunction]
+fn convert_frame(position: [f64; 3], velocity: [f64; 3], jd: f64, frame: &str) -> PyResult<PyState> {
+    let frame: Frame = match frame.to_ascii_uppercase().as_str() {
+        "TEME" => Frame::Teme,
+        "ECEF" | "PEF" | "ITRF" => Frame::Ecef,
+        "J2000" | "EME2000" => Frame::J2000,
+        _ => return Err(PyValueError::new_err(format!("unknown frame {}", frame))),
+    };
+    let state: StateVector = frame.from_teme(&StateVector { position, velocity }, jd);
+    Ok((state.position, state.velocity))
+}
+
+/// Converts an Earth-fixed position into geodetic coordinates on the WGS84 ellipsoid.
+///
+/// # Arguments
+/// * `position` - Earth-fixed position in kilometers.
+///
+/// # Returns
+/// * Latitude and longitude in degrees, and altitude in kilometers.
+#[pyfunction]
+fn geodetic(position: [f64; 3]) -> (f64, f64, f64) {
+    let geodetic: Geodetic = ecef_to_geodetic(position);
+    (geodetic.latitude.to_degrees(), geodetic.longitude.to_degrees(), geodetic.altitude)
+}
+
+/// Computes the Greenwich Mean Sidereal Time.
+///
+/// # Arguments
+/// * `jd` - Julian date (UT1, UTC is usually close enough).
+///
+/// # Returns
+/// * GMST in radians, in [0, 2π).
+#[pyfunction(name = "gmst")]
+fn py_gmst(jd: f64) -> f64 {
+    gmst(jd)
+}
+
+/// Registers the classes and functions of the `sgp4_rust` module.
+#[pymodule]
+fn sgp4_rust(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyTle>()?;
+    module.add_function(wrap_pyfunction!(convert_frame, module)?)?;
+    module.add_function(wrap_pyfunction!(geodetic, module)?)?;
+    module.add_function(wrap_pyfunction!(py_gmst, module)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{ISS_LINES, ISS_NAME};
+
+    /// Tests parsing and propagation through the exported methods.
+    #[test]
+    fn test_python_tle() {
+        let tle = PyTle::new(
+            ISS_LINES[0],
+            ISS_LINES[1],
+            Some(ISS_NAME),
+        )
+        .unwrap();
+        assert_eq!(tle.name().as_deref(), Some("ISS (ZARYA)"));
+
+        let (position, velocity) = tle.propagate(90.0).unwrap();
+        let expected: StateVector = tle.sgp4.propagate(90.0).unwrap();
+        assert_eq!(position, expected.position);
+        assert_eq!(velocity, expected.velocity);
+        assert_eq!(tle.propagate_many(vec![0.0, 90.0]).unwrap()[1].0, position);
+        assert!(PyTle::new("1 25544U", "1 25544U", None).is_err());
+    }
+
+    /// Tests the frame and geodetic conversions.
+    #[test]
+    fn test_python_frames() {
+        let position: [f64; 3] = [6778.0, 0.0, 0.0];
+        let velocity: [f64; 3] = [0.0, 7.6, 0.0];
+        let jd: f64 = 2454730.0;
+        let (ecef, _) = convert_frame(position, velocity, jd, "ecef").unwrap();
+        assert_eq!(ecef, Frame::Ecef.from_teme(&StateVector { position, velocity }, jd).position);
+        assert!(convert_frame(position, velocity, jd, "GCRF").is_err());
+
+        let (latitude, longitude, altitude) = geodetic([6378.137 + 400.0, 0.0, 0.0]);
+        assert!(latitude.abs() < 1e-9 && longitude.abs() < 1e-9);
+        assert!((altitude - 400.0).abs() < 1e-6);
+    }
+}
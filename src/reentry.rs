@@ -0,0 +1,166 @@
+//! Re-entry epoch estimation of decaying objects.
+//! The elements are propagated with the SGP4 drag model, driven by B*, until the osculating
+//! perigee falls below a threshold altitude or the propagation fails. The bounds repeat the
+//! search with B* scaled by its relative uncertainty, the dominant error of decay predictions.
+
+use crate::keplerian::KeplerianElements;
+use crate::{OrbitalElements, Sgp4, XKMPER};
+
+/// Resolution of the decay time in minutes.
+const RESOLUTION: f64 = 1.0;
+
+/// Holds the settings of a re-entry estimation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Reentry {
+    /// Perigee altitude marking the re-entry, in kilometers.
+    pub altitude: f64,
+    /// Relative uncertainty of B*, e.g. 0.3 for ±30 %.
+    pub bstar_uncertainty: f64,
+    /// Longest prediction in days.
+    pub max_days: f64,
+    /// Step of the search in minutes, small compared to the time the perigee takes to drop by a
+    /// few kilometers.
+    pub step: f64,
+}
+
+impl Default for Reentry {
+    fn default() -> Reentry {
+        Reentry {
+            altitude: 100.0,
+            bstar_uncertainty: 0.3,
+            max_days: 3650.0,
+            step: 60.0,
+        }
+    }
+}
+
+/// Represents the predicted re-entry of an object.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReentryEstimate {
+    /// Predicted re-entry epoch as a Julian date (UTC).
+    pub epoch_jd: f64,
+    /// Earliest re-entry epoch, with B* raised by its uncertainty.
+    pub earliest_jd: f64,
+    /// Latest re-entry epoch, with B* lowered by its uncertainty, or `None` past the longest
+    /// prediction.
+    pub latest_jd: Option<f64>,
+}
+
+impl Reentry {
+    /// Checks whether an object has re-entered at a time since epoch.
+    fn decayed(&self, sgp4: &Sgp4, tsince: f64) -> bool {
+        match sgp4.propagate(tsince) {
+            Ok(state) => {
+                let osculating = KeplerianElements::from_state(&state);
+                osculating.semi_major_axis * (1.0 - osculating.eccentricity) - XKMPER < self.altitude
+            }
+            Err(_) => true,
+        }
+    }
+
+    /// Finds the re-entry time of an element set.
+    ///
+    /// # Returns
+    /// * The time since epoch in minutes, or `None` if the object stays up.
+    fn decay_time(&self, elements: &OrbitalElements) -> Option<f64> {
+        let sgp4: Sgp4 = Sgp4::new(elements);
+        let end: f64 = self.max_days * 1440.0;
+        let mut low: f64 = 0.0;
+        if self.decayed(&sgp4, low) {
+            return Some(low);
+        }
+        let mut high: f64 = loop {
+            let next: f64 = (low + self.step).min(end);
+            if self.decayed(&sgp4, next) {
+                break next;
+            }
+            if next >= end {
+                return None;
+            }
+            low = next;
+        };
+        while high - low > RESOLUTION {
+            let middle: f64 = 0.5 * (low + high);
+            if self.decayed(&sgp4, middle) {
+                high = middle;
+            } else {
+                low = middle;
+            }
+        }
+        Some(high)
+    }
+
+    /// Estimates the re-entry epoch of an object.
+    ///
+    /// # Arguments
+    /// * `elements` - Mean orbital elements of the object, with a positive B*.
+    ///
+    /// # Returns
+    /// * The predicted re-entry epoch and its bounds, or `None` if the object does not re-enter
+    ///   within the longest prediction.
+    pub fn estimate(&self, elements: &OrbitalElements) -> Option<ReentryEstimate> {
+        let scaled = |factor: f64| OrbitalElements {
+            bstar: elements.bstar * factor,
+            ..*elements
+        };
+        let nominal: f64 = self.decay_time(elements)?;
+        let earliest: f64 = self.decay_time(&scaled(1.0 + self.bstar_uncertainty)).unwrap_or(nominal).min(nominal);
+        let latest: Option<f64> = self.decay_time(&scaled(1.0 - self.bstar_uncertainty)).map(|t| t.max(nominal));
+        Some(ReentryEstimate {
+            epoch_jd: elements.jd_at(nominal),
+            earliest_jd: elements.jd_at(earliest),
+            latest_jd: latest.map(|t| elements.jd_at(t)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::iss_elements;
+
+    /// Returns the elements of the ISS with a high drag term.
+    fn decaying() -> OrbitalElements {
+        let mut elements: OrbitalElements = iss_elements();
+        elements.bstar = 2e-3;
+        elements
+    }
+
+    /// Tests the predicted epoch and its bounds.
+    #[test]
+    fn test_reentry_estimate() {
+        let elements: OrbitalElements = decaying();
+        let reentry = Reentry::default();
+        let estimate: ReentryEstimate = reentry.estimate(&elements).unwrap();
+        let days: f64 = estimate.epoch_jd - elements.epoch_jd();
+        assert!(days > 1.0 && days < 365.0, "{}", days);
+        assert!(estimate.earliest_jd < estimate.epoch_jd && estimate.epoch_jd < estimate.latest_jd.unwrap());
+
+        // The perigee is above the threshold a little before the epoch, and not after.
+        let sgp4: Sgp4 = Sgp4::new(&elements);
+        let tsince: f64 = days * 1440.0;
+        assert!(!reentry.decayed(&sgp4, tsince - 2.0 * RESOLUTION));
+        assert!(reentry.decayed(&sgp4, tsince));
+    }
+
+    /// Tests that a high or drag-free orbit does not re-enter.
+    #[test]
+    fn test_no_reentry() {
+        let mut elements: OrbitalElements = decaying();
+        elements.bstar = 0.0;
+        let reentry = Reentry {
+            max_days: 30.0,
+            ..Reentry::default()
+        };
+        assert_eq!(reentry.estimate(&elements), None);
+        // Already below the threshold at epoch.
+        let low = Reentry {
+            altitude: 500.0,
+            ..reentry
+        };
+        let estimate: ReentryEstimate = low.estimate(&decaying()).unwrap();
+        assert_eq!(estimate.epoch_jd, elements.epoch_jd());
+    }
+}
@@ -0,0 +1,120 @@
+//! Classification of orbits by regime.
+//! The regime is derived from the period, eccentricity and inclination of the mean elements, with
+//! the usual thresholds of catalog filtering tools.
+
+use crate::OrbitalElements;
+use crate::mission_analysis::SUN_SYNCHRONOUS_RATE;
+
+/// Perigee altitude below which an orbit is considered decaying, in kilometers.
+const DECAY_ALTITUDE: f64 = 200.0;
+/// Apogee altitude below which an orbit is considered low, in kilometers.
+const LEO_ALTITUDE: f64 = 2000.0;
+/// Sidereal day in minutes.
+const SIDEREAL_DAY: f64 = 1436.0681;
+
+/// Represents the regime of an orbit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OrbitRegime {
+    /// Low orbit with a perigee low enough for the satellite to re-enter within weeks.
+    Decaying,
+    /// Low Earth orbit whose plane follows the Sun.
+    SunSynchronous,
+    /// Low Earth orbit, with an apogee below 2000 km.
+    Leo,
+    /// Medium Earth orbit, between the low orbits and the geosynchronous belt.
+    Meo,
+    /// Near-circular orbit with a period of one sidereal day.
+    Geo,
+    /// Molniya orbit: highly eccentric, half a sidereal day, at the critical inclination.
+    Molniya,
+    /// Highly eccentric orbit.
+    Heo,
+    /// Near-circular orbit above the geosynchronous belt.
+    High,
+}
+
+impl OrbitalElements {
+    /// Classifies the orbit by regime.
+    ///
+    /// # Returns
+    /// * The regime of the orbit. Decaying orbits take precedence over the other regimes, then the
+    ///   eccentric ones.
+    pub fn classify(&self) -> OrbitRegime {
+        let period: f64 = self.period_minutes();
+        let inclination: f64 = self.inclination.to_degrees();
+        let critical: bool = (inclination - 63.4).abs() < 2.0;
+
+        if self.perigee_altitude_km() < DECAY_ALTITUDE {
+            OrbitRegime::Decaying
+        } else if self.eccentricity > 0.5 && critical && (period / (0.5 * SIDEREAL_DAY) - 1.0).abs() < 0.05 {
+            OrbitRegime::Molniya
+        } else if self.eccentricity > 0.25 {
+            OrbitRegime::Heo
+        } else if self.apogee_altitude_km() < LEO_ALTITUDE {
+            let drift: f64 = self.nodal_regression_rate() - SUN_SYNCHRONOUS_RATE;
+            if drift.abs() < 0.05 * SUN_SYNCHRONOUS_RATE {
+                OrbitRegime::SunSynchronous
+            } else {
+                OrbitRegime::Leo
+            }
+        } else if (period / SIDEREAL_DAY - 1.0).abs() < 0.02 {
+            OrbitRegime::Geo
+        } else if period < SIDEREAL_DAY {
+            OrbitRegime::Meo
+        } else {
+            OrbitRegime::High
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Tle, convert_satellite_data};
+    use crate::test_support::iss_elements;
+
+    /// Tests the classification of real element sets.
+    #[test]
+    fn test_classify() {
+        let elements = |line1: &str, line2: &str| convert_satellite_data(&Tle::from_lines(&[line1, line2]).unwrap());
+
+        let iss = iss_elements();
+        let noaa = elements(
+            "1 33591U 09005A   24001.50000000  .00000100  00000-0  80000-4 0  9990",
+            "2 33591  99.1900  50.0000 0013000 200.0000 160.0000 14.12500000770000",
+        );
+        let gps = elements(
+            "1 28474U 04045A   24001.50000000 -.00000030  00000-0  00000-0 0  9990",
+            "2 28474  55.0000 100.0000 0100000  50.0000 310.0000  2.00563000140000",
+        );
+        let geo = elements(
+            "1 41866U 16071A   24001.50000000 -.00000090  00000-0  00000-0 0  9990",
+            "2 41866   0.0500 100.0000 0001000  50.0000 300.0000  1.00270000 26000",
+        );
+        let molniya = elements(
+            "1 40296U 14069A   24001.50000000  .00000100  00000-0  10000-3 0  9990",
+            "2 40296  63.4000 200.0000 7200000 270.0000  20.0000  2.00600000 70000",
+        );
+
+        assert_eq!(iss.classify(), OrbitRegime::Leo);
+        assert_eq!(noaa.classify(), OrbitRegime::SunSynchronous);
+        assert_eq!(gps.classify(), OrbitRegime::Meo);
+        assert_eq!(geo.classify(), OrbitRegime::Geo);
+        assert_eq!(molniya.classify(), OrbitRegime::Molniya);
+    }
+
+    /// Tests that low perigees and eccentric orbits take precedence.
+    #[test]
+    fn test_classify_precedence() {
+        let elements = |line2: &str| {
+            convert_satellite_data(
+                &Tle::from_lines(&["1 99999U 24001A   24001.50000000  .00010000  00000-0  10000-3 0  9990", line2]).unwrap(),
+            )
+        };
+
+        assert_eq!(elements("2 99999  51.6000 100.0000 0005000  50.0000 310.0000 16.30000000 10000").classify(), OrbitRegime::Decaying);
+        assert_eq!(elements("2 99999  27.0000 100.0000 7300000 180.0000   0.0000  2.25000000 10000").classify(), OrbitRegime::Heo);
+        assert_eq!(elements("2 99999   2.0000 100.0000 0002000  50.0000 310.0000  0.95000000 10000").classify(), OrbitRegime::High);
+    }
+}
@@ -0,0 +1,256 @@
+//! Relative motion in the radial, in-track and cross-track (RIC, also RSW) frame of a chief.
+//! The frame follows the chief: radial along its position, cross-track along its angular momentum
+//! and in-track completing the triad. Relative velocities are taken in this rotating frame, as
+//! seen by an observer riding with the chief. Around a circular chief, the linearized
+//! Clohessy-Wiltshire equations propagate that relative state in closed form.
+
+use crate::{OrbitalElements, StateVector};
+use crate::vector::{cross, dot};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Normalizes a vector.
+fn unit(a: [f64; 3]) -> [f64; 3] {
+    let norm: f64 = dot(a, a).sqrt();
+    a.map(|x| x / norm)
+}
+
+/// Computes the RIC axes of a chief.
+///
+/// # Arguments
+/// * `chief` - State vector of the chief in an inertial frame (km, km/s).
+///
+/// # Returns
+/// * The radial, in-track and cross-track unit vectors in the inertial frame, i.e. the rows of
+///   the rotation from the inertial frame to the RIC frame.
+pub fn ric_axes(chief: &StateVector) -> [[f64; 3]; 3] {
+    let radial: [f64; 3] = unit(chief.position);
+    let cross_track: [f64; 3] = unit(cross(chief.position, chief.velocity));
+    [radial, cross(cross_track, radial), cross_track]
+}
+
+/// Computes the angular velocity of the RIC frame of a chief, along its cross-track axis.
+fn frame_rate(chief: &StateVector) -> f64 {
+    let momentum: [f64; 3] = cross(chief.position, chief.velocity);
+    dot(momentum, momentum).sqrt() / dot(chief.position, chief.position)
+}
+
+/// Expresses the state of a deputy relative to a chief in the chief's RIC frame.
+///
+/// # Arguments
+/// * `chief` - State vector of the chief in an inertial frame (km, km/s).
+/// * `deputy` - State vector of the deputy in the same frame.
+///
+/// # Returns
+/// * The relative position (km) and the relative velocity in the rotating frame (km/s).
+pub fn to_ric(chief: &StateVector, deputy: &StateVector) -> StateVector {
+    let axes: [[f64; 3]; 3] = ric_axes(chief);
+    let position: [f64; 3] = axes.map(|axis| dot(axis, [0, 1, 2].map(|k| deputy.position[k] - chief.position[k])));
+    let inertial: [f64; 3] = axes.map(|axis| dot(axis, [0, 1, 2].map(|k| deputy.velocity[k] - chief.velocity[k])));
+    // Remove the transport term ω × ρ, with ω along the cross-track axis.
+    let rate: f64 = frame_rate(chief);
+    StateVector {
+        position,
+        velocity: [inertial[0] + rate * position[1], inertial[1] - rate * position[0], inertial[2]],
+    }
+}
+
+/// Computes the inertial state of a deputy from its state relative to a chief, the inverse of
+/// `to_ric`.
+///
+/// # Arguments
+/// * `chief` - State vector of the chief in an inertial frame (km, km/s).
+/// * `relative` - State of the deputy in the chief's RIC frame (km, km/s).
+///
+/// # Returns
+/// * The state vector of the deputy in the inertial frame.
+pub fn from_ric(chief: &StateVector, relative: &StateVector) -> StateVector {
+    let axes: [[f64; 3]; 3] = ric_axes(chief);
+    let rate: f64 = frame_rate(chief);
+    let [x, y, _] = relative.position;
+    let inertial: [f64; 3] = [relative.velocity[0] - rate * y, relative.velocity[1] + rate * x, relative.velocity[2]];
+    let rotate = |v: [f64; 3]| -> [f64; 3] { [0, 1, 2].map(|k| axes[0][k] * v[0] + axes[1][k] * v[1] + axes[2][k] * v[2]) };
+    let (position, velocity) = (rotate(relative.position), rotate(inertial));
+    StateVector {
+        position: [0, 1, 2].map(|k| chief.position[k] + position[k]),
+        velocity: [0, 1, 2].map(|k| chief.velocity[k] + velocity[k]),
+    }
+}
+
+/// Represents the Clohessy-Wiltshire (Hill) linearized relative motion about a circular chief orbit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClohessyWiltshire {
+    /// Mean motion of the chief in radians per second.
+    pub mean_motion: f64,
+}
+
+impl ClohessyWiltshire {
+    /// Builds the relative motion about a chief with a given mean motion.
+    ///
+    /// # Arguments
+    /// * `mean_motion` - Mean motion of the chief in radians per second.
+    ///
+    /// # Returns
+    /// * The relative motion model.
+    pub fn new(mean_motion: f64) -> ClohessyWiltshire {
+        ClohessyWiltshire { mean_motion }
+    }
+
+    /// Builds the relative motion about the circular orbit of the mean motion of an element set.
+    ///
+    /// # Arguments
+    /// * `elements` - Orbital elements of the chief, typically from a TLE.
+    ///
+    /// # Returns
+    /// * The relative motion model. The eccentricity of the chief is neglected.
+    pub fn from_elements(elements: &OrbitalElements) -> ClohessyWiltshire {
+        ClohessyWiltshire::new(elements.mean_motion / 60.0)
+    }
+
+    /// Computes the state transition matrix of the relative state in the RIC frame.
+    ///
+    /// # Arguments
+    /// * `minutes` - Time step in minutes.
+    ///
+    /// # Returns
+    /// * The 6×6 matrix mapping the relative position (km) and velocity (km/s) at the start of the
+    ///   step to those at its end.
+    pub fn transition_matrix(&self, minutes: f64) -> [[f64; 6]; 6] {
+        let n: f64 = self.mean_motion;
+        let t: f64 = minutes * 60.0;
+        let (s, c) = (n * t).sin_cos();
+        [
+            [4.0 - 3.0 * c, 0.0, 0.0, s / n, 2.0 * (1.0 - c) / n, 0.0],
+            [6.0 * (s - n * t), 1.0, 0.0, -2.0 * (1.0 - c) / n, (4.0 * s - 3.0 * n * t) / n, 0.0],
+            [0.0, 0.0, c, 0.0, 0.0, s / n],
+            [3.0 * n * s, 0.0, 0.0, c, 2.0 * s, 0.0],
+            [-6.0 * n * (1.0 - c), 0.0, 0.0, -2.0 * s, 4.0 * c - 3.0, 0.0],
+            [0.0, 0.0, -n * s, 0.0, 0.0, c],
+        ]
+    }
+
+    /// Propagates a relative state.
+    ///
+    /// # Arguments
+    /// * `relative` - State of the deputy in the chief's RIC frame (km, km/s).
+    /// * `minutes` - Time step in minutes.
+    ///
+    /// # Returns
+    /// * The relative state at the end of the step.
+    pub fn propagate(&self, relative: &StateVector, minutes: f64) -> StateVector {
+        let [x, y, z] = relative.position;
+        let [vx, vy, vz] = relative.velocity;
+        let initial: [f64; 6] = [x, y, z, vx, vy, vz];
+        let result: [f64; 6] = self.transition_matrix(minutes).map(|row| row.iter().zip(&initial).map(|(a, b)| a * b).sum());
+        StateVector {
+            position: [result[0], result[1], result[2]],
+            velocity: [result[3], result[4], result[5]],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keplerian::{EARTH_MU, KeplerianElements};
+    use crate::numerical::{Gravity, NumericalPropagator};
+    use crate::propagator::Propagator;
+    use crate::Sgp4;
+    use crate::test_support::iss_elements;
+
+    /// Tests the axes on offsets along each of them, and the round trip.
+    #[test]
+    fn test_ric_offsets() {
+        let chief: StateVector = Sgp4::new(&iss_elements()).propagate(0.0).unwrap();
+        let axes: [[f64; 3]; 3] = ric_axes(&chief);
+        for (index, axis) in axes.iter().enumerate() {
+            let deputy = StateVector {
+                position: [0, 1, 2].map(|k| chief.position[k] + 2.0 * axis[k]),
+                velocity: chief.velocity,
+            };
+            let relative: StateVector = to_ric(&chief, &deputy);
+            for k in 0..3 {
+                assert!((relative.position[k] - if k == index { 2.0 } else { 0.0 }).abs() < 1e-9);
+            }
+            let back: StateVector = from_ric(&chief, &relative);
+            for k in 0..3 {
+                assert!((back.position[k] - deputy.position[k]).abs() < 1e-9);
+                assert!((back.velocity[k] - deputy.velocity[k]).abs() < 1e-12);
+            }
+        }
+        assert!(dot(axes[1], chief.velocity) > 0.99 * dot(chief.velocity, chief.velocity).sqrt());
+    }
+
+    /// Tests that a deputy trailing on the same near-circular orbit is almost at rest in the
+    /// rotating frame, behind the chief.
+    #[test]
+    fn test_ric_trailing() {
+        let elements: OrbitalElements = iss_elements();
+        let mut trailing: OrbitalElements = iss_elements();
+        trailing.mean_anomaly -= 0.001;
+        let chief: StateVector = Sgp4::new(&elements).propagate(10.0).unwrap();
+        let deputy: StateVector = Sgp4::new(&trailing).propagate(10.0).unwrap();
+        let relative: StateVector = to_ric(&chief, &deputy);
+
+        // 1 mrad behind on a 6700 km orbit, within the short-period terms of SGP4.
+        assert!((relative.position[1] + 6.7).abs() < 0.1, "{:?}", relative.position);
+        assert!(relative.position[0].abs() < 0.1 && relative.position[2].abs() < 1e-2, "{:?}", relative.position);
+        let inertial: f64 = (0..3).map(|k| (deputy.velocity[k] - chief.velocity[k]).powi(2)).sum::<f64>().sqrt();
+        let rotating: f64 = dot(relative.velocity, relative.velocity).sqrt();
+        assert!(rotating < 0.02 * inertial, "{} {}", rotating, inertial);
+    }
+
+    /// Tests that the 2:1 ellipse of the bounded motion closes after one period.
+    #[test]
+    fn test_cw_bounded_ellipse() {
+        let cw: ClohessyWiltshire = ClohessyWiltshire::from_elements(&iss_elements());
+        let n: f64 = cw.mean_motion;
+        let initial = StateVector {
+            position: [1.0, 0.0, 0.5],
+            velocity: [0.0, -2.0 * n, 0.0],
+        };
+        let period: f64 = core::f64::consts::TAU / n / 60.0;
+        let half: StateVector = cw.propagate(&initial, period / 2.0);
+        assert!((half.position[0] + 1.0).abs() < 1e-9 && (half.position[2] + 0.5).abs() < 1e-9);
+        let full: StateVector = cw.propagate(&initial, period);
+        for k in 0..3 {
+            assert!((full.position[k] - initial.position[k]).abs() < 1e-9);
+            assert!((full.velocity[k] - initial.velocity[k]).abs() < 1e-12);
+        }
+    }
+
+    /// Tests the linearized motion against the numerical propagation of both objects.
+    #[test]
+    fn test_cw_against_numerical() {
+        let a: f64 = 6778.0;
+        let chief: StateVector = KeplerianElements {
+            semi_major_axis: a,
+            eccentricity: 0.0,
+            inclination: 0.9,
+            raan: 0.3,
+            arg_perigee: 0.0,
+            true_anomaly: 1.0,
+        }
+        .to_state();
+        let initial = StateVector {
+            position: [0.2, -1.0, 0.3],
+            velocity: [1e-4, 2e-4, -1e-4],
+        };
+        let deputy: StateVector = from_ric(&chief, &initial);
+        let numerical = |state: StateVector| {
+            let mut propagator = NumericalPropagator::new(state, 2451545.0);
+            propagator.forces.gravity = Gravity::PointMass;
+            propagator
+        };
+        let cw: ClohessyWiltshire = ClohessyWiltshire::new((EARTH_MU / (a * a * a)).sqrt());
+        for minutes in [10.0, 45.0] {
+            let expected: StateVector = to_ric(&numerical(chief).propagate(minutes).unwrap(), &numerical(deputy).propagate(minutes).unwrap());
+            let relative: StateVector = cw.propagate(&initial, minutes);
+            for k in 0..3 {
+                assert!((relative.position[k] - expected.position[k]).abs() < 0.01, "{:?} {:?}", relative.position, expected.position);
+                assert!((relative.velocity[k] - expected.velocity[k]).abs() < 1e-5);
+            }
+        }
+    }
+}
@@ -0,0 +1,241 @@
+//! High-level satellite type.
+//! A `Satellite` bundles the catalog identity of an element set with its mean elements and the
+//! SGP4 initialization, so callers carry a single value instead of the TLE, the elements and the
+//! propagator side by side. Element sets lose accuracy with age, so a satellite reports the age of
+//! its set at a date and can flag propagations beyond a validity window.
+
+use crate::geodetic::Geodetic;
+use crate::propagator::{PropagationError, Propagator, Sgp4};
+use crate::tle::{Tle, TleError, TleFields, TleMetadata, parse_tle};
+use crate::{OrbitalElements, StateVector, convert_fields, time};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Represents a satellite described by an element set.
+pub struct Satellite {
+    /// Name written on the title line of the TLE, if any.
+    pub name: Option<String>,
+    /// NORAD catalog number.
+    pub norad_id: u32,
+    /// International designator (launch year, launch number and piece).
+    pub intl_designator: String,
+    /// Epoch of the element set as a Julian date (UTC).
+    pub epoch_jd: f64,
+    /// Mean orbital elements.
+    pub elements: OrbitalElements,
+    /// Remaining fields of the TLE: classification, mean motion derivatives, element set and
+    /// revolution numbers.
+    pub metadata: TleMetadata,
+    /// Validity window of the element set in days on either side of its epoch, beyond which
+    /// `propagate_checked` flags the states as stale; `None` for no window.
+    pub validity: Option<f64>,
+    /// SGP4 initialization of the elements.
+    propagator: Sgp4,
+}
+
+/// Represents a state propagated with a check of the validity window.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CheckedState {
+    /// State vector in the TEME frame (km, km/s).
+    pub state: StateVector,
+    /// Whether the time is outside the validity window of the element set.
+    pub stale: bool,
+}
+
+impl Satellite {
+    /// Builds a satellite from a TLE set.
+    ///
+    /// # Arguments
+    /// * `tle` - The TLE set, with or without a title line.
+    ///
+    /// # Returns
+    /// * The satellite, initialized for propagation.
+    pub fn from_tle(tle: &Tle) -> Satellite {
+        Satellite::from_fields(&parse_tle(tle))
+    }
+
+    /// Builds a satellite from the fields of an element set, as parsed from a TLE or an OMM.
+    ///
+    /// # Arguments
+    /// * `fields` - The element set fields, in TLE units.
+    ///
+    /// # Returns
+    /// * The satellite, initialized for propagation.
+    pub fn from_fields(fields: &TleFields) -> Satellite {
+        let elements: OrbitalElements = convert_fields(fields);
+        let metadata: TleMetadata = TleMetadata::from(fields);
+        Satellite {
+            name: metadata.name.clone(),
+            norad_id: metadata.catalog_number,
+            intl_designator: metadata.international_designator.clone(),
+            epoch_jd: elements.epoch_jd(),
+            validity: None,
+            propagator: Sgp4::new(&elements),
+            elements,
+            metadata,
+        }
+    }
+
+    /// Builds a satellite from the lines of a TLE set (see `Tle::from_lines`).
+    ///
+    /// # Arguments
+    /// * `lines` - Two lines, or three with a title line first.
+    ///
+    /// # Returns
+    /// * The satellite, or the reason why the lines are not a valid TLE.
+    pub fn from_lines(lines: &[&str]) -> Result<Satellite, TleError> {
+        Tle::from_lines(lines).map(|tle| Satellite::from_tle(&tle))
+    }
+
+    /// Propagates the satellite to a time since epoch.
+    ///
+    /// # Arguments
+    /// * `tsince` - Time since epoch in minutes.
+    ///
+    /// # Returns
+    /// * State vector in the TEME frame (km, km/s), or the reason why the orbit cannot be propagated.
+    pub fn propagate(&self, tsince: f64) -> Result<StateVector, PropagationError> {
+        self.propagator.propagate(tsince)
+    }
+
+    /// Propagates the satellite to a time since epoch, flagging times outside the validity window.
+    ///
+    /// # Arguments
+    /// * `tsince` - Time since epoch in minutes.
+    ///
+    /// # Returns
+    /// * The state vector with whether it is stale, or the reason why the orbit cannot be
+    ///   propagated.
+    pub fn propagate_checked(&self, tsince: f64) -> Result<CheckedState, PropagationError> {
+        Ok(CheckedState {
+            state: self.propagate(tsince)?,
+            stale: self.validity.is_some_and(|days| tsince.abs() > days * 1440.0),
+        })
+    }
+
+    /// Returns the age of the element set at a date.
+    ///
+    /// # Arguments
+    /// * `jd` - The date as a Julian date (UTC).
+    ///
+    /// # Returns
+    /// * The age in days, negative before the epoch.
+    pub fn age_at(&self, jd: f64) -> f64 {
+        jd - self.epoch_jd
+    }
+
+    /// Checks whether the element set is too old, or too far in the future, at a date.
+    ///
+    /// # Arguments
+    /// * `jd` - The date as a Julian date (UTC).
+    /// * `max_age` - Longest time from the epoch in days.
+    ///
+    /// # Returns
+    /// * `true` if the date is more than `max_age` days from the epoch.
+    pub fn is_stale(&self, jd: f64, max_age: f64) -> bool {
+        self.age_at(jd).abs() > max_age
+    }
+
+    /// Propagates the satellite to an absolute instant.
+    ///
+    /// # Arguments
+    /// * `when` - The instant, in UTC.
+    ///
+    /// # Returns
+    /// * State vector in the TEME frame (km, km/s), or the reason why the orbit cannot be propagated.
+    #[cfg(feature = "chrono")]
+    pub fn propagate_at(&self, when: chrono::DateTime<chrono::Utc>) -> Result<StateVector, PropagationError> {
+        self.propagate(self.elements.minutes_since_epoch(when))
+    }
+
+    /// Returns the epoch of the element set as a UTC date and time.
+    #[cfg(feature = "chrono")]
+    pub fn epoch(&self) -> chrono::DateTime<chrono::Utc> {
+        self.elements.epoch()
+    }
+
+    /// Computes the sub-satellite point at a time since epoch.
+    ///
+    /// # Arguments
+    /// * `tsince` - Time since epoch in minutes.
+    ///
+    /// # Returns
+    /// * The latitude and longitude of the sub-satellite point and the altitude of the satellite.
+    pub fn subpoint(&self, tsince: f64) -> Result<Geodetic, PropagationError> {
+        let state: StateVector = self.propagate(tsince)?;
+        Ok(state.to_geodetic(time::gmst(self.elements.jd_at(tsince))))
+    }
+
+    /// Returns the orbital period in minutes.
+    pub fn period(&self) -> f64 {
+        self.elements.period_minutes()
+    }
+}
+
+impl Propagator for Satellite {
+    fn propagate(&self, tsince: f64) -> Result<StateVector, PropagationError> {
+        self.propagator.propagate(tsince)
+    }
+
+    fn epoch_jd(&self) -> f64 {
+        self.epoch_jd
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sgp4;
+    use crate::test_support::{ISS_LINES, ISS_NAME};
+
+    /// Lines of the ISS with a title line.
+    const ISS: [&str; 3] = [ISS_NAME, ISS_LINES[0], ISS_LINES[1]];
+
+    /// Tests the identity and the propagation of a satellite built from a TLE.
+    #[test]
+    fn test_satellite() {
+        let satellite: Satellite = Satellite::from_lines(&ISS).unwrap();
+        assert_eq!(satellite.name.as_deref(), Some("ISS (ZARYA)"));
+        assert_eq!(satellite.norad_id, 25544);
+        assert_eq!(satellite.intl_designator, "98067A");
+        assert_eq!(satellite.metadata.revolution_number, 56353);
+        assert!((satellite.period() - 91.6).abs() < 0.1);
+
+        let state: StateVector = satellite.propagate(60.0).unwrap();
+        assert_eq!(state.position, sgp4(60.0, &satellite.elements).position);
+        let subpoint: Geodetic = satellite.subpoint(60.0).unwrap();
+        assert_eq!(subpoint, satellite.elements.subpoint(60.0).unwrap());
+        assert!(Satellite::from_lines(&ISS[..1]).is_err());
+    }
+
+    /// Tests the propagation to an absolute instant.
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_propagate_at() {
+        let satellite: Satellite = Satellite::from_lines(&ISS).unwrap();
+        let when: chrono::DateTime<chrono::Utc> = satellite.epoch() + chrono::Duration::minutes(90);
+        let state: StateVector = satellite.propagate_at(when).unwrap();
+        let expected: StateVector = satellite.propagate(90.0).unwrap();
+        assert!((state.position[0] - expected.position[0]).abs() < 1e-3);
+    }
+
+    /// Tests the age of the element set and the validity window.
+    #[test]
+    fn test_staleness() {
+        let mut satellite: Satellite = Satellite::from_lines(&ISS).unwrap();
+        let epoch: f64 = satellite.epoch_jd;
+        assert!((satellite.age_at(epoch + 3.5) - 3.5).abs() < 1e-9);
+        assert!(satellite.age_at(epoch - 1.0) < 0.0);
+        assert!(!satellite.is_stale(epoch + 6.0, 7.0));
+        assert!(satellite.is_stale(epoch + 8.0, 7.0));
+        assert!(satellite.is_stale(epoch - 8.0, 7.0));
+
+        assert!(!satellite.propagate_checked(30.0 * 1440.0).unwrap().stale);
+        satellite.validity = Some(7.0);
+        let checked: CheckedState = satellite.propagate_checked(8.0 * 1440.0).unwrap();
+        assert!(checked.stale);
+        assert_eq!(checked.state.position, satellite.propagate(8.0 * 1440.0).unwrap().position);
+        assert!(!satellite.propagate_checked(-6.0 * 1440.0).unwrap().stale);
+    }
+}
@@ -0,0 +1,207 @@
+//! Conjunction screening of a whole catalog.
+//! Pairs whose perigee-apogee shells cannot overlap are sieved out. The remaining objects are
+//! propagated on a common time grid and hashed into a spatial grid at each step: only objects in
+//! neighbouring cells become candidates, which avoids the O(n²) distance checks. Candidate
+//! intervals are then refined pair by pair with the closest-approach search. With the `rayon`
+//! feature, the time steps and the refinements run in parallel.
+
+use crate::conjunction::{Conjunction, close_approaches};
+use crate::{OrbitalElements, Sgp4, XKMPER};
+use std::collections::HashMap;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// Largest relative speed of two objects in Earth orbit, in km/s.
+const MAX_RELATIVE_SPEED: f64 = 16.0;
+/// Margin of the perigee-apogee sieve for the short-period variations of the radius, in km.
+const SIEVE_MARGIN: f64 = 50.0;
+
+/// Holds the settings of a screening.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Screening {
+    /// Largest miss distance reported, in kilometers.
+    pub threshold: f64,
+    /// Time step of the spatial grid in minutes. The cells grow with the distance the objects can
+    /// cover in half a step, so a smaller step gives fewer candidates but more steps.
+    pub step: f64,
+}
+
+impl Default for Screening {
+    fn default() -> Screening {
+        Screening { threshold: 5.0, step: 0.2 }
+    }
+}
+
+/// Represents a conjunction between two objects of a catalog.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CatalogConjunction {
+    /// Index of the first object in the catalog.
+    pub index_a: usize,
+    /// Index of the second object in the catalog, greater than the first one.
+    pub index_b: usize,
+    /// Time and geometry of the closest approach.
+    pub conjunction: Conjunction,
+}
+
+/// Checks whether the radii of two orbits can come within a distance of each other.
+///
+/// # Arguments
+/// * `a` - Orbital elements of the first object.
+/// * `b` - Orbital elements of the second object.
+/// * `distance` - The distance in kilometers.
+///
+/// # Returns
+/// * `false` if one orbit is entirely above the other by more than the distance.
+pub fn shells_overlap(a: &OrbitalElements, b: &OrbitalElements, distance: f64) -> bool {
+    let perigee = |e: &OrbitalElements| e.perigee_altitude_km() + XKMPER;
+    let apogee = |e: &OrbitalElements| e.apogee_altitude_km() + XKMPER;
+    perigee(a).max(perigee(b)) - apogee(a).min(apogee(b)) <= distance + SIEVE_MARGIN
+}
+
+impl Screening {
+    /// Finds all pairs of objects of a catalog passing within the threshold distance.
+    ///
+    /// # Arguments
+    /// * `catalog` - Orbital elements of the objects.
+    /// * `start_jd` - Start of the window as a Julian date (UTC).
+    /// * `end_jd` - End of the window as a Julian date (UTC).
+    ///
+    /// # Returns
+    /// * The conjunctions in chronological order. Objects are skipped at the times they cannot be
+    ///   propagated.
+    pub fn screen(&self, catalog: &[OrbitalElements], start_jd: f64, end_jd: f64) -> Vec<CatalogConjunction> {
+        let propagators: Vec<Sgp4> = catalog.iter().map(Sgp4::new).collect();
+        let cell: f64 = self.threshold + 0.5 * MAX_RELATIVE_SPEED * self.step * 60.0;
+        let steps: usize = ((end_jd - start_jd) * 1440.0 / self.step).ceil() as usize;
+        let time = |k: usize| (start_jd + k as f64 * self.step / 1440.0).min(end_jd);
+
+        // Candidate pairs at each time step, from the objects of neighbouring cells.
+        let candidates_at = |k: usize| -> Vec<(usize, usize, usize)> {
+            let jd: f64 = time(k);
+            let mut grid: HashMap<[i64; 3], Vec<(usize, [f64; 3])>> = HashMap::new();
+            for (index, (sgp4, elements)) in propagators.iter().zip(catalog).enumerate() {
+                if let Ok(state) = sgp4.propagate((jd - elements.epoch_jd()) * 1440.0) {
+                    let key: [i64; 3] = state.position.map(|x| (x / cell).floor() as i64);
+                    grid.entry(key).or_default().push((index, state.position));
+                }
+            }
+            let mut pairs: Vec<(usize, usize, usize)> = Vec::new();
+            for (key, objects) in &grid {
+                for dx in -1..=1 {
+                    for dy in -1..=1 {
+                        for dz in -1..=1 {
+                            let Some(neighbours) = grid.get(&[key[0] + dx, key[1] + dy, key[2] + dz]) else {
+                                continue;
+                            };
+                            for &(i, position_i) in objects {
+                                for &(j, position_j) in neighbours {
+                                    let distance: f64 = (0..3).map(|c| (position_i[c] - position_j[c]).powi(2)).sum::<f64>().sqrt();
+                                    if i < j && distance <= cell && shells_overlap(&catalog[i], &catalog[j], self.threshold) {
+                                        pairs.push((i, j, k));
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            pairs
+        };
+        #[cfg(feature = "rayon")]
+        let candidates: Vec<(usize, usize, usize)> = (0..=steps).into_par_iter().flat_map_iter(candidates_at).collect();
+        #[cfg(not(feature = "rayon"))]
+        let candidates: Vec<(usize, usize, usize)> = (0..=steps).flat_map(candidates_at).collect();
+
+        // Runs of consecutive candidate steps of each pair, widened by one step on each side.
+        let mut steps_by_pair: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for (i, j, k) in candidates {
+            steps_by_pair.entry((i, j)).or_default().push(k);
+        }
+        let mut intervals: Vec<(usize, usize, f64, f64)> = Vec::new();
+        for ((i, j), mut indices) in steps_by_pair {
+            indices.sort_unstable();
+            let mut first: usize = indices[0];
+            for (position, &k) in indices.iter().enumerate() {
+                let last: bool = position + 1 == indices.len() || indices[position + 1] > k + 1;
+                if last {
+                    intervals.push((i, j, time(first.saturating_sub(1)), time(k + 1)));
+                    if position + 1 < indices.len() {
+                        first = indices[position + 1];
+                    }
+                }
+            }
+        }
+
+        let refine = |&(i, j, start, end): &(usize, usize, f64, f64)| -> Vec<CatalogConjunction> {
+            close_approaches(&propagators[i], &propagators[j], start, end, self.threshold)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|conjunction| CatalogConjunction {
+                    index_a: i,
+                    index_b: j,
+                    conjunction,
+                })
+                .collect()
+        };
+        #[cfg(feature = "rayon")]
+        let mut conjunctions: Vec<CatalogConjunction> = intervals.par_iter().flat_map_iter(refine).collect();
+        #[cfg(not(feature = "rayon"))]
+        let mut conjunctions: Vec<CatalogConjunction> = intervals.iter().flat_map(refine).collect();
+        conjunctions.sort_by(|a, b| a.conjunction.tca_jd.total_cmp(&b.conjunction.tca_jd));
+        conjunctions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Tle, convert_satellite_data};
+    use crate::test_support::iss_elements;
+
+    /// Parses the elements of a TLE.
+    fn elements(line1: &str, line2: &str) -> OrbitalElements {
+        convert_satellite_data(&Tle::from_lines(&[line1, line2]).unwrap())
+    }
+
+    /// Tests the sieve on orbits of different altitudes.
+    #[test]
+    fn test_shells_overlap() {
+        let geo = elements(
+            "1 28626U 05008A   06176.46683397 -.00000205  00000-0  10000-3 0  2190",
+            "2 28626   0.0019 286.9433 0000335  13.7918  55.6504  1.00270176  4891",
+        );
+        let eccentric = elements(
+            "1 00005U 58002B   00179.78495062  .00000023  00000-0  28098-4 0  4753",
+            "2 00005  34.2682 348.7242 1859667 331.7664  19.3264 10.82419157413667",
+        );
+        assert!(!shells_overlap(&iss_elements(), &geo, 10.0));
+        assert!(shells_overlap(&iss_elements(), &iss_elements(), 0.0));
+        // The perigee of 00005 is above 600 km, the ISS below 400 km.
+        assert!(!shells_overlap(&iss_elements(), &eccentric, 10.0));
+        assert!(shells_overlap(&iss_elements(), &eccentric, 500.0));
+    }
+
+    /// Tests the screening against the pairwise search on a small catalog.
+    #[test]
+    fn test_screen_catalog() {
+        let mut crossing: OrbitalElements = iss_elements();
+        crossing.inclination += 0.01;
+        let mut trailing: OrbitalElements = iss_elements();
+        trailing.mean_anomaly += 1.0;
+        let catalog: Vec<OrbitalElements> = vec![iss_elements(), crossing, trailing];
+        let start_jd: f64 = catalog[0].epoch_jd();
+        let end_jd: f64 = start_jd + 0.3;
+
+        let screening = Screening { threshold: 10.0, step: 0.2 };
+        let conjunctions: Vec<CatalogConjunction> = screening.screen(&catalog, start_jd, end_jd);
+        let expected = close_approaches(&Sgp4::new(&catalog[0]), &Sgp4::new(&catalog[1]), start_jd, end_jd, 10.0).unwrap();
+        assert_eq!(conjunctions.len(), expected.len());
+        for (found, expected) in conjunctions.iter().zip(&expected) {
+            assert_eq!((found.index_a, found.index_b), (0, 1));
+            assert!((found.conjunction.tca_jd - expected.tca_jd).abs() * 86400.0 < 0.01);
+            assert!((found.conjunction.miss_distance - expected.miss_distance).abs() < 1e-6);
+        }
+    }
+}
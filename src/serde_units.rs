@@ -0,0 +1,29 @@
+//! Serde adapters storing internal SI-like units as user-friendly units.
+//! Used through `#[serde(with = "...")]` on the public types when the `serde` feature is enabled.
+
+/// Serializes an angle stored in radians as degrees.
+pub(crate) mod degrees {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(value.to_degrees())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        Ok(f64::deserialize(deserializer)?.to_radians())
+    }
+}
+
+/// Serializes a mean motion stored in radians per minute as revolutions per day.
+pub(crate) mod rev_per_day {
+    use crate::{TWOPI, XMNPDA};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(value * XMNPDA / TWOPI)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        Ok(f64::deserialize(deserializer)? * TWOPI / XMNPDA)
+    }
+}
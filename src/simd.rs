@@ -0,0 +1,218 @@
+//! SIMD propagation of four time steps at once with `wide`.
+//! The per-step SGP4 math of near-Earth orbits runs on `f64x4` lanes, one time step per lane, so
+//! the trigonometry and Kepler iterations of long time grids are shared between four steps.
+//! Deep-space orbits fall back to the scalar path.
+
+use crate::propagator::{PropagationError, Sgp4};
+use crate::gravity::GravityConstants;
+use crate::{INVALID_STATE, OrbitalElements, StateVector, TWOPI};
+use wide::f64x4;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Two-thirds constant.
+const X2O3: f64 = 2.0 / 3.0;
+
+/// Computes the remainder of a division by 2π with the sign of the dividend, like `%`.
+fn rem_twopi(x: f64x4) -> f64x4 {
+    x - (x / TWOPI).trunc() * TWOPI
+}
+
+impl Sgp4 {
+    /// Computes the state vectors of the satellite at four times since epoch, one per SIMD lane.
+    ///
+    /// # Arguments
+    /// * `times` - Times since epoch in minutes.
+    ///
+    /// # Returns
+    /// * For each time, the state vector in the TEME frame (km, km/s), or the error that
+    ///   `propagate` would return.
+    pub fn propagate_x4(&self, times: [f64; 4]) -> [Result<StateVector, PropagationError>; 4] {
+        if self.is_deep_space() {
+            return times.map(|tsince| self.propagate(tsince));
+        }
+        let t: f64x4 = f64x4::new(times);
+        let GravityConstants { xke, radius, j2, .. } = self.gravity;
+
+        // Secular gravity and atmospheric drag.
+        let xmdf: f64x4 = self.mo + self.mdot * t;
+        let argpdf: f64x4 = self.argpo + self.argpdot * t;
+        let nodedf: f64x4 = self.nodeo + self.nodedot * t;
+        let t2: f64x4 = t * t;
+        let mut tempa: f64x4 = 1.0 - self.cc1 * t;
+        let mut tempe: f64x4 = self.bstar * self.cc4 * t;
+        let mut templ: f64x4 = self.t2cof * t2;
+        let mut mp: f64x4 = xmdf;
+        let mut argpp: f64x4 = argpdf;
+        let mut nodep: f64x4 = nodedf + self.nodecf * t2;
+
+        if !self.isimp {
+            let delomg: f64x4 = self.omgcof * t;
+            let cubed: f64x4 = 1.0 + self.eta * xmdf.sin_cos().1;
+            let delm: f64x4 = self.xmcof * (cubed * cubed * cubed - self.delmo);
+            let temp: f64x4 = delomg + delm;
+            mp = xmdf + temp;
+            argpp = argpdf - temp;
+            let t3: f64x4 = t2 * t;
+            let t4: f64x4 = t3 * t;
+            tempa -= self.d2 * t2 + self.d3 * t3 + self.d4 * t4;
+            tempe += self.bstar * self.cc5 * (mp.sin_cos().0 - self.sinmao);
+            templ += self.t3cof * t3 + t4 * (self.t4cof + t * self.t5cof);
+        }
+
+        let am: f64x4 = (xke / self.no).powf(X2O3) * tempa * tempa;
+        let nm: f64x4 = xke / (am * am.sqrt());
+        let em: f64x4 = self.ecco - tempe;
+        let ep: f64x4 = em.max(f64x4::splat(1.0e-6));
+        mp += self.no * templ;
+        let xlm: f64x4 = rem_twopi(mp + argpp + nodep);
+        nodep = rem_twopi(nodep);
+        argpp = rem_twopi(argpp);
+        mp = rem_twopi(xlm - argpp - nodep);
+
+        // Long-period periodics.
+        let (sinargp, cosargp) = argpp.sin_cos();
+        let axnl: f64x4 = ep * cosargp;
+        let temp: f64x4 = 1.0 / (am * (1.0 - ep * ep));
+        let aynl: f64x4 = ep * sinargp + temp * self.aycof;
+        let xl: f64x4 = mp + argpp + nodep + temp * self.xlcof * axnl;
+
+        // Kepler's equation for the eccentric longitude, until every lane has converged.
+        let u: f64x4 = rem_twopi(xl - nodep);
+        let mut eo1: f64x4 = u;
+        let (mut sineo1, mut coseo1) = (f64x4::splat(0.0), f64x4::splat(1.0));
+        for _ in 0..10 {
+            (sineo1, coseo1) = eo1.sin_cos();
+            let step: f64x4 = ((u - aynl * coseo1 + axnl * sineo1 - eo1) / (1.0 - coseo1 * axnl - sineo1 * aynl)).clamp(f64x4::splat(-0.95), f64x4::splat(0.95));
+            eo1 += step;
+            if step.abs().simd_lt(f64x4::splat(1.0e-12)).all() {
+                break;
+            }
+        }
+
+        // Short-period preliminary quantities.
+        let ecose: f64x4 = axnl * coseo1 + aynl * sineo1;
+        let esine: f64x4 = axnl * sineo1 - aynl * coseo1;
+        let el2: f64x4 = axnl * axnl + aynl * aynl;
+        let pl: f64x4 = am * (1.0 - el2);
+        let rl: f64x4 = am * (1.0 - ecose);
+        let rdotl: f64x4 = am.sqrt() * esine / rl;
+        let rvdotl: f64x4 = pl.sqrt() / rl;
+        let betal: f64x4 = (1.0 - el2).sqrt();
+        let temp: f64x4 = esine / (1.0 + betal);
+        let sinu: f64x4 = am / rl * (sineo1 - aynl - axnl * temp);
+        let cosu: f64x4 = am / rl * (coseo1 - axnl + aynl * temp);
+        let su: f64x4 = sinu.atan2(cosu);
+        let sin2u: f64x4 = (cosu + cosu) * sinu;
+        let cos2u: f64x4 = 1.0 - 2.0 * sinu * sinu;
+        let temp: f64x4 = 1.0 / pl;
+        let temp1: f64x4 = 0.5 * j2 * temp;
+        let temp2: f64x4 = temp1 * temp;
+
+        // Short-period periodics.
+        let mrt: f64x4 = rl * (1.0 - 1.5 * temp2 * betal * self.con41) + 0.5 * temp1 * self.x1mth2 * cos2u;
+        let su: f64x4 = su - 0.25 * temp2 * self.x7thm1 * sin2u;
+        let xnode: f64x4 = nodep + 1.5 * temp2 * self.cosio * sin2u;
+        let xinc: f64x4 = self.inclo + 1.5 * temp2 * self.cosio * self.sinio * cos2u;
+        let mvt: f64x4 = rdotl - nm * temp1 * self.x1mth2 * sin2u / xke;
+        let rvdot: f64x4 = rvdotl + nm * temp1 * (self.x1mth2 * cos2u + 1.5 * self.con41) / xke;
+
+        // Orientation vectors.
+        let (sinsu, cossu) = su.sin_cos();
+        let (snod, cnod) = xnode.sin_cos();
+        let (sini, cosi) = xinc.sin_cos();
+        let xmx: f64x4 = -snod * cosi;
+        let xmy: f64x4 = cnod * cosi;
+        let ux: [f64x4; 3] = [xmx * sinsu + cnod * cossu, xmy * sinsu + snod * cossu, sini * sinsu];
+        let vx: [f64x4; 3] = [xmx * cossu - cnod * sinsu, xmy * cossu - snod * sinsu, sini * cossu];
+        let position: [[f64; 4]; 3] = ux.map(|c| (mrt * c * radius).to_array());
+        let velocity: [[f64; 4]; 3] = [0, 1, 2].map(|k| ((mvt * ux[k] + rvdot * vx[k]) * self.gravity.vkmpersec()).to_array());
+
+        // The lanes are checked separately, as the scalar path does.
+        let em: [f64; 4] = em.to_array();
+        let pl: [f64; 4] = pl.to_array();
+        let mrt: [f64; 4] = mrt.to_array();
+        [0, 1, 2, 3].map(|lane| {
+            if !(-0.001..1.0).contains(&em[lane]) {
+                return Err(PropagationError::MeanEccentricity(em[lane]));
+            }
+            if pl[lane] < 0.0 {
+                return Err(PropagationError::SemiLatusRectum(pl[lane]));
+            }
+            if mrt[lane] < 1.0 {
+                return Err(PropagationError::Decayed { tsince: times[lane] });
+            }
+            Ok(StateVector {
+                position: position.map(|c| c[lane]),
+                velocity: velocity.map(|c| c[lane]),
+            })
+        })
+    }
+}
+
+/// Computes the state vectors of a satellite at several times, four times per SIMD lane group.
+///
+/// # Arguments
+/// * `times` - Times since epoch in minutes.
+/// * `elements` - Orbital elements of the satellite.
+///
+/// # Returns
+/// * One state vector per requested time, in the same order, as `sgp4_batch` returns them.
+pub fn sgp4_batch_simd(times: &[f64], elements: &OrbitalElements) -> Vec<StateVector> {
+    let propagator = Sgp4::new(elements);
+    let mut states: Vec<StateVector> = Vec::with_capacity(times.len());
+    for chunk in times.chunks(4) {
+        let mut lanes: [f64; 4] = [chunk[chunk.len() - 1]; 4];
+        lanes[..chunk.len()].copy_from_slice(chunk);
+        let results = propagator.propagate_x4(lanes);
+        states.extend(results[..chunk.len()].iter().map(|result| result.clone().unwrap_or(INVALID_STATE)));
+    }
+    states
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Tle, convert_satellite_data, sgp4_batch};
+    use crate::test_support::iss_elements;
+
+    /// Tests the SIMD path against the scalar one over a day, with a partial last chunk.
+    #[test]
+    fn test_simd_against_scalar() {
+        let elements = iss_elements();
+        let times: Vec<f64> = (0..1443).map(|i| i as f64 - 1.5).collect();
+
+        let expected: Vec<StateVector> = sgp4_batch(&times, &elements);
+        let states: Vec<StateVector> = sgp4_batch_simd(&times, &elements);
+        assert_eq!(states.len(), times.len());
+        for (state, expected) in states.iter().zip(&expected) {
+            for k in 0..3 {
+                assert!((state.position[k] - expected.position[k]).abs() < 1e-6, "{:?} {:?}", state, expected);
+                assert!((state.velocity[k] - expected.velocity[k]).abs() < 1e-9, "{:?} {:?}", state, expected);
+            }
+        }
+    }
+
+    /// Tests that lanes fail separately and that deep-space orbits use the scalar path.
+    #[test]
+    fn test_simd_lanes() {
+        let iss = iss_elements();
+        let sgp4: Sgp4 = Sgp4::new(&OrbitalElements { bstar: 0.5, ..iss });
+        let results = sgp4.propagate_x4([0.0, 10.0, 1.0e6, 20.0]);
+        for (result, tsince) in results.iter().zip([0.0, 10.0, 1.0e6, 20.0]) {
+            assert_eq!(result.is_ok(), sgp4.propagate(tsince).is_ok());
+        }
+        assert!(results[0].is_ok() && results[2].is_err());
+
+        let geo = convert_satellite_data(
+            &Tle::from_lines(&[
+                "1 28626U 05008A   06176.46683397 -.00000205  00000-0  10000-3 0  2190",
+                "2 28626   0.0019 286.9433 0000335  13.7918  55.6504  1.00270176  4891",
+            ])
+            .unwrap(),
+        );
+        let sgp4: Sgp4 = Sgp4::new(&geo);
+        let results = sgp4.propagate_x4([0.0, 360.0, 720.0, 1440.0]);
+        assert_eq!(results[3].as_ref().unwrap().position, sgp4.propagate(1440.0).unwrap().position);
+    }
+}
@@ -0,0 +1,227 @@
+//! Authenticated client of the Space-Track GP API.
+//! Space-Track is the authoritative source of element sets; its API needs an account, a login
+//! keeping a session cookie, and a throttle on requests. Queries select the current element sets
+//! (`gp` class) or the history over an epoch range (`gp_history` class), page by page.
+
+use crate::celestrak::{FetchError, throttle};
+use crate::omm::parse_json;
+use crate::satellite::Satellite;
+use crate::time;
+use std::time::{Duration, Instant};
+
+/// Base URL of Space-Track.
+pub const SPACE_TRACK_URL: &str = "https://www.space-track.org";
+/// Default number of element sets per page.
+pub const DEFAULT_PAGE_SIZE: usize = 1000;
+/// Default minimum interval between two requests, within the limit of 30 requests per minute.
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(2);
+/// Timeout of a request.
+const TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Selects the element sets of a GP query. Empty filters are not applied.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GpQuery {
+    /// NORAD catalog numbers.
+    pub norad_ids: Vec<u32>,
+    /// International designator in OMM form, e.g. `1998-067A`.
+    pub object_id: Option<String>,
+    /// Epoch range as Julian dates (UTC); when set, the history of the element sets is queried
+    /// instead of the current ones.
+    pub epoch_range: Option<(f64, f64)>,
+}
+
+/// Formats a Julian date as a Space-Track date and time, with the space escaped.
+///
+/// # Arguments
+/// * `jd` - The Julian date (UTC).
+///
+/// # Returns
+/// * The date and time to the second.
+fn format_epoch(jd: f64) -> String {
+    let (year, month, day, hour, minute, second) = time::calendar_date(jd);
+    format!("{:04}-{:02}-{:02}%20{:02}:{:02}:{:02}", year, month, day, hour, minute, second.floor() as u32)
+}
+
+impl GpQuery {
+    /// Builds the query path of a page.
+    ///
+    /// # Arguments
+    /// * `limit` - Number of element sets of the page.
+    /// * `offset` - Number of element sets before the page.
+    ///
+    /// # Returns
+    /// * The path, from `/basicspacedata` to the JSON format.
+    pub fn path(&self, limit: usize, offset: usize) -> String {
+        let class: &str = if self.epoch_range.is_some() { "gp_history" } else { "gp" };
+        let mut path: String = format!("/basicspacedata/query/class/{}", class);
+        if !self.norad_ids.is_empty() {
+            let ids: Vec<String> = self.norad_ids.iter().map(|id| id.to_string()).collect();
+            path.push_str(&format!("/NORAD_CAT_ID/{}", ids.join(",")));
+        }
+        if let Some(object_id) = &self.object_id {
+            let object_id: String = object_id.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '-').collect();
+            path.push_str(&format!("/OBJECT_ID/{}", object_id));
+        }
+        if let Some((start, end)) = self.epoch_range {
+            path.push_str(&format!("/EPOCH/{}--{}", format_epoch(start), format_epoch(end)));
+        }
+        // A stable order keeps the pages from overlapping.
+        path.push_str(&format!("/orderby/NORAD_CAT_ID,EPOCH/limit/{},{}/format/json", limit, offset));
+        path
+    }
+}
+
+/// Parses a page of a Space-Track response.
+///
+/// # Arguments
+/// * `text` - The body of the response.
+///
+/// # Returns
+/// * The satellites of the page, possibly none, or the error returned by the server.
+pub fn parse_page(text: &str) -> Result<Vec<Satellite>, FetchError> {
+    // Element sets come as an array; errors as an object such as {"error":"..."}.
+    if !text.trim_start().starts_with('[') {
+        return Err(FetchError::Server(text.trim().to_string()));
+    }
+    Ok(parse_json(text).map_err(FetchError::Omm)?.iter().map(Satellite::from_fields).collect())
+}
+
+/// Downloads element sets from Space-Track with an authenticated session.
+pub struct SpaceTrackClient {
+    /// HTTP agent, keeping the session cookie.
+    agent: ureq::Agent,
+    /// Base URL of Space-Track.
+    pub base_url: String,
+    /// Number of element sets per page.
+    pub page_size: usize,
+    /// Minimum interval between two requests.
+    pub min_interval: Duration,
+    /// Time of the last request.
+    last_request: Option<Instant>,
+}
+
+impl SpaceTrackClient {
+    /// Builds a client identifying itself with a user agent.
+    ///
+    /// # Arguments
+    /// * `user_agent` - The user agent sent with the requests.
+    ///
+    /// # Returns
+    /// * The client, not logged in yet.
+    pub fn new(user_agent: &str) -> SpaceTrackClient {
+        let agent: ureq::Agent = ureq::Agent::config_builder()
+            .user_agent(user_agent)
+            .timeout_global(Some(TIMEOUT))
+            .http_status_as_error(false)
+            .build()
+            .into();
+        SpaceTrackClient {
+            agent,
+            base_url: SPACE_TRACK_URL.to_string(),
+            page_size: DEFAULT_PAGE_SIZE,
+            min_interval: DEFAULT_INTERVAL,
+            last_request: None,
+        }
+    }
+
+    /// Logs in, keeping the session cookie for the following queries.
+    ///
+    /// # Arguments
+    /// * `identity` - The user name of the account.
+    /// * `password` - The password of the account.
+    ///
+    /// # Returns
+    /// * Nothing, or the reason why the login failed.
+    pub fn login(&mut self, identity: &str, password: &str) -> Result<(), FetchError> {
+        throttle(&mut self.last_request, self.min_interval);
+        let url: String = format!("{}/ajaxauth/login", self.base_url);
+        let mut response = self.agent.post(&url).send_form([("identity", identity), ("password", password)]).map_err(|e| FetchError::Http(e.to_string()))?;
+        let status: u16 = response.status().as_u16();
+        if status != 200 {
+            return Err(FetchError::Status(status));
+        }
+        let text: String = response.body_mut().read_to_string().map_err(|e| FetchError::Http(e.to_string()))?;
+        // A rejected login is answered with {"Login":"Failed"} and a success status.
+        if text.contains("Failed") {
+            return Err(FetchError::Authentication);
+        }
+        Ok(())
+    }
+
+    /// Downloads one page of a query.
+    ///
+    /// # Arguments
+    /// * `query` - The element sets requested.
+    /// * `offset` - Number of element sets before the page.
+    ///
+    /// # Returns
+    /// * The satellites of the page, or the reason why it could not be fetched.
+    pub fn fetch_page(&mut self, query: &GpQuery, offset: usize) -> Result<Vec<Satellite>, FetchError> {
+        throttle(&mut self.last_request, self.min_interval);
+        let url: String = format!("{}{}", self.base_url, query.path(self.page_size, offset));
+        let mut response = self.agent.get(&url).call().map_err(|e| FetchError::Http(e.to_string()))?;
+        match response.status().as_u16() {
+            200 => {}
+            401 => return Err(FetchError::Authentication),
+            status => return Err(FetchError::Status(status)),
+        }
+        let text: String = response.body_mut().read_to_string().map_err(|e| FetchError::Http(e.to_string()))?;
+        parse_page(&text)
+    }
+
+    /// Downloads every page of a query.
+    ///
+    /// # Arguments
+    /// * `query` - The element sets requested.
+    ///
+    /// # Returns
+    /// * The satellites, possibly none, or the reason why a page could not be fetched.
+    pub fn fetch(&mut self, query: &GpQuery) -> Result<Vec<Satellite>, FetchError> {
+        let mut satellites: Vec<Satellite> = Vec::new();
+        loop {
+            let page: Vec<Satellite> = self.fetch_page(query, satellites.len())?;
+            let last: bool = page.len() < self.page_size.max(1);
+            satellites.extend(page);
+            if last {
+                return Ok(satellites);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests the query paths of the current and historical element sets.
+    #[test]
+    fn test_path() {
+        let query = GpQuery {
+            norad_ids: vec![25544, 20580],
+            ..GpQuery::default()
+        };
+        assert_eq!(query.path(1000, 0), "/basicspacedata/query/class/gp/NORAD_CAT_ID/25544,20580/orderby/NORAD_CAT_ID,EPOCH/limit/1000,0/format/json");
+
+        let query = GpQuery {
+            object_id: Some("1998-067A".to_string()),
+            epoch_range: Some((time::julian_date(2024, 1, 1, 0, 0, 0.0), time::julian_date(2024, 1, 31, 12, 30, 0.0))),
+            ..GpQuery::default()
+        };
+        assert_eq!(
+            query.path(500, 1500),
+            "/basicspacedata/query/class/gp_history/OBJECT_ID/1998-067A/EPOCH/2024-01-01%2000:00:00--2024-01-31%2012:30:00/orderby/NORAD_CAT_ID,EPOCH/limit/500,1500/format/json"
+        );
+    }
+
+    /// Tests the parsing of pages and of error messages.
+    #[test]
+    fn test_parse_page() {
+        let json: &str = r#"[{"OBJECT_NAME":"ISS (ZARYA)","OBJECT_ID":"1998-067A","EPOCH":"2008-09-20T12:25:40.104192","MEAN_MOTION":"15.72125391","ECCENTRICITY":"0.0006703","INCLINATION":"51.6416","RA_OF_ASC_NODE":"247.4627","ARG_OF_PERICENTER":"130.5360","MEAN_ANOMALY":"325.0288","EPHEMERIS_TYPE":"0","CLASSIFICATION_TYPE":"U","NORAD_CAT_ID":"25544","ELEMENT_SET_NO":"292","REV_AT_EPOCH":"56353","BSTAR":"-0.000011606","MEAN_MOTION_DOT":"-0.00002182","MEAN_MOTION_DDOT":"0"}]"#;
+        let satellites: Vec<Satellite> = parse_page(json).unwrap();
+        assert_eq!(satellites.len(), 1);
+        assert_eq!(satellites[0].norad_id, 25544);
+        assert_eq!(satellites[0].intl_designator, "98067A");
+        assert!(parse_page("[]").unwrap().is_empty());
+        assert!(matches!(parse_page(r#"{"error":"You must be logged in to complete this action"}"#), Err(FetchError::Server(_))));
+    }
+}
@@ -0,0 +1,381 @@
+//! Time utilities: Julian dates, modified Julian dates, Greenwich Mean Sidereal Time and
+//! conversions between the UTC, TAI, TT and UT1 time scales.
+//! These are the building blocks for Earth-fixed and topocentric conversions of SGP4 states.
+
+use crate::TWOPI;
+use core::f64::consts::PI;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Julian date of the J2000 epoch (2000-01-01 12:00:00).
+pub const JD_J2000: f64 = 2451545.0;
+/// Julian date of the Unix epoch (1970-01-01 00:00:00 UTC).
+pub const JD_UNIX_EPOCH: f64 = 2440587.5;
+/// Offset between Julian dates and modified Julian dates.
+pub const MJD_OFFSET: f64 = 2400000.5;
+/// Number of days in a Julian century.
+pub const DAYS_PER_CENTURY: f64 = 36525.0;
+/// Seconds per day.
+const SECONDS_PER_DAY: f64 = 86400.0;
+/// Offset between Terrestrial Time and International Atomic Time, in seconds.
+pub const TT_MINUS_TAI: f64 = 32.184;
+
+/// Built-in leap second table: first UTC day (year, month) of each TAI - UTC value, in seconds.
+const LEAP_SECONDS: [(i32, u32, f64); 28] = [
+    (1972, 1, 10.0),
+    (1972, 7, 11.0),
+    (1973, 1, 12.0),
+    (1974, 1, 13.0),
+    (1975, 1, 14.0),
+    (1976, 1, 15.0),
+    (1977, 1, 16.0),
+    (1978, 1, 17.0),
+    (1979, 1, 18.0),
+    (1980, 1, 19.0),
+    (1981, 7, 20.0),
+    (1982, 7, 21.0),
+    (1983, 7, 22.0),
+    (1985, 7, 23.0),
+    (1988, 1, 24.0),
+    (1990, 1, 25.0),
+    (1991, 1, 26.0),
+    (1992, 7, 27.0),
+    (1993, 7, 28.0),
+    (1994, 7, 29.0),
+    (1996, 1, 30.0),
+    (1997, 7, 31.0),
+    (1999, 1, 32.0),
+    (2006, 1, 33.0),
+    (2009, 1, 34.0),
+    (2012, 7, 35.0),
+    (2015, 7, 36.0),
+    (2017, 1, 37.0),
+];
+
+/// Represents an astronomical time scale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeScale {
+    /// Coordinated Universal Time, the scale of TLE epochs.
+    Utc,
+    /// International Atomic Time.
+    Tai,
+    /// Terrestrial Time, used by precession-nutation and solar system models.
+    Tt,
+    /// Universal Time UT1, following the rotation of the Earth.
+    Ut1,
+}
+
+/// Represents a table of leap seconds, giving TAI - UTC from a given UTC date onward.
+#[derive(Debug, Clone)]
+pub struct LeapSecondTable {
+    /// Entries sorted by date: Julian date (UTC) of the start of the period and TAI - UTC in seconds.
+    entries: Vec<(f64, f64)>,
+}
+
+impl LeapSecondTable {
+    /// Builds the table embedded in the library, up to the leap second of 2017-01-01.
+    pub fn builtin() -> LeapSecondTable {
+        LeapSecondTable {
+            entries: LEAP_SECONDS
+                .iter()
+                .map(|&(year, month, offset)| (julian_date(year, month, 1, 0, 0, 0.0), offset))
+                .collect(),
+        }
+    }
+
+    /// Builds a table from custom entries, e.g. a newer IERS bulletin loaded at runtime.
+    ///
+    /// # Arguments
+    /// * `entries` - Pairs of Julian date (UTC) at which an offset starts and TAI - UTC in seconds.
+    ///
+    /// # Returns
+    /// * The leap second table, with entries sorted by date.
+    pub fn from_entries(mut entries: Vec<(f64, f64)>) -> LeapSecondTable {
+        entries.sort_by(|a, b| a.0.total_cmp(&b.0));
+        LeapSecondTable { entries }
+    }
+
+    /// Returns TAI - UTC at a given UTC instant.
+    /// Before the first entry, the first offset is used.
+    ///
+    /// # Arguments
+    /// * `jd_utc` - The Julian date in the UTC time scale.
+    ///
+    /// # Returns
+    /// * TAI - UTC, in seconds.
+    pub fn tai_minus_utc(&self, jd_utc: f64) -> f64 {
+        let index: usize = self.entries.partition_point(|&(start, _)| start <= jd_utc);
+        match index {
+            0 => self.entries.first().map_or(0.0, |&(_, offset)| offset),
+            _ => self.entries[index - 1].1,
+        }
+    }
+}
+
+impl Default for LeapSecondTable {
+    fn default() -> LeapSecondTable {
+        LeapSecondTable::builtin()
+    }
+}
+
+/// Converts Julian dates between time scales, given a leap second table and UT1 - UTC.
+#[derive(Debug, Clone, Default)]
+pub struct TimeScales {
+    /// Leap second table used for UTC conversions.
+    pub leap_seconds: LeapSecondTable,
+    /// UT1 - UTC (ΔUT1) in seconds, as published by the IERS.
+    pub dut1: f64,
+}
+
+impl TimeScales {
+    /// Builds a converter with the built-in leap second table.
+    ///
+    /// # Arguments
+    /// * `dut1` - UT1 - UTC in seconds.
+    ///
+    /// # Returns
+    /// * The time scale converter.
+    pub fn new(dut1: f64) -> TimeScales {
+        TimeScales {
+            leap_seconds: LeapSecondTable::builtin(),
+            dut1,
+        }
+    }
+
+    /// Replaces the leap second table.
+    ///
+    /// # Arguments
+    /// * `leap_seconds` - The table to use instead of the built-in one.
+    ///
+    /// # Returns
+    /// * The updated converter.
+    pub fn with_leap_seconds(mut self, leap_seconds: LeapSecondTable) -> TimeScales {
+        self.leap_seconds = leap_seconds;
+        self
+    }
+
+    /// Converts a Julian date from one time scale to another.
+    ///
+    /// # Arguments
+    /// * `jd` - The Julian date in the `from` time scale.
+    /// * `from` - The time scale of `jd`.
+    /// * `to` - The requested time scale.
+    ///
+    /// # Returns
+    /// * The Julian date in the `to` time scale.
+    pub fn convert(&self, jd: f64, from: TimeScale, to: TimeScale) -> f64 {
+        if from == to {
+            return jd;
+        }
+        self.tai_to(self.tai_from(jd, from), to)
+    }
+
+    /// Converts a Julian date from any time scale to TAI.
+    fn tai_from(&self, jd: f64, scale: TimeScale) -> f64 {
+        match scale {
+            TimeScale::Tai => jd,
+            TimeScale::Tt => jd - TT_MINUS_TAI / SECONDS_PER_DAY,
+            TimeScale::Utc => jd + self.leap_seconds.tai_minus_utc(jd) / SECONDS_PER_DAY,
+            TimeScale::Ut1 => self.tai_from(jd - self.dut1 / SECONDS_PER_DAY, TimeScale::Utc),
+        }
+    }
+
+    /// Converts a Julian date from TAI to any time scale.
+    fn tai_to(&self, jd_tai: f64, scale: TimeScale) -> f64 {
+        match scale {
+            TimeScale::Tai => jd_tai,
+            TimeScale::Tt => jd_tai + TT_MINUS_TAI / SECONDS_PER_DAY,
+            TimeScale::Utc => {
+                // TAI - UTC is looked up on the UTC date, so refine the first guess once.
+                let guess: f64 = jd_tai - self.leap_seconds.tai_minus_utc(jd_tai) / SECONDS_PER_DAY;
+                jd_tai - self.leap_seconds.tai_minus_utc(guess) / SECONDS_PER_DAY
+            }
+            TimeScale::Ut1 => self.tai_to(jd_tai, TimeScale::Utc) + self.dut1 / SECONDS_PER_DAY,
+        }
+    }
+}
+
+/// Computes the Julian date of a Gregorian calendar date and time.
+///
+/// # Arguments
+/// * `year` - The four-digit year.
+/// * `month` - The month, from 1 to 12.
+/// * `day` - The day of the month.
+/// * `hour` - The hour of the day.
+/// * `minute` - The minute of the hour.
+/// * `second` - The second of the minute, including its fractional part.
+///
+/// # Returns
+/// * The Julian date, in days.
+pub fn julian_date(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: f64) -> f64 {
+    let (y, m): (f64, f64) = if month <= 2 {
+        (year as f64 - 1.0, month as f64 + 12.0)
+    } else {
+        (year as f64, month as f64)
+    };
+    let a: f64 = (y / 100.0).floor();
+    let b: f64 = 2.0 - a + (a / 4.0).floor();
+
+    (365.25 * (y + 4716.0)).floor() + (30.6001 * (m + 1.0)).floor() + day as f64 + b - 1524.5
+        + (hour as f64 + minute as f64 / 60.0 + second / 3600.0) / 24.0
+}
+
+/// Computes the Julian date of a TLE-style epoch given as a year and a fractional day of the year.
+///
+/// # Arguments
+/// * `year` - The four-digit year.
+/// * `day_of_year` - The day of the year, 1.0 being January 1st at 0h.
+///
+/// # Returns
+/// * The Julian date, in days.
+pub fn julian_date_from_day_of_year(year: i32, day_of_year: f64) -> f64 {
+    julian_date(year, 1, 1, 0, 0, 0.0) - 1.0 + day_of_year
+}
+
+/// Converts a Julian date into a TLE-style epoch, as a year and a fractional day of the year.
+///
+/// # Arguments
+/// * `jd` - The Julian date, in days.
+///
+/// # Returns
+/// * The four-digit year and the day of the year, 1.0 being January 1st at 0h.
+pub fn day_of_year(jd: f64) -> (i32, f64) {
+    let (year, ..) = calendar_date(jd);
+    (year, jd - julian_date(year, 1, 1, 0, 0, 0.0) + 1.0)
+}
+
+/// Converts a Julian date into a Gregorian calendar date and time.
+///
+/// # Arguments
+/// * `jd` - The Julian date, in days.
+///
+/// # Returns
+/// * The year, month, day, hour, minute and second (with its fractional part).
+pub fn calendar_date(jd: f64) -> (i32, u32, u32, u32, u32, f64) {
+    let z: f64 = (jd + 0.5).floor();
+    let f: f64 = jd + 0.5 - z;
+    let a: f64 = if z < 2299161.0 {
+        z
+    } else {
+        let alpha: f64 = ((z - 1867216.25) / 36524.25).floor();
+        z + 1.0 + alpha - (alpha / 4.0).floor()
+    };
+    let b: f64 = a + 1524.0;
+    let c: f64 = ((b - 122.1) / 365.25).floor();
+    let d: f64 = (365.25 * c).floor();
+    let e: f64 = ((b - d) / 30.6001).floor();
+
+    let day: u32 = (b - d - (30.6001 * e).floor()) as u32;
+    let month: u32 = if e < 14.0 { e as u32 - 1 } else { e as u32 - 13 };
+    let year: i32 = if month > 2 { c as i32 - 4716 } else { c as i32 - 4715 };
+
+    let seconds: f64 = f * 86400.0;
+    let hour: u32 = (seconds / 3600.0).floor() as u32;
+    let minute: u32 = ((seconds - hour as f64 * 3600.0) / 60.0).floor() as u32;
+    let second: f64 = seconds - hour as f64 * 3600.0 - minute as f64 * 60.0;
+
+    (year, month, day, hour, minute, second)
+}
+
+/// Converts a Julian date into a modified Julian date.
+///
+/// # Arguments
+/// * `jd` - The Julian date, in days.
+///
+/// # Returns
+/// * The modified Julian date, in days.
+pub fn modified_julian_date(jd: f64) -> f64 {
+    jd - MJD_OFFSET
+}
+
+/// Converts a modified Julian date into a Julian date.
+///
+/// # Arguments
+/// * `mjd` - The modified Julian date, in days.
+///
+/// # Returns
+/// * The Julian date, in days.
+pub fn julian_date_from_mjd(mjd: f64) -> f64 {
+    mjd + MJD_OFFSET
+}
+
+/// Computes the Greenwich Mean Sidereal Time using the IAU-82 model, which is the one
+/// consistent with the TEME frame of SGP4.
+///
+/// # Arguments
+/// * `jd_ut1` - The Julian date in the UT1 time scale (UTC is usually close enough).
+///
+/// # Returns
+/// * GMST in radians, in [0, 2π).
+pub fn gmst(jd_ut1: f64) -> f64 {
+    let tut1: f64 = (jd_ut1 - JD_J2000) / DAYS_PER_CENTURY;
+    let seconds: f64 = -6.2e-6 * tut1.powi(3)
+        + 0.093104 * tut1 * tut1
+        + (876600.0 * 3600.0 + 8640184.812866) * tut1
+        + 67310.54841;
+
+    // 240 seconds of time per degree of rotation
+    (seconds * PI / 180.0 / 240.0).rem_euclid(TWOPI)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests Julian date conversions against reference values.
+    #[test]
+    fn test_julian_date() {
+        assert_eq!(julian_date(2000, 1, 1, 12, 0, 0.0), JD_J2000);
+        assert!((julian_date(1996, 10, 26, 14, 20, 0.0) - 2450383.09722222).abs() < 1e-8);
+        assert!((julian_date_from_day_of_year(2008, 264.51782528) - 2454730.01782528).abs() < 1e-8);
+        let (year, day) = day_of_year(2454730.01782528);
+        assert_eq!(year, 2008);
+        assert!((day - 264.51782528).abs() < 1e-8);
+        assert_eq!(modified_julian_date(JD_J2000), 51544.5);
+        assert_eq!(julian_date_from_mjd(51544.5), JD_J2000);
+
+        let (year, month, day, hour, minute, second) = calendar_date(JD_J2000 + 59.25);
+        assert_eq!((year, month, day, hour, minute), (2000, 2, 29, 18, 0));
+        assert!(second.abs() < 1e-4);
+    }
+
+    /// Tests GMST against Vallado's example 3-5 (1992-08-20 12:14 UT1).
+    #[test]
+    fn test_gmst() {
+        let jd: f64 = julian_date(1992, 8, 20, 12, 14, 0.0);
+        let gmst_deg: f64 = gmst(jd) * 180.0 / PI;
+        assert!((gmst_deg - 152.578787810).abs() < 1e-6);
+    }
+
+    /// Tests conversions between UTC, TAI, TT and UT1.
+    #[test]
+    fn test_time_scales() {
+        let scales = TimeScales::new(-0.4);
+        let jd_utc: f64 = julian_date(2017, 6, 1, 0, 0, 0.0);
+
+        assert_eq!(scales.leap_seconds.tai_minus_utc(jd_utc), 37.0);
+        assert_eq!(scales.leap_seconds.tai_minus_utc(julian_date(2016, 12, 31, 23, 59, 59.0)), 36.0);
+        assert_eq!(scales.leap_seconds.tai_minus_utc(julian_date(1960, 1, 1, 0, 0, 0.0)), 10.0);
+
+        let jd_tt: f64 = scales.convert(jd_utc, TimeScale::Utc, TimeScale::Tt);
+        assert!(((jd_tt - jd_utc) * 86400.0 - 69.184).abs() < 1e-4);
+        assert!((scales.convert(jd_tt, TimeScale::Tt, TimeScale::Utc) - jd_utc).abs() * 86400.0 < 1e-4);
+
+        let jd_ut1: f64 = scales.convert(jd_utc, TimeScale::Utc, TimeScale::Ut1);
+        assert!(((jd_ut1 - jd_utc) * 86400.0 + 0.4).abs() < 1e-4);
+        assert!(((scales.convert(jd_ut1, TimeScale::Ut1, TimeScale::Tai) - jd_utc) * 86400.0 - 37.0).abs() < 1e-4);
+    }
+
+    /// Tests overriding the built-in leap second table at runtime.
+    #[test]
+    fn test_custom_leap_seconds() {
+        let future: f64 = julian_date(2030, 1, 1, 0, 0, 0.0);
+        let table = LeapSecondTable::from_entries(vec![(future, 38.0), (julian_date(2017, 1, 1, 0, 0, 0.0), 37.0)]);
+        let scales = TimeScales::new(0.0).with_leap_seconds(table);
+
+        assert_eq!(scales.leap_seconds.tai_minus_utc(future + 1.0), 38.0);
+        assert_eq!(scales.leap_seconds.tai_minus_utc(future - 1.0), 37.0);
+        let jd_tai: f64 = scales.convert(future + 1.0, TimeScale::Utc, TimeScale::Tai);
+        assert!(((jd_tai - future - 1.0) * 86400.0 - 38.0).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,825 @@
+//! Two-Line Element (TLE) parsing.
+//! This module extracts every field of a TLE set from its fixed-width columns.
+
+use crate::OrbitalElements;
+use core::fmt;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+#[cfg(feature = "std")]
+use std::io::{self, BufRead};
+
+/// Represents a Two-Line Element set (TLE) for a satellite.
+/// TLEs are used to describe the orbits of Earth-orbiting objects.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tle {
+    /// Optional title line holding the satellite name (3-line TLE sets).
+    pub name: Option<String>,
+    /// First line of the TLE data.
+    pub line1: String,
+    /// Second line of the TLE data.
+    pub line2: String,
+}
+
+/// Errors that can occur while reading or writing a TLE set.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TleError {
+    /// The set does not contain two or three lines.
+    LineCount(usize),
+    /// A data line does not start with its expected line number.
+    LineNumber {
+        /// The expected line number (1 or 2).
+        expected: u8,
+    },
+    /// The catalog numbers of the two data lines differ.
+    CatalogMismatch,
+    /// The catalog number cannot be written in the five-column Alpha-5 field.
+    CatalogRange(u32),
+    /// A value cannot be written in the fixed-width columns of its field.
+    FieldRange {
+        /// The name of the field.
+        field: &'static str,
+        /// The value that does not fit.
+        value: f64,
+    },
+}
+
+impl fmt::Display for TleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TleError::LineCount(count) => write!(f, "expected 2 or 3 TLE lines, got {}", count),
+            TleError::LineNumber { expected } => write!(f, "TLE line {} does not start with '{}'", expected, expected),
+            TleError::CatalogMismatch => write!(f, "TLE lines have different catalog numbers"),
+            TleError::CatalogRange(number) => write!(f, "catalog number {} cannot be written in a TLE", number),
+            TleError::FieldRange { field, value } => write!(f, "{} {} cannot be written in a TLE", field, value),
+        }
+    }
+}
+
+impl core::error::Error for TleError {}
+
+impl Tle {
+    /// Builds a TLE set from two data lines or from a title line followed by two data lines.
+    ///
+    /// A leading `0 ` on the title line, as used by Space-Track 3LE files, is removed.
+    ///
+    /// # Arguments
+    /// * `lines` - The two or three lines of the TLE set.
+    ///
+    /// # Returns
+    /// * The TLE set, or an error if the lines do not form a TLE set.
+    pub fn from_lines(lines: &[&str]) -> Result<Tle, TleError> {
+        let (name, line1, line2) = match lines {
+            [line1, line2] => (None, line1, line2),
+            [title, line1, line2] => {
+                let title: &str = title.trim();
+                let title: &str = title.strip_prefix("0 ").unwrap_or(title).trim();
+                (Some(title.to_string()), line1, line2)
+            }
+            _ => return Err(TleError::LineCount(lines.len())),
+        };
+
+        let line1: &str = line1.trim_end();
+        let line2: &str = line2.trim_end();
+        if !line1.starts_with('1') {
+            return Err(TleError::LineNumber { expected: 1 });
+        }
+        if !line2.starts_with('2') {
+            return Err(TleError::LineNumber { expected: 2 });
+        }
+        if field(line1, 3, 5) != field(line2, 3, 5) {
+            return Err(TleError::CatalogMismatch);
+        }
+
+        Ok(Tle {
+            name,
+            line1: line1.to_string(),
+            line2: line2.to_string(),
+        })
+    }
+}
+
+/// Computes the modulo-10 checksum of a TLE line.
+/// Digits count for their value, minus signs count as 1, every other character is ignored.
+///
+/// # Arguments
+/// * `line` - The TLE line, whose first 68 columns are summed.
+///
+/// # Returns
+/// * The checksum digit.
+pub fn checksum(line: &str) -> u8 {
+    let sum: u32 = line
+        .chars()
+        .take(68)
+        .map(|c| match c {
+            '0'..='9' => c as u32 - '0' as u32,
+            '-' => 1,
+            _ => 0,
+        })
+        .sum();
+    (sum % 10) as u8
+}
+
+/// Formats a value with an assumed leading decimal point and a power of ten exponent,
+/// the inverse of `parse_exponent`.
+///
+/// # Arguments
+/// * `value` - The value to format.
+/// * `field` - The name of the field, reported in the error.
+///
+/// # Returns
+/// * The eight-character field, e.g. ` 50843-4` for 0.50843e-4, or an error if the exponent does
+///   not fit in its single digit.
+fn format_exponent(value: f64, field: &'static str) -> Result<String, TleError> {
+    if value == 0.0 {
+        return Ok(" 00000-0".to_string());
+    }
+
+    let sign: char = if value < 0.0 { '-' } else { ' ' };
+    let mut power: i32 = value.abs().log10().floor() as i32 + 1;
+    let mut digits: i64 = (value.abs() / 10f64.powi(power) * 1e5).round() as i64;
+    if digits >= 100000 {
+        digits /= 10;
+        power += 1;
+    }
+    if !value.is_finite() || power.abs() > 9 {
+        return Err(TleError::FieldRange { field, value });
+    }
+    let power_sign: char = if power < 0 { '-' } else { '+' };
+    Ok(format!("{}{:05}{}{}", sign, digits, power_sign, power.abs()))
+}
+
+/// Formats the first derivative of mean motion without its leading zero, e.g. ` .00002418`.
+///
+/// # Arguments
+/// * `value` - The first derivative of mean motion divided by two, in revolutions per day squared.
+///
+/// # Returns
+/// * The ten-character field, or an error if the value rounds to one or more.
+fn format_mean_motion_dot(value: f64) -> Result<String, TleError> {
+    let sign: char = if value < 0.0 { '-' } else { ' ' };
+    let text: String = format!("{:.8}", value.abs());
+    match text.strip_prefix('0') {
+        Some(fraction) => Ok(format!("{}{}", sign, fraction)),
+        None => Err(TleError::FieldRange { field: "mean motion first derivative", value }),
+    }
+}
+
+/// Normalizes an angle in degrees to the [0, 360) range.
+///
+/// # Arguments
+/// * `deg` - Angle in degrees.
+///
+/// # Returns
+/// * Equivalent angle in [0, 360) degrees.
+fn normalize_degrees(deg: f64) -> f64 {
+    let deg: f64 = deg.rem_euclid(360.0);
+    if deg >= 359.99995 { 0.0 } else { deg }
+}
+
+impl TleFields {
+    /// Writes the fields into the fixed-width columns of a TLE set, with checksums.
+    ///
+    /// # Returns
+    /// * The TLE set, or an error if the catalog number or a field value cannot be written.
+    pub fn to_tle(&self) -> Result<Tle, TleError> {
+        let catalog: String = encode_alpha5(self.catalog_number).ok_or(TleError::CatalogRange(self.catalog_number))?;
+
+        let mut line1: String = format!(
+            "1 {}{} {:<8} {:02}{:012.8} {} {} {} {} {:>4}",
+            catalog,
+            self.classification,
+            self.international_designator,
+            self.epoch_year,
+            self.epoch_day,
+            format_mean_motion_dot(self.mean_motion_dot)?,
+            format_exponent(self.mean_motion_ddot, "mean motion second derivative")?,
+            format_exponent(self.bstar, "BSTAR")?,
+            self.ephemeris_type,
+            self.element_set_number % 10000,
+        );
+        line1.push(char::from(b'0' + checksum(&line1)));
+
+        // The eccentricity has seven digits after an assumed decimal point, the mean motion two
+        // digits before the decimal point.
+        let eccentricity: f64 = (self.eccentricity * 1e7).round();
+        if !(0.0..=9999999.0).contains(&eccentricity) {
+            return Err(TleError::FieldRange { field: "eccentricity", value: self.eccentricity });
+        }
+        if !(0.0..99.999999995).contains(&self.mean_motion) {
+            return Err(TleError::FieldRange { field: "mean motion", value: self.mean_motion });
+        }
+        let eccentricity: u32 = eccentricity as u32;
+        let mut line2: String = format!(
+            "2 {} {:>8.4} {:>8.4} {:07} {:>8.4} {:>8.4} {:>11.8}{:>5}",
+            catalog,
+            normalize_degrees(self.inclination),
+            normalize_degrees(self.raan),
+            eccentricity,
+            normalize_degrees(self.arg_perigee),
+            normalize_degrees(self.mean_anomaly),
+            self.mean_motion,
+            self.revolution_number % 100000,
+        );
+        line2.push(char::from(b'0' + checksum(&line2)));
+
+        Ok(Tle {
+            name: self.name.clone(),
+            line1,
+            line2,
+        })
+    }
+}
+
+impl Tle {
+    /// Builds a TLE set from orbital elements and the remaining TLE metadata.
+    ///
+    /// The mean motion is converted back to the Kozai convention used by TLEs, so that
+    /// `convert_satellite_data` on the result gives back the same elements.
+    ///
+    /// # Arguments
+    /// * `elements` - The orbital elements to write.
+    /// * `metadata` - The identification, epoch and bookkeeping fields.
+    ///
+    /// # Returns
+    /// * The TLE set, or an error if the catalog number or a field value cannot be written.
+    pub fn from_elements(elements: &OrbitalElements, metadata: &TleMetadata) -> Result<Tle, TleError> {
+        let xno: f64 = crate::kozai_mean_motion(elements.mean_motion, elements.inclination, elements.eccentricity, &crate::GravityConstants::default());
+
+        let fields = TleFields {
+            name: metadata.name.clone(),
+            catalog_number: metadata.catalog_number,
+            classification: metadata.classification,
+            international_designator: metadata.international_designator.clone(),
+            epoch_year: metadata.epoch_year,
+            epoch_day: metadata.epoch_day,
+            mean_motion_dot: metadata.mean_motion_dot,
+            mean_motion_ddot: metadata.mean_motion_ddot,
+            bstar: elements.bstar,
+            ephemeris_type: metadata.ephemeris_type,
+            element_set_number: metadata.element_set_number,
+            inclination: crate::degrees(elements.inclination),
+            raan: crate::degrees(elements.raan),
+            eccentricity: elements.eccentricity,
+            arg_perigee: crate::degrees(elements.arg_perigee),
+            mean_anomaly: crate::degrees(elements.mean_anomaly),
+            mean_motion: xno * crate::XMNPDA / crate::TWOPI,
+            revolution_number: metadata.revolution_number,
+        };
+        fields.to_tle()
+    }
+}
+
+/// Checks whether a line looks like a TLE data line with the given line number.
+///
+/// # Arguments
+/// * `line` - The line to check.
+/// * `number` - The expected line number character ('1' or '2').
+///
+/// # Returns
+/// * `true` if the line starts with the line number followed by a space.
+#[cfg(feature = "std")]
+fn is_data_line(line: &str, number: char) -> bool {
+    let mut chars = line.chars();
+    chars.next() == Some(number) && chars.next() == Some(' ')
+}
+
+/// Reads every TLE set from a Celestrak-style file.
+///
+/// The file may mix 2-line and 3-line sets. Blank lines are skipped, and malformed sets
+/// (orphan data lines, mismatched catalog numbers) are ignored so the remaining sets can
+/// still be read.
+///
+/// # Arguments
+/// * `reader` - The reader providing the file contents.
+///
+/// # Returns
+/// * The TLE sets found in the file, or the I/O error that interrupted reading.
+#[cfg(feature = "std")]
+pub fn parse_tle_file(reader: impl BufRead) -> io::Result<Vec<Tle>> {
+    let mut tles: Vec<Tle> = Vec::new();
+    let mut title: Option<String> = None;
+    let mut line1: Option<String> = None;
+
+    for line in reader.lines() {
+        let line: String = line?;
+        let line: &str = line.trim_end();
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if is_data_line(line, '1') {
+            line1 = Some(line.to_string());
+        } else if is_data_line(line, '2') && line1.is_some() {
+            let first: String = line1.take().unwrap_or_default();
+            let result = match title.take() {
+                Some(name) => Tle::from_lines(&[&name, &first, line]),
+                None => Tle::from_lines(&[&first, line]),
+            };
+            if let Ok(tle) = result {
+                tles.push(tle);
+            }
+        } else {
+            line1 = None;
+            title = Some(line.to_string());
+        }
+    }
+
+    Ok(tles)
+}
+
+/// Represents every field stored in a Two-Line Element set, in TLE units.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TleFields {
+    /// Satellite name from the title line, if any.
+    pub name: Option<String>,
+    /// NORAD satellite catalog number, decoded from the Alpha-5 scheme if needed.
+    pub catalog_number: u32,
+    /// Classification character (U: unclassified, C: classified, S: secret).
+    pub classification: char,
+    /// International designator (launch year, launch number and piece).
+    pub international_designator: String,
+    /// Last two digits of the epoch year (see `epoch_full_year`).
+    pub epoch_year: u8,
+    /// Epoch day of the year, including the fractional part of the day.
+    pub epoch_day: f64,
+    /// First derivative of mean motion divided by two, in revolutions per day squared.
+    pub mean_motion_dot: f64,
+    /// Second derivative of mean motion divided by six, in revolutions per day cubed.
+    pub mean_motion_ddot: f64,
+    /// Bstar drag term in 1/earth radii.
+    pub bstar: f64,
+    /// Ephemeris type (always 0 in distributed TLEs).
+    pub ephemeris_type: u8,
+    /// Element set number.
+    pub element_set_number: u32,
+    /// Inclination of the orbit in degrees.
+    pub inclination: f64,
+    /// Right Ascension of the Ascending Node (RAAN) in degrees.
+    pub raan: f64,
+    /// Eccentricity of the orbit, unitless.
+    pub eccentricity: f64,
+    /// Argument of perigee in degrees.
+    pub arg_perigee: f64,
+    /// Mean anomaly in degrees.
+    pub mean_anomaly: f64,
+    /// Mean motion in revolutions per day.
+    pub mean_motion: f64,
+    /// Revolution number at epoch.
+    pub revolution_number: u32,
+}
+
+/// Represents the TLE fields that are not orbital elements: identification, epoch,
+/// mean motion derivatives and bookkeeping numbers.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TleMetadata {
+    /// Satellite name written on the title line, if any.
+    pub name: Option<String>,
+    /// NORAD satellite catalog number.
+    pub catalog_number: u32,
+    /// Classification character (U: unclassified, C: classified, S: secret).
+    pub classification: char,
+    /// International designator (launch year, launch number and piece).
+    pub international_designator: String,
+    /// Last two digits of the epoch year (see `epoch_full_year`).
+    pub epoch_year: u8,
+    /// Epoch day of the year, including the fractional part of the day.
+    pub epoch_day: f64,
+    /// First derivative of mean motion divided by two, in revolutions per day squared.
+    pub mean_motion_dot: f64,
+    /// Second derivative of mean motion divided by six, in revolutions per day cubed.
+    pub mean_motion_ddot: f64,
+    /// Ephemeris type.
+    pub ephemeris_type: u8,
+    /// Element set number.
+    pub element_set_number: u32,
+    /// Revolution number at epoch.
+    pub revolution_number: u32,
+}
+
+impl From<&TleFields> for TleMetadata {
+    fn from(fields: &TleFields) -> TleMetadata {
+        TleMetadata {
+            name: fields.name.clone(),
+            catalog_number: fields.catalog_number,
+            classification: fields.classification,
+            international_designator: fields.international_designator.clone(),
+            epoch_year: fields.epoch_year,
+            epoch_day: fields.epoch_day,
+            mean_motion_dot: fields.mean_motion_dot,
+            mean_motion_ddot: fields.mean_motion_ddot,
+            ephemeris_type: fields.ephemeris_type,
+            element_set_number: fields.element_set_number,
+            revolution_number: fields.revolution_number,
+        }
+    }
+}
+
+/// Expands a two-digit TLE epoch year using the 57-year pivot: years 57 to 99 are in the
+/// 1900s (Sputnik was launched in 1957), years 00 to 56 are in the 2000s.
+///
+/// # Arguments
+/// * `year` - The last two digits of the year.
+///
+/// # Returns
+/// * The four-digit year.
+pub fn full_year(year: u8) -> i32 {
+    if year < 57 { 2000 + year as i32 } else { 1900 + year as i32 }
+}
+
+/// Converts a year and fractional day of the year into a UTC date and time.
+///
+/// # Arguments
+/// * `year` - The four-digit year.
+/// * `day` - The day of the year, 1.0 being January 1st at 0h.
+///
+/// # Returns
+/// * The corresponding UTC date and time, rounded to the microsecond.
+#[cfg(feature = "chrono")]
+pub fn epoch_to_datetime(year: i32, day: f64) -> chrono::DateTime<chrono::Utc> {
+    let whole: f64 = day.floor();
+    let micros: i64 = ((day - whole) * 86400e6).round() as i64;
+    let start = chrono::NaiveDate::from_yo_opt(year, 1).unwrap_or_default().and_time(chrono::NaiveTime::MIN);
+    let date = start + chrono::Duration::days(whole as i64 - 1) + chrono::Duration::microseconds(micros);
+    date.and_utc()
+}
+
+impl TleFields {
+    /// Returns the four-digit epoch year, using the 57-year pivot.
+    pub fn epoch_full_year(&self) -> i32 {
+        full_year(self.epoch_year)
+    }
+
+    /// Returns the epoch as a UTC date and time.
+    #[cfg(feature = "chrono")]
+    pub fn epoch(&self) -> chrono::DateTime<chrono::Utc> {
+        epoch_to_datetime(self.epoch_full_year(), self.epoch_day)
+    }
+}
+
+impl TleMetadata {
+    /// Returns the four-digit epoch year, using the 57-year pivot.
+    pub fn epoch_full_year(&self) -> i32 {
+        full_year(self.epoch_year)
+    }
+
+    /// Returns the epoch as a UTC date and time.
+    #[cfg(feature = "chrono")]
+    pub fn epoch(&self) -> chrono::DateTime<chrono::Utc> {
+        epoch_to_datetime(self.epoch_full_year(), self.epoch_day)
+    }
+}
+
+/// Extracts a trimmed substring from a TLE line.
+///
+/// # Arguments
+/// * `line` - The TLE line to parse.
+/// * `start` - The starting index of the substring (1-based).
+/// * `len` - The length of the substring.
+///
+/// # Returns
+/// * The trimmed substring, or an empty string if the line is too short.
+fn field(line: &str, start: usize, len: usize) -> &str {
+    let end: usize = (start - 1 + len).min(line.len());
+    line.get(start - 1..end).unwrap_or("").trim()
+}
+
+/// Parses a substring from a TLE line and converts it to a real number.
+///
+/// # Arguments
+/// * `line` - The TLE line to parse.
+/// * `start` - The starting index of the substring (1-based).
+/// * `len` - The length of the substring.
+///
+/// # Returns
+/// * The parsed real number, or 0 if the field is missing or malformed.
+fn parse_real(line: &str, start: usize, len: usize) -> f64 {
+    field(line, start, len).parse::<f64>().unwrap_or(0.0)
+}
+
+/// Parses a substring from a TLE line and converts it to an unsigned integer.
+///
+/// # Arguments
+/// * `line` - The TLE line to parse.
+/// * `start` - The starting index of the substring (1-based).
+/// * `len` - The length of the substring.
+///
+/// # Returns
+/// * The parsed integer, or 0 if the field is missing or malformed.
+fn parse_int(line: &str, start: usize, len: usize) -> u32 {
+    field(line, start, len).parse::<u32>().unwrap_or(0)
+}
+
+/// Letters used by the Alpha-5 scheme, in order, starting at 10 ('I' and 'O' are skipped
+/// to avoid confusion with 1 and 0).
+const ALPHA5_LETTERS: &str = "ABCDEFGHJKLMNPQRSTUVWXYZ";
+
+/// Decodes a catalog number written in the Alpha-5 scheme.
+///
+/// Numbers below 100 000 are plain digits. Larger numbers replace the leading digit pair
+/// by a letter, so that "A0000" is 100 000 and "E8493" is 148 493.
+///
+/// # Arguments
+/// * `text` - The catalog number field, up to five characters.
+///
+/// # Returns
+/// * The catalog number, or `None` if the field is not a valid Alpha-5 number.
+pub fn decode_alpha5(text: &str) -> Option<u32> {
+    let text: &str = text.trim();
+    let first: char = text.chars().next()?;
+    if first.is_ascii_digit() {
+        return text.parse::<u32>().ok();
+    }
+
+    let rest: &str = &text[first.len_utf8()..];
+    if rest.len() != 4 || !rest.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let prefix: u32 = ALPHA5_LETTERS.find(first.to_ascii_uppercase())? as u32 + 10;
+    Some(prefix * 10000 + rest.parse::<u32>().ok()?)
+}
+
+/// Encodes a catalog number in the five-character Alpha-5 scheme.
+///
+/// # Arguments
+/// * `number` - The catalog number, up to 339 999.
+///
+/// # Returns
+/// * The five-character field, or `None` if the number cannot be represented.
+pub fn encode_alpha5(number: u32) -> Option<String> {
+    if number < 100000 {
+        return Some(format!("{:05}", number));
+    }
+
+    let letter: char = ALPHA5_LETTERS.chars().nth((number / 10000 - 10) as usize)?;
+    Some(format!("{}{:04}", letter, number % 10000))
+}
+
+/// Parses a field written with an assumed leading decimal point and a power of ten exponent,
+/// such as ` 50843-4` for 0.50843e-4 or `-11606-4` for -0.11606e-4.
+///
+/// # Arguments
+/// * `line` - The TLE line to parse.
+/// * `start` - The starting index of the substring (1-based).
+/// * `len` - The length of the substring.
+///
+/// # Returns
+/// * The parsed real number, or 0 if the field is missing or malformed.
+fn parse_exponent(line: &str, start: usize, len: usize) -> f64 {
+    let text: &str = field(line, start, len);
+    let (mantissa, exponent) = match text.rfind(['-', '+']) {
+        Some(i) if i > 0 => (&text[..i], &text[i..]),
+        _ => (text, "0"),
+    };
+    let (sign, digits) = match mantissa.strip_prefix('-') {
+        Some(digits) => (-1.0, digits),
+        None => (1.0, mantissa.trim_start_matches('+')),
+    };
+
+    let value: f64 = format!("0.{}", digits.trim()).parse::<f64>().unwrap_or(0.0);
+    let power: i32 = exponent.parse::<i32>().unwrap_or(0);
+    sign * value * 10f64.powi(power)
+}
+
+/// Parses every field of a TLE set.
+///
+/// Missing or malformed fields are set to zero so that truncated TLEs can still be used.
+///
+/// # Arguments
+/// * `tle` - The Two-Line Element set to parse.
+///
+/// # Returns
+/// * All the fields of the TLE, in TLE units.
+pub fn parse_tle(tle: &Tle) -> TleFields {
+    let line1: &str = &tle.line1;
+    let line2: &str = &tle.line2;
+
+    TleFields {
+        name: tle.name.clone(),
+        catalog_number: decode_alpha5(field(line1, 3, 5)).unwrap_or(0),
+        classification: field(line1, 8, 1).chars().next().unwrap_or('U'),
+        international_designator: field(line1, 10, 8).to_string(),
+        epoch_year: parse_int(line1, 19, 2) as u8,
+        epoch_day: parse_real(line1, 21, 12),
+        mean_motion_dot: parse_real(line1, 34, 10),
+        mean_motion_ddot: parse_exponent(line1, 45, 8),
+        bstar: parse_exponent(line1, 54, 8),
+        ephemeris_type: parse_int(line1, 63, 1) as u8,
+        element_set_number: parse_int(line1, 65, 4),
+        inclination: parse_real(line2, 9, 8),
+        raan: parse_real(line2, 18, 8),
+        eccentricity: parse_real(line2, 27, 7) * 1e-7,
+        arg_perigee: parse_real(line2, 35, 8),
+        mean_anomaly: parse_real(line2, 44, 8),
+        mean_motion: parse_real(line2, 53, 11),
+        revolution_number: parse_int(line2, 64, 5),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{ISS_LINES, iss_elements, iss_tle};
+
+    /// Tests that every field of a complete TLE is parsed.
+    #[test]
+    fn test_parse_tle() {
+        let tle = Tle {
+            name: None,
+            line1: "1 25544U 98067A   21135.57634567  .00002418  00000-0  50843-4 0  9993".to_string(),
+            line2: "2 25544  51.6443 126.6639 0006738  34.7758 325.3542 15.48913328283873".to_string(),
+        };
+
+        let fields = parse_tle(&tle);
+
+        assert_eq!(fields.catalog_number, 25544);
+        assert_eq!(fields.classification, 'U');
+        assert_eq!(fields.international_designator, "98067A");
+        assert_eq!(fields.epoch_year, 21);
+        assert!((fields.epoch_day - 135.57634567).abs() < 1e-9);
+        assert!((fields.mean_motion_dot - 0.00002418).abs() < 1e-12);
+        assert_eq!(fields.mean_motion_ddot, 0.0);
+        assert!((fields.bstar - 0.50843e-4).abs() < 1e-12);
+        assert_eq!(fields.ephemeris_type, 0);
+        assert_eq!(fields.element_set_number, 999);
+        assert!((fields.eccentricity - 0.0006738).abs() < 1e-12);
+        assert!((fields.mean_motion - 15.48913328).abs() < 1e-9);
+        assert_eq!(fields.revolution_number, 28387);
+    }
+
+    /// Tests that negative assumed-decimal fields and truncated lines are handled.
+    #[test]
+    fn test_parse_tle_negative_and_truncated() {
+        let tle = Tle {
+            name: None,
+            line1: "1 11801U          80230.29629788 -.01431103 -12345-5 -14311-1".to_string(),
+            line2: "2 11801  46.7916 230.4354 7318036  47.4722  10.4117  2.28537848".to_string(),
+        };
+
+        let fields = parse_tle(&tle);
+
+        assert!((fields.mean_motion_dot + 0.01431103).abs() < 1e-12);
+        assert!((fields.mean_motion_ddot + 0.12345e-5).abs() < 1e-15);
+        assert!((fields.bstar + 0.14311e-1).abs() < 1e-12);
+        assert_eq!(fields.international_designator, "");
+        assert_eq!(fields.element_set_number, 0);
+        assert_eq!(fields.revolution_number, 0);
+    }
+
+    /// Tests building TLE sets from two and three lines.
+    #[test]
+    fn test_from_lines() {
+        let line1 = "1 25544U 98067A   21135.57634567  .00002418  00000-0  50843-4 0  9993";
+        let line2 = "2 25544  51.6443 126.6639 0006738  34.7758 325.3542 15.48913328283873";
+
+        let tle = Tle::from_lines(&[line1, line2]).unwrap();
+        assert!(tle.name.is_none());
+
+        let tle = Tle::from_lines(&["ISS (ZARYA)             ", line1, line2]).unwrap();
+        assert_eq!(tle.name.as_deref(), Some("ISS (ZARYA)"));
+        assert_eq!(parse_tle(&tle).name.as_deref(), Some("ISS (ZARYA)"));
+
+        let tle = Tle::from_lines(&["0 ISS (ZARYA)", line1, line2]).unwrap();
+        assert_eq!(tle.name.as_deref(), Some("ISS (ZARYA)"));
+
+        assert_eq!(Tle::from_lines(&[line1]).unwrap_err(), TleError::LineCount(1));
+        assert_eq!(Tle::from_lines(&[line2, line1]).unwrap_err(), TleError::LineNumber { expected: 1 });
+    }
+
+    /// Tests reading a file mixing named, unnamed, blank and malformed entries.
+    #[test]
+    fn test_parse_tle_file() {
+        let input = "\
+ISS (ZARYA)
+1 25544U 98067A   21135.57634567  .00002418  00000-0  50843-4 0  9993
+2 25544  51.6443 126.6639 0006738  34.7758 325.3542 15.48913328283873
+
+1 11801U          80230.29629788  .01431103  00000-0  14311-1
+2 11801  46.7916 230.4354 7318036  47.4722  10.4117  2.28537848
+BROKEN
+1 25544U 98067A   21135.57634567  .00002418  00000-0  50843-4 0  9993
+2 11801  46.7916 230.4354 7318036  47.4722  10.4117  2.28537848
+ORPHAN
+2 25544  51.6443 126.6639 0006738  34.7758 325.3542 15.48913328283873
+";
+
+        let tles = parse_tle_file(input.as_bytes()).unwrap();
+
+        assert_eq!(tles.len(), 2);
+        assert_eq!(tles[0].name.as_deref(), Some("ISS (ZARYA)"));
+        assert!(tles[1].name.is_none());
+        assert_eq!(parse_tle(&tles[1]).catalog_number, 11801);
+    }
+
+    /// Tests decoding and encoding Alpha-5 catalog numbers.
+    #[test]
+    fn test_alpha5() {
+        assert_eq!(decode_alpha5("25544"), Some(25544));
+        assert_eq!(decode_alpha5("00005"), Some(5));
+        assert_eq!(decode_alpha5("A0000"), Some(100000));
+        assert_eq!(decode_alpha5("E8493"), Some(148493));
+        assert_eq!(decode_alpha5("J0001"), Some(180001));
+        assert_eq!(decode_alpha5("Z9999"), Some(339999));
+        assert_eq!(decode_alpha5("I1234"), None);
+        assert_eq!(decode_alpha5("E84"), None);
+
+        assert_eq!(encode_alpha5(5).as_deref(), Some("00005"));
+        assert_eq!(encode_alpha5(148493).as_deref(), Some("E8493"));
+        assert_eq!(encode_alpha5(180001).as_deref(), Some("J0001"));
+        assert_eq!(encode_alpha5(340000), None);
+
+        let tle = Tle::from_lines(&[
+            "1 E8493U 21001A   21135.57634567  .00002418  00000-0  50843-4 0  9993",
+            "2 E8493  51.6443 126.6639 0006738  34.7758 325.3542 15.48913328283873",
+        ])
+        .unwrap();
+        assert_eq!(parse_tle(&tle).catalog_number, 148493);
+    }
+
+    /// Tests that writing parsed fields gives back the original lines and checksums.
+    #[test]
+    fn test_to_tle_round_trip() {
+        let [line1, line2] = ISS_LINES;
+        let tle = iss_tle();
+
+        let written = parse_tle(&tle).to_tle().unwrap();
+
+        assert_eq!(written.line1, line1);
+        assert_eq!(written.line2, line2);
+        assert_eq!(checksum(line1), 7);
+        assert_eq!(format_exponent(-0.11606e-4, "BSTAR").unwrap(), "-11606-4");
+        assert_eq!(format_exponent(0.5, "BSTAR").unwrap(), " 50000+0");
+        assert_eq!(format_exponent(0.12345e9, "BSTAR").unwrap(), " 12345+9");
+        assert_eq!(format_mean_motion_dot(-0.5).unwrap(), "-.50000000");
+    }
+
+    /// Tests that values beyond the columns of their field are rejected.
+    #[test]
+    fn test_to_tle_out_of_range() {
+        assert_eq!(format_exponent(2e9, "BSTAR"), Err(TleError::FieldRange { field: "BSTAR", value: 2e9 }));
+        assert_eq!(format_exponent(-3e-11, "BSTAR"), Err(TleError::FieldRange { field: "BSTAR", value: -3e-11 }));
+        assert!(format_exponent(f64::NAN, "BSTAR").is_err());
+        assert!(format_mean_motion_dot(1.5).is_err());
+        assert!(format_mean_motion_dot(-0.999999999).is_err());
+
+        let fields = TleFields { mean_motion_dot: 1.0, ..parse_tle(&iss_tle()) };
+        assert_eq!(fields.to_tle().unwrap_err(), TleError::FieldRange { field: "mean motion first derivative", value: 1.0 });
+        let fields = TleFields { bstar: 1e12, ..parse_tle(&iss_tle()) };
+        assert_eq!(fields.to_tle().unwrap_err().to_string(), "BSTAR 1000000000000 cannot be written in a TLE");
+
+        let fields = TleFields { eccentricity: 1.2, ..parse_tle(&iss_tle()) };
+        assert_eq!(fields.to_tle().unwrap_err(), TleError::FieldRange { field: "eccentricity", value: 1.2 });
+        let fields = TleFields { eccentricity: -1e-3, ..parse_tle(&iss_tle()) };
+        assert!(fields.to_tle().is_err());
+        let fields = TleFields { mean_motion: 100.0, ..parse_tle(&iss_tle()) };
+        assert_eq!(fields.to_tle().unwrap_err(), TleError::FieldRange { field: "mean motion", value: 100.0 });
+        let fields = TleFields { mean_motion: 99.999999999, ..parse_tle(&iss_tle()) };
+        assert!(fields.to_tle().is_err());
+        let fields = TleFields { mean_motion: 99.99999999, ..parse_tle(&iss_tle()) };
+        assert_eq!(fields.to_tle().unwrap().line2.len(), 69);
+
+        let mut elements = iss_elements();
+        elements.bstar = 1e-12;
+        assert!(Tle::from_elements(&elements, &TleMetadata::from(&parse_tle(&iss_tle()))).is_err());
+    }
+
+    /// Tests writing a TLE from orbital elements and metadata.
+    #[test]
+    fn test_from_elements() {
+        let [line1, line2] = ISS_LINES;
+        let tle = iss_tle();
+        let elements = iss_elements();
+        let metadata = TleMetadata::from(&parse_tle(&tle));
+
+        let written = Tle::from_elements(&elements, &metadata).unwrap();
+
+        assert_eq!(written.line1, line1);
+        assert_eq!(written.line2, line2);
+
+        let metadata = TleMetadata { catalog_number: 400000, ..metadata };
+        assert_eq!(Tle::from_elements(&elements, &metadata).unwrap_err(), TleError::CatalogRange(400000));
+    }
+
+    /// Tests the 57-year pivot rule for epoch years.
+    #[test]
+    fn test_full_year() {
+        assert_eq!(full_year(57), 1957);
+        assert_eq!(full_year(99), 1999);
+        assert_eq!(full_year(0), 2000);
+        assert_eq!(full_year(8), 2008);
+        assert_eq!(full_year(56), 2056);
+    }
+
+    /// Tests converting the TLE epoch into a UTC date and time.
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_epoch_datetime() {
+        let tle = iss_tle();
+
+        let epoch = parse_tle(&tle).epoch();
+
+        assert_eq!(epoch.to_rfc3339(), "2008-09-20T12:25:40.104192+00:00");
+    }
+}
@@ -0,0 +1,158 @@
+//! Topocentric look angles for a ground observer.
+//! This module converts satellite TEME states into azimuth, elevation, range and range-rate as
+//! seen from a station on the Earth's surface, using the local East-North-Up frame.
+
+use crate::StateVector;
+use crate::frames::teme_to_ecef;
+use crate::geodetic::{Geodetic, geodetic_to_ecef};
+use crate::vector::dot;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Represents a ground observer (or ground station) at a fixed geodetic location.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Observer {
+    /// Geodetic location of the observer.
+    pub location: Geodetic,
+}
+
+/// Represents the direction and distance of a satellite as seen by an observer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LookAngles {
+    /// Azimuth in radians, measured clockwise from north, in [0, 2π).
+    pub azimuth: f64,
+    /// Elevation above the local horizon in radians.
+    pub elevation: f64,
+    /// Distance between the observer and the satellite in kilometers.
+    pub range: f64,
+    /// Rate of change of the range in kilometers per second (positive when receding).
+    pub range_rate: f64,
+}
+
+impl Observer {
+    /// Builds an observer from its location in degrees and kilometers.
+    ///
+    /// # Arguments
+    /// * `latitude` - Geodetic latitude in degrees, positive north.
+    /// * `longitude` - Longitude in degrees, positive east.
+    /// * `altitude` - Altitude above the WGS84 ellipsoid in kilometers.
+    ///
+    /// # Returns
+    /// * The observer.
+    pub fn new(latitude: f64, longitude: f64, altitude: f64) -> Observer {
+        Observer {
+            location: Geodetic {
+                latitude: latitude.to_radians(),
+                longitude: longitude.to_radians(),
+                altitude,
+            },
+        }
+    }
+
+    /// Returns the East, North and Up unit vectors of the observer in the Earth-fixed frame.
+    pub fn enu_axes(&self) -> [[f64; 3]; 3] {
+        let (sin_lat, cos_lat) = self.location.latitude.sin_cos();
+        let (sin_lon, cos_lon) = self.location.longitude.sin_cos();
+        [
+            [-sin_lon, cos_lon, 0.0],
+            [-sin_lat * cos_lon, -sin_lat * sin_lon, cos_lat],
+            [cos_lat * cos_lon, cos_lat * sin_lon, sin_lat],
+        ]
+    }
+
+    /// Computes the look angles of a satellite from an Earth-fixed state vector.
+    ///
+    /// # Arguments
+    /// * `ecef` - State vector of the satellite in the Earth-fixed frame (km, km/s).
+    ///
+    /// # Returns
+    /// * The azimuth, elevation, range and range-rate of the satellite.
+    pub fn look_angles_ecef(&self, ecef: &StateVector) -> LookAngles {
+        let station: [f64; 3] = geodetic_to_ecef(&self.location);
+        let rho: [f64; 3] = [
+            ecef.position[0] - station[0],
+            ecef.position[1] - station[1],
+            ecef.position[2] - station[2],
+        ];
+        let [east, north, up] = self.enu_axes();
+
+        let range: f64 = dot(rho, rho).sqrt();
+        let elevation: f64 = (dot(rho, up) / range).asin();
+        let azimuth: f64 = dot(rho, east).atan2(dot(rho, north)).rem_euclid(crate::TWOPI);
+        let range_rate: f64 = dot(rho, ecef.velocity) / range;
+
+        LookAngles {
+            azimuth,
+            elevation,
+            range,
+            range_rate,
+        }
+    }
+
+    /// Computes the look angles of a satellite from its TEME state vector.
+    ///
+    /// # Arguments
+    /// * `state` - State vector of the satellite in the TEME frame (km, km/s), as given by `sgp4()`.
+    /// * `gmst` - Greenwich Mean Sidereal Time in radians at the time of the state.
+    ///
+    /// # Returns
+    /// * The azimuth, elevation, range and range-rate of the satellite.
+    pub fn look_angles(&self, state: &StateVector, gmst: f64) -> LookAngles {
+        self.look_angles_ecef(&teme_to_ecef(state, gmst))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests a satellite directly above the observer, climbing vertically.
+    #[test]
+    fn test_look_angles_zenith() {
+        let observer = Observer::new(45.0, 10.0, 0.2);
+        let up: Geodetic = Geodetic {
+            altitude: 500.2,
+            ..observer.location
+        };
+        let position: [f64; 3] = geodetic_to_ecef(&up);
+        let [_, _, axis] = observer.enu_axes();
+        let ecef = StateVector {
+            position,
+            velocity: [axis[0], axis[1], axis[2]],
+        };
+
+        let angles = observer.look_angles_ecef(&ecef);
+
+        assert!((angles.elevation.to_degrees() - 90.0).abs() < 1e-6);
+        assert!((angles.range - 500.0).abs() < 1e-6);
+        assert!((angles.range_rate - 1.0).abs() < 1e-9);
+    }
+
+    /// Tests azimuths of satellites due north and due east of an equatorial observer.
+    #[test]
+    fn test_look_angles_azimuth() {
+        let observer = Observer::new(0.0, 0.0, 0.0);
+        let north = StateVector {
+            position: [6378.137, 0.0, 1000.0],
+            velocity: [0.0; 3],
+        };
+        let east = StateVector {
+            position: [6378.137, 1000.0, 0.0],
+            velocity: [0.0, -2.0, 0.0],
+        };
+
+        let angles = observer.look_angles_ecef(&north);
+        assert!(angles.azimuth.abs() < 1e-9);
+        assert!(angles.elevation.abs() < 1e-9);
+
+        let angles = observer.look_angles_ecef(&east);
+        assert!((angles.azimuth.to_degrees() - 90.0).abs() < 1e-9);
+        assert!((angles.range_rate + 2.0).abs() < 1e-9);
+
+        // Same geometry expressed in TEME with a zero sidereal angle.
+        let teme = observer.look_angles(&north, 0.0);
+        assert!(teme.azimuth.abs() < 1e-9);
+    }
+}
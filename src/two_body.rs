@@ -0,0 +1,214 @@
+//! Two-body Keplerian propagation.
+//! This model ignores the Earth's oblateness, the atmosphere and third bodies; it is fast and
+//! simple, but only an approximation of the SGP4 trajectory.
+
+use crate::propagator::{PropagationError, Propagator};
+use crate::gravity::GravityConstants;
+use crate::{OrbitalElements, StateVector, TWOPI, solve_kepler};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Propagates a satellite on a fixed Keplerian ellipse, without any perturbation.
+///
+/// The orbit keeps the mean elements of the TLE: the node and perigee do not precess and there is
+/// no drag, so the error grows quickly compared to SGP4. It is meant for teaching and coarse planning.
+#[derive(Debug, Clone)]
+pub struct TwoBody {
+    /// Julian date (UTC) of the epoch.
+    epoch_jd: f64,
+    /// Mean anomaly at epoch in radians.
+    mean_anomaly: f64,
+    /// Mean motion in radians per minute.
+    mean_motion: f64,
+    /// Eccentricity of the orbit.
+    eccentricity: f64,
+    /// Semi-major axis in body radii.
+    a: f64,
+    /// Equatorial radius of the central body in kilometers.
+    radius: f64,
+    /// sqrt(1 + e), used by the half-angle true anomaly formula.
+    sqrt_1pe: f64,
+    /// sqrt(1 - e), used by the half-angle true anomaly formula.
+    sqrt_1me: f64,
+    /// Radial velocity factor, XKE * sqrt(a).
+    xke_sqrt_a: f64,
+    /// Transverse velocity factor, XKE * sqrt(p).
+    xke_sqrt_p: f64,
+    /// Unit vector towards the perigee in the inertial frame.
+    p_axis: [f64; 3],
+    /// Unit vector 90° ahead of the perigee in the orbital plane, in the inertial frame.
+    q_axis: [f64; 3],
+}
+
+impl TwoBody {
+    /// Computes the time-independent quantities of the propagation.
+    ///
+    /// # Arguments
+    /// * `elements` - Orbital elements of the satellite.
+    ///
+    /// # Returns
+    /// * The two-body propagator.
+    pub fn new(elements: &OrbitalElements) -> TwoBody {
+        TwoBody::with_constants(elements, &GravityConstants::default())
+    }
+
+    /// Computes the time-independent quantities of the propagation around any central body.
+    ///
+    /// # Arguments
+    /// * `elements` - Orbital elements of the satellite, with the mean motion in radians per minute.
+    /// * `constants` - Constants of the central body; only μ and the radius are used.
+    ///
+    /// # Returns
+    /// * The two-body propagator.
+    pub fn with_constants(elements: &OrbitalElements, constants: &GravityConstants) -> TwoBody {
+        let xke: f64 = constants.xke;
+        let a: f64 = (xke / elements.mean_motion).powf(2.0 / 3.0); // Semi-major axis (body radii)
+        let e: f64 = elements.eccentricity;
+        let p: f64 = a * (1.0 - e * e); // Semi-latus rectum
+
+        // Pre-calculations for inertial transformation
+        let cos_omega: f64 = elements.arg_perigee.cos();
+        let sin_omega: f64 = elements.arg_perigee.sin();
+        let cos_raan: f64 = elements.raan.cos();
+        let sin_raan: f64 = elements.raan.sin();
+        let cos_i: f64 = elements.inclination.cos();
+        let sin_i: f64 = elements.inclination.sin();
+
+        TwoBody {
+            epoch_jd: elements.epoch_jd(),
+            mean_anomaly: elements.mean_anomaly,
+            mean_motion: elements.mean_motion,
+            eccentricity: e,
+            a,
+            radius: constants.radius,
+            sqrt_1pe: (1.0 + e).sqrt(),
+            sqrt_1me: (1.0 - e).sqrt(),
+            xke_sqrt_a: xke * a.sqrt(),
+            xke_sqrt_p: xke * p.sqrt(),
+            p_axis: [
+                cos_raan * cos_omega - sin_raan * sin_omega * cos_i,
+                sin_raan * cos_omega + cos_raan * sin_omega * cos_i,
+                sin_omega * sin_i,
+            ],
+            q_axis: [
+                -(cos_raan * sin_omega + sin_raan * cos_omega * cos_i),
+                -(sin_raan * sin_omega - cos_raan * cos_omega * cos_i),
+                cos_omega * sin_i,
+            ],
+        }
+    }
+}
+
+impl Propagator for TwoBody {
+    fn propagate(&self, tsince: f64) -> Result<StateVector, PropagationError> {
+        let e: f64 = self.eccentricity;
+
+        // Mean anomaly + Kepler's equation solution
+        let m: f64 = (self.mean_anomaly + self.mean_motion * tsince) % TWOPI;
+        let e_anomaly: f64 = solve_kepler(m, e, 1e-8).map_err(PropagationError::Kepler)?;
+
+        // True anomaly
+        let v: f64 = 2.0 * (self.sqrt_1pe * (e_anomaly / 2.0).sin()).atan2(self.sqrt_1me * (e_anomaly / 2.0).cos());
+
+        // Distance (body radii)
+        let r: f64 = self.a * (1.0 - e * e_anomaly.cos());
+
+        // Coordinates in the orbital plane
+        let x_orb: f64 = r * v.cos();
+        let y_orb: f64 = r * v.sin();
+
+        // Velocity in the orbital plane
+        let r_dot: f64 = self.xke_sqrt_a * e * e_anomaly.sin() / r;
+        let r_fi_dot: f64 = self.xke_sqrt_p / (r * r);
+
+        let vx_orb: f64 = r_dot * v.cos() - r * r_fi_dot * v.sin();
+        let vy_orb: f64 = r_dot * v.sin() + r * r_fi_dot * v.cos();
+
+        // Inertial position (body radii) and velocity (body radii per minute)
+        let position: [f64; 3] = [0, 1, 2].map(|k| x_orb * self.p_axis[k] + y_orb * self.q_axis[k]);
+        let velocity: [f64; 3] = [0, 1, 2].map(|k| vx_orb * self.p_axis[k] + vy_orb * self.q_axis[k]);
+
+        Ok(StateVector {
+            position: position.map(|x| x * self.radius), // km
+            velocity: velocity.map(|v| v * self.radius / 60.0), // km/s
+        })
+    }
+
+    fn epoch_jd(&self) -> f64 {
+        self.epoch_jd
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Sgp4, Tle, XKE, XKMPER, convert_satellite_data};
+    use crate::test_support::iss_elements;
+
+    /// Tests that the two-body orbit is periodic and keeps its energy.
+    #[test]
+    fn test_two_body_periodic() {
+        let elements = convert_satellite_data(
+            &Tle::from_lines(&[
+                "1 00005U 58002B   00179.78495062  .00000023  00000-0  28098-4 0  4753",
+                "2 00005  34.2682 348.7242 1859667 331.7664  19.3264 10.82419157413667",
+            ])
+            .unwrap(),
+        );
+        let two_body = TwoBody::new(&elements);
+
+        let start = two_body.propagate(0.0).unwrap();
+        let later = two_body.propagate(3.0 * elements.period_minutes()).unwrap();
+        for k in 0..3 {
+            assert!((start.position[k] - later.position[k]).abs() < 1e-6);
+        }
+
+        let mu: f64 = XKE * XKE * XKMPER.powi(3) / 3600.0;
+        let energy = |state: &StateVector| -> f64 {
+            let r: f64 = state.position.iter().map(|x| x * x).sum::<f64>().sqrt();
+            let v2: f64 = state.velocity.iter().map(|v| v * v).sum();
+            v2 / 2.0 - mu / r
+        };
+        let quarter = two_body.propagate(elements.period_minutes() / 4.0).unwrap();
+        assert!((energy(&start) - energy(&quarter)).abs() < 1e-9);
+        assert!((energy(&start) + mu / (2.0 * elements.semi_major_axis_km())).abs() < 1e-9);
+    }
+
+    /// Tests that the models are interchangeable through the trait and stay close at epoch.
+    #[test]
+    fn test_two_body_against_sgp4() {
+        let elements = iss_elements();
+        let models: [Box<dyn Propagator>; 2] = [Box::new(TwoBody::new(&elements)), Box::new(Sgp4::new(&elements))];
+
+        let states: Vec<StateVector> = models.iter().map(|model| model.propagate(0.0).unwrap()).collect();
+        let distance: f64 = (0..3).map(|k| (states[0].position[k] - states[1].position[k]).powi(2)).sum::<f64>().sqrt();
+        assert!(distance > 0.0 && distance < 30.0, "{}", distance);
+    }
+
+    /// Tests a Mars orbiter described by custom constants: the period is counted in sols.
+    #[test]
+    fn test_two_body_custom_body() {
+        let mars: GravityConstants = GravityConstants::new(42828.37, 3396.19, 0.00196045, 0.0000315, -0.0000154, 1479.37);
+        let mut elements = convert_satellite_data(
+            &Tle::from_lines(&[
+                "1 00005U 58002B   00179.78495062  .00000023  00000-0  28098-4 0  4753",
+                "2 00005  34.2682 348.7242 1859667 331.7664  19.3264 10.82419157413667",
+            ])
+            .unwrap(),
+        );
+        elements.mean_motion = mars.mean_motion(12.0);
+        let two_body = TwoBody::with_constants(&elements, &mars);
+
+        let period: f64 = mars.minutes_per_day / 12.0;
+        let start = two_body.propagate(0.0).unwrap();
+        let later = two_body.propagate(period).unwrap();
+        for k in 0..3 {
+            assert!((start.position[k] - later.position[k]).abs() < 1e-6);
+        }
+
+        let a: f64 = (mars.mu * (period * 60.0 / TWOPI).powi(2)).cbrt();
+        let r: f64 = start.position.iter().map(|x| x * x).sum::<f64>().sqrt();
+        let v2: f64 = start.velocity.iter().map(|v| v * v).sum();
+        assert!((v2 / 2.0 - mars.mu / r + mars.mu / (2.0 * a)).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,172 @@
+//! Typed quantities with `uom`.
+//! The crate works in kilometers, seconds, minutes since epoch and radians; this layer exchanges
+//! `uom` quantities instead, so that mixing minutes with seconds or degrees with radians in
+//! downstream code is a compile error rather than a wrong orbit.
+
+use crate::geodetic::Geodetic;
+use crate::propagator::PropagationError;
+use crate::topocentric::{LookAngles, Observer};
+use crate::{OrbitalElements, Sgp4, StateVector};
+use ::uom::si::angle::radian;
+use ::uom::si::f64::{Angle, Length, Time, Velocity};
+use ::uom::si::length::kilometer;
+use ::uom::si::time::minute;
+use ::uom::si::velocity::kilometer_per_second;
+
+/// Represents a state vector with typed components.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TypedState {
+    /// Position in the frame of the state vector.
+    pub position: [Length; 3],
+    /// Velocity in the frame of the state vector.
+    pub velocity: [Velocity; 3],
+}
+
+impl From<StateVector> for TypedState {
+    fn from(state: StateVector) -> TypedState {
+        TypedState {
+            position: state.position.map(Length::new::<kilometer>),
+            velocity: state.velocity.map(Velocity::new::<kilometer_per_second>),
+        }
+    }
+}
+
+impl From<TypedState> for StateVector {
+    fn from(state: TypedState) -> StateVector {
+        StateVector {
+            position: state.position.map(|length| length.get::<kilometer>()),
+            velocity: state.velocity.map(|velocity| velocity.get::<kilometer_per_second>()),
+        }
+    }
+}
+
+/// Represents a geodetic position with typed components.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TypedGeodetic {
+    /// Geodetic latitude, positive north.
+    pub latitude: Angle,
+    /// Longitude, positive east.
+    pub longitude: Angle,
+    /// Altitude above the WGS84 ellipsoid.
+    pub altitude: Length,
+}
+
+impl From<Geodetic> for TypedGeodetic {
+    fn from(geodetic: Geodetic) -> TypedGeodetic {
+        TypedGeodetic {
+            latitude: Angle::new::<radian>(geodetic.latitude),
+            longitude: Angle::new::<radian>(geodetic.longitude),
+            altitude: Length::new::<kilometer>(geodetic.altitude),
+        }
+    }
+}
+
+impl From<TypedGeodetic> for Geodetic {
+    fn from(geodetic: TypedGeodetic) -> Geodetic {
+        Geodetic {
+            latitude: geodetic.latitude.get::<radian>(),
+            longitude: geodetic.longitude.get::<radian>(),
+            altitude: geodetic.altitude.get::<kilometer>(),
+        }
+    }
+}
+
+/// Represents look angles with typed components.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TypedLookAngles {
+    /// Azimuth, measured clockwise from north.
+    pub azimuth: Angle,
+    /// Elevation above the local horizon.
+    pub elevation: Angle,
+    /// Distance between the observer and the satellite.
+    pub range: Length,
+    /// Rate of change of the range, positive when receding.
+    pub range_rate: Velocity,
+}
+
+impl From<LookAngles> for TypedLookAngles {
+    fn from(angles: LookAngles) -> TypedLookAngles {
+        TypedLookAngles {
+            azimuth: Angle::new::<radian>(angles.azimuth),
+            elevation: Angle::new::<radian>(angles.elevation),
+            range: Length::new::<kilometer>(angles.range),
+            range_rate: Velocity::new::<kilometer_per_second>(angles.range_rate),
+        }
+    }
+}
+
+impl Observer {
+    /// Builds an observer from typed coordinates.
+    ///
+    /// # Arguments
+    /// * `location` - Geodetic location of the observer.
+    ///
+    /// # Returns
+    /// * The observer.
+    pub fn from_typed(location: TypedGeodetic) -> Observer {
+        Observer { location: location.into() }
+    }
+}
+
+impl OrbitalElements {
+    /// Propagates the elements to a typed time since epoch.
+    ///
+    /// # Arguments
+    /// * `since_epoch` - Time since the epoch of the elements, in any unit.
+    ///
+    /// # Returns
+    /// * The typed state vector in the TEME frame, or the propagation error.
+    pub fn propagate_typed(&self, since_epoch: Time) -> Result<TypedState, PropagationError> {
+        Ok(Sgp4::new(self).propagate(since_epoch.get::<minute>())?.into())
+    }
+
+    /// Returns the orbital period as a typed time.
+    pub fn period(&self) -> Time {
+        Time::new::<minute>(self.period_minutes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::uom::si::angle::degree;
+    use ::uom::si::length::meter;
+    use ::uom::si::time::second;
+    use ::uom::si::velocity::meter_per_second;
+    use crate::test_support::iss_elements;
+
+    /// Tests that the typed propagation agrees with the untyped one whatever the unit of time.
+    #[test]
+    fn test_propagate_typed() {
+        let elements: OrbitalElements = iss_elements();
+        let typed: TypedState = elements.propagate_typed(Time::new::<second>(600.0)).unwrap();
+        let expected: StateVector = crate::sgp4(10.0, &elements);
+        assert_eq!(StateVector::from(typed).position, expected.position);
+        assert!((typed.position[0].get::<meter>() - expected.position[0] * 1000.0).abs() < 1e-6);
+        assert!((typed.velocity[2].get::<meter_per_second>() - expected.velocity[2] * 1000.0).abs() < 1e-9);
+        assert!((elements.period().get::<minute>() - 91.6).abs() < 0.1);
+    }
+
+    /// Tests the typed geodetic coordinates and look angles.
+    #[test]
+    fn test_typed_observer() {
+        let location = TypedGeodetic {
+            latitude: Angle::new::<degree>(45.0),
+            longitude: Angle::new::<degree>(10.0),
+            altitude: Length::new::<meter>(500.0),
+        };
+        let observer: Observer = Observer::from_typed(location);
+        assert_eq!(observer, Observer::new(45.0, 10.0, 0.5));
+        let back: TypedGeodetic = observer.location.into();
+        assert!((back.latitude.get::<degree>() - 45.0).abs() < 1e-12);
+
+        let angles = TypedLookAngles::from(LookAngles {
+            azimuth: 1.0,
+            elevation: 0.5,
+            range: 1000.0,
+            range_rate: -2.0,
+        });
+        assert!((angles.elevation.get::<radian>() - 0.5).abs() < 1e-15);
+        assert!((angles.range_rate.get::<meter_per_second>() + 2000.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,100 @@
+//! Vector helpers on `[f64; 3]` arrays and state vectors.
+//! The arrays are the vectors of the whole crate; these helpers, and the methods they back on
+//! `StateVector`, save callers from indexing the components by hand.
+
+use crate::StateVector;
+use core::ops::Sub;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Computes the dot product of two vectors.
+pub fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// Computes the cross product of two vectors.
+pub fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+/// Computes the Euclidean norm of a vector.
+pub fn norm(a: [f64; 3]) -> f64 {
+    dot(a, a).sqrt()
+}
+
+/// Subtracts two vectors.
+pub fn difference(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+impl StateVector {
+    /// Returns the distance from the center of the Earth in kilometers.
+    pub fn position_magnitude(&self) -> f64 {
+        norm(self.position)
+    }
+
+    /// Returns the speed in km/s.
+    pub fn speed(&self) -> f64 {
+        norm(self.velocity)
+    }
+
+    /// Returns the radial velocity in km/s, positive when the distance increases.
+    pub fn radial_velocity(&self) -> f64 {
+        dot(self.position, self.velocity) / self.position_magnitude()
+    }
+
+    /// Returns the specific angular momentum r × v in km²/s.
+    pub fn angular_momentum(&self) -> [f64; 3] {
+        cross(self.position, self.velocity)
+    }
+}
+
+/// Subtracting two states gives the state of the first relative to the second, in the same
+/// frame.
+impl Sub for StateVector {
+    type Output = StateVector;
+
+    fn sub(self, other: StateVector) -> StateVector {
+        StateVector {
+            position: difference(self.position, other.position),
+            velocity: difference(self.velocity, other.velocity),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests the vector products.
+    #[test]
+    fn test_products() {
+        let (a, b): ([f64; 3], [f64; 3]) = ([1.0, 2.0, 3.0], [4.0, -5.0, 6.0]);
+        assert_eq!(dot(a, b), 12.0);
+        assert_eq!(cross([1.0, 0.0, 0.0], [0.0, 1.0, 0.0]), [0.0, 0.0, 1.0]);
+        assert_eq!(dot(cross(a, b), a), 0.0);
+        assert_eq!(norm([3.0, 4.0, 12.0]), 13.0);
+        assert_eq!(difference(a, b), [-3.0, 7.0, -3.0]);
+    }
+
+    /// Tests the magnitudes, the radial velocity and the relative state.
+    #[test]
+    fn test_state_helpers() {
+        let state = StateVector {
+            position: [7000.0, 0.0, 0.0],
+            velocity: [1.0, 7.5, 0.0],
+        };
+        assert_eq!(state.position_magnitude(), 7000.0);
+        assert!((state.speed() - 57.25_f64.sqrt()).abs() < 1e-12);
+        assert_eq!(state.radial_velocity(), 1.0);
+        assert_eq!(state.angular_momentum(), [0.0, 0.0, 52500.0]);
+
+        let other = StateVector {
+            position: [6990.0, 10.0, -5.0],
+            velocity: [1.0, 7.4, 0.1],
+        };
+        let relative: StateVector = state - other;
+        assert_eq!(relative.position, [10.0, -10.0, 5.0]);
+        assert!((relative.velocity[1] - 0.1).abs() < 1e-12);
+    }
+}
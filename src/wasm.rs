@@ -0,0 +1,176 @@
+//! WebAssembly bindings for browser-based satellite trackers.
+//! The exports wrap TLE parsing, SGP4 propagation and look angles in plain structs of numbers,
+//! with angles in degrees and times as Julian dates, so JavaScript code needs no conversion layer.
+
+use crate::time::gmst;
+use crate::topocentric::Observer;
+use crate::{OrbitalElements, Sgp4, StateVector, Tle, convert_satellite_data};
+use wasm_bindgen::prelude::*;
+
+/// Represents a state vector in the TEME frame, as a flat JavaScript object.
+#[wasm_bindgen(js_name = State)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WasmState {
+    /// Position along X in kilometers.
+    pub x: f64,
+    /// Position along Y in kilometers.
+    pub y: f64,
+    /// Position along Z in kilometers.
+    pub z: f64,
+    /// Velocity along X in kilometers per second.
+    pub vx: f64,
+    /// Velocity along Y in kilometers per second.
+    pub vy: f64,
+    /// Velocity along Z in kilometers per second.
+    pub vz: f64,
+}
+
+/// Represents the direction and distance of a satellite seen from an observer, in degrees.
+#[wasm_bindgen(js_name = LookAngles)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WasmLookAngles {
+    /// Azimuth in degrees, clockwise from north, in [0, 360).
+    pub azimuth: f64,
+    /// Elevation above the local horizon in degrees.
+    pub elevation: f64,
+    /// Distance to the satellite in kilometers.
+    pub range: f64,
+    /// Rate of change of the range in kilometers per second (positive when receding).
+    pub range_rate: f64,
+}
+
+/// Holds a parsed TLE and its SGP4 initialization.
+#[wasm_bindgen(js_name = Satellite)]
+pub struct WasmSatellite {
+    name: Option<String>,
+    elements: OrbitalElements,
+    sgp4: Sgp4,
+}
+
+#[wasm_bindgen(js_class = Satellite)]
+impl WasmSatellite {
+    /// Parses a TLE set and initializes the propagator.
+    ///
+    /// # Arguments
+    /// * `text` - The two data lines of the TLE, optionally preceded by a name line.
+    ///
+    /// # Returns
+    /// * The satellite, or an error if the text is not a TLE set.
+    #[wasm_bindgen(constructor)]
+    pub fn new(text: &str) -> Result<WasmSatellite, JsError> {
+        let lines: Vec<&str> = text.lines().filter(|line| !line.trim().is_empty()).collect();
+        let tle: Tle = Tle::from_lines(&lines)?;
+        let elements: OrbitalElements = convert_satellite_data(&tle);
+        Ok(WasmSatellite {
+            name: tle.name,
+            sgp4: Sgp4::new(&elements),
+            elements,
+        })
+    }
+
+    /// Returns the name line of the TLE, if it had one.
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> Option<String> {
+        self.name.clone()
+    }
+
+    /// Returns the Julian date (UTC) of the epoch.
+    #[wasm_bindgen(getter, js_name = epochJd)]
+    pub fn epoch_jd(&self) -> f64 {
+        self.elements.epoch_jd()
+    }
+
+    /// Returns the orbital period in minutes.
+    #[wasm_bindgen(getter, js_name = periodMinutes)]
+    pub fn period_minutes(&self) -> f64 {
+        self.elements.period_minutes()
+    }
+
+    /// Computes the state vector of the satellite at a time since epoch.
+    ///
+    /// # Arguments
+    /// * `tsince` - Time since epoch in minutes.
+    ///
+    /// # Returns
+    /// * State vector in the TEME frame (km, km/s), or an error when the orbit has decayed.
+    pub fn propagate(&self, tsince: f64) -> Result<WasmState, JsError> {
+        let state: StateVector = self.sgp4.propagate(tsince)?;
+        Ok(WasmState {
+            x: state.position[0],
+            y: state.position[1],
+            z: state.position[2],
+            vx: state.velocity[0],
+            vy: state.velocity[1],
+            vz: state.velocity[2],
+        })
+    }
+
+    /// Computes the state vector of the satellite at a date.
+    ///
+    /// # Arguments
+    /// * `jd` - Julian date (UTC).
+    ///
+    /// # Returns
+    /// * State vector in the TEME frame (km, km/s), or an error when the orbit has decayed.
+    #[wasm_bindgen(js_name = propagateJd)]
+    pub fn propagate_jd(&self, jd: f64) -> Result<WasmState, JsError> {
+        self.propagate((jd - self.elements.epoch_jd()) * 1440.0)
+    }
+
+    /// Computes the look angles of the satellite from a ground observer at a date.
+    ///
+    /// # Arguments
+    /// * `jd` - Julian date (UTC, used as UT1 for the Earth rotation).
+    /// * `latitude` - Geodetic latitude of the observer in degrees, positive north.
+    /// * `longitude` - Longitude of the observer in degrees, positive east.
+    /// * `altitude` - Altitude of the observer above the WGS84 ellipsoid in kilometers.
+    ///
+    /// # Returns
+    /// * The azimuth, elevation, range and range-rate, or an error when the orbit has decayed.
+    #[wasm_bindgen(js_name = lookAngles)]
+    pub fn look_angles(&self, jd: f64, latitude: f64, longitude: f64, altitude: f64) -> Result<WasmLookAngles, JsError> {
+        let state: StateVector = self.sgp4.propagate((jd - self.elements.epoch_jd()) * 1440.0)?;
+        let angles = Observer::new(latitude, longitude, altitude).look_angles(&state, gmst(jd));
+        Ok(WasmLookAngles {
+            azimuth: angles.azimuth.to_degrees(),
+            elevation: angles.elevation.to_degrees(),
+            range: angles.range,
+            range_rate: angles.range_rate,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::iss_text;
+
+    /// Tests that the exported propagation matches the Rust one.
+    #[test]
+    fn test_wasm_propagate() {
+        let satellite = WasmSatellite::new(&iss_text()).ok().unwrap();
+        assert_eq!(satellite.name().as_deref(), Some("ISS (ZARYA)"));
+        assert!((satellite.period_minutes() - 91.6).abs() < 0.1);
+
+        let expected: StateVector = satellite.sgp4.propagate(90.0).unwrap();
+        let state: WasmState = satellite.propagate(90.0).ok().unwrap();
+        assert_eq!([state.x, state.y, state.z], expected.position);
+        assert_eq!([state.vx, state.vy, state.vz], expected.velocity);
+        assert!((satellite.propagate_jd(satellite.epoch_jd() + 90.0 / 1440.0).ok().unwrap().x - state.x).abs() < 1e-3);
+    }
+
+    /// Tests the look angles in degrees against the topocentric module.
+    #[test]
+    fn test_wasm_look_angles() {
+        let satellite = WasmSatellite::new(&iss_text()).ok().unwrap();
+        let jd: f64 = satellite.epoch_jd() + 0.25;
+        let angles: WasmLookAngles = satellite.look_angles(jd, 48.85, 2.35, 0.035).ok().unwrap();
+
+        let state: StateVector = satellite.sgp4.propagate(360.0).unwrap();
+        let expected = Observer::new(48.85, 2.35, 0.035).look_angles(&state, gmst(jd));
+        assert!((angles.azimuth - expected.azimuth.to_degrees()).abs() < 1e-9);
+        assert!((angles.elevation - expected.elevation.to_degrees()).abs() < 1e-9);
+        assert!((0.0..360.0).contains(&angles.azimuth));
+        assert!((angles.range - expected.range).abs() < 1e-6);
+    }
+}